@@ -9,24 +9,38 @@ use serde::Deserialize;
 pub(crate) const DEFAULT_BIND_ADDRESS: &str = "/ip4/0.0.0.0/tcp/14265";
 
 // all available routes
+pub(crate) const ROUTE_ADD_BAN: &str = "/api/v1/peers/bans";
 pub(crate) const ROUTE_ADD_PEER: &str = "/api/v1/peers";
 pub(crate) const ROUTE_BALANCE_BECH32: &str = "/api/v1/addresses/:address";
 pub(crate) const ROUTE_BALANCE_ED25519: &str = "/api/v1/addresses/ed25519/:address";
+pub(crate) const ROUTE_BANS: &str = "/api/v1/peers/bans";
+pub(crate) const ROUTE_COORDINATOR_KEYS: &str = "/api/v1/coordinator/keys";
 pub(crate) const ROUTE_HEALTH: &str = "/health";
 pub(crate) const ROUTE_INFO: &str = "/api/v1/info";
 pub(crate) const ROUTE_MESSAGE: &str = "/api/v1/messages/:messageId";
 pub(crate) const ROUTE_MESSAGE_CHILDREN: &str = "/api/v1/messages/:messageId/children";
 pub(crate) const ROUTE_MESSAGE_METADATA: &str = "/api/v1/messages/:messageId/metadata";
+pub(crate) const ROUTE_MESSAGE_PROMOTE: &str = "/api/v1/messages/:messageId/promote";
 pub(crate) const ROUTE_MESSAGE_RAW: &str = "/api/v1/messages/:messageId/raw";
+pub(crate) const ROUTE_MESSAGE_REATTACH: &str = "/api/v1/messages/:messageId/reattach";
 pub(crate) const ROUTE_MESSAGES_FIND: &str = "/api/v1/messages";
+pub(crate) const ROUTE_MESSAGES_ORPHANED: &str = "/api/v1/messages/orphaned";
 pub(crate) const ROUTE_MILESTONE: &str = "/api/v1/milestones/:milestoneIndex";
+pub(crate) const ROUTE_MILESTONE_STATS: &str = "/api/v1/milestones/:milestoneIndex/stats";
+pub(crate) const ROUTE_MILESTONE_TIMELINE: &str = "/api/v1/milestones/timeline";
 pub(crate) const ROUTE_MILESTONE_UTXO_CHANGES: &str = "/api/v1/milestones/:milestoneIndex/utxo-changes";
 pub(crate) const ROUTE_OUTPUT: &str = "/api/v1/outputs/:outputId";
 pub(crate) const ROUTE_OUTPUTS_BECH32: &str = "/api/v1/addresses/:address/outputs";
 pub(crate) const ROUTE_OUTPUTS_ED25519: &str = "/api/v1/addresses/ed25519/:address/outputs";
 pub(crate) const ROUTE_PEER: &str = "/api/v1/peers/:peerId";
+pub(crate) const ROUTE_PEER_HISTORY: &str = "/api/v1/peers/:peerId/history";
 pub(crate) const ROUTE_PEERS: &str = "/api/v1/peers";
+pub(crate) const ROUTE_RELOAD_COORDINATOR_KEYS: &str = "/api/v1/coordinator/keys/reload";
+pub(crate) const ROUTE_REMOVE_BAN: &str = "/api/v1/peers/bans";
 pub(crate) const ROUTE_REMOVE_PEER: &str = "/api/v1/peers/:peerId";
+pub(crate) const ROUTE_SOLID_ENTRY_POINT: &str = "/api/v1/solid-entry-points/:messageId";
+pub(crate) const ROUTE_SOLID_ENTRY_POINTS: &str = "/api/v1/solid-entry-points";
+pub(crate) const ROUTE_SPAM_METRICS: &str = "/api/v1/metrics/spam";
 pub(crate) const ROUTE_SUBMIT_MESSAGE: &str = "/api/v1/messages";
 pub(crate) const ROUTE_SUBMIT_MESSAGE_RAW: &str = "/api/v1/messages";
 pub(crate) const ROUTE_TIPS: &str = "/api/v1/tips";
@@ -35,9 +49,10 @@ pub(crate) const ROUTE_RECEIPTS_AT: &str = "/api/v1/receipts/:milestoneIndex";
 pub(crate) const ROUTE_TREASURY: &str = "/api/v1/treasury";
 pub(crate) const ROUTE_TRANSACTION_INCLUDED_MESSAGE: &str = "/api/v1/transactions/:transactionId/included-message";
 pub(crate) const ROUTE_WHITE_FLAG: &str = "/api/plugins/debug/whiteflag";
+pub(crate) const ROUTE_REDERIVE_SOLID_ENTRY_POINTS: &str = "/api/plugins/debug/solid-entry-points";
 
 /// the routes that are available for public use
-pub(crate) const DEFAULT_PUBLIC_ROUTES: [&str; 21] = [
+pub(crate) const DEFAULT_PUBLIC_ROUTES: [&str; 23] = [
     ROUTE_BALANCE_BECH32,
     ROUTE_BALANCE_ED25519,
     ROUTE_HEALTH,
@@ -48,6 +63,8 @@ pub(crate) const DEFAULT_PUBLIC_ROUTES: [&str; 21] = [
     ROUTE_MESSAGE_RAW,
     ROUTE_MESSAGES_FIND,
     ROUTE_MILESTONE,
+    ROUTE_MILESTONE_STATS,
+    ROUTE_MILESTONE_TIMELINE,
     ROUTE_MILESTONE_UTXO_CHANGES,
     ROUTE_OUTPUT,
     ROUTE_OUTPUTS_BECH32,
@@ -3,10 +3,11 @@
 
 use std::convert::Infallible;
 
-use bee_gossip::NetworkCommandSender;
+use bee_gossip::{NetworkBans, NetworkCommandSender, NetworkHistory};
 use bee_ledger::workers::consensus::ConsensusWorkerCommand;
 use bee_protocol::workers::{
-    config::ProtocolConfig, MessageRequesterWorker, MessageSubmitterWorkerEvent, PeerManager, RequestedMessages,
+    config::ProtocolConfig, MessageRequesterWorker, MessageSubmitterWorkerEvent, MilestoneKeys, OrphanedMessages,
+    PeerManager, RequestedMessages, SpamTracker,
 };
 use bee_runtime::{event::Bus, node::NodeInfo, resource::ResourceHandle};
 use bee_tangle::Tangle;
@@ -63,6 +64,36 @@ pub(crate) fn with_peer_manager(
     warp::any().map(move || peer_manager.clone())
 }
 
+pub(crate) fn with_spam_tracker(
+    spam_tracker: ResourceHandle<SpamTracker>,
+) -> impl Filter<Extract = (ResourceHandle<SpamTracker>,), Error = Infallible> + Clone {
+    warp::any().map(move || spam_tracker.clone())
+}
+
+pub(crate) fn with_milestone_keys(
+    milestone_keys: ResourceHandle<MilestoneKeys>,
+) -> impl Filter<Extract = (ResourceHandle<MilestoneKeys>,), Error = Infallible> + Clone {
+    warp::any().map(move || milestone_keys.clone())
+}
+
+pub(crate) fn with_orphaned_messages(
+    orphaned_messages: ResourceHandle<OrphanedMessages>,
+) -> impl Filter<Extract = (ResourceHandle<OrphanedMessages>,), Error = Infallible> + Clone {
+    warp::any().map(move || orphaned_messages.clone())
+}
+
+pub(crate) fn with_network_bans(
+    network_bans: ResourceHandle<NetworkBans>,
+) -> impl Filter<Extract = (ResourceHandle<NetworkBans>,), Error = Infallible> + Clone {
+    warp::any().map(move || network_bans.clone())
+}
+
+pub(crate) fn with_network_history(
+    network_history: ResourceHandle<NetworkHistory>,
+) -> impl Filter<Extract = (ResourceHandle<NetworkHistory>,), Error = Infallible> + Clone {
+    warp::any().map(move || network_history.clone())
+}
+
 pub(crate) fn with_network_command_sender(
     command_sender: ResourceHandle<NetworkCommandSender>,
 ) -> impl Filter<Extract = (ResourceHandle<NetworkCommandSender>,), Error = Infallible> + Clone {
@@ -13,11 +13,11 @@ pub mod storage;
 use std::{any::TypeId, convert::Infallible};
 
 use async_trait::async_trait;
-use bee_gossip::NetworkCommandSender;
+use bee_gossip::{NetworkBans, NetworkCommandSender, NetworkHistory};
 use bee_ledger::workers::consensus::ConsensusWorker;
 use bee_protocol::workers::{
-    config::ProtocolConfig, MessageRequesterWorker, MessageSubmitterWorker, PeerManager, PeerManagerResWorker,
-    RequestedMessages,
+    config::ProtocolConfig, MessageRequesterWorker, MessageSubmitterWorker, MilestoneKeys, MilestoneKeysWorker,
+    MpsWorker, OrphanedMessages, PeerManager, PeerManagerResWorker, RequestedMessages, SpamTracker,
 };
 use bee_runtime::{
     node::{Node, NodeBuilder},
@@ -63,6 +63,8 @@ where
             TypeId::of::<TangleWorker>(),
             TypeId::of::<MessageSubmitterWorker>(),
             TypeId::of::<PeerManagerResWorker>(),
+            TypeId::of::<MilestoneKeysWorker>(),
+            TypeId::of::<MpsWorker>(),
         ]
         .leak()
     }
@@ -79,8 +81,13 @@ where
         let message_submitter = node.worker::<MessageSubmitterWorker>().unwrap().tx.clone();
         let message_requester = node.worker::<MessageRequesterWorker>().unwrap().clone();
         let requested_messages = node.resource::<RequestedMessages>();
+        let orphaned_messages = node.resource::<OrphanedMessages>();
+        let milestone_keys = node.resource::<MilestoneKeys>();
+        let spam_tracker = node.resource::<SpamTracker>();
         let peer_manager = node.resource::<PeerManager>();
         let network_controller = node.resource::<NetworkCommandSender>();
+        let network_bans = node.resource::<NetworkBans>();
+        let network_history = node.resource::<NetworkHistory>();
         let node_info = node.info();
         let bus = node.bus();
 
@@ -104,6 +111,11 @@ where
                 message_requester,
                 requested_messages,
                 consensus_worker,
+                orphaned_messages,
+                milestone_keys,
+                spam_tracker,
+                network_bans,
+                network_history,
             )
             .recover(|err| async { handle_rejection(err) });
 
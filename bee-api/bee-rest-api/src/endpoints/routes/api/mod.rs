@@ -6,10 +6,11 @@ pub mod v1;
 
 use std::net::IpAddr;
 
-use bee_gossip::NetworkCommandSender;
+use bee_gossip::{NetworkBans, NetworkCommandSender, NetworkHistory};
 use bee_ledger::workers::consensus::ConsensusWorkerCommand;
 use bee_protocol::workers::{
-    config::ProtocolConfig, MessageRequesterWorker, MessageSubmitterWorkerEvent, PeerManager, RequestedMessages,
+    config::ProtocolConfig, MessageRequesterWorker, MessageSubmitterWorkerEvent, MilestoneKeys, OrphanedMessages,
+    PeerManager, RequestedMessages, SpamTracker,
 };
 use bee_runtime::{event::Bus, node::NodeInfo, resource::ResourceHandle};
 use bee_tangle::Tangle;
@@ -40,6 +41,11 @@ pub(crate) fn filter<B: StorageBackend>(
     message_requester: MessageRequesterWorker,
     requested_messages: ResourceHandle<RequestedMessages>,
     consensus_worker: mpsc::UnboundedSender<ConsensusWorkerCommand>,
+    orphaned_messages: ResourceHandle<OrphanedMessages>,
+    milestone_keys: ResourceHandle<MilestoneKeys>,
+    spam_tracker: ResourceHandle<SpamTracker>,
+    network_bans: ResourceHandle<NetworkBans>,
+    network_history: ResourceHandle<NetworkHistory>,
 ) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
     v1::filter(
         public_routes.clone(),
@@ -54,7 +60,12 @@ pub(crate) fn filter<B: StorageBackend>(
         peer_manager,
         network_command_sender,
         node_info,
-        consensus_worker,
+        consensus_worker.clone(),
+        orphaned_messages,
+        milestone_keys,
+        spam_tracker,
+        network_bans,
+        network_history,
     )
     .or(plugins::filter(
         public_routes,
@@ -65,5 +76,6 @@ pub(crate) fn filter<B: StorageBackend>(
         message_requester,
         requested_messages,
         rest_api_config,
+        consensus_worker,
     ))
 }
@@ -1,13 +1,16 @@
 // Copyright 2020-2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+mod solid_entry_points;
 mod white_flag;
 
 use std::net::IpAddr;
 
+use bee_ledger::workers::consensus::ConsensusWorkerCommand;
 use bee_protocol::workers::{MessageRequesterWorker, RequestedMessages};
 use bee_runtime::{event::Bus, resource::ResourceHandle};
 use bee_tangle::Tangle;
+use tokio::sync::mpsc;
 use warp::{self, Filter, Rejection, Reply};
 
 use crate::endpoints::{config::RestApiConfig, storage::StorageBackend};
@@ -26,8 +29,9 @@ pub(crate) fn filter<B: StorageBackend>(
     message_requester: MessageRequesterWorker,
     requested_messages: ResourceHandle<RequestedMessages>,
     rest_api_config: RestApiConfig,
+    consensus_worker: mpsc::UnboundedSender<ConsensusWorkerCommand>,
 ) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
-    white_flag::filter(
+    solid_entry_points::filter(public_routes.clone(), allowed_ips.clone(), consensus_worker).or(white_flag::filter(
         public_routes,
         allowed_ips,
         storage,
@@ -36,5 +40,5 @@ pub(crate) fn filter<B: StorageBackend>(
         message_requester,
         requested_messages,
         rest_api_config,
-    )
+    ))
 }
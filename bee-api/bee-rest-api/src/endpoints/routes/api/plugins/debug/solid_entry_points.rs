@@ -0,0 +1,83 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::IpAddr;
+
+use bee_ledger::workers::{consensus::ConsensusWorkerCommand, error::Error};
+use bee_message::milestone::MilestoneIndex;
+use futures::channel::oneshot;
+use log::error;
+use serde_json::Value as JsonValue;
+use tokio::sync::mpsc;
+use warp::{filters::BoxedFilter, reject, Filter, Rejection, Reply};
+
+use crate::{
+    endpoints::{
+        config::ROUTE_REDERIVE_SOLID_ENTRY_POINTS, filters::with_consensus_worker, permission::has_permission,
+        rejection::CustomRejection,
+    },
+    types::{body::SuccessBody, responses::RederiveSolidEntryPointsResponse},
+};
+
+fn path() -> impl Filter<Extract = (), Error = warp::Rejection> + Clone {
+    super::path()
+        .and(warp::path("solid-entry-points"))
+        .and(warp::path::end())
+}
+
+pub(crate) fn filter(
+    public_routes: Box<[String]>,
+    allowed_ips: Box<[IpAddr]>,
+    consensus_worker: mpsc::UnboundedSender<ConsensusWorkerCommand>,
+) -> BoxedFilter<(impl Reply,)> {
+    self::path()
+        .and(warp::post())
+        .and(has_permission(
+            ROUTE_REDERIVE_SOLID_ENTRY_POINTS,
+            public_routes,
+            allowed_ips,
+        ))
+        .and(warp::body::json())
+        .and(with_consensus_worker(consensus_worker))
+        .and_then(rederive_solid_entry_points)
+        .boxed()
+}
+
+pub(crate) async fn rederive_solid_entry_points(
+    body: JsonValue,
+    consensus_worker: mpsc::UnboundedSender<ConsensusWorkerCommand>,
+) -> Result<impl Reply, Rejection> {
+    let index = match body["index"].as_u64() {
+        Some(index) if index <= u32::MAX as u64 => MilestoneIndex(index as u32),
+        _ => {
+            return Err(reject::custom(CustomRejection::BadRequest(
+                "Invalid index: expected a MilestoneIndex".to_string(),
+            )));
+        }
+    };
+
+    let (cmd_tx, cmd_rx) = oneshot::channel::<Result<usize, Error>>();
+
+    if let Err(e) = consensus_worker.send(ConsensusWorkerCommand::RederiveSolidEntryPoints(index, cmd_tx)) {
+        error!("request to consensus worker failed: {}.", e);
+    }
+
+    match cmd_rx.await.map_err(|e| {
+        error!("response from consensus worker failed: {}.", e);
+        reject::custom(CustomRejection::ServiceUnavailable(
+            "unable to re-derive the solid entry points".to_string(),
+        ))
+    })? {
+        Ok(count) => Ok(warp::reply::json(&SuccessBody::new(RederiveSolidEntryPointsResponse {
+            index: *index,
+            count,
+        }))),
+        Err(e) => {
+            error!("unable to re-derive the solid entry points: {}", e);
+            Err(reject::custom(CustomRejection::BadRequest(format!(
+                "unable to re-derive the solid entry points: {}",
+                e
+            ))))
+        }
+    }
+}
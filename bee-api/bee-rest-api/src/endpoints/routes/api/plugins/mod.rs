@@ -5,9 +5,11 @@ mod debug;
 
 use std::net::IpAddr;
 
+use bee_ledger::workers::consensus::ConsensusWorkerCommand;
 use bee_protocol::workers::{MessageRequesterWorker, RequestedMessages};
 use bee_runtime::{event::Bus, resource::ResourceHandle};
 use bee_tangle::Tangle;
+use tokio::sync::mpsc;
 use warp::{self, Filter, Rejection, Reply};
 
 use crate::endpoints::{config::RestApiConfig, storage::StorageBackend};
@@ -26,6 +28,7 @@ pub(crate) fn filter<B: StorageBackend>(
     message_requester: MessageRequesterWorker,
     requested_messages: ResourceHandle<RequestedMessages>,
     rest_api_config: RestApiConfig,
+    consensus_worker: mpsc::UnboundedSender<ConsensusWorkerCommand>,
 ) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
     debug::filter(
         public_routes,
@@ -36,5 +39,6 @@ pub(crate) fn filter<B: StorageBackend>(
         message_requester,
         requested_messages,
         rest_api_config,
+        consensus_worker,
     )
 }
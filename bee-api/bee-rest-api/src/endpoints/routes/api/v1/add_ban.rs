@@ -0,0 +1,129 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{net::IpAddr, time::Duration};
+
+use bee_gossip::{Ban, Command, Multiaddr, NetworkCommandSender, PeerId};
+use bee_runtime::resource::ResourceHandle;
+use serde_json::Value as JsonValue;
+use warp::{filters::BoxedFilter, reject, Filter, Rejection, Reply};
+
+use crate::{
+    endpoints::{
+        config::ROUTE_ADD_BAN, filters::with_network_command_sender, permission::has_permission,
+        rejection::CustomRejection,
+    },
+    types::{body::SuccessBody, dtos::BanDto, responses::AddBanResponse},
+};
+
+/// What a ban applies to.
+pub(crate) enum BanTarget {
+    Peer(PeerId),
+    Address(Multiaddr),
+}
+
+/// Parses the `peerId` or `address`, exactly one of them being expected, a ban applies to.
+pub(crate) fn ban_target(value: &JsonValue) -> Result<BanTarget, Rejection> {
+    match (&value["peerId"], &value["address"]) {
+        (peer_id_v, JsonValue::Null) if !peer_id_v.is_null() => peer_id_v
+            .as_str()
+            .and_then(|peer_id| peer_id.parse::<PeerId>().ok())
+            .map(BanTarget::Peer)
+            .ok_or_else(|| reject::custom(CustomRejection::BadRequest("invalid peer id".to_string()))),
+        (JsonValue::Null, address_v) if !address_v.is_null() => address_v
+            .as_str()
+            .ok_or_else(|| {
+                reject::custom(CustomRejection::BadRequest(
+                    "invalid address: expected a string".to_string(),
+                ))
+            })?
+            .parse::<Multiaddr>()
+            .map(BanTarget::Address)
+            .map_err(|e| reject::custom(CustomRejection::BadRequest(format!("invalid address: {}", e)))),
+        _ => Err(reject::custom(CustomRejection::BadRequest(
+            "expected either a peer id or an address".to_string(),
+        ))),
+    }
+}
+
+fn path() -> impl Filter<Extract = (), Error = warp::Rejection> + Clone {
+    super::path()
+        .and(warp::path("peers"))
+        .and(warp::path("bans"))
+        .and(warp::path::end())
+}
+
+pub(crate) fn filter(
+    public_routes: Box<[String]>,
+    allowed_ips: Box<[IpAddr]>,
+    network_command_sender: ResourceHandle<NetworkCommandSender>,
+) -> BoxedFilter<(impl Reply,)> {
+    self::path()
+        .and(warp::post())
+        .and(has_permission(ROUTE_ADD_BAN, public_routes, allowed_ips))
+        .and(warp::body::json())
+        .and(with_network_command_sender(network_command_sender))
+        .and_then(|value, network_controller| async move { add_ban(value, network_controller) })
+        .boxed()
+}
+
+pub(crate) fn add_ban(
+    value: JsonValue,
+    network_controller: ResourceHandle<NetworkCommandSender>,
+) -> Result<impl Reply, Rejection> {
+    let target = ban_target(&value)?;
+    let reason_v = &value["reason"];
+    let duration_v = &value["duration"];
+
+    let reason = if reason_v.is_null() {
+        None
+    } else {
+        Some(
+            reason_v
+                .as_str()
+                .ok_or_else(|| {
+                    reject::custom(CustomRejection::BadRequest(
+                        "invalid reason: expected a string".to_string(),
+                    ))
+                })?
+                .to_string(),
+        )
+    };
+    let duration = if duration_v.is_null() {
+        None
+    } else {
+        Some(Duration::from_secs(duration_v.as_u64().ok_or_else(|| {
+            reject::custom(CustomRejection::BadRequest(
+                "invalid duration: expected a number of seconds".to_string(),
+            ))
+        })?))
+    };
+
+    let ban = Ban::new(reason, duration);
+    let mut ban_dto = BanDto {
+        peer_id: None,
+        address: None,
+        reason: ban.reason.clone(),
+        expires_at: ban.expires_at,
+    };
+
+    let command = match target {
+        BanTarget::Peer(peer_id) => {
+            ban_dto.peer_id = Some(peer_id.to_string());
+            Command::BanPeer { peer_id, ban }
+        }
+        BanTarget::Address(address) => {
+            ban_dto.address = Some(address.to_string());
+            Command::BanAddress { address, ban }
+        }
+    };
+
+    if let Err(e) = network_controller.send(command) {
+        return Err(reject::custom(CustomRejection::NotFound(format!(
+            "failed to add ban: {}",
+            e
+        ))));
+    }
+
+    Ok(warp::reply::json(&SuccessBody::new(AddBanResponse(ban_dto))))
+}
@@ -3,7 +3,7 @@
 
 use std::net::IpAddr;
 
-use bee_gossip::{Command::AddPeer, Multiaddr, NetworkCommandSender, PeerId, PeerRelation, Protocol};
+use bee_gossip::{Command::AddPeer, Multiaddr, NetworkCommandSender, PeerRelation, Protocol};
 use bee_protocol::workers::PeerManager;
 use bee_runtime::resource::ResourceHandle;
 use serde_json::Value as JsonValue;
@@ -64,11 +64,7 @@ pub(crate) fn add_peer(
         .map_err(|e| reject::custom(CustomRejection::BadRequest(format!("invalid multi address: {}", e))))?;
 
     let peer_id = match multi_address.pop() {
-        Some(Protocol::P2p(multihash)) => PeerId::from_multihash(multihash).map_err(|_| {
-            reject::custom(CustomRejection::BadRequest(
-                "invalid multi address: can not parse peer id".to_string(),
-            ))
-        })?,
+        Some(Protocol::P2p(peer_id)) => peer_id,
         _ => {
             return Err(reject::custom(CustomRejection::BadRequest(
                 "invalid multi address: invalid protocol type".to_string(),
@@ -118,8 +114,11 @@ pub(crate) fn add_peer(
                     alias,
                     multi_addresses: vec![multi_address.to_string()],
                     relation: RelationDto::Known,
+                    group: None,
                     connected: false,
                     gossip: None,
+                    reconnect: None,
+                    bandwidth: None,
                 }))),
                 StatusCode::OK,
             ))
@@ -0,0 +1,56 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{convert::Infallible, net::IpAddr};
+
+use bee_gossip::NetworkBans;
+use bee_runtime::resource::ResourceHandle;
+use warp::{filters::BoxedFilter, Filter, Rejection, Reply};
+
+use crate::{
+    endpoints::{config::ROUTE_BANS, filters::with_network_bans, permission::has_permission},
+    types::{body::SuccessBody, dtos::BanDto, responses::BansResponse},
+};
+
+fn path() -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    super::path()
+        .and(warp::path("peers"))
+        .and(warp::path("bans"))
+        .and(warp::path::end())
+}
+
+pub(crate) fn filter(
+    public_routes: Box<[String]>,
+    allowed_ips: Box<[IpAddr]>,
+    network_bans: ResourceHandle<NetworkBans>,
+) -> BoxedFilter<(impl Reply,)> {
+    self::path()
+        .and(warp::get())
+        .and(has_permission(ROUTE_BANS, public_routes, allowed_ips))
+        .and(with_network_bans(network_bans))
+        .and_then(bans)
+        .boxed()
+}
+
+pub(crate) async fn bans(network_bans: ResourceHandle<NetworkBans>) -> Result<impl Reply, Infallible> {
+    let mut bans_dtos = Vec::new();
+
+    for (peer_id, ban) in network_bans.peers().await {
+        bans_dtos.push(BanDto {
+            peer_id: Some(peer_id.to_string()),
+            address: None,
+            reason: ban.reason,
+            expires_at: ban.expires_at,
+        });
+    }
+    for (address, ban) in network_bans.addresses().await {
+        bans_dtos.push(BanDto {
+            peer_id: None,
+            address: Some(address.to_string()),
+            reason: ban.reason,
+            expires_at: ban.expires_at,
+        });
+    }
+
+    Ok(warp::reply::json(&SuccessBody::new(BansResponse(bans_dtos))))
+}
@@ -0,0 +1,46 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{convert::Infallible, net::IpAddr};
+
+use bee_protocol::workers::MilestoneKeys;
+use bee_runtime::resource::ResourceHandle;
+use warp::{filters::BoxedFilter, Filter, Rejection, Reply};
+
+use crate::{
+    endpoints::{config::ROUTE_COORDINATOR_KEYS, filters::with_milestone_keys, permission::has_permission},
+    types::{body::SuccessBody, dtos::MilestoneKeyRangeDto, responses::CoordinatorKeysResponse},
+};
+
+fn path() -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    super::path()
+        .and(warp::path("coordinator"))
+        .and(warp::path("keys"))
+        .and(warp::path::end())
+}
+
+pub(crate) fn filter(
+    public_routes: Box<[String]>,
+    allowed_ips: Box<[IpAddr]>,
+    milestone_keys: ResourceHandle<MilestoneKeys>,
+) -> BoxedFilter<(impl Reply,)> {
+    self::path()
+        .and(warp::get())
+        .and(has_permission(ROUTE_COORDINATOR_KEYS, public_routes, allowed_ips))
+        .and(with_milestone_keys(milestone_keys))
+        .and_then(|milestone_keys| async move { coordinator_keys(milestone_keys) })
+        .boxed()
+}
+
+pub(crate) fn coordinator_keys(milestone_keys: ResourceHandle<MilestoneKeys>) -> Result<impl Reply, Infallible> {
+    let key_manager = milestone_keys.get();
+
+    Ok(warp::reply::json(&SuccessBody::new(CoordinatorKeysResponse {
+        min_threshold: key_manager.min_threshold(),
+        public_key_ranges: key_manager
+            .key_ranges()
+            .iter()
+            .map(MilestoneKeyRangeDto::from)
+            .collect(),
+    })))
+}
@@ -3,9 +3,12 @@
 
 use std::net::IpAddr;
 
-use bee_message::{payload::Payload, MessageId};
+use bee_message::{milestone::MilestoneIndex, payload::Payload, MessageId};
 use bee_runtime::resource::ResourceHandle;
-use bee_tangle::{ConflictReason, Tangle};
+use bee_tangle::{
+    urts::{OMRSI_DELTA, YMRSI_DELTA},
+    ConflictReason, Tangle,
+};
 use warp::{filters::BoxedFilter, reject, Filter, Rejection, Reply};
 
 use crate::{
@@ -16,6 +19,40 @@ use crate::{
     types::{body::SuccessBody, dtos::LedgerInclusionStateDto, responses::MessageMetadataResponse},
 };
 
+/// Action to take so that a message that is not referenced by a milestone yet gets referenced.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum StuckMessageAction {
+    /// The message is not stuck.
+    None,
+    /// The message can still be referenced if a new message approves it together with a fresh tip.
+    Promote,
+    /// The message is below max depth and needs to be attached again to fresh tips.
+    Reattach,
+}
+
+/// Decides on the action to take for a message that is not referenced by a milestone yet, based on its OMRSI and YMRSI,
+/// following the tip selection rules.
+pub(crate) fn stuck_message_action(
+    is_solid: bool,
+    omrsi_and_ymrsi: Option<(MilestoneIndex, MilestoneIndex)>,
+    confirmed_milestone_index: MilestoneIndex,
+    below_max_depth: u32,
+) -> StuckMessageAction {
+    let (omrsi, ymrsi) = match omrsi_and_ymrsi {
+        Some((omrsi, ymrsi)) if is_solid => (*omrsi, *ymrsi),
+        _ => return StuckMessageAction::Reattach,
+    };
+    let cmi = *confirmed_milestone_index;
+
+    if cmi.saturating_sub(omrsi) > below_max_depth {
+        StuckMessageAction::Reattach
+    } else if cmi.saturating_sub(ymrsi) > YMRSI_DELTA || cmi.saturating_sub(omrsi) > OMRSI_DELTA {
+        StuckMessageAction::Promote
+    } else {
+        StuckMessageAction::None
+    }
+}
+
 fn path() -> impl Filter<Extract = (MessageId,), Error = warp::Rejection> + Clone {
     super::path()
         .and(warp::path("messages"))
@@ -33,11 +70,11 @@ pub(crate) fn filter<B: StorageBackend>(
         .and(warp::get())
         .and(has_permission(ROUTE_MESSAGE_METADATA, public_routes, allowed_ips))
         .and(with_tangle(tangle))
-        .and_then(|message_id, tangle| async move { message_metadata(message_id, tangle) })
+        .and_then(|message_id, tangle| async move { message_metadata(message_id, tangle).await })
         .boxed()
 }
 
-pub(crate) fn message_metadata<B: StorageBackend>(
+pub(crate) async fn message_metadata<B: StorageBackend>(
     message_id: MessageId,
     tangle: ResourceHandle<Tangle<B>>,
 ) -> Result<impl Reply, Rejection> {
@@ -49,11 +86,6 @@ pub(crate) fn message_metadata<B: StorageBackend>(
 
     match tangle.get_message_and_metadata(&message_id) {
         Some((message, metadata)) => {
-            // TODO: access constants from URTS
-            let ymrsi_delta = 8;
-            let omrsi_delta = 13;
-            let below_max_depth = 15;
-
             let (
                 is_solid,
                 referenced_by_milestone_index,
@@ -100,41 +132,26 @@ pub(crate) fn message_metadata<B: StorageBackend>(
                     });
                     should_reattach = None;
                     should_promote = None;
-                } else if metadata.flags().is_solid() {
-                    // message is not referenced by a milestone but solid
-                    is_solid = true;
-                    referenced_by_milestone_index = None;
-                    milestone_index = None;
-                    ledger_inclusion_state = None;
-                    conflict_reason = None;
-
-                    let cmi = *tangle.get_confirmed_milestone_index();
-
-                    // unwrap() of OMRSI/YMRSI is safe since message is solid
-                    let (omrsi, ymrsi) = metadata
-                        .omrsi_and_ymrsi()
-                        .map(|(o, y)| (*o.index(), *y.index()))
-                        .unwrap();
-
-                    if (cmi - omrsi) > below_max_depth {
-                        should_promote = Some(false);
-                        should_reattach = Some(true);
-                    } else if (cmi - ymrsi) > ymrsi_delta || (cmi - omrsi) > omrsi_delta {
-                        should_promote = Some(true);
-                        should_reattach = Some(false);
-                    } else {
-                        should_promote = Some(false);
-                        should_reattach = Some(false);
-                    };
                 } else {
-                    // the message is not referenced by a milestone and not solid
-                    is_solid = false;
+                    // message is not referenced by a milestone
+                    is_solid = metadata.flags().is_solid();
                     referenced_by_milestone_index = None;
                     milestone_index = None;
                     ledger_inclusion_state = None;
                     conflict_reason = None;
-                    should_reattach = Some(true);
-                    should_promote = Some(false);
+
+                    let action = stuck_message_action(
+                        is_solid,
+                        tangle
+                            .omrsi_and_ymrsi(&message_id)
+                            .await
+                            .map(|(omrsi, ymrsi)| (omrsi.index(), ymrsi.index())),
+                        tangle.get_confirmed_milestone_index(),
+                        tangle.config().below_max_depth(),
+                    );
+
+                    should_promote = Some(action == StuckMessageAction::Promote);
+                    should_reattach = Some(action == StuckMessageAction::Reattach);
                 }
 
                 (
@@ -143,8 +160,8 @@ pub(crate) fn message_metadata<B: StorageBackend>(
                     milestone_index,
                     ledger_inclusion_state,
                     conflict_reason,
-                    should_reattach,
                     should_promote,
+                    should_reattach,
                 )
             };
 
@@ -165,3 +182,42 @@ pub(crate) fn message_metadata<B: StorageBackend>(
         ))),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn action(is_solid: bool, omrsi_and_ymrsi: Option<(u32, u32)>, cmi: u32) -> StuckMessageAction {
+        stuck_message_action(
+            is_solid,
+            omrsi_and_ymrsi.map(|(omrsi, ymrsi)| (MilestoneIndex(omrsi), MilestoneIndex(ymrsi))),
+            MilestoneIndex(cmi),
+            15,
+        )
+    }
+
+    #[test]
+    fn reattach_unsolid_messages() {
+        assert_eq!(action(false, None, 100), StuckMessageAction::Reattach);
+        assert_eq!(action(false, Some((100, 100)), 100), StuckMessageAction::Reattach);
+        assert_eq!(action(true, None, 100), StuckMessageAction::Reattach);
+    }
+
+    #[test]
+    fn reattach_below_max_depth() {
+        assert_eq!(action(true, Some((85, 100)), 100), StuckMessageAction::Promote);
+        assert_eq!(action(true, Some((84, 100)), 100), StuckMessageAction::Reattach);
+    }
+
+    #[test]
+    fn promote_lazy_messages() {
+        // OMRSI too old.
+        assert_eq!(action(true, Some((86, 100)), 100), StuckMessageAction::Promote);
+        assert_eq!(action(true, Some((87, 100)), 100), StuckMessageAction::None);
+        // YMRSI too old.
+        assert_eq!(action(true, Some((91, 91)), 100), StuckMessageAction::Promote);
+        assert_eq!(action(true, Some((92, 92)), 100), StuckMessageAction::None);
+        // Indexes beyond the confirmed milestone index.
+        assert_eq!(action(true, Some((101, 101)), 100), StuckMessageAction::None);
+    }
+}
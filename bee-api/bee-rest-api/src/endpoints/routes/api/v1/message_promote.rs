@@ -0,0 +1,140 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::IpAddr;
+
+use bee_message::MessageId;
+use bee_protocol::workers::{config::ProtocolConfig, MessageSubmitterWorkerEvent};
+use bee_runtime::resource::ResourceHandle;
+use bee_tangle::Tangle;
+use tokio::sync::mpsc;
+use warp::{filters::BoxedFilter, http::StatusCode, reject, Filter, Rejection, Reply};
+
+use crate::{
+    endpoints::{
+        config::{RestApiConfig, ROUTE_MESSAGE_PROMOTE},
+        filters::{with_message_submitter, with_protocol_config, with_rest_api_config, with_tangle},
+        path_params::message_id,
+        permission::has_permission,
+        rejection::CustomRejection,
+        routes::api::v1::{
+            message_metadata::{stuck_message_action, StuckMessageAction},
+            submit_message::{build_message, forward_to_message_submitter},
+        },
+        storage::StorageBackend,
+    },
+    types::{body::SuccessBody, responses::SubmitMessageResponse},
+};
+
+/// Decides on the action to take for a message, failing if it is unknown or already referenced by a milestone.
+pub(crate) async fn stuck_message_action_of<B: StorageBackend>(
+    tangle: &Tangle<B>,
+    message_id: &MessageId,
+) -> Result<StuckMessageAction, Rejection> {
+    let metadata = tangle.get_metadata(message_id).ok_or_else(|| {
+        reject::custom(CustomRejection::NotFound(
+            "can not find message with given message id".to_string(),
+        ))
+    })?;
+
+    if metadata.milestone_index().is_some() {
+        return Err(reject::custom(CustomRejection::BadRequest(
+            "message is already referenced by a milestone".to_string(),
+        )));
+    }
+
+    Ok(stuck_message_action(
+        metadata.flags().is_solid(),
+        tangle
+            .omrsi_and_ymrsi(message_id)
+            .await
+            .map(|(omrsi, ymrsi)| (omrsi.index(), ymrsi.index())),
+        tangle.get_confirmed_milestone_index(),
+        tangle.config().below_max_depth(),
+    ))
+}
+
+fn path() -> impl Filter<Extract = (MessageId,), Error = Rejection> + Clone {
+    super::path()
+        .and(warp::path("messages"))
+        .and(message_id())
+        .and(warp::path("promote"))
+        .and(warp::path::end())
+}
+
+pub(crate) fn filter<B: StorageBackend>(
+    public_routes: Box<[String]>,
+    allowed_ips: Box<[IpAddr]>,
+    tangle: ResourceHandle<Tangle<B>>,
+    message_submitter: mpsc::UnboundedSender<MessageSubmitterWorkerEvent>,
+    rest_api_config: RestApiConfig,
+    protocol_config: ProtocolConfig,
+) -> BoxedFilter<(impl Reply,)> {
+    self::path()
+        .and(warp::post())
+        .and(has_permission(ROUTE_MESSAGE_PROMOTE, public_routes, allowed_ips))
+        .and(with_tangle(tangle))
+        .and(with_message_submitter(message_submitter))
+        .and(with_rest_api_config(rest_api_config))
+        .and(with_protocol_config(protocol_config))
+        .and_then(message_promote)
+        .boxed()
+}
+
+pub(crate) async fn message_promote<B: StorageBackend>(
+    message_id: MessageId,
+    tangle: ResourceHandle<Tangle<B>>,
+    message_submitter: mpsc::UnboundedSender<MessageSubmitterWorkerEvent>,
+    rest_api_config: RestApiConfig,
+    protocol_config: ProtocolConfig,
+) -> Result<impl Reply, Rejection> {
+    match stuck_message_action_of(&tangle, &message_id).await? {
+        StuckMessageAction::Promote => {}
+        StuckMessageAction::Reattach => {
+            return Err(reject::custom(CustomRejection::BadRequest(
+                "can not promote message: message is below max depth and needs to be reattached".to_string(),
+            )));
+        }
+        StuckMessageAction::None => {
+            return Err(reject::custom(CustomRejection::BadRequest(
+                "can not promote message: message does not need to be promoted".to_string(),
+            )));
+        }
+    }
+
+    let message = tangle.get(&message_id).ok_or_else(|| {
+        reject::custom(CustomRejection::NotFound(
+            "can not find message with given message id".to_string(),
+        ))
+    })?;
+
+    let tip = tangle
+        .get_messages_to_approve()
+        .await
+        .and_then(|tips| tips.into_iter().find(|tip| *tip != message_id))
+        .ok_or_else(|| {
+            reject::custom(CustomRejection::ServiceUnavailable(
+                "can not promote message: no tips available".to_string(),
+            ))
+        })?;
+
+    let mut parents = vec![message_id, tip];
+    parents.sort_unstable_by(|a, b| a.as_ref().cmp(b.as_ref()));
+
+    let promotion = build_message(
+        message.network_id(),
+        parents,
+        None,
+        None,
+        rest_api_config,
+        protocol_config,
+    )?;
+    let promotion_id = forward_to_message_submitter(promotion, tangle, message_submitter).await?;
+
+    Ok(warp::reply::with_status(
+        warp::reply::json(&SuccessBody::new(SubmitMessageResponse {
+            message_id: promotion_id.to_string(),
+        })),
+        StatusCode::CREATED,
+    ))
+}
@@ -0,0 +1,107 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::IpAddr;
+
+use bee_message::MessageId;
+use bee_protocol::workers::{config::ProtocolConfig, MessageSubmitterWorkerEvent};
+use bee_runtime::resource::ResourceHandle;
+use bee_tangle::Tangle;
+use tokio::sync::mpsc;
+use warp::{filters::BoxedFilter, http::StatusCode, reject, Filter, Rejection, Reply};
+
+use crate::{
+    endpoints::{
+        config::{RestApiConfig, ROUTE_MESSAGE_REATTACH},
+        filters::{with_message_submitter, with_protocol_config, with_rest_api_config, with_tangle},
+        path_params::message_id,
+        permission::has_permission,
+        rejection::CustomRejection,
+        routes::api::v1::{
+            message_metadata::StuckMessageAction,
+            message_promote::stuck_message_action_of,
+            submit_message::{build_message, forward_to_message_submitter},
+        },
+        storage::StorageBackend,
+    },
+    types::{body::SuccessBody, responses::SubmitMessageResponse},
+};
+
+fn path() -> impl Filter<Extract = (MessageId,), Error = Rejection> + Clone {
+    super::path()
+        .and(warp::path("messages"))
+        .and(message_id())
+        .and(warp::path("reattach"))
+        .and(warp::path::end())
+}
+
+pub(crate) fn filter<B: StorageBackend>(
+    public_routes: Box<[String]>,
+    allowed_ips: Box<[IpAddr]>,
+    tangle: ResourceHandle<Tangle<B>>,
+    message_submitter: mpsc::UnboundedSender<MessageSubmitterWorkerEvent>,
+    rest_api_config: RestApiConfig,
+    protocol_config: ProtocolConfig,
+) -> BoxedFilter<(impl Reply,)> {
+    self::path()
+        .and(warp::post())
+        .and(has_permission(ROUTE_MESSAGE_REATTACH, public_routes, allowed_ips))
+        .and(with_tangle(tangle))
+        .and(with_message_submitter(message_submitter))
+        .and(with_rest_api_config(rest_api_config))
+        .and(with_protocol_config(protocol_config))
+        .and_then(message_reattach)
+        .boxed()
+}
+
+pub(crate) async fn message_reattach<B: StorageBackend>(
+    message_id: MessageId,
+    tangle: ResourceHandle<Tangle<B>>,
+    message_submitter: mpsc::UnboundedSender<MessageSubmitterWorkerEvent>,
+    rest_api_config: RestApiConfig,
+    protocol_config: ProtocolConfig,
+) -> Result<impl Reply, Rejection> {
+    match stuck_message_action_of(&tangle, &message_id).await? {
+        StuckMessageAction::Reattach => {}
+        StuckMessageAction::Promote => {
+            return Err(reject::custom(CustomRejection::BadRequest(
+                "can not reattach message: message can still be promoted".to_string(),
+            )));
+        }
+        StuckMessageAction::None => {
+            return Err(reject::custom(CustomRejection::BadRequest(
+                "can not reattach message: message does not need to be reattached".to_string(),
+            )));
+        }
+    }
+
+    let message = tangle.get(&message_id).ok_or_else(|| {
+        reject::custom(CustomRejection::NotFound(
+            "can not find message with given message id".to_string(),
+        ))
+    })?;
+
+    let mut parents = tangle.get_messages_to_approve().await.ok_or_else(|| {
+        reject::custom(CustomRejection::ServiceUnavailable(
+            "can not reattach message: no tips available".to_string(),
+        ))
+    })?;
+    parents.sort_unstable_by(|a, b| a.as_ref().cmp(b.as_ref()));
+
+    let reattachment = build_message(
+        message.network_id(),
+        parents,
+        message.payload().clone(),
+        None,
+        rest_api_config,
+        protocol_config,
+    )?;
+    let reattachment_id = forward_to_message_submitter(reattachment, tangle, message_submitter).await?;
+
+    Ok(warp::reply::with_status(
+        warp::reply::json(&SuccessBody::new(SubmitMessageResponse {
+            message_id: reattachment_id.to_string(),
+        })),
+        StatusCode::CREATED,
+    ))
+}
@@ -0,0 +1,39 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{convert::Infallible, net::IpAddr};
+
+use bee_protocol::workers::OrphanedMessages;
+use bee_runtime::resource::ResourceHandle;
+use warp::{filters::BoxedFilter, Filter, Rejection, Reply};
+
+use crate::{
+    endpoints::{config::ROUTE_MESSAGES_ORPHANED, filters::with_orphaned_messages, permission::has_permission},
+    types::{body::SuccessBody, responses::OrphanedMessagesResponse},
+};
+
+fn path() -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    super::path()
+        .and(warp::path("messages"))
+        .and(warp::path("orphaned"))
+        .and(warp::path::end())
+}
+
+pub(crate) fn filter(
+    public_routes: Box<[String]>,
+    allowed_ips: Box<[IpAddr]>,
+    orphaned_messages: ResourceHandle<OrphanedMessages>,
+) -> BoxedFilter<(impl Reply,)> {
+    self::path()
+        .and(warp::get())
+        .and(has_permission(ROUTE_MESSAGES_ORPHANED, public_routes, allowed_ips))
+        .and(with_orphaned_messages(orphaned_messages))
+        .and_then(|orphaned_messages| async move { messages_orphaned(orphaned_messages) })
+        .boxed()
+}
+
+pub(crate) fn messages_orphaned(orphaned_messages: ResourceHandle<OrphanedMessages>) -> Result<impl Reply, Infallible> {
+    Ok(warp::reply::json(&SuccessBody::new(OrphanedMessagesResponse(
+        orphaned_messages.orphaned().iter().map(Into::into).collect(),
+    ))))
+}
@@ -0,0 +1,52 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::IpAddr;
+
+use bee_message::milestone::MilestoneIndex;
+use bee_runtime::resource::ResourceHandle;
+use bee_tangle::Tangle;
+use warp::{filters::BoxedFilter, reject, Filter, Rejection, Reply};
+
+use crate::{
+    endpoints::{
+        config::ROUTE_MILESTONE_STATS, filters::with_tangle, path_params::milestone_index, permission::has_permission,
+        rejection::CustomRejection, storage::StorageBackend,
+    },
+    types::{body::SuccessBody, responses::MilestoneStatsResponse},
+};
+
+fn path() -> impl Filter<Extract = (MilestoneIndex,), Error = Rejection> + Clone {
+    super::path()
+        .and(warp::path("milestones"))
+        .and(milestone_index())
+        .and(warp::path("stats"))
+        .and(warp::path::end())
+}
+
+pub(crate) fn filter<B: StorageBackend>(
+    public_routes: Box<[String]>,
+    allowed_ips: Box<[IpAddr]>,
+    tangle: ResourceHandle<Tangle<B>>,
+) -> BoxedFilter<(impl Reply,)> {
+    self::path()
+        .and(warp::get())
+        .and(has_permission(ROUTE_MILESTONE_STATS, public_routes, allowed_ips))
+        .and(with_tangle(tangle))
+        .and_then(milestone_stats)
+        .boxed()
+}
+
+pub(crate) async fn milestone_stats<B: StorageBackend>(
+    milestone_index: MilestoneIndex,
+    tangle: ResourceHandle<Tangle<B>>,
+) -> Result<impl Reply, Rejection> {
+    match tangle.get_milestone_stats(milestone_index).await {
+        Some(stats) => Ok(warp::reply::json(&SuccessBody::new(MilestoneStatsResponse(
+            (&stats).into(),
+        )))),
+        None => Err(reject::custom(CustomRejection::NotFound(
+            "can not find statistics for given milestone index".to_string(),
+        ))),
+    }
+}
@@ -0,0 +1,43 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::IpAddr;
+
+use bee_runtime::resource::ResourceHandle;
+use bee_tangle::Tangle;
+use warp::{filters::BoxedFilter, Filter, Rejection, Reply};
+
+use crate::{
+    endpoints::{
+        config::ROUTE_MILESTONE_TIMELINE, filters::with_tangle, permission::has_permission, storage::StorageBackend,
+    },
+    types::{body::SuccessBody, responses::MilestoneTimelineResponse},
+};
+
+fn path() -> impl Filter<Extract = (), Error = warp::Rejection> + Clone {
+    super::path()
+        .and(warp::path("milestones"))
+        .and(warp::path("timeline"))
+        .and(warp::path::end())
+}
+
+pub(crate) fn filter<B: StorageBackend>(
+    public_routes: Box<[String]>,
+    allowed_ips: Box<[IpAddr]>,
+    tangle: ResourceHandle<Tangle<B>>,
+) -> BoxedFilter<(impl Reply,)> {
+    self::path()
+        .and(warp::get())
+        .and(has_permission(ROUTE_MILESTONE_TIMELINE, public_routes, allowed_ips))
+        .and(with_tangle(tangle))
+        .and_then(milestone_timeline)
+        .boxed()
+}
+
+pub(crate) async fn milestone_timeline<B: StorageBackend>(
+    tangle: ResourceHandle<Tangle<B>>,
+) -> Result<impl Reply, Rejection> {
+    Ok(warp::reply::json(&SuccessBody::new(MilestoneTimelineResponse(
+        tangle.get_milestone_timeline().await.iter().map(Into::into).collect(),
+    ))))
+}
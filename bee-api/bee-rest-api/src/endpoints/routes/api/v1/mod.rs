@@ -1,25 +1,39 @@
 // Copyright 2020-2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod add_ban;
 pub mod add_peer;
 pub mod balance_bech32;
 pub mod balance_ed25519;
+pub mod bans;
+pub mod coordinator_keys;
 pub mod info;
 pub mod message;
 pub mod message_children;
 pub mod message_metadata;
+pub mod message_promote;
 pub mod message_raw;
+pub mod message_reattach;
 pub mod messages_find;
+pub mod messages_orphaned;
 pub mod milestone;
+pub mod milestone_stats;
+pub mod milestone_timeline;
 pub mod milestone_utxo_changes;
 pub mod output;
 pub mod outputs_bech32;
 pub mod outputs_ed25519;
 pub mod peer;
+pub mod peer_history;
 pub mod peers;
 pub mod receipts;
 pub mod receipts_at;
+pub mod reload_coordinator_keys;
+pub mod remove_ban;
 pub mod remove_peer;
+pub mod solid_entry_point;
+pub mod solid_entry_points;
+pub mod spam_metrics;
 pub mod submit_message;
 pub mod tips;
 pub mod transaction_included_message;
@@ -27,9 +41,11 @@ pub mod treasury;
 
 use std::net::IpAddr;
 
-use bee_gossip::NetworkCommandSender;
+use bee_gossip::{NetworkBans, NetworkCommandSender, NetworkHistory};
 use bee_ledger::workers::consensus::ConsensusWorkerCommand;
-use bee_protocol::workers::{config::ProtocolConfig, MessageSubmitterWorkerEvent, PeerManager};
+use bee_protocol::workers::{
+    config::ProtocolConfig, MessageSubmitterWorkerEvent, MilestoneKeys, OrphanedMessages, PeerManager, SpamTracker,
+};
 use bee_runtime::{node::NodeInfo, resource::ResourceHandle};
 use bee_tangle::Tangle;
 use tokio::sync::mpsc;
@@ -56,13 +72,23 @@ pub(crate) fn filter<B: StorageBackend>(
     network_command_sender: ResourceHandle<NetworkCommandSender>,
     node_info: ResourceHandle<NodeInfo>,
     consensus_worker: mpsc::UnboundedSender<ConsensusWorkerCommand>,
+    orphaned_messages: ResourceHandle<OrphanedMessages>,
+    milestone_keys: ResourceHandle<MilestoneKeys>,
+    spam_tracker: ResourceHandle<SpamTracker>,
+    network_bans: ResourceHandle<NetworkBans>,
+    network_history: ResourceHandle<NetworkHistory>,
 ) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
-    add_peer::filter(
+    add_ban::filter(
         public_routes.clone(),
         allowed_ips.clone(),
-        peer_manager.clone(),
         network_command_sender.clone(),
     )
+    .or(add_peer::filter(
+        public_routes.clone(),
+        allowed_ips.clone(),
+        peer_manager.clone(),
+        network_command_sender.clone(),
+    ))
     .or(balance_bech32::filter(
         public_routes.clone(),
         allowed_ips.clone(),
@@ -73,6 +99,12 @@ pub(crate) fn filter<B: StorageBackend>(
         allowed_ips.clone(),
         consensus_worker.clone(),
     ))
+    .or(bans::filter(public_routes.clone(), allowed_ips.clone(), network_bans))
+    .or(coordinator_keys::filter(
+        public_routes.clone(),
+        allowed_ips.clone(),
+        milestone_keys.clone(),
+    ))
     .or(info::filter(
         public_routes.clone(),
         allowed_ips.clone(),
@@ -99,21 +131,52 @@ pub(crate) fn filter<B: StorageBackend>(
         allowed_ips.clone(),
         tangle.clone(),
     ))
+    .or(message_promote::filter(
+        public_routes.clone(),
+        allowed_ips.clone(),
+        tangle.clone(),
+        message_submitter.clone(),
+        rest_api_config.clone(),
+        protocol_config.clone(),
+    ))
     .or(message_raw::filter(
         public_routes.clone(),
         allowed_ips.clone(),
         tangle.clone(),
     ))
+    .or(message_reattach::filter(
+        public_routes.clone(),
+        allowed_ips.clone(),
+        tangle.clone(),
+        message_submitter.clone(),
+        rest_api_config.clone(),
+        protocol_config.clone(),
+    ))
     .or(messages_find::filter(
         public_routes.clone(),
         allowed_ips.clone(),
         storage.clone(),
     ))
+    .or(messages_orphaned::filter(
+        public_routes.clone(),
+        allowed_ips.clone(),
+        orphaned_messages,
+    ))
     .or(milestone::filter(
         public_routes.clone(),
         allowed_ips.clone(),
         tangle.clone(),
     ))
+    .or(milestone_stats::filter(
+        public_routes.clone(),
+        allowed_ips.clone(),
+        tangle.clone(),
+    ))
+    .or(milestone_timeline::filter(
+        public_routes.clone(),
+        allowed_ips.clone(),
+        tangle.clone(),
+    ))
     .or(milestone_utxo_changes::filter(
         public_routes.clone(),
         allowed_ips.clone(),
@@ -140,6 +203,11 @@ pub(crate) fn filter<B: StorageBackend>(
         allowed_ips.clone(),
         peer_manager.clone(),
     ))
+    .or(peer_history::filter(
+        public_routes.clone(),
+        allowed_ips.clone(),
+        network_history,
+    ))
     .or(peers::filter(public_routes.clone(), allowed_ips.clone(), peer_manager))
     .or(receipts::filter(
         public_routes.clone(),
@@ -151,11 +219,36 @@ pub(crate) fn filter<B: StorageBackend>(
         allowed_ips.clone(),
         storage.clone(),
     ))
+    .or(reload_coordinator_keys::filter(
+        public_routes.clone(),
+        allowed_ips.clone(),
+        milestone_keys,
+    ))
+    .or(remove_ban::filter(
+        public_routes.clone(),
+        allowed_ips.clone(),
+        network_command_sender.clone(),
+    ))
     .or(remove_peer::filter(
         public_routes.clone(),
         allowed_ips.clone(),
         network_command_sender,
     ))
+    .or(solid_entry_point::filter(
+        public_routes.clone(),
+        allowed_ips.clone(),
+        tangle.clone(),
+    ))
+    .or(solid_entry_points::filter(
+        public_routes.clone(),
+        allowed_ips.clone(),
+        tangle.clone(),
+    ))
+    .or(spam_metrics::filter(
+        public_routes.clone(),
+        allowed_ips.clone(),
+        spam_tracker,
+    ))
     .or(submit_message::filter(
         public_routes.clone(),
         allowed_ips.clone(),
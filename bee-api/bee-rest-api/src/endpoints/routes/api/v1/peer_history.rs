@@ -0,0 +1,143 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{convert::Infallible, net::IpAddr};
+
+use bee_gossip::{ConnectionEvent, ConnectionEventKind, NetworkHistory, PeerId};
+use bee_runtime::resource::ResourceHandle;
+use warp::{filters::BoxedFilter, Filter, Rejection, Reply};
+
+use crate::{
+    endpoints::{
+        config::ROUTE_PEER_HISTORY, filters::with_network_history, path_params::peer_id, permission::has_permission,
+    },
+    types::{
+        body::SuccessBody,
+        dtos::{ConnectionEventDto, ConnectionEventKindDto},
+        responses::PeerHistoryResponse,
+    },
+};
+
+fn path() -> impl Filter<Extract = (PeerId,), Error = Rejection> + Clone {
+    super::path()
+        .and(warp::path("peers"))
+        .and(peer_id())
+        .and(warp::path("history"))
+        .and(warp::path::end())
+}
+
+pub(crate) fn filter(
+    public_routes: Box<[String]>,
+    allowed_ips: Box<[IpAddr]>,
+    network_history: ResourceHandle<NetworkHistory>,
+) -> BoxedFilter<(impl Reply,)> {
+    self::path()
+        .and(warp::get())
+        .and(has_permission(ROUTE_PEER_HISTORY, public_routes, allowed_ips))
+        .and(with_network_history(network_history))
+        .and_then(peer_history)
+        .boxed()
+}
+
+pub(crate) async fn peer_history(
+    peer_id: PeerId,
+    network_history: ResourceHandle<NetworkHistory>,
+) -> Result<impl Reply, Infallible> {
+    // Peers that are gone are not an error, their history is what helps figuring out why.
+    let events = network_history
+        .peer(&peer_id)
+        .await
+        .into_iter()
+        .map(event_dto)
+        .collect();
+
+    Ok(warp::reply::json(&SuccessBody::new(PeerHistoryResponse(events))))
+}
+
+fn event_dto(event: ConnectionEvent) -> ConnectionEventDto {
+    let kind = match event.kind {
+        ConnectionEventKind::Dialed { address } => ConnectionEventKindDto::Dialed {
+            address: address.to_string(),
+        },
+        ConnectionEventKind::DialFailed { error } => ConnectionEventKindDto::DialFailed { reason: error },
+        ConnectionEventKind::Unreachable { reason } => ConnectionEventKindDto::Unreachable { reason },
+        ConnectionEventKind::ProtocolEstablished { address, origin } => ConnectionEventKindDto::ProtocolEstablished {
+            address: address.to_string(),
+            origin: origin.to_string(),
+        },
+        ConnectionEventKind::ProtocolRejected { reason } => ConnectionEventKindDto::ProtocolRejected { reason },
+        ConnectionEventKind::ProtocolStopped { cause } => ConnectionEventKindDto::ProtocolStopped { reason: cause },
+        ConnectionEventKind::Disconnected { cause } => ConnectionEventKindDto::Disconnected { reason: cause },
+    };
+
+    ConnectionEventDto {
+        timestamp: event.timestamp,
+        kind,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bee_gossip::Origin;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn map_connection_events() {
+        let address = "/ip4/127.0.0.1/tcp/15600".parse().unwrap();
+
+        let dialed = event_dto(ConnectionEvent {
+            timestamp: 42,
+            kind: ConnectionEventKind::Dialed { address },
+        });
+        assert_eq!(
+            serde_json::to_value(&dialed).unwrap(),
+            json!({ "timestamp": 42, "type": "dialed", "address": "/ip4/127.0.0.1/tcp/15600" })
+        );
+
+        let established = event_dto(ConnectionEvent {
+            timestamp: 43,
+            kind: ConnectionEventKind::ProtocolEstablished {
+                address: "/ip4/127.0.0.1/tcp/15600".parse().unwrap(),
+                origin: Origin::Inbound,
+            },
+        });
+        assert_eq!(
+            serde_json::to_value(&established).unwrap(),
+            json!({
+                "timestamp": 43,
+                "type": "protocolEstablished",
+                "address": "/ip4/127.0.0.1/tcp/15600",
+                "origin": Origin::Inbound.to_string(),
+            })
+        );
+
+        let failed = event_dto(ConnectionEvent {
+            timestamp: 44,
+            kind: ConnectionEventKind::DialFailed {
+                error: "timeout".to_string(),
+            },
+        });
+        let json = serde_json::to_value(&failed).unwrap();
+        assert_eq!(
+            json,
+            json!({ "timestamp": 44, "type": "dialFailed", "reason": "timeout" })
+        );
+        // Clients deserialize the events back into the DTO.
+        assert_eq!(serde_json::from_value::<ConnectionEventDto>(json).unwrap(), failed);
+
+        let stopped = event_dto(ConnectionEvent {
+            timestamp: 45,
+            kind: ConnectionEventKind::ProtocolStopped {
+                cause: "stream closed".to_string(),
+            },
+        });
+        assert_eq!(
+            stopped.kind,
+            ConnectionEventKindDto::ProtocolStopped {
+                reason: "stream closed".to_string()
+            }
+        );
+    }
+}
@@ -0,0 +1,64 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::IpAddr;
+
+use bee_protocol::{types::milestone_key_range::MilestoneKeyRange, workers::MilestoneKeys};
+use bee_runtime::resource::ResourceHandle;
+use log::info;
+use serde_json::Value as JsonValue;
+use warp::{filters::BoxedFilter, reject, Filter, Rejection, Reply};
+
+use crate::{
+    endpoints::{
+        config::ROUTE_RELOAD_COORDINATOR_KEYS, filters::with_milestone_keys, permission::has_permission,
+        rejection::CustomRejection, routes::api::v1::coordinator_keys::coordinator_keys,
+    },
+    types::dtos::MilestoneKeyRangeDto,
+};
+
+fn path() -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    super::path()
+        .and(warp::path("coordinator"))
+        .and(warp::path("keys"))
+        .and(warp::path("reload"))
+        .and(warp::path::end())
+}
+
+pub(crate) fn filter(
+    public_routes: Box<[String]>,
+    allowed_ips: Box<[IpAddr]>,
+    milestone_keys: ResourceHandle<MilestoneKeys>,
+) -> BoxedFilter<(impl Reply,)> {
+    self::path()
+        .and(warp::post())
+        .and(has_permission(
+            ROUTE_RELOAD_COORDINATOR_KEYS,
+            public_routes,
+            allowed_ips,
+        ))
+        .and(warp::body::json())
+        .and(with_milestone_keys(milestone_keys))
+        .and_then(|value, milestone_keys| async move { reload_coordinator_keys(value, milestone_keys) })
+        .boxed()
+}
+
+pub(crate) fn reload_coordinator_keys(
+    value: JsonValue,
+    milestone_keys: ResourceHandle<MilestoneKeys>,
+) -> Result<impl Reply, Rejection> {
+    let key_ranges = serde_json::from_value::<Vec<MilestoneKeyRangeDto>>(value["publicKeyRanges"].clone())
+        .map_err(|e| reject::custom(CustomRejection::BadRequest(format!("invalid public key ranges: {}", e))))?
+        .iter()
+        .map(MilestoneKeyRange::from)
+        .collect::<Vec<_>>();
+    let count = key_ranges.len();
+
+    milestone_keys
+        .reload(key_ranges)
+        .map_err(|e| reject::custom(CustomRejection::BadRequest(format!("invalid public key ranges: {}", e))))?;
+
+    info!("Reloaded {} coordinator public key ranges.", count);
+
+    coordinator_keys(milestone_keys).map_err(|e| match e {})
+}
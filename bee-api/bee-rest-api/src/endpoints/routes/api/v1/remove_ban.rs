@@ -0,0 +1,54 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::IpAddr;
+
+use bee_gossip::{Command, NetworkCommandSender};
+use bee_runtime::resource::ResourceHandle;
+use serde_json::Value as JsonValue;
+use warp::{filters::BoxedFilter, http::StatusCode, reject, Filter, Rejection, Reply};
+
+use super::add_ban::{ban_target, BanTarget};
+use crate::endpoints::{
+    config::ROUTE_REMOVE_BAN, filters::with_network_command_sender, permission::has_permission,
+    rejection::CustomRejection,
+};
+
+fn path() -> impl Filter<Extract = (), Error = warp::Rejection> + Clone {
+    super::path()
+        .and(warp::path("peers"))
+        .and(warp::path("bans"))
+        .and(warp::path::end())
+}
+
+pub(crate) fn filter(
+    public_routes: Box<[String]>,
+    allowed_ips: Box<[IpAddr]>,
+    network_command_sender: ResourceHandle<NetworkCommandSender>,
+) -> BoxedFilter<(impl Reply,)> {
+    self::path()
+        .and(warp::delete())
+        .and(has_permission(ROUTE_REMOVE_BAN, public_routes, allowed_ips))
+        .and(warp::body::json())
+        .and(with_network_command_sender(network_command_sender))
+        .and_then(|value, network_controller| async move { remove_ban(value, network_controller) })
+        .boxed()
+}
+
+pub(crate) fn remove_ban(
+    value: JsonValue,
+    network_controller: ResourceHandle<NetworkCommandSender>,
+) -> Result<impl Reply, Rejection> {
+    let command = match ban_target(&value)? {
+        BanTarget::Peer(peer_id) => Command::UnbanPeer { peer_id },
+        BanTarget::Address(address) => Command::UnbanAddress { address },
+    };
+
+    if let Err(e) = network_controller.send(command) {
+        return Err(reject::custom(CustomRejection::NotFound(format!(
+            "failed to remove ban: {}",
+            e
+        ))));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,60 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::IpAddr;
+
+use bee_message::MessageId;
+use bee_runtime::resource::ResourceHandle;
+use bee_tangle::{solid_entry_point::SolidEntryPoint, Tangle};
+use warp::{filters::BoxedFilter, reject, Filter, Rejection, Reply};
+
+use crate::{
+    endpoints::{
+        config::ROUTE_SOLID_ENTRY_POINT, filters::with_tangle, path_params::message_id, permission::has_permission,
+        rejection::CustomRejection, storage::StorageBackend,
+    },
+    types::{body::SuccessBody, responses::SolidEntryPointResponse},
+};
+
+fn path() -> impl Filter<Extract = (MessageId,), Error = Rejection> + Clone {
+    super::path()
+        .and(warp::path("solid-entry-points"))
+        .and(message_id())
+        .and(warp::path::end())
+}
+
+pub(crate) fn filter<B: StorageBackend>(
+    public_routes: Box<[String]>,
+    allowed_ips: Box<[IpAddr]>,
+    tangle: ResourceHandle<Tangle<B>>,
+) -> BoxedFilter<(impl Reply,)> {
+    self::path()
+        .and(warp::get())
+        .and(has_permission(ROUTE_SOLID_ENTRY_POINT, public_routes, allowed_ips))
+        .and(with_tangle(tangle))
+        .and_then(solid_entry_point)
+        .boxed()
+}
+
+pub(crate) async fn solid_entry_point<B: StorageBackend>(
+    message_id: MessageId,
+    tangle: ResourceHandle<Tangle<B>>,
+) -> Result<impl Reply, Rejection> {
+    match tangle
+        .get_solid_entry_point_index(&SolidEntryPoint::from(message_id))
+        .await
+    {
+        Some(index) => Ok(warp::reply::json(&SuccessBody::new(SolidEntryPointResponse {
+            message_id: message_id.to_string(),
+            milestone_index: *index,
+            is_stored: tangle.contains(&message_id),
+            referenced_by_milestone_index: tangle
+                .get_metadata(&message_id)
+                .and_then(|metadata| metadata.milestone_index())
+                .map(|index| *index),
+        }))),
+        None => Err(reject::custom(CustomRejection::NotFound(
+            "message is not a solid entry point".to_string(),
+        ))),
+    }
+}
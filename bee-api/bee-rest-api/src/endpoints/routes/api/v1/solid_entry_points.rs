@@ -0,0 +1,52 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::IpAddr;
+
+use bee_runtime::resource::ResourceHandle;
+use bee_tangle::Tangle;
+use warp::{filters::BoxedFilter, Filter, Rejection, Reply};
+
+use crate::{
+    endpoints::{
+        config::ROUTE_SOLID_ENTRY_POINTS, filters::with_tangle, permission::has_permission, storage::StorageBackend,
+    },
+    types::{body::SuccessBody, dtos::SolidEntryPointDto, responses::SolidEntryPointsResponse},
+};
+
+fn path() -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    super::path()
+        .and(warp::path("solid-entry-points"))
+        .and(warp::path::end())
+}
+
+pub(crate) fn filter<B: StorageBackend>(
+    public_routes: Box<[String]>,
+    allowed_ips: Box<[IpAddr]>,
+    tangle: ResourceHandle<Tangle<B>>,
+) -> BoxedFilter<(impl Reply,)> {
+    self::path()
+        .and(warp::get())
+        .and(has_permission(ROUTE_SOLID_ENTRY_POINTS, public_routes, allowed_ips))
+        .and(with_tangle(tangle))
+        .and_then(solid_entry_points)
+        .boxed()
+}
+
+pub(crate) async fn solid_entry_points<B: StorageBackend>(
+    tangle: ResourceHandle<Tangle<B>>,
+) -> Result<impl Reply, Rejection> {
+    let mut seps = tangle
+        .get_solid_entry_points()
+        .await
+        .iter()
+        .map(|(sep, index)| SolidEntryPointDto {
+            message_id: sep.message_id().to_string(),
+            milestone_index: **index,
+        })
+        .collect::<Vec<_>>();
+
+    seps.sort_unstable_by_key(|sep| sep.milestone_index);
+
+    Ok(warp::reply::json(&SuccessBody::new(SolidEntryPointsResponse(seps))))
+}
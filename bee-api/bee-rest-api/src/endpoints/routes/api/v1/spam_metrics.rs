@@ -0,0 +1,40 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{convert::Infallible, net::IpAddr};
+
+use bee_protocol::workers::SpamTracker;
+use bee_runtime::resource::ResourceHandle;
+use warp::{filters::BoxedFilter, Filter, Rejection, Reply};
+
+use crate::{
+    endpoints::{config::ROUTE_SPAM_METRICS, filters::with_spam_tracker, permission::has_permission},
+    types::{body::SuccessBody, dtos::SpamMetricsDto, responses::SpamMetricsResponse},
+};
+
+fn path() -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    super::path()
+        .and(warp::path("metrics"))
+        .and(warp::path("spam"))
+        .and(warp::path::end())
+}
+
+pub(crate) fn filter(
+    public_routes: Box<[String]>,
+    allowed_ips: Box<[IpAddr]>,
+    spam_tracker: ResourceHandle<SpamTracker>,
+) -> BoxedFilter<(impl Reply,)> {
+    self::path()
+        .and(warp::get())
+        .and(has_permission(ROUTE_SPAM_METRICS, public_routes, allowed_ips))
+        .and(with_spam_tracker(spam_tracker))
+        .and_then(|spam_tracker| async move { spam_metrics(spam_tracker) })
+        .boxed()
+}
+
+pub(crate) fn spam_metrics(spam_tracker: ResourceHandle<SpamTracker>) -> Result<impl Reply, Infallible> {
+    Ok(warp::reply::json(&SuccessBody::new(SpamMetricsResponse {
+        latest: SpamMetricsDto::from(&spam_tracker.latest()),
+        average: SpamMetricsDto::from(&spam_tracker.average()),
+    })))
+}
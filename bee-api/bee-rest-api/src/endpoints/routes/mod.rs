@@ -6,10 +6,11 @@ pub mod health;
 
 use std::net::IpAddr;
 
-use bee_gossip::NetworkCommandSender;
+use bee_gossip::{NetworkBans, NetworkCommandSender, NetworkHistory};
 use bee_ledger::workers::consensus::ConsensusWorkerCommand;
 use bee_protocol::workers::{
-    config::ProtocolConfig, MessageRequesterWorker, MessageSubmitterWorkerEvent, PeerManager, RequestedMessages,
+    config::ProtocolConfig, MessageRequesterWorker, MessageSubmitterWorkerEvent, MilestoneKeys, OrphanedMessages,
+    PeerManager, RequestedMessages, SpamTracker,
 };
 use bee_runtime::{event::Bus, node::NodeInfo, resource::ResourceHandle};
 use bee_tangle::Tangle;
@@ -36,6 +37,11 @@ pub(crate) fn filter_all<B: StorageBackend>(
     message_requester: MessageRequesterWorker,
     requested_messages: ResourceHandle<RequestedMessages>,
     consensus_worker: mpsc::UnboundedSender<ConsensusWorkerCommand>,
+    orphaned_messages: ResourceHandle<OrphanedMessages>,
+    milestone_keys: ResourceHandle<MilestoneKeys>,
+    spam_tracker: ResourceHandle<SpamTracker>,
+    network_bans: ResourceHandle<NetworkBans>,
+    network_history: ResourceHandle<NetworkHistory>,
 ) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
     api::filter(
         public_routes.clone(),
@@ -54,6 +60,11 @@ pub(crate) fn filter_all<B: StorageBackend>(
         message_requester,
         requested_messages,
         consensus_worker,
+        orphaned_messages,
+        milestone_keys,
+        spam_tracker,
+        network_bans,
+        network_history,
     )
     .or(health::filter(public_routes, allowed_ips, tangle, peer_manager))
 }
@@ -24,7 +24,11 @@ use bee_message::{
     Message, MessageBuilder, MessageId,
 };
 #[cfg(feature = "peer")]
-use bee_protocol::types::peer::Peer;
+use bee_protocol::types::{metrics::SpamMetrics, milestone_key_range::MilestoneKeyRange, peer::Peer};
+#[cfg(feature = "endpoints")]
+use bee_protocol::workers::event::MessageOrphaned;
+#[cfg(feature = "endpoints")]
+use bee_tangle::milestone_stats::{LatencyStats, MilestoneStats};
 use serde::{Deserialize, Serialize, Serializer};
 use serde_json::Value;
 
@@ -808,6 +812,141 @@ impl TryFrom<&TreasuryTransactionPayloadDto> for TreasuryTransactionPayload {
     }
 }
 
+/// Describes a solid entry point.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SolidEntryPointDto {
+    #[serde(rename = "messageId")]
+    pub message_id: String,
+    #[serde(rename = "milestoneIndex")]
+    pub milestone_index: u32,
+}
+
+/// Describes a message that was declared orphaned.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OrphanedMessageDto {
+    #[serde(rename = "messageId")]
+    pub message_id: String,
+    #[serde(rename = "milestoneIndex")]
+    pub milestone_index: u32,
+    #[serde(rename = "arrivalTimestamp")]
+    pub arrival_timestamp: u64,
+    #[serde(rename = "peerId", skip_serializing_if = "Option::is_none")]
+    pub peer_id: Option<String>,
+}
+
+#[cfg(feature = "endpoints")]
+impl From<&MessageOrphaned> for OrphanedMessageDto {
+    fn from(value: &MessageOrphaned) -> Self {
+        OrphanedMessageDto {
+            message_id: value.message_id.to_string(),
+            milestone_index: *value.milestone_index,
+            arrival_timestamp: value.arrival_timestamp,
+            peer_id: value.peer_id.map(|peer_id| peer_id.to_string()),
+        }
+    }
+}
+
+/// Describes a coordinator public key and the milestones it is applicable for.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MilestoneKeyRangeDto {
+    #[serde(rename = "publicKey")]
+    pub public_key: String,
+    pub start: u32,
+    pub end: u32,
+}
+
+#[cfg(feature = "peer")]
+impl From<&MilestoneKeyRange> for MilestoneKeyRangeDto {
+    fn from(value: &MilestoneKeyRange) -> Self {
+        MilestoneKeyRangeDto {
+            public_key: value.public_key().clone(),
+            start: *value.start(),
+            end: *value.end(),
+        }
+    }
+}
+
+#[cfg(feature = "peer")]
+impl From<&MilestoneKeyRangeDto> for MilestoneKeyRange {
+    fn from(value: &MilestoneKeyRangeDto) -> Self {
+        MilestoneKeyRange::new(value.public_key.clone(), value.start.into(), value.end.into())
+    }
+}
+
+/// Describes the messages per second by payload kind and by the most used indexation tags.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SpamMetricsDto {
+    pub indexation: f64,
+    pub transaction: f64,
+    pub milestone: f64,
+    #[serde(rename = "noPayload")]
+    pub no_payload: f64,
+    #[serde(rename = "topTags")]
+    pub top_tags: Vec<SpamTagDto>,
+}
+
+/// Describes the messages per second of an indexation tag.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SpamTagDto {
+    pub tag: String,
+    #[serde(rename = "messagesPerSecond")]
+    pub messages_per_second: f64,
+}
+
+#[cfg(feature = "peer")]
+impl From<&SpamMetrics> for SpamMetricsDto {
+    fn from(value: &SpamMetrics) -> Self {
+        SpamMetricsDto {
+            indexation: value.indexation,
+            transaction: value.transaction,
+            milestone: value.milestone,
+            no_payload: value.no_payload,
+            top_tags: value
+                .top_tags
+                .iter()
+                .map(|(tag, messages_per_second)| SpamTagDto {
+                    tag: hex::encode(tag),
+                    messages_per_second: *messages_per_second,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Describes the ban of a peer or of an address.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BanDto {
+    #[serde(rename = "peerId", skip_serializing_if = "Option::is_none")]
+    pub peer_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(rename = "expiresAt", skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+}
+
+/// Describes an event in the connection history of a peer.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ConnectionEventDto {
+    pub timestamp: u64,
+    #[serde(flatten)]
+    pub kind: ConnectionEventKindDto,
+}
+
+/// Describes what happened in a connection event.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ConnectionEventKindDto {
+    Dialed { address: String },
+    DialFailed { reason: String },
+    Unreachable { reason: String },
+    ProtocolEstablished { address: String, origin: String },
+    ProtocolRejected { reason: String },
+    ProtocolStopped { reason: String },
+    Disconnected { reason: String },
+}
+
 /// Describes a peer.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct PeerDto {
@@ -817,9 +956,15 @@ pub struct PeerDto {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub alias: Option<String>,
     pub relation: RelationDto,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
     pub connected: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub gossip: Option<GossipDto>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reconnect: Option<ReconnectDto>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bandwidth: Option<BandwidthDto>,
 }
 
 #[cfg(feature = "peer")]
@@ -838,6 +983,7 @@ impl From<&Peer> for PeerDto {
                     RelationDto::Autopeered
                 }
             },
+            group: peer.group().map(|group| group.name.clone()),
             connected: peer.is_connected(),
             gossip: Some(GossipDto {
                 heartbeat: HeartbeatDto {
@@ -861,10 +1007,92 @@ impl From<&Peer> for PeerDto {
                     dropped_packets: 0,
                 },
             }),
+            reconnect: {
+                let reconnect = peer.reconnect();
+
+                if reconnect.attempts > 0 || reconnect.last_error.is_some() {
+                    Some(ReconnectDto {
+                        attempts: reconnect.attempts,
+                        next_attempt_at: reconnect.next_attempt_at,
+                        last_error: reconnect.last_error,
+                    })
+                } else {
+                    None
+                }
+            },
+            bandwidth: {
+                let bandwidth = peer.bandwidth();
+
+                Some(BandwidthDto {
+                    inbound_bytes: bandwidth.inbound_bytes(),
+                    outbound_bytes: bandwidth.outbound_bytes(),
+                    protocols: bandwidth
+                        .protocols()
+                        .into_iter()
+                        .map(|(version, traffic)| ProtocolBandwidthDto {
+                            protocol: version.to_string(),
+                            inbound_bytes: traffic.inbound_bytes,
+                            outbound_bytes: traffic.outbound_bytes,
+                        })
+                        .collect(),
+                    packet_types: bandwidth
+                        .packet_types()
+                        .into_iter()
+                        .map(|(packet_type, traffic)| PacketTypeBandwidthDto {
+                            packet_type,
+                            inbound_bytes: traffic.inbound_bytes,
+                            outbound_bytes: traffic.outbound_bytes,
+                        })
+                        .collect(),
+                })
+            },
         }
     }
 }
 
+/// Describes the bytes exchanged with a peer over the gossip protocol.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BandwidthDto {
+    #[serde(rename = "inboundBytes")]
+    pub inbound_bytes: u64,
+    #[serde(rename = "outboundBytes")]
+    pub outbound_bytes: u64,
+    pub protocols: Vec<ProtocolBandwidthDto>,
+    #[serde(rename = "packetTypes")]
+    pub packet_types: Vec<PacketTypeBandwidthDto>,
+}
+
+/// Describes the bytes exchanged with a peer over a version of the gossip protocol.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProtocolBandwidthDto {
+    pub protocol: String,
+    #[serde(rename = "inboundBytes")]
+    pub inbound_bytes: u64,
+    #[serde(rename = "outboundBytes")]
+    pub outbound_bytes: u64,
+}
+
+/// Describes the bytes exchanged with a peer for a packet type.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PacketTypeBandwidthDto {
+    #[serde(rename = "packetType")]
+    pub packet_type: u8,
+    #[serde(rename = "inboundBytes")]
+    pub inbound_bytes: u64,
+    #[serde(rename = "outboundBytes")]
+    pub outbound_bytes: u64,
+}
+
+/// Describes the attempts to reconnect a peer since its last stable connection.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReconnectDto {
+    pub attempts: u32,
+    #[serde(rename = "nextAttemptAt", skip_serializing_if = "Option::is_none")]
+    pub next_attempt_at: Option<u64>,
+    #[serde(rename = "lastError", skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
 /// Returns all information about the gossip stream with the peer.
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 pub struct GossipDto {
@@ -952,3 +1180,52 @@ pub enum LedgerInclusionStateDto {
     #[serde(rename = "noTransaction")]
     NoTransaction,
 }
+
+/// Describes the distribution of latencies, in milliseconds.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LatencyStatsDto {
+    pub min: u64,
+    pub p50: u64,
+    pub p90: u64,
+    pub p99: u64,
+    pub max: u64,
+}
+
+#[cfg(feature = "endpoints")]
+impl From<&LatencyStats> for LatencyStatsDto {
+    fn from(value: &LatencyStats) -> Self {
+        LatencyStatsDto {
+            min: value.min,
+            p50: value.p50,
+            p90: value.p90,
+            p99: value.p99,
+            max: value.max,
+        }
+    }
+}
+
+/// Describes the confirmation statistics of a milestone.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MilestoneStatsDto {
+    pub index: u32,
+    pub timestamp: u64,
+    #[serde(rename = "referencedMessages")]
+    pub referenced_messages: usize,
+    #[serde(rename = "confirmationLatency")]
+    pub confirmation_latency: LatencyStatsDto,
+    #[serde(rename = "solidificationDelay")]
+    pub solidification_delay: LatencyStatsDto,
+}
+
+#[cfg(feature = "endpoints")]
+impl From<&MilestoneStats> for MilestoneStatsDto {
+    fn from(value: &MilestoneStats) -> Self {
+        MilestoneStatsDto {
+            index: *value.index,
+            timestamp: value.timestamp,
+            referenced_messages: value.referenced_messages,
+            confirmation_latency: (&value.confirmation_latency).into(),
+            solidification_delay: (&value.solidification_delay).into(),
+        }
+    }
+}
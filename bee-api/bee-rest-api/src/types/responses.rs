@@ -5,9 +5,35 @@ use serde::{Deserialize, Serialize};
 
 use crate::types::{
     body::BodyInner,
-    dtos::{LedgerInclusionStateDto, MessageDto, OutputDto, PeerDto, ReceiptDto},
+    dtos::{
+        BanDto, ConnectionEventDto, LedgerInclusionStateDto, MessageDto, MilestoneKeyRangeDto, MilestoneStatsDto,
+        OrphanedMessageDto, OutputDto, PeerDto, ReceiptDto, SolidEntryPointDto, SpamMetricsDto,
+    },
 };
 
+/// Response of GET /api/v1/coordinator/keys and POST /api/v1/coordinator/keys/reload.
+/// Returns the coordinator public key ranges used to validate milestones.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CoordinatorKeysResponse {
+    #[serde(rename = "minThreshold")]
+    pub min_threshold: usize,
+    #[serde(rename = "publicKeyRanges")]
+    pub public_key_ranges: Vec<MilestoneKeyRangeDto>,
+}
+
+impl BodyInner for CoordinatorKeysResponse {}
+
+/// Response of GET /api/v1/metrics/spam.
+/// Returns the messages per second by payload kind and indexation tag, of the last second and averaged over the last
+/// minute.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SpamMetricsResponse {
+    pub latest: SpamMetricsDto,
+    pub average: SpamMetricsDto,
+}
+
+impl BodyInner for SpamMetricsResponse {}
+
 /// Response of GET /api/v1/info.
 /// Returns general information about the node.
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -123,6 +149,13 @@ pub struct MessageChildrenResponse {
 
 impl BodyInner for MessageChildrenResponse {}
 
+/// Response of GET /api/v1/messages/orphaned.
+/// Returns the most recently orphaned messages.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OrphanedMessagesResponse(pub Vec<OrphanedMessageDto>);
+
+impl BodyInner for OrphanedMessagesResponse {}
+
 /// Response of GET /api/v1/outputs/{output_id}.
 /// Returns all information about a specific output.
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -227,6 +260,20 @@ pub struct UtxoChangesResponse {
 
 impl BodyInner for UtxoChangesResponse {}
 
+/// Response of GET /api/v1/milestones/{milestone_index}/stats.
+/// Returns the confirmation statistics of a milestone.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MilestoneStatsResponse(pub MilestoneStatsDto);
+
+impl BodyInner for MilestoneStatsResponse {}
+
+/// Response of GET /api/v1/milestones/timeline.
+/// Returns the confirmation statistics of the most recently confirmed milestones.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MilestoneTimelineResponse(pub Vec<MilestoneStatsDto>);
+
+impl BodyInner for MilestoneTimelineResponse {}
+
 /// Response of GET /api/v1/peers.
 /// Returns information about all peers of the node.
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -241,6 +288,27 @@ pub struct AddPeerResponse(pub PeerDto);
 
 impl BodyInner for AddPeerResponse {}
 
+/// Response of GET /api/v1/peers/bans.
+/// Returns the banned peers and addresses.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BansResponse(pub Vec<BanDto>);
+
+impl BodyInner for BansResponse {}
+
+/// Response of GET /api/v1/peers/{peer_id}/history.
+/// Returns the connection history of a peer, oldest event first.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PeerHistoryResponse(pub Vec<ConnectionEventDto>);
+
+impl BodyInner for PeerHistoryResponse {}
+
+/// Response of POST /api/v1/peers/bans.
+/// Returns the added ban.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AddBanResponse(pub BanDto);
+
+impl BodyInner for AddBanResponse {}
+
 /// Response of GET /api/v1/peer/{peer_id}.
 /// Returns information about a specific peer of the node.
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -248,6 +316,39 @@ pub struct PeerResponse(pub PeerDto);
 
 impl BodyInner for PeerResponse {}
 
+/// Response of GET /api/v1/solid-entry-points.
+/// Returns all solid entry points of the node.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SolidEntryPointsResponse(pub Vec<SolidEntryPointDto>);
+
+impl BodyInner for SolidEntryPointsResponse {}
+
+/// Response of GET /api/v1/solid-entry-points/{message_id}.
+/// Returns information about a specific solid entry point.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SolidEntryPointResponse {
+    #[serde(rename = "messageId")]
+    pub message_id: String,
+    #[serde(rename = "milestoneIndex")]
+    pub milestone_index: u32,
+    #[serde(rename = "isStored")]
+    pub is_stored: bool,
+    #[serde(rename = "referencedByMilestoneIndex", skip_serializing_if = "Option::is_none")]
+    pub referenced_by_milestone_index: Option<u32>,
+}
+
+impl BodyInner for SolidEntryPointResponse {}
+
+/// Response of POST /api/plugins/debug/solid-entry-points.
+/// Returns the number of solid entry points re-derived from the confirmed cone of a milestone.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RederiveSolidEntryPointsResponse {
+    pub index: u32,
+    pub count: usize,
+}
+
+impl BodyInner for RederiveSolidEntryPointsResponse {}
+
 /// Response of GET /api/plugins/debug/whiteflag.
 /// Returns the computed merkle tree hash for the given white flag traversal.
 #[derive(Clone, Debug, Serialize, Deserialize)]
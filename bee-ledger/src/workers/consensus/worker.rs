@@ -12,7 +12,7 @@ use bee_message::{
     MessageId,
 };
 use bee_runtime::{event::Bus, node::Node, shutdown_stream::ShutdownStream, worker::Worker};
-use bee_tangle::{ConflictReason, Tangle, TangleWorker};
+use bee_tangle::{milestone_stats::MilestoneStatsBuilder, ConflictReason, Tangle, TangleWorker};
 use futures::{channel::oneshot, stream::StreamExt};
 use log::{debug, error, info, warn};
 use tokio::sync::mpsc;
@@ -50,6 +50,8 @@ pub enum ConsensusWorkerCommand {
         Address,
         oneshot::Sender<(Result<Option<Vec<OutputId>>, Error>, LedgerIndex)>,
     ),
+    /// Command to re-derive the solid entry points from the confirmed cone of a milestone.
+    RederiveSolidEntryPoints(MilestoneIndex, oneshot::Sender<Result<usize, Error>>),
 }
 
 /// The consensus worker.
@@ -180,36 +182,49 @@ where
     *ledger_index = LedgerIndex(milestone.essence().index());
     tangle.update_confirmed_milestone_index(milestone.essence().index());
 
+    let mut stats = MilestoneStatsBuilder::new(milestone.essence().index(), milestone.essence().timestamp());
+
     for message_id in metadata.excluded_no_transaction_messages.iter() {
-        tangle.update_metadata(message_id, |message_metadata| {
+        if let Some(message_metadata) = tangle.update_metadata(message_id, |message_metadata| {
             message_metadata.set_conflict(ConflictReason::None);
             message_metadata.reference(milestone.essence().timestamp());
-        });
+            message_metadata.clone()
+        }) {
+            stats.add(&message_metadata);
+        }
         bus.dispatch(MessageReferenced {
             message_id: *message_id,
         });
     }
 
     for (message_id, conflict) in metadata.excluded_conflicting_messages.iter() {
-        tangle.update_metadata(message_id, |message_metadata| {
+        if let Some(message_metadata) = tangle.update_metadata(message_id, |message_metadata| {
             message_metadata.set_conflict(*conflict);
             message_metadata.reference(milestone.essence().timestamp());
-        });
+            message_metadata.clone()
+        }) {
+            stats.add(&message_metadata);
+        }
         bus.dispatch(MessageReferenced {
             message_id: *message_id,
         });
     }
 
     for message_id in metadata.included_messages.iter() {
-        tangle.update_metadata(message_id, |message_metadata| {
+        if let Some(message_metadata) = tangle.update_metadata(message_id, |message_metadata| {
             message_metadata.set_conflict(ConflictReason::None);
             message_metadata.reference(milestone.essence().timestamp());
-        });
+            message_metadata.clone()
+        }) {
+            stats.add(&message_metadata);
+        }
         bus.dispatch(MessageReferenced {
             message_id: *message_id,
         });
     }
 
+    tangle.add_milestone_stats(stats.finish()).await;
+
     info!(
         "Confirmed milestone {}: referenced {}, no transaction {}, conflicting {}, included {}, consumed {}, created {}, receipt {}.",
         milestone.essence().index(),
@@ -378,6 +393,13 @@ where
                             }
                         }
                     },
+                    ConsensusWorkerCommand::RederiveSolidEntryPoints(index, sender) => {
+                        let result = prune::rederive_seps(&tangle, &storage, index).await.map_err(Into::into);
+
+                        if let Err(e) = sender.send(result) {
+                            error!("Error while sending solid entry points count: {:?}", e);
+                        }
+                    }
                 }
             }
 
@@ -7,7 +7,7 @@ use bee_message::{address::Address, milestone::MilestoneIndex, Error as MessageE
 
 use crate::{
     types::{Balance, Error as TypesError, Unspent},
-    workers::snapshot::error::Error as SnapshotError,
+    workers::{pruning::error::Error as PruningError, snapshot::error::Error as SnapshotError},
 };
 
 /// Errors occurring during ledger workers operations.
@@ -16,6 +16,9 @@ pub enum Error {
     /// Snapshot error.
     #[error("Snapshot error: {0}")]
     Snapshot(#[from] SnapshotError),
+    /// Pruning error.
+    #[error("Pruning error: {0}")]
+    Pruning(#[from] PruningError),
     /// Types error.
     #[error("Types error: {0}")]
     Types(#[from] TypesError),
@@ -143,82 +143,215 @@ pub fn prune_confirmed_data<S: StorageBackend>(
 
         // We can safely skip messages whose approvers are all part of the currently pruned cone. If we are lucky
         // (chances are better with the chosen breadth-first traversal) we've already seen all of its approvers.
-        let mut unvisited_approvers = approvers.into_iter().filter(|id| !visited.contains(id)).peekable();
-        if unvisited_approvers.peek().is_none() {
-            metrics.all_approvers_visited += 1;
-            continue;
+        let max_conf_index = match max_approver_conf_index(
+            approvers,
+            &visited,
+            &mut approver_cache,
+            prune_index,
+            mitigation_threshold,
+            &mut metrics,
+            |approver_id| {
+                Fetch::<MessageId, MessageMetadata>::fetch(storage, approver_id)
+                    .map_err(|e| Error::Storage(Box::new(e)))?
+                    .map(Some)
+                    .ok_or(Error::MissingMetadata(*approver_id))
+            },
+        )? {
+            Some(max_conf_index) => {
+                metrics.not_all_approvers_visited += 1;
+                *max_conf_index
+            }
+            None => {
+                metrics.all_approvers_visited += 1;
+                continue;
+            }
+        };
+
+        // If the highest confirmation index of all its approvers is greater than the index we're pruning, then we need
+        // to keep its message id as a solid entry point.
+        if max_conf_index > *prune_index {
+            new_seps.insert(message_id.into(), max_conf_index.into());
+
+            log::trace!("New SEP: {} until {}", message_id, max_conf_index);
+
+            metrics.found_seps += 1;
         }
+    }
 
-        metrics.not_all_approvers_visited += 1;
+    metrics.prunable_messages = visited.len();
+    metrics.new_seps = new_seps.len();
 
-        // To decide for how long we need to keep a particular SEP around, we need to know the greatest confirming index
-        // taken over all its approvers. We initialise this value with the lowest possible value (the current pruning
-        // target index).
-        let mut max_conf_index = *prune_index;
+    Ok((new_seps, metrics))
+}
 
-        for unvisited_id in unvisited_approvers {
-            let approver_conf_index = if let Some(conf_index) = approver_cache.get(&unvisited_id) {
-                // We fetched the metadata of this approver before (fast path).
-                metrics.approver_cache_hit += 1;
+/// Derives the set of SEPs from the confirmed cone of `target_index`, using the same criterion as
+/// `prune_confirmed_data`, but without deleting anything from the storage.
+///
+/// The traversal does not rely on the current set of SEPs, and stops at messages that are no longer available or that
+/// were confirmed too long before `target_index` to still be directly referenced by a future milestone cone.
+pub fn derive_seps<S: StorageBackend>(
+    tangle: &Tangle<S>,
+    storage: &S,
+    target_index: MilestoneIndex,
+) -> Result<Seps, Error> {
+    // FIXME: mitigation code, see `prune_confirmed_data`.
+    let mitigation_threshold = tangle.config().below_max_depth() + EXTRA_PRUNING_DEPTH; // = BMD + 5
 
-                **conf_index
-            } else {
-                // We need to fetch the metadata of this approver (slow path).
-                metrics.approver_cache_miss += 1;
+    let target_id = *Fetch::<MilestoneIndex, Milestone>::fetch(storage, &target_index)
+        .map_err(|e| Error::Storage(Box::new(e)))?
+        .ok_or(Error::MissingMilestone(target_index))?
+        .message_id();
 
-                let unvisited_md = Fetch::<MessageId, MessageMetadata>::fetch(storage, &unvisited_id)
-                    .map_err(|e| Error::Storage(Box::new(e)))?
-                    .ok_or(Error::MissingMetadata(unvisited_id))?;
+    derive_seps_from(
+        target_id,
+        target_index,
+        mitigation_threshold,
+        |message_id| {
+            Fetch::<MessageId, MessageMetadata>::fetch(storage, message_id).map_err(|e| Error::Storage(Box::new(e)))
+        },
+        |message_id| {
+            Ok(Fetch::<MessageId, Message>::fetch(storage, message_id)
+                .map_err(|e| Error::Storage(Box::new(e)))?
+                .map(|msg| msg.parents().iter().copied().collect()))
+        },
+        |message_id| {
+            Ok(Fetch::<MessageId, Vec<MessageId>>::fetch(storage, message_id)
+                .map_err(|e| Error::Storage(Box::new(e)))?
+                .unwrap_or_default())
+        },
+    )
+}
 
-                // Note, that an unvisited approver of this message can still be confirmed by the same milestone
-                // (despite the breadth-first traversal), if it is also its sibling.
-                let conf_index = unvisited_md.milestone_index().unwrap_or_else(|| {
-                    // ---
-                    // BUG/FIXME:
-                    // In very rare situations the milestone index has not been set for a confirmed message. If that
-                    // message happens to be the one with the highest confirmation index, then the SEP created from the
-                    // current message would be removed too early, i.e. before all of its referrers, and pruning would
-                    // fail without a way to ever recover. We suspect the bug to be a race condition in the
-                    // `update_metadata` method of the `Tangle` implementation.
-                    //
-                    // Mitigation strategy:
-                    // We rely on the coordinator to not confirm something that attaches to a message that was confirmed
-                    // more than 20 milestones (BMD + EXTRA_PRUNING_DEPTH) ago, i.e. a lazy tip.
-                    // ---
-                    log::trace!(
-                        "Bug mitigation: Using '{} + mitigation_threshold ({})' for approver '{}'",
-                        prune_index,
-                        mitigation_threshold,
-                        &unvisited_id
-                    );
+fn derive_seps_from(
+    target_id: MessageId,
+    target_index: MilestoneIndex,
+    mitigation_threshold: u32,
+    fetch_metadata: impl Fn(&MessageId) -> Result<Option<MessageMetadata>, Error>,
+    fetch_parents: impl Fn(&MessageId) -> Result<Option<Vec<MessageId>>, Error>,
+    fetch_approvers: impl Fn(&MessageId) -> Result<Vec<MessageId>, Error>,
+) -> Result<Seps, Error> {
+    let mut visited = Messages::with_capacity(512);
+    let mut approver_cache = ApproverCache::with_capacity(512);
+    let mut seps = Seps::with_capacity(512);
+    // The metrics are only collected to share the approver logic with `prune_confirmed_data`.
+    let mut metrics = ConfirmedDataPruningMetrics::default();
+    let min_conf_index = MilestoneIndex(target_index.saturating_sub(mitigation_threshold));
 
-                    prune_index + mitigation_threshold
-                });
+    let mut to_visit: VecDeque<_> = vec![target_id].into_iter().collect();
 
-                // Update the approver cache.
-                approver_cache.insert(unvisited_id, conf_index);
+    while let Some(message_id) = to_visit.pop_front() {
+        if !visited.insert(message_id) {
+            continue;
+        }
 
-                *conf_index
-            };
+        // Messages that are not available anymore have already been pruned.
+        let conf_index = match fetch_metadata(&message_id)?.and_then(|md| md.milestone_index()) {
+            Some(conf_index) => conf_index,
+            None => continue,
+        };
 
-            max_conf_index = max_conf_index.max(approver_conf_index);
+        if conf_index < min_conf_index {
+            continue;
         }
 
-        // If the highest confirmation index of all its approvers is greater than the index we're pruning, then we need
-        // to keep its message id as a solid entry point.
-        if max_conf_index > *prune_index {
-            new_seps.insert(message_id.into(), max_conf_index.into());
+        if let Some(parents) = fetch_parents(&message_id)? {
+            to_visit.extend(parents);
+        }
 
-            log::trace!("New SEP: {} until {}", message_id, max_conf_index);
+        let max_conf_index = match max_approver_conf_index(
+            fetch_approvers(&message_id)?,
+            &visited,
+            &mut approver_cache,
+            target_index,
+            mitigation_threshold,
+            &mut metrics,
+            &fetch_metadata,
+        )? {
+            Some(max_conf_index) => max_conf_index,
+            None => continue,
+        };
 
-            metrics.found_seps += 1;
+        if max_conf_index > target_index {
+            seps.insert(message_id.into(), max_conf_index);
         }
     }
 
-    metrics.prunable_messages = visited.len();
-    metrics.new_seps = new_seps.len();
+    Ok(seps)
+}
 
-    Ok((new_seps, metrics))
+/// Returns the greatest confirmation index taken over the approvers of a message that are not part of the `visited`
+/// cone, starting from `index`, or `None` if all of its approvers are part of it.
+///
+/// Approvers whose metadata can't be fetched are skipped, approvers without confirmation index are assumed to be
+/// confirmed `mitigation_threshold` milestones after `index`.
+fn max_approver_conf_index(
+    approvers: Vec<MessageId>,
+    visited: &Messages,
+    approver_cache: &mut ApproverCache,
+    index: MilestoneIndex,
+    mitigation_threshold: u32,
+    metrics: &mut ConfirmedDataPruningMetrics,
+    fetch_metadata: impl Fn(&MessageId) -> Result<Option<MessageMetadata>, Error>,
+) -> Result<Option<MilestoneIndex>, Error> {
+    let mut unvisited_approvers = approvers.into_iter().filter(|id| !visited.contains(id)).peekable();
+    if unvisited_approvers.peek().is_none() {
+        return Ok(None);
+    }
+
+    // To decide for how long we need to keep a particular SEP around, we need to know the greatest confirming index
+    // taken over all its approvers. We initialise this value with the lowest possible value (the given index).
+    let mut max_conf_index = index;
+
+    for unvisited_id in unvisited_approvers {
+        let approver_conf_index = if let Some(conf_index) = approver_cache.get(&unvisited_id) {
+            // We fetched the metadata of this approver before (fast path).
+            metrics.approver_cache_hit += 1;
+
+            *conf_index
+        } else {
+            // We need to fetch the metadata of this approver (slow path).
+            metrics.approver_cache_miss += 1;
+
+            let unvisited_md = match fetch_metadata(&unvisited_id)? {
+                Some(unvisited_md) => unvisited_md,
+                None => continue,
+            };
+
+            // Note, that an unvisited approver of this message can still be confirmed by the same milestone
+            // (despite the breadth-first traversal), if it is also its sibling.
+            let conf_index = unvisited_md.milestone_index().unwrap_or_else(|| {
+                // ---
+                // BUG/FIXME:
+                // In very rare situations the milestone index has not been set for a confirmed message. If that
+                // message happens to be the one with the highest confirmation index, then the SEP created from the
+                // current message would be removed too early, i.e. before all of its referrers, and pruning would
+                // fail without a way to ever recover. We suspect the bug to be a race condition in the
+                // `update_metadata` method of the `Tangle` implementation.
+                //
+                // Mitigation strategy:
+                // We rely on the coordinator to not confirm something that attaches to a message that was confirmed
+                // more than 20 milestones (BMD + EXTRA_PRUNING_DEPTH) ago, i.e. a lazy tip.
+                // ---
+                log::trace!(
+                    "Bug mitigation: Using '{} + mitigation_threshold ({})' for approver '{}'",
+                    index,
+                    mitigation_threshold,
+                    &unvisited_id
+                );
+
+                index + mitigation_threshold
+            });
+
+            // Update the approver cache.
+            approver_cache.insert(unvisited_id, conf_index);
+
+            conf_index
+        };
+
+        max_conf_index = max_conf_index.max(approver_conf_index);
+    }
+
+    Ok(Some(max_conf_index))
 }
 
 pub fn prune_unconfirmed_data<S: StorageBackend>(
@@ -462,8 +595,11 @@ fn unwrap_indexation(payload: Option<&Payload>) -> Option<&IndexationPayload> {
 }
 
 // TODO: consider using this instead of 'truncate'
-#[allow(dead_code)]
-fn prune_seps<S: StorageBackend>(storage: &S, batch: &mut S::Batch, seps: &[SolidEntryPoint]) -> Result<usize, Error> {
+pub fn prune_seps<S: StorageBackend>(
+    storage: &S,
+    batch: &mut S::Batch,
+    seps: &[SolidEntryPoint],
+) -> Result<usize, Error> {
     let mut num = 0;
     for sep in seps {
         Batch::<SolidEntryPoint, MilestoneIndex>::batch_delete(storage, batch, sep)
@@ -474,3 +610,66 @@ fn prune_seps<S: StorageBackend>(storage: &S, batch: &mut S::Batch, seps: &[Soli
 
     Ok(num)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap as StdHashMap;
+
+    use super::*;
+
+    // The confirmation index, parents and approvers of the messages of a cone.
+    type Cone = StdHashMap<MessageId, (Option<u32>, Vec<MessageId>, Vec<MessageId>)>;
+
+    #[test]
+    fn derive_seps_from_cone() {
+        let id = |i: u8| MessageId::new([i; 32]);
+        let (milestone, a, b, c, e, f, g, h, old) = (id(1), id(2), id(3), id(4), id(5), id(6), id(7), id(8), id(9));
+
+        // Messages without entry have been pruned.
+        let messages: Cone = vec![
+            (milestone, (Some(10), vec![a, b], vec![])),
+            // All approvers are part of the cone.
+            (a, (Some(10), vec![old, g], vec![milestone])),
+            // Also approved by a message confirmed later, or pruned.
+            (b, (Some(9), vec![e], vec![milestone, c, h])),
+            (c, (Some(12), vec![b], vec![])),
+            // Also approved by a message without confirmation index.
+            (e, (Some(7), vec![], vec![b, f])),
+            (f, (None, vec![e], vec![])),
+            // Confirmed too long ago to be referenced by a future cone.
+            (old, (Some(3), vec![], vec![a, c])),
+        ]
+        .into_iter()
+        .collect();
+
+        let seps = derive_seps_from(
+            milestone,
+            MilestoneIndex(10),
+            5,
+            |message_id| {
+                Ok(messages.get(message_id).map(|(conf_index, _, _)| {
+                    let mut metadata = MessageMetadata::default();
+                    if let Some(conf_index) = conf_index {
+                        metadata.set_milestone_index(MilestoneIndex(*conf_index));
+                    }
+                    metadata
+                }))
+            },
+            |message_id| Ok(messages.get(message_id).map(|(_, parents, _)| parents.clone())),
+            |message_id| {
+                Ok(messages
+                    .get(message_id)
+                    .map(|(_, _, approvers)| approvers.clone())
+                    .unwrap_or_default())
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            seps,
+            vec![(b.into(), MilestoneIndex(12)), (e.into(), MilestoneIndex(15))]
+                .into_iter()
+                .collect()
+        );
+    }
+}
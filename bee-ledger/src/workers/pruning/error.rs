@@ -10,6 +10,12 @@ pub enum Error {
         selected: MilestoneIndex,
         minimum: MilestoneIndex,
     },
+    #[error("solid entry points index {selected} out of range {minimum}..={maximum}")]
+    InvalidSepIndex {
+        selected: MilestoneIndex,
+        minimum: MilestoneIndex,
+        maximum: MilestoneIndex,
+    },
     #[error("missing snapshot info")]
     MissingSnapshotInfo,
     #[error("missing milestone {0}")]
@@ -6,10 +6,10 @@ use std::{
     time::{Instant, SystemTime},
 };
 
-use bee_message::milestone::MilestoneIndex;
+use bee_message::{milestone::MilestoneIndex, MessageId};
 use bee_runtime::event::Bus;
-use bee_storage::access::{Batch, Truncate};
-use bee_tangle::{solid_entry_point::SolidEntryPoint, Tangle};
+use bee_storage::access::{AsIterator, Batch, Fetch, Truncate};
+use bee_tangle::{metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, Tangle};
 use log::{debug, info};
 
 use crate::workers::{
@@ -197,3 +197,119 @@ pub async fn prune<S: StorageBackend>(
 
     Ok(())
 }
+
+/// Re-derives the set of SEPs from the confirmed cone of `index` and replaces the current set with it.
+///
+/// Current SEPs that can't be re-derived because their message has already been pruned are kept as long as they are
+/// still relevant beyond `index`. Returns the size of the new set.
+pub async fn rederive_seps<S: StorageBackend>(
+    tangle: &Tangle<S>,
+    storage: &S,
+    index: MilestoneIndex,
+) -> Result<usize, Error> {
+    let minimum = tangle.get_pruning_index() + 1;
+    let maximum = tangle.get_confirmed_milestone_index();
+
+    if index < minimum || index > maximum {
+        return Err(Error::InvalidSepIndex {
+            selected: index,
+            minimum,
+            maximum,
+        });
+    }
+
+    info!("Re-deriving solid entry points from milestone {}...", index);
+
+    let mut seps = batch::derive_seps(tangle, storage, index)?;
+
+    keep_pruned_seps(&mut seps, tangle.get_solid_entry_points().await, index, |sep| {
+        Ok(Fetch::<MessageId, MessageMetadata>::fetch(storage, sep.message_id())
+            .map_err(|e| Error::Storage(Box::new(e)))?
+            .is_none())
+    })?;
+
+    // The SEPs are replaced within a single batch, since a node without SEPs can't solidify anymore.
+    let mut stale_seps = Vec::new();
+
+    for result in
+        AsIterator::<SolidEntryPoint, MilestoneIndex>::iter(storage).map_err(|e| Error::Storage(Box::new(e)))?
+    {
+        let (sep, _) = result.map_err(|e| Error::Storage(Box::new(e)))?;
+
+        if !seps.contains_key(&sep) {
+            stale_seps.push(sep);
+        }
+    }
+
+    let mut batch = S::batch_begin();
+
+    batch::prune_seps(storage, &mut batch, &stale_seps)?;
+
+    for (sep, sep_index) in &seps {
+        Batch::<SolidEntryPoint, MilestoneIndex>::batch_insert(storage, &mut batch, sep, sep_index)
+            .map_err(|e| Error::Storage(Box::new(e)))?;
+    }
+
+    storage
+        .batch_commit(batch, true)
+        .map_err(|e| Error::Storage(Box::new(e)))?;
+
+    let num_seps = seps.len();
+
+    tangle.replace_solid_entry_points(seps).await;
+
+    info!("Re-derived {} solid entry points from milestone {}.", num_seps, index);
+
+    Ok(num_seps)
+}
+
+/// Adds the current SEPs whose message has already been pruned, and that can therefore not be re-derived, to the
+/// re-derived SEPs, as long as they are still relevant beyond `index`.
+fn keep_pruned_seps(
+    seps: &mut batch::Seps,
+    current_seps: impl IntoIterator<Item = (SolidEntryPoint, MilestoneIndex)>,
+    index: MilestoneIndex,
+    is_pruned: impl Fn(&SolidEntryPoint) -> Result<bool, Error>,
+) -> Result<(), Error> {
+    for (sep, sep_index) in current_seps {
+        if sep_index > index && is_pruned(&sep)? {
+            seps.entry(sep).or_insert(sep_index);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keep_pruned_seps_beyond_index() {
+        let sep = |i: u8| SolidEntryPoint::from(MessageId::new([i; 32]));
+
+        let mut seps = vec![(sep(1), MilestoneIndex(12))].into_iter().collect::<batch::Seps>();
+        let current_seps = vec![
+            // Re-derived.
+            (sep(1), MilestoneIndex(11)),
+            // Pruned, and still relevant.
+            (sep(2), MilestoneIndex(15)),
+            // Not pruned, but not re-derived.
+            (sep(3), MilestoneIndex(15)),
+            // Pruned, but not relevant anymore.
+            (sep(4), MilestoneIndex(9)),
+        ];
+
+        keep_pruned_seps(&mut seps, current_seps, MilestoneIndex(10), |sep| {
+            Ok(sep != &SolidEntryPoint::from(MessageId::new([3; 32])))
+        })
+        .unwrap();
+
+        assert_eq!(
+            seps,
+            vec![(sep(1), MilestoneIndex(12)), (sep(2), MilestoneIndex(15))]
+                .into_iter()
+                .collect()
+        );
+    }
+}
@@ -73,6 +73,7 @@ where
         tangle.update_solid_milestone_index(ledger_index);
         tangle.update_confirmed_milestone_index(ledger_index);
         tangle.update_latest_milestone_index(ledger_index);
+        tangle.restore_tips().await;
 
         Ok(Self {})
     }
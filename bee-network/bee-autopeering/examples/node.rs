@@ -9,7 +9,7 @@ use bee_autopeering::{
     config::AutopeeringConfigBuilder, init, stores::InMemoryPeerStore, AutopeeringConfig, Event, Local,
     NeighborValidator, Peer, ServiceProtocol, AUTOPEERING_SERVICE_NAME,
 };
-use libp2p_core::identity::ed25519::Keypair;
+use libp2p_identity::ed25519::Keypair;
 use log::LevelFilter;
 use serde_json::Value;
 use tokio::signal::ctrl_c;
@@ -55,7 +55,7 @@ async fn main() {
 
     // Set up a local peer, that provides the Autopeering service.
     let mut keypair = hex::decode(BS16_ED25519_PRIVATE_KEY).expect("error decoding keypair");
-    let local = Local::from_keypair(Keypair::decode(&mut keypair).expect("error decoding keypair"))
+    let local = Local::from_keypair(Keypair::try_from_bytes(&mut keypair).expect("error decoding keypair"))
         .expect("error creating local");
 
     local.add_service(
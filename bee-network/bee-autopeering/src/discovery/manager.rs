@@ -1,7 +1,10 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{net::SocketAddr, time::Duration};
+use std::{
+    net::{IpAddr, SocketAddr},
+    time::Duration,
+};
 
 use rand::{seq::index, Rng as _};
 
@@ -546,7 +549,6 @@ fn validate_verification_request(
         // NOTE: the validity of the transmitted source and target addresses is ensured through the
         // `VerificationRequest` type.
         // TODO: maybe add check whether the peer sent the correct source address in the packet.
-        // TODO: store own external IP address as perceived by the peer
         Ok(())
     }
 }
@@ -622,6 +624,9 @@ fn validate_discovery_response(
 fn handle_verification_request(verif_req: VerificationRequest, ctx: RecvContext) {
     log::trace!("Handling verification request from {}.", ctx.peer_id);
 
+    // The peer sent the request to our external IP address as it perceives it.
+    record_external_ip(verif_req.target_addr(), &ctx);
+
     // In any case send a response.
     send_verification_response_to_addr(
         ctx.peer_addr,
@@ -656,6 +661,9 @@ fn handle_verification_request(verif_req: VerificationRequest, ctx: RecvContext)
 fn handle_verification_response(verif_res: VerificationResponse, verif_reqval: RequestValue, ctx: RecvContext) {
     log::trace!("Handling verification response from {}.", ctx.peer_id);
 
+    // The peer received our request from our external IP address as it perceives it.
+    record_external_ip(verif_res.target_addr(), &ctx);
+
     if let Some(verified_count) = peer::set_front_and_update(ctx.peer_id, ctx.active_peers) {
         // If this is the first time the peer was verified:
         // * Update its services;
@@ -689,6 +697,48 @@ fn handle_verification_response(verif_res: VerificationResponse, verif_reqval: R
     }
 }
 
+fn record_external_ip(ip: IpAddr, ctx: &RecvContext) {
+    // Peers within the same local network don't know about the external IP address.
+    if !is_public_ip(ip) {
+        return;
+    }
+
+    if let Some(ip) = ctx.local.report_external_ip(*ctx.peer_id, ip) {
+        log::info!("External IP address as perceived by several peers: {}.", ip);
+
+        // Panic: we don't allow channel send errors.
+        ctx.event_tx
+            .send(Event::ExternalIpDetected { ip })
+            .expect("error publishing external-ip-detected event");
+    }
+}
+
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let octets = ip.octets();
+            // Shared address space used by carrier-grade NATs (100.64.0.0/10).
+            let is_shared = octets[0] == 100 && (octets[1] & 0xc0) == 64;
+
+            !(ip.is_private()
+                || is_shared
+                || ip.is_loopback()
+                || ip.is_link_local()
+                || ip.is_broadcast()
+                || ip.is_documentation()
+                || ip.is_unspecified())
+        }
+        IpAddr::V6(ip) => {
+            let first_segment = ip.segments()[0];
+            // Unique local (fc00::/7) and link-local (fe80::/10) addresses.
+            let is_unique_local = (first_segment & 0xfe00) == 0xfc00;
+            let is_link_local = (first_segment & 0xffc0) == 0xfe80;
+
+            !(ip.is_loopback() || ip.is_unspecified() || is_unique_local || is_link_local)
+        }
+    }
+}
+
 fn handle_discovery_request(_disc_req: DiscoveryRequest, ctx: RecvContext) {
     log::trace!("Handling discovery request from {}.", ctx.peer_id);
 
@@ -1016,3 +1066,44 @@ pub(crate) fn get_verified_peers(active_peers: &ActivePeersList) -> Vec<ActivePe
 
     peers
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn public_ips() {
+        for ip in [
+            "1.1.1.1",
+            "100.63.255.255",
+            "100.128.0.0",
+            "2001:4860:4860::8888",
+            "2a00::1",
+        ] {
+            assert!(is_public_ip(ip.parse().unwrap()), "{}", ip);
+        }
+    }
+
+    #[test]
+    fn non_public_ips() {
+        for ip in [
+            "10.0.0.1",
+            "172.16.0.1",
+            "192.168.1.1",
+            "100.64.0.1",
+            "100.127.255.255",
+            "127.0.0.1",
+            "169.254.0.1",
+            "255.255.255.255",
+            "192.0.2.1",
+            "0.0.0.0",
+            "::1",
+            "::",
+            "fd00::1",
+            "fe80::1",
+            "febf::1",
+        ] {
+            assert!(!is_public_ip(ip.parse().unwrap()), "{}", ip);
+        }
+    }
+}
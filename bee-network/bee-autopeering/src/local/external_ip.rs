@@ -0,0 +1,88 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::VecDeque, net::IpAddr};
+
+use crate::peer::PeerId;
+
+/// The number of distinct peers that need to perceive the same external IP address before it is adopted.
+pub(crate) const MIN_EXTERNAL_IP_AGREEMENT: usize = 3;
+
+// The number of most recent reports that are taken into account, one per peer.
+const MAX_EXTERNAL_IP_REPORTS: usize = 16;
+
+/// Determines the external IP address from the reports of several peers, so that a single peer can't make up one.
+#[derive(Debug, Default)]
+pub(crate) struct ExternalIp {
+    reports: VecDeque<(PeerId, IpAddr)>,
+    ip: Option<IpAddr>,
+}
+
+impl ExternalIp {
+    /// Returns the external IP address, if enough peers agreed on one.
+    pub(crate) fn ip(&self) -> Option<IpAddr> {
+        self.ip
+    }
+
+    /// Records the external IP address as perceived by a peer, and returns the external IP address if it changed.
+    pub(crate) fn report(&mut self, peer_id: PeerId, ip: IpAddr) -> Option<IpAddr> {
+        self.reports.retain(|(reporter, _)| *reporter != peer_id);
+        if self.reports.len() == MAX_EXTERNAL_IP_REPORTS {
+            self.reports.pop_front();
+        }
+        self.reports.push_back((peer_id, ip));
+
+        if self.ip == Some(ip) {
+            return None;
+        }
+
+        let agreement = self.agreement(ip);
+
+        // A new address has to be agreed on by more peers than the current one, e.g. after the ISP assigned a new one.
+        if agreement >= MIN_EXTERNAL_IP_AGREEMENT && self.ip.map_or(true, |current| agreement > self.agreement(current))
+        {
+            self.ip = Some(ip);
+            self.ip
+        } else {
+            None
+        }
+    }
+
+    fn agreement(&self, ip: IpAddr) -> usize {
+        self.reports.iter().filter(|(_, reported)| *reported == ip).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::local::Local;
+
+    #[test]
+    fn external_ip_needs_agreement() {
+        let (ip, other_ip): (IpAddr, IpAddr) = ("198.51.100.1".parse().unwrap(), "203.0.113.1".parse().unwrap());
+        let mut external_ip = ExternalIp::default();
+        let peer_ids = (0..8).map(|_| Local::generate().peer_id()).collect::<Vec<_>>();
+
+        // The same peer reporting several times doesn't count.
+        for _ in 0..MIN_EXTERNAL_IP_AGREEMENT {
+            assert_eq!(external_ip.report(peer_ids[0], ip), None);
+        }
+        assert_eq!(external_ip.report(peer_ids[1], other_ip), None);
+        assert_eq!(external_ip.report(peer_ids[2], ip), None);
+        assert_eq!(external_ip.ip(), None);
+
+        assert_eq!(external_ip.report(peer_ids[3], ip), Some(ip));
+        assert_eq!(external_ip.report(peer_ids[4], ip), None);
+        assert_eq!(external_ip.ip(), Some(ip));
+
+        // A different address is only adopted once more peers agree on it than on the current one.
+        for peer_id in &peer_ids[5..] {
+            assert_eq!(external_ip.report(*peer_id, other_ip), None);
+        }
+        assert_eq!(external_ip.ip(), Some(ip));
+        // Peers changing their mind take back their previous report.
+        assert_eq!(external_ip.report(peer_ids[0], other_ip), Some(other_ip));
+        assert_eq!(external_ip.ip(), Some(other_ip));
+    }
+}
@@ -1,18 +1,21 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+pub(crate) mod external_ip;
 pub(crate) mod salt;
 pub mod services;
 
 use std::{
     fmt,
+    net::IpAddr,
     sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard},
 };
 
 use crypto::signatures::ed25519::{PublicKey, SecretKey as PrivateKey, Signature, SECRET_KEY_LENGTH};
-use libp2p_core::identity::ed25519::Keypair;
+use libp2p_identity::ed25519::Keypair;
 
 use self::{
+    external_ip::ExternalIp,
     salt::{Salt, SALT_LIFETIME_SECS},
     services::{ServiceMap, ServiceProtocol},
 };
@@ -36,6 +39,7 @@ pub enum Error {
 /// * message signing and verification;
 /// * neighbor distance calculation;
 /// * service announcements;
+/// * remembering the external IP address as perceived by other peers;
 #[derive(Clone)]
 pub struct Local {
     inner: Arc<RwLock<LocalInner>>,
@@ -47,6 +51,7 @@ pub struct LocalInner {
     private_key: PrivateKey,
     private_salt: Salt,
     services: ServiceMap,
+    external_ip: ExternalIp,
 }
 
 impl Local {
@@ -62,6 +67,7 @@ impl Local {
             private_key,
             private_salt: Salt::default(),
             services: ServiceMap::default(),
+            external_ip: ExternalIp::default(),
         };
 
         Self {
@@ -113,6 +119,7 @@ impl Local {
                 private_salt: Salt::new(SALT_LIFETIME_SECS),
                 public_salt: Salt::new(SALT_LIFETIME_SECS),
                 services: ServiceMap::default(),
+                external_ip: ExternalIp::default(),
             })),
         }
     }
@@ -162,6 +169,17 @@ impl Local {
         self.read().services().clone()
     }
 
+    /// Returns the external IP address of this identity as perceived by several other peers, if any.
+    pub fn external_ip(&self) -> Option<IpAddr> {
+        self.read().external_ip.ip()
+    }
+
+    /// Records the external IP address of this identity as perceived by a peer, and returns the external IP address
+    /// if enough peers agreed on a new one.
+    pub(crate) fn report_external_ip(&self, peer_id: PeerId, ip: IpAddr) -> Option<IpAddr> {
+        self.write().external_ip.report(peer_id, ip)
+    }
+
     fn read(&self) -> RwLockReadGuard<LocalInner> {
         // Panic: we do not allow the lock to be poisened.
         self.inner.read().expect("error getting read access")
@@ -55,20 +55,20 @@ impl PeerId {
     }
 
     /// Creates the corresponding `libp2p::PeerId`.
-    pub fn libp2p_peer_id(&self) -> libp2p_core::PeerId {
+    pub fn libp2p_peer_id(&self) -> libp2p_identity::PeerId {
         libp2p_peer_id(self.public_key())
     }
 }
 
-/// Creates the corresponding `libp2p_core::PeerId` from a crypto.rs ED25519 public key.
-pub fn libp2p_peer_id(public_key: &PublicKey) -> libp2p_core::PeerId {
-    libp2p_core::PeerId::from_public_key(&libp2p_public_key(public_key))
+/// Creates the corresponding `libp2p_identity::PeerId` from a crypto.rs ED25519 public key.
+pub fn libp2p_peer_id(public_key: &PublicKey) -> libp2p_identity::PeerId {
+    libp2p_identity::PeerId::from_public_key(&libp2p_public_key(public_key))
 }
 
-/// Creates the corresponding `libp2p_core::PublicKey` from a crypto.rs ED25519 public key.
-pub fn libp2p_public_key(public_key: &PublicKey) -> libp2p_core::PublicKey {
-    libp2p_core::PublicKey::Ed25519(
-        libp2p_core::identity::ed25519::PublicKey::decode(public_key.as_ref())
+/// Creates the corresponding `libp2p_identity::PublicKey` from a crypto.rs ED25519 public key.
+pub fn libp2p_public_key(public_key: &PublicKey) -> libp2p_identity::PublicKey {
+    libp2p_identity::PublicKey::from(
+        libp2p_identity::ed25519::PublicKey::try_from_bytes(public_key.as_ref())
             .expect("error decoding ed25519 public key from bytes"),
     )
 }
@@ -123,7 +123,7 @@ impl From<&PeerId> for sled::IVec {
     }
 }
 
-impl From<&PeerId> for libp2p_core::PeerId {
+impl From<&PeerId> for libp2p_identity::PeerId {
     fn from(peer_id: &PeerId) -> Self {
         libp2p_peer_id(peer_id.public_key())
     }
@@ -14,12 +14,12 @@ use libp2p::{
 };
 
 pub fn gen_random_peer_id() -> PeerId {
-    PeerId::from_public_key(&libp2p_core::PublicKey::Ed25519(Keypair::generate().public()))
+    PeerId::from_public_key(&PublicKey::from(Keypair::generate().public()))
 }
 
 pub fn gen_deterministic_peer_id(gen: impl ToString) -> PeerId {
     let keys = gen_deterministic_keys(gen);
-    PeerId::from_public_key(&PublicKey::Ed25519(keys.public()))
+    PeerId::from_public_key(&PublicKey::from(keys.public()))
 }
 
 pub fn gen_deterministic_keys(gen: impl ToString) -> Keypair {
@@ -37,7 +37,7 @@ pub fn gen_deterministic_keys(gen: impl ToString) -> Keypair {
     // The input consists only of valid hex chars and the length for the secret key
     // is also correct. Hence, the `unwrap`s are fine.
     let mut hex_sk = hex::decode(identity_sk).expect("invalid generated secret key");
-    let sk = SecretKey::from_bytes(&mut hex_sk).unwrap();
+    let sk = SecretKey::try_from_bytes(&mut hex_sk).unwrap();
     sk.into()
 }
 
@@ -46,7 +46,7 @@ pub fn get_constant_keys() -> Keypair {
 
     let mut hex_kp = hex::decode(identity_kp).expect("hex decode");
 
-    Keypair::decode(&mut hex_kp[..]).expect("keypair decode")
+    Keypair::try_from_bytes(&mut hex_kp[..]).expect("keypair decode")
 }
 
 pub fn gen_random_keys() -> Keypair {
@@ -3,17 +3,26 @@
 
 #![cfg(feature = "full")]
 
-use std::{borrow::Cow, collections::HashSet};
-
-use libp2p::{multiaddr::Protocol, Multiaddr, PeerId};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
+
+use libp2p::{
+    multiaddr::Protocol,
+    pnet::{KeyParseError, PreSharedKey},
+    Multiaddr, PeerId,
+};
 use serde::Deserialize;
 
-use crate::alias;
+use crate::{alias, peer::info::PeerGroup};
 
 const DEFAULT_BIND_MULTIADDR: &str = "/ip4/0.0.0.0/tcp/15600";
 
 pub const DEFAULT_RECONNECT_INTERVAL_SECS: u64 = 30;
 const MIN_RECONNECT_INTERVAL_SECS: u64 = 1;
+pub const DEFAULT_MAX_RECONNECT_INTERVAL_SECS: u64 = 600;
 
 pub const DEFAULT_MAX_UNKNOWN_PEERS: usize = 4;
 pub const DEFAULT_MAX_DISCOVERED_PEERS: usize = 4;
@@ -56,16 +65,59 @@ pub enum Error {
     /// The provided [`Multiaddr`] lacks the P2p [`Protocol`].
     #[error("Invalid P2p Multiaddr. Did you forget to add '.../p2p/12D3Koo...'?")]
     MissingP2pProtocol,
+
+    /// A static peer belongs to a group that wasn't configured.
+    #[error("Peer group '{}' is not configured.", .0)]
+    UnknownPeerGroup(String),
+
+    /// No bind address was provided.
+    #[error("No bind address provided.")]
+    MissingBindAddress,
+
+    /// The provided [`Multiaddr`] requires a transport that is not supported.
+    #[error("The {} transport is not supported, only TCP, WebSocket and QUIC are.", .0)]
+    UnsupportedTransport(&'static str),
+
+    /// The pre-shared key of the private network is invalid.
+    #[error("Invalid pre-shared key: {}.", .0)]
+    InvalidPreSharedKey(KeyParseError),
+
+    /// A peer id of the private network allow-list is invalid.
+    #[error("Invalid allowed peer id '{}'.", .0)]
+    InvalidAllowedPeer(String),
+
+    /// The private network has no pre-shared key.
+    #[error("The private network has no pre-shared key.")]
+    MissingPreSharedKey,
+
+    /// The allow-list of the private network is empty.
+    #[error("The allow-list of the private network is empty.")]
+    EmptyAllowList,
+
+    /// The private network uses the QUIC transport, whose connections can't be protected by the pre-shared key.
+    #[error("The QUIC address {} can't be used in a private network.", .0)]
+    QuicInPrivateNetwork(Multiaddr),
+
+    /// A static peer is not on the allow-list of the private network.
+    #[error("Static peer {} is not on the allow-list of the private network.", alias!(.0))]
+    StaticPeerNotAllowed(PeerId),
 }
 
 /// The network configuration.
 #[derive(Clone)]
 pub struct NetworkConfig {
-    pub(crate) bind_multiaddr: Multiaddr,
+    pub(crate) bind_multiaddrs: Vec<Multiaddr>,
+    pub(crate) announce_multiaddrs: Vec<Multiaddr>,
     pub(crate) reconnect_interval_secs: u64,
+    pub(crate) max_reconnect_interval_secs: u64,
     pub(crate) max_unknown_peers: usize,
     pub(crate) max_discovered_peers: usize,
+    pub(crate) max_bandwidth_bytes_per_sec: Option<u64>,
+    pub(crate) max_peer_bandwidth_bytes_per_sec: Option<u64>,
     pub(crate) static_peers: HashSet<Peer>,
+    pub(crate) write_back_peers: bool,
+    pub(crate) bans_file: Option<PathBuf>,
+    pub(crate) private_network: Option<PrivateNetworkConfig>,
 }
 
 impl NetworkConfig {
@@ -85,7 +137,7 @@ impl NetworkConfig {
         InMemoryNetworkConfigBuilder::new()
     }
 
-    /// Replaces the address, but keeps the port of the bind address.
+    /// Replaces the bind addresses with a single one using `addr`, but keeps the port of the first bind address.
     ///
     /// The argument `addr` must be either the `Ip4`, `Ip6`, or `Dns` variant of [`Protocol`].
     pub fn replace_addr(&mut self, mut addr: Protocol) -> Result<(), Error> {
@@ -98,27 +150,45 @@ impl NetworkConfig {
         }
 
         // Panic:
-        // The builder ensures that the following unwraps are fine.
-        let port = self.bind_multiaddr.pop().unwrap();
+        // The builder ensures that there is at least one bind address and that the following indexing is fine.
+        let bind_multiaddr = replace_first_protocol(&self.bind_multiaddrs[0], addr);
 
-        let _ = self.bind_multiaddr.pop().unwrap();
-
-        self.bind_multiaddr.push(addr);
-        self.bind_multiaddr.push(port);
+        self.bind_multiaddrs = vec![bind_multiaddr];
 
         Ok(())
     }
 
-    /// Replaces the port of the bind address.
+    /// Replaces the port of all bind addresses.
     ///
-    /// The argument `port` must be the TCP variant of [`Protocol`].
+    /// The argument `port` must be the TCP variant of [`Protocol`]. QUIC bind addresses use the same port over UDP.
     pub fn replace_port(&mut self, port: Protocol) -> Result<(), Error> {
-        if !matches!(port, Protocol::Tcp(_)) {
+        let port = if let Protocol::Tcp(port) = port {
+            port
+        } else {
             return Err(Error::InvalidPortProtocol);
+        };
+
+        for bind_multiaddr in self.bind_multiaddrs.iter_mut() {
+            *bind_multiaddr = bind_multiaddr
+                .iter()
+                .map(|protocol| match protocol {
+                    Protocol::Tcp(_) => Protocol::Tcp(port),
+                    Protocol::Udp(_) => Protocol::Udp(port),
+                    protocol => protocol,
+                })
+                .collect();
         }
 
-        self.bind_multiaddr.pop();
-        self.bind_multiaddr.push(port);
+        Ok(())
+    }
+
+    /// Adds an address to announce to other peers, e.g. the external address of a port mapped on the gateway.
+    pub fn add_announce_multiaddr(&mut self, multiaddr: Multiaddr) -> Result<(), Error> {
+        check_multiaddr(&multiaddr)?;
+
+        if !self.announce_multiaddrs.contains(&multiaddr) {
+            self.announce_multiaddrs.push(multiaddr);
+        }
 
         Ok(())
     }
@@ -134,6 +204,7 @@ impl NetworkConfig {
             peer_id,
             multiaddr,
             alias,
+            group: None,
         }) {
             return Err(Error::DuplicateStaticPeer(peer_id));
         }
@@ -141,9 +212,21 @@ impl NetworkConfig {
         Ok(())
     }
 
-    /// Returns the configured bind address as a [`Multiaddr`].
+    /// Returns the first configured bind address as a [`Multiaddr`].
     pub fn bind_multiaddr(&self) -> &Multiaddr {
-        &self.bind_multiaddr
+        // Panic:
+        // The builder ensures that there is at least one bind address.
+        &self.bind_multiaddrs[0]
+    }
+
+    /// Returns all configured bind addresses.
+    pub fn bind_multiaddrs(&self) -> &[Multiaddr] {
+        &self.bind_multiaddrs
+    }
+
+    /// Returns the addresses announced to other peers, in addition to the bound ones.
+    pub fn announce_multiaddrs(&self) -> &[Multiaddr] {
+        &self.announce_multiaddrs
     }
 
     /// Returns the number of seconds at which reconnect attempts occur.
@@ -151,6 +234,11 @@ impl NetworkConfig {
         self.reconnect_interval_secs
     }
 
+    /// Returns the maximum number of seconds between reconnect attempts to a peer.
+    pub fn max_reconnect_interval_secs(&self) -> u64 {
+        self.max_reconnect_interval_secs
+    }
+
     /// Returns the maximum number of unknown peers that are allowed to connect.
     pub fn max_unknown_peers(&self) -> usize {
         self.max_unknown_peers
@@ -161,10 +249,35 @@ impl NetworkConfig {
         self.max_discovered_peers
     }
 
+    /// Returns the maximum number of bytes per second received, and sent, over all gossip connections, if limited.
+    pub fn max_bandwidth_bytes_per_sec(&self) -> Option<u64> {
+        self.max_bandwidth_bytes_per_sec
+    }
+
+    /// Returns the maximum number of bytes per second received from, and sent to, a single peer, if limited.
+    pub fn max_peer_bandwidth_bytes_per_sec(&self) -> Option<u64> {
+        self.max_peer_bandwidth_bytes_per_sec
+    }
+
     /// Returns the statically configured peers.
     pub fn static_peers(&self) -> &HashSet<Peer> {
         &self.static_peers
     }
+
+    /// Returns whether known peers added or removed at runtime should be written back to the config file.
+    pub fn write_back_peers(&self) -> bool {
+        self.write_back_peers
+    }
+
+    /// Returns the file the banned peers and addresses are persisted to, if any.
+    pub fn bans_file(&self) -> Option<&PathBuf> {
+        self.bans_file.as_ref()
+    }
+
+    /// Returns the private network configuration, if the node is part of a private network.
+    pub fn private_network(&self) -> Option<&PrivateNetworkConfig> {
+        self.private_network.as_ref()
+    }
 }
 
 fn resolve_dns_multiaddr(dns: Cow<'_, str>) -> Result<Protocol, Error> {
@@ -187,11 +300,18 @@ impl Default for NetworkConfig {
         Self {
             // Panic:
             // Unwrapping is fine, because we made sure that the default is parsable.
-            bind_multiaddr: DEFAULT_BIND_MULTIADDR.parse().unwrap(),
+            bind_multiaddrs: vec![DEFAULT_BIND_MULTIADDR.parse().unwrap()],
+            announce_multiaddrs: Vec::new(),
             reconnect_interval_secs: DEFAULT_RECONNECT_INTERVAL_SECS,
+            max_reconnect_interval_secs: DEFAULT_MAX_RECONNECT_INTERVAL_SECS,
             max_unknown_peers: DEFAULT_MAX_UNKNOWN_PEERS,
             max_discovered_peers: DEFAULT_MAX_DISCOVERED_PEERS,
+            max_bandwidth_bytes_per_sec: None,
+            max_peer_bandwidth_bytes_per_sec: None,
             static_peers: Default::default(),
+            write_back_peers: false,
+            bans_file: None,
+            private_network: None,
         }
     }
 }
@@ -202,13 +322,29 @@ impl Default for NetworkConfig {
 pub struct NetworkConfigBuilder {
     #[serde(alias = "bindAddress", alias = "bind_address")]
     bind_multiaddr: Option<Multiaddr>,
+    #[serde(alias = "bindAddresses", alias = "bind_addresses")]
+    bind_multiaddrs: Option<Vec<Multiaddr>>,
+    #[serde(alias = "announceAddresses", alias = "announce_addresses")]
+    announce_multiaddrs: Option<Vec<Multiaddr>>,
     #[serde(alias = "reconnectIntervalSecs")]
     reconnect_interval_secs: Option<u64>,
+    #[serde(alias = "maxReconnectIntervalSecs")]
+    max_reconnect_interval_secs: Option<u64>,
     #[serde(alias = "maxUnknownPeers")]
     max_unknown_peers: Option<usize>,
     #[serde(alias = "maxDiscoveredPeers")]
     max_discovered_peers: Option<usize>,
+    #[serde(alias = "maxBandwidthBytesPerSec")]
+    max_bandwidth_bytes_per_sec: Option<u64>,
+    #[serde(alias = "maxPeerBandwidthBytesPerSec")]
+    max_peer_bandwidth_bytes_per_sec: Option<u64>,
+    #[serde(alias = "bansFile")]
+    bans_file: Option<PathBuf>,
+    #[serde(alias = "peerGroups")]
+    peer_groups: Option<HashMap<String, PeerGroupBuilder>>,
     peering: ManualPeeringConfigBuilder,
+    #[serde(alias = "privateNetwork")]
+    private_network: Option<PrivateNetworkConfigBuilder>,
 }
 
 impl NetworkConfigBuilder {
@@ -217,63 +353,29 @@ impl NetworkConfigBuilder {
         Self::default()
     }
 
-    /// Specifies the bind addresses.
-    pub fn with_bind_multiaddr(mut self, mut multiaddr: Multiaddr) -> Result<Self, Error> {
-        let mut valid = false;
-        let mut is_dns = false;
-
-        for (i, p) in multiaddr.iter().enumerate() {
-            match i {
-                0 => {
-                    if !matches!(p, Protocol::Ip4(_) | Protocol::Ip6(_) | Protocol::Dns(_)) {
-                        return Err(Error::InvalidProtocol(0));
-                    }
-
-                    if matches!(p, Protocol::Dns(_)) {
-                        is_dns = true;
-                    }
-                }
-                1 => {
-                    if !matches!(p, Protocol::Tcp(_)) {
-                        return Err(Error::InvalidProtocol(1));
-                    }
-                    valid = true;
-                }
-                _ => return Err(Error::MultiaddrOverspecified),
-            }
-        }
-        if !valid {
-            return Err(Error::MultiaddrUnderspecified);
-        }
+    /// Specifies the bind address, replacing the bind addresses specified before.
+    pub fn with_bind_multiaddr(mut self, multiaddr: Multiaddr) -> Result<Self, Error> {
+        self.bind_multiaddr.replace(resolve_bind_multiaddr(multiaddr)?);
+        self.bind_multiaddrs = None;
+        Ok(self)
+    }
 
-        if is_dns {
-            let port = multiaddr.pop().unwrap();
-            let port = if let Protocol::Tcp(port) = port {
-                port
-            } else {
-                unreachable!("already checked");
-            };
-            // Panic:
-            // We know at this point, that `multiaddr` is valid, so unwrapping is fine.
-            let ip = if let Protocol::Dns(dns) = multiaddr.pop().unwrap() {
-                let socket_dns = {
-                    let mut socket_addr = String::with_capacity(16);
-                    socket_addr.push_str(&dns);
-                    socket_addr.push(':');
-                    socket_addr.push_str(&port.to_string());
-                    socket_addr
-                };
-
-                resolve_dns_multiaddr(socket_dns.into())?
-            } else {
-                unreachable!("already checked");
-            };
-
-            multiaddr.push(ip);
-            multiaddr.push(Protocol::Tcp(port));
-        }
+    /// Specifies several bind addresses, e.g. an IPv4 and an IPv6 one, replacing the bind addresses specified before.
+    pub fn with_bind_multiaddrs(mut self, multiaddrs: impl IntoIterator<Item = Multiaddr>) -> Result<Self, Error> {
+        self.bind_multiaddrs.replace(
+            multiaddrs
+                .into_iter()
+                .map(resolve_bind_multiaddr)
+                .collect::<Result<_, _>>()?,
+        );
+        self.bind_multiaddr = None;
+        Ok(self)
+    }
 
-        self.bind_multiaddr.replace(multiaddr);
+    /// Specifies an address to announce to other peers, e.g. the public address of a node behind NAT.
+    pub fn with_announce_multiaddr(mut self, multiaddr: Multiaddr) -> Result<Self, Error> {
+        check_multiaddr(&multiaddr)?;
+        self.announce_multiaddrs.get_or_insert_with(Vec::new).push(multiaddr);
         Ok(self)
     }
 
@@ -286,6 +388,15 @@ impl NetworkConfigBuilder {
         self
     }
 
+    /// Specifies the maximum interval (in seconds) between reconnect attempts to a peer, which exponentially back off
+    /// from the reconnect interval.
+    ///
+    /// Values below the reconnect interval are raised to it.
+    pub fn with_max_reconnect_interval_secs(mut self, secs: u64) -> Self {
+        self.max_reconnect_interval_secs.replace(secs);
+        self
+    }
+
     /// Specifies the maximum number of gossip connections with unknown peers.
     pub fn with_max_unknown_peers(mut self, n: usize) -> Self {
         self.max_unknown_peers.replace(n);
@@ -298,18 +409,115 @@ impl NetworkConfigBuilder {
         self
     }
 
+    /// Specifies the maximum number of bytes per second received, and sent, over all gossip connections.
+    pub fn with_max_bandwidth_bytes_per_sec(mut self, bytes_per_sec: u64) -> Self {
+        self.max_bandwidth_bytes_per_sec.replace(bytes_per_sec);
+        self
+    }
+
+    /// Specifies the maximum number of bytes per second received from, and sent to, a single peer.
+    pub fn with_max_peer_bandwidth_bytes_per_sec(mut self, bytes_per_sec: u64) -> Self {
+        self.max_peer_bandwidth_bytes_per_sec.replace(bytes_per_sec);
+        self
+    }
+
+    /// Specifies the file the banned peers and addresses are persisted to. Without it, bans are kept in memory only,
+    /// and are lost on restart.
+    pub fn with_bans_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.bans_file.replace(path.into());
+        self
+    }
+
+    /// Makes the node part of a private network, that only the peers using the same pre-shared key and on the
+    /// allow-list, if any, can connect to.
+    pub fn with_private_network(
+        mut self,
+        pre_shared_key: PreSharedKey,
+        allowed_peers: Option<impl IntoIterator<Item = PeerId>>,
+    ) -> Self {
+        self.private_network.replace(PrivateNetworkConfigBuilder {
+            pre_shared_key: Some(pre_shared_key.to_string()),
+            allowed_peers: allowed_peers
+                .map(|allowed_peers| allowed_peers.into_iter().map(|peer_id| peer_id.to_string()).collect()),
+        });
+        self
+    }
+
     /// Builds the network config.
     pub fn finish(self) -> Result<NetworkConfig, Error> {
+        let reconnect_interval_secs = self.reconnect_interval_secs.unwrap_or(DEFAULT_RECONNECT_INTERVAL_SECS);
+
+        let mut bind_multiaddrs = Vec::new();
+        match (self.bind_multiaddr, self.bind_multiaddrs) {
+            // Panic:
+            // We made sure that the default is parsable.
+            (None, None) => bind_multiaddrs.push(DEFAULT_BIND_MULTIADDR.parse().unwrap()),
+            (bind_multiaddr, multiaddrs) => {
+                for multiaddr in bind_multiaddr.into_iter().chain(multiaddrs.into_iter().flatten()) {
+                    let multiaddr = resolve_bind_multiaddr(multiaddr)?;
+                    if !bind_multiaddrs.contains(&multiaddr) {
+                        bind_multiaddrs.push(multiaddr);
+                    }
+                }
+            }
+        }
+        if bind_multiaddrs.is_empty() {
+            return Err(Error::MissingBindAddress);
+        }
+
+        let announce_multiaddrs = self.announce_multiaddrs.unwrap_or_default();
+        for multiaddr in announce_multiaddrs.iter() {
+            check_multiaddr(multiaddr)?;
+        }
+
+        let peering = match self.peer_groups {
+            Some(peer_groups) => self.peering.finish_with_groups(
+                &peer_groups
+                    .into_iter()
+                    .map(|(name, builder)| (name.clone(), builder.finish(name)))
+                    .collect(),
+            )?,
+            None => self.peering.finish()?,
+        };
+
+        let private_network = self
+            .private_network
+            .map(PrivateNetworkConfigBuilder::finish)
+            .transpose()?;
+        if let Some(private_network) = &private_network {
+            if let Some(multiaddr) = bind_multiaddrs
+                .iter()
+                .chain(announce_multiaddrs.iter())
+                .chain(peering.peers.iter().map(|peer| &peer.multiaddr))
+                .find(|multiaddr| is_quic(multiaddr))
+            {
+                return Err(Error::QuicInPrivateNetwork(multiaddr.clone()));
+            }
+            if let Some(peer) = peering
+                .peers
+                .iter()
+                .find(|peer| !private_network.is_allowed(&peer.peer_id))
+            {
+                return Err(Error::StaticPeerNotAllowed(peer.peer_id));
+            }
+        }
+
         Ok(NetworkConfig {
-            bind_multiaddr: self
-                .bind_multiaddr
-                // Panic:
-                // We made sure that the default is parsable.
-                .unwrap_or_else(|| DEFAULT_BIND_MULTIADDR.parse().unwrap()),
-            reconnect_interval_secs: self.reconnect_interval_secs.unwrap_or(DEFAULT_RECONNECT_INTERVAL_SECS),
+            bind_multiaddrs,
+            announce_multiaddrs,
+            reconnect_interval_secs,
+            max_reconnect_interval_secs: self
+                .max_reconnect_interval_secs
+                .unwrap_or(DEFAULT_MAX_RECONNECT_INTERVAL_SECS)
+                .max(reconnect_interval_secs),
             max_unknown_peers: self.max_unknown_peers.unwrap_or(DEFAULT_MAX_UNKNOWN_PEERS),
             max_discovered_peers: self.max_discovered_peers.unwrap_or(DEFAULT_MAX_DISCOVERED_PEERS),
-            static_peers: self.peering.finish()?.peers,
+            max_bandwidth_bytes_per_sec: self.max_bandwidth_bytes_per_sec,
+            max_peer_bandwidth_bytes_per_sec: self.max_peer_bandwidth_bytes_per_sec,
+            static_peers: peering.peers,
+            write_back_peers: peering.write_back,
+            bans_file: self.bans_file,
+            private_network,
         })
     }
 }
@@ -351,13 +559,20 @@ impl InMemoryNetworkConfigBuilder {
         const DEFAULT_BIND_MULTIADDR_MEM: &str = "/memory/0";
 
         NetworkConfig {
-            bind_multiaddr: self
+            bind_multiaddrs: vec![self
                 .bind_multiaddr
-                .unwrap_or_else(|| DEFAULT_BIND_MULTIADDR_MEM.parse().unwrap()),
+                .unwrap_or_else(|| DEFAULT_BIND_MULTIADDR_MEM.parse().unwrap())],
+            announce_multiaddrs: Vec::new(),
             reconnect_interval_secs: DEFAULT_RECONNECT_INTERVAL_SECS,
+            max_reconnect_interval_secs: DEFAULT_MAX_RECONNECT_INTERVAL_SECS,
             max_unknown_peers: DEFAULT_MAX_UNKNOWN_PEERS,
             max_discovered_peers: DEFAULT_MAX_DISCOVERED_PEERS,
+            max_bandwidth_bytes_per_sec: None,
+            max_peer_bandwidth_bytes_per_sec: None,
             static_peers: Default::default(),
+            write_back_peers: false,
+            bans_file: None,
+            private_network: None,
         }
     }
 }
@@ -365,6 +580,7 @@ impl InMemoryNetworkConfigBuilder {
 #[derive(Clone)]
 pub struct ManualPeeringConfig {
     pub peers: HashSet<Peer>,
+    pub write_back: bool,
 }
 
 #[derive(Clone)]
@@ -372,6 +588,7 @@ pub struct Peer {
     pub peer_id: PeerId,
     pub multiaddr: Multiaddr,
     pub alias: Option<String>,
+    pub group: Option<PeerGroup>,
 }
 
 impl Eq for Peer {}
@@ -390,10 +607,17 @@ impl std::hash::Hash for Peer {
 #[must_use]
 pub struct ManualPeeringConfigBuilder {
     pub peers: Option<Vec<PeerBuilder>>,
+    #[serde(alias = "writeBack")]
+    pub write_back: Option<bool>,
 }
 
 impl ManualPeeringConfigBuilder {
     pub fn finish(self) -> Result<ManualPeeringConfig, Error> {
+        self.finish_with_groups(&HashMap::new())
+    }
+
+    /// Builds the manual peering config, assigning the peers to the given groups.
+    pub fn finish_with_groups(self, groups: &HashMap<String, PeerGroup>) -> Result<ManualPeeringConfig, Error> {
         let peers = match self.peers {
             None => Default::default(),
             Some(peer_builders) => {
@@ -403,10 +627,15 @@ impl ManualPeeringConfigBuilder {
 
                 for builder in peer_builders {
                     let (multiaddr, peer_id) = split_multiaddr(&builder.multiaddr)?;
+                    let group = match builder.group {
+                        Some(name) => Some(groups.get(&name).cloned().ok_or(Error::UnknownPeerGroup(name))?),
+                        None => None,
+                    };
                     if !peers.insert(Peer {
                         peer_id,
                         multiaddr,
                         alias: builder.alias,
+                        group,
                     }) {
                         return Err(Error::DuplicateStaticPeer(peer_id));
                     }
@@ -416,20 +645,93 @@ impl ManualPeeringConfigBuilder {
             }
         };
 
-        Ok(ManualPeeringConfig { peers })
+        Ok(ManualPeeringConfig {
+            peers,
+            write_back: self.write_back.unwrap_or(false),
+        })
     }
 }
 
+/// The configuration of a private network.
+#[derive(Clone, Debug)]
+pub struct PrivateNetworkConfig {
+    pub(crate) pre_shared_key: PreSharedKey,
+    pub(crate) allowed_peers: Option<hashbrown::HashSet<PeerId>>,
+}
+
+impl PrivateNetworkConfig {
+    /// Returns the key the transport is encrypted with.
+    pub fn pre_shared_key(&self) -> &PreSharedKey {
+        &self.pre_shared_key
+    }
+
+    /// Returns the peers that are allowed to connect, if the private network has an allow-list.
+    pub fn allowed_peers(&self) -> Option<&hashbrown::HashSet<PeerId>> {
+        self.allowed_peers.as_ref()
+    }
+
+    /// Returns whether a peer is allowed to connect.
+    ///
+    /// Note: Without an allow-list, all peers using the pre-shared key are allowed.
+    pub fn is_allowed(&self, peer_id: &PeerId) -> bool {
+        self.allowed_peers
+            .as_ref()
+            .map_or(true, |allowed_peers| allowed_peers.contains(peer_id))
+    }
+}
+
+#[derive(Default, Deserialize, PartialEq)]
+pub struct PrivateNetworkConfigBuilder {
+    #[serde(alias = "preSharedKey")]
+    pre_shared_key: Option<String>,
+    #[serde(alias = "allowedPeers")]
+    allowed_peers: Option<Vec<String>>,
+}
+
+impl PrivateNetworkConfigBuilder {
+    pub fn finish(self) -> Result<PrivateNetworkConfig, Error> {
+        // Without the key, anyone on the open network could connect, given that a missing allow-list allows all peers.
+        let pre_shared_key = parse_pre_shared_key(&self.pre_shared_key.ok_or(Error::MissingPreSharedKey)?)?;
+        let allowed_peers = self
+            .allowed_peers
+            .map(|allowed_peers| {
+                if allowed_peers.is_empty() {
+                    return Err(Error::EmptyAllowList);
+                }
+
+                allowed_peers
+                    .into_iter()
+                    .map(|peer_id| peer_id.parse().map_err(|_| Error::InvalidAllowedPeer(peer_id)))
+                    .collect::<Result<_, _>>()
+            })
+            .transpose()?;
+
+        Ok(PrivateNetworkConfig {
+            pre_shared_key,
+            allowed_peers,
+        })
+    }
+}
+
+/// Parses a pre-shared key, given either as the content of a `swarm.key` file or as its 64 hex characters.
+fn parse_pre_shared_key(key: &str) -> Result<PreSharedKey, Error> {
+    let key = key.trim();
+
+    if key.starts_with("/key/") {
+        key.parse()
+    } else {
+        format!("/key/swarm/psk/1.0.0/\n/base16/\n{}", key).parse()
+    }
+    .map_err(Error::InvalidPreSharedKey)
+}
+
 fn split_multiaddr(multiaddr: &str) -> Result<(Multiaddr, PeerId), Error> {
     let mut multiaddr: Multiaddr = multiaddr
         .parse()
         .map_err(|_| Error::ParsingFailed(multiaddr.to_string()))?;
 
-    if let Protocol::P2p(multihash) = multiaddr.pop().ok_or(Error::MultiaddrUnderspecified)? {
-        Ok((
-            multiaddr,
-            PeerId::from_multihash(multihash).expect("Invalid peer Multiaddr: Make sure your peer's Id is complete."),
-        ))
+    if let Protocol::P2p(peer_id) = multiaddr.pop().ok_or(Error::MultiaddrUnderspecified)? {
+        Ok((multiaddr, peer_id))
     } else {
         Err(Error::MissingP2pProtocol)
     }
@@ -441,6 +743,105 @@ pub struct PeerBuilder {
     #[serde(alias = "address")]
     multiaddr: String,
     alias: Option<String>,
+    group: Option<String>,
+}
+
+/// A builder for the priority class of a [`PeerGroup`].
+#[derive(Default, Deserialize, PartialEq)]
+#[must_use]
+pub struct PeerGroupBuilder {
+    #[serde(alias = "maxPeers")]
+    max_peers: Option<usize>,
+    preferred: Option<bool>,
+    #[serde(alias = "banExempt")]
+    ban_exempt: Option<bool>,
+}
+
+impl PeerGroupBuilder {
+    /// Builds the peer group.
+    pub fn finish(self, name: String) -> PeerGroup {
+        PeerGroup {
+            name,
+            max_peers: self.max_peers,
+            preferred: self.preferred.unwrap_or(false),
+            ban_exempt: self.ban_exempt.unwrap_or(true),
+        }
+    }
+}
+
+/// Checks that `multiaddr` consists of an IP address or domain name followed by either a TCP port, optionally
+/// followed by WebSocket, or a UDP port followed by QUIC, and returns whether it uses a domain name.
+fn check_multiaddr(multiaddr: &Multiaddr) -> Result<bool, Error> {
+    let mut valid = false;
+    let mut is_dns = false;
+    let mut is_udp = false;
+
+    for (i, p) in multiaddr.iter().enumerate() {
+        match i {
+            0 => {
+                if !matches!(p, Protocol::Ip4(_) | Protocol::Ip6(_) | Protocol::Dns(_)) {
+                    return Err(Error::InvalidProtocol(0));
+                }
+
+                if matches!(p, Protocol::Dns(_)) {
+                    is_dns = true;
+                }
+            }
+            1 => match p {
+                Protocol::Tcp(_) => valid = true,
+                Protocol::Udp(_) => is_udp = true,
+                _ => return Err(Error::InvalidProtocol(1)),
+            },
+            // WebSocket runs on top of TCP.
+            2 if !is_udp && matches!(p, Protocol::Ws(_) | Protocol::Wss(_)) => {}
+            // QUIC runs on top of UDP.
+            2 if is_udp && matches!(p, Protocol::QuicV1) => valid = true,
+            2 if is_udp && matches!(p, Protocol::Quic) => return Err(Error::UnsupportedTransport("QUIC draft-29")),
+            _ => return Err(Error::MultiaddrOverspecified),
+        }
+    }
+    if is_udp && !valid {
+        return Err(Error::UnsupportedTransport("UDP"));
+    }
+    if !valid {
+        return Err(Error::MultiaddrUnderspecified);
+    }
+
+    Ok(is_dns)
+}
+
+/// Returns whether `multiaddr` requires the QUIC transport.
+fn is_quic(multiaddr: &Multiaddr) -> bool {
+    multiaddr.iter().any(|p| matches!(p, Protocol::QuicV1))
+}
+
+/// Checks a bind address and resolves its domain name, if any, to an IP address.
+fn resolve_bind_multiaddr(multiaddr: Multiaddr) -> Result<Multiaddr, Error> {
+    if !check_multiaddr(&multiaddr)? {
+        return Ok(multiaddr);
+    }
+
+    // Panic:
+    // We know at this point, that `multiaddr` is valid, so unwrapping is fine.
+    let port = multiaddr
+        .iter()
+        .find_map(|p| match p {
+            Protocol::Tcp(port) | Protocol::Udp(port) => Some(port),
+            _ => None,
+        })
+        .unwrap();
+    let ip = if let Protocol::Dns(dns) = multiaddr.iter().next().unwrap() {
+        resolve_dns_multiaddr(format!("{}:{}", dns, port).into())?
+    } else {
+        unreachable!("already checked");
+    };
+
+    Ok(replace_first_protocol(&multiaddr, ip))
+}
+
+/// Returns `multiaddr` with its first protocol, i.e. its IP address or domain name, replaced by `protocol`.
+fn replace_first_protocol(multiaddr: &Multiaddr, protocol: Protocol<'_>) -> Multiaddr {
+    std::iter::once(protocol).chain(multiaddr.iter().skip(1)).collect()
 }
 
 #[cfg(test)]
@@ -455,6 +856,19 @@ mod tests {
             config.bind_multiaddr(),
             &DEFAULT_BIND_MULTIADDR.parse::<Multiaddr>().unwrap()
         );
+        assert!(config.bans_file().is_none());
+    }
+
+    #[test]
+    fn create_with_builder_and_bans_file() {
+        let config = NetworkConfig::build().finish().unwrap();
+        assert!(config.bans_file().is_none());
+
+        let config = NetworkConfig::build()
+            .with_bans_file("./storage/testnet/bans.json")
+            .finish()
+            .unwrap();
+        assert_eq!(config.bans_file(), Some(&PathBuf::from("./storage/testnet/bans.json")));
     }
 
     #[test]
@@ -479,6 +893,181 @@ mod tests {
             .finish();
     }
 
+    #[test]
+    fn create_with_builder_and_websocket_bind_address() {
+        let mut config = NetworkConfig::build()
+            .with_bind_multiaddr("/ip4/127.0.0.1/tcp/1337/ws".parse().unwrap())
+            .unwrap()
+            .finish()
+            .unwrap();
+
+        config.replace_port(Protocol::Tcp(1338)).unwrap();
+
+        assert_eq!(
+            config.bind_multiaddr(),
+            &"/ip4/127.0.0.1/tcp/1338/ws".parse::<Multiaddr>().unwrap()
+        );
+    }
+
+    #[test]
+    fn create_with_builder_and_quic_bind_address() {
+        let mut config = NetworkConfig::build()
+            .with_bind_multiaddrs(vec![
+                "/ip4/127.0.0.1/tcp/1337".parse().unwrap(),
+                "/ip4/127.0.0.1/udp/1337/quic-v1".parse().unwrap(),
+            ])
+            .unwrap()
+            .finish()
+            .unwrap();
+
+        config.replace_port(Protocol::Tcp(1338)).unwrap();
+
+        assert_eq!(
+            config.bind_multiaddrs(),
+            &[
+                "/ip4/127.0.0.1/tcp/1338".parse::<Multiaddr>().unwrap(),
+                "/ip4/127.0.0.1/udp/1338/quic-v1".parse::<Multiaddr>().unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn create_with_builder_and_unsupported_transport() {
+        assert!(matches!(
+            NetworkConfig::build().with_bind_multiaddr("/ip4/127.0.0.1/tcp/1337/quic-v1".parse().unwrap()),
+            Err(Error::MultiaddrOverspecified)
+        ));
+        assert!(matches!(
+            NetworkConfig::build().with_bind_multiaddr("/ip4/127.0.0.1/udp/1337/quic".parse().unwrap()),
+            Err(Error::UnsupportedTransport("QUIC draft-29"))
+        ));
+        assert!(matches!(
+            NetworkConfig::build().with_bind_multiaddr("/ip4/127.0.0.1/udp/1337".parse().unwrap()),
+            Err(Error::UnsupportedTransport("UDP"))
+        ));
+    }
+
+    #[test]
+    fn assign_static_peers_to_groups() {
+        let peer = |group: &str| PeerBuilder {
+            multiaddr: format!("/ip4/127.0.0.1/tcp/15600/p2p/{}", PeerId::random()),
+            alias: None,
+            group: Some(group.to_string()),
+        };
+        let groups = vec![(
+            "internal".to_string(),
+            PeerGroupBuilder {
+                max_peers: Some(2),
+                ..Default::default()
+            }
+            .finish("internal".to_string()),
+        )]
+        .into_iter()
+        .collect();
+
+        let peering = ManualPeeringConfigBuilder {
+            peers: Some(vec![peer("internal")]),
+            ..Default::default()
+        }
+        .finish_with_groups(&groups)
+        .unwrap();
+        let group = peering.peers.into_iter().next().unwrap().group.unwrap();
+
+        assert_eq!(group.name, "internal");
+        assert_eq!(group.max_peers, Some(2));
+        assert!(!group.preferred);
+        assert!(group.ban_exempt);
+
+        assert!(matches!(
+            ManualPeeringConfigBuilder {
+                peers: Some(vec![peer("partner")]),
+                ..Default::default()
+            }
+            .finish_with_groups(&groups),
+            Err(Error::UnknownPeerGroup(_))
+        ));
+    }
+
+    #[test]
+    fn create_private_network() {
+        let key = "a".repeat(64);
+        let peer_id = PeerId::random();
+
+        let private_network = PrivateNetworkConfigBuilder {
+            pre_shared_key: Some(key.clone()),
+            allowed_peers: Some(vec![peer_id.to_string()]),
+        }
+        .finish()
+        .unwrap();
+
+        assert_eq!(
+            private_network.pre_shared_key(),
+            &format!("/key/swarm/psk/1.0.0/\n/base16/\n{}", key).parse().unwrap()
+        );
+        assert!(private_network.is_allowed(&peer_id));
+        assert!(!private_network.is_allowed(&PeerId::random()));
+
+        assert!(matches!(
+            PrivateNetworkConfigBuilder {
+                pre_shared_key: Some("abc".to_string()),
+                allowed_peers: None,
+            }
+            .finish(),
+            Err(Error::InvalidPreSharedKey(_))
+        ));
+
+        // Without an allow-list, the pre-shared key alone keeps others out.
+        let private_network = PrivateNetworkConfigBuilder {
+            pre_shared_key: Some(key.clone()),
+            allowed_peers: None,
+        }
+        .finish()
+        .unwrap();
+
+        assert!(private_network.allowed_peers().is_none());
+        assert!(private_network.is_allowed(&PeerId::random()));
+
+        // An empty private network section, or an allow-list alone, doesn't make a network private.
+        assert!(matches!(
+            PrivateNetworkConfigBuilder::default().finish(),
+            Err(Error::MissingPreSharedKey)
+        ));
+        assert!(matches!(
+            PrivateNetworkConfigBuilder {
+                pre_shared_key: None,
+                allowed_peers: Some(vec![peer_id.to_string()]),
+            }
+            .finish(),
+            Err(Error::MissingPreSharedKey)
+        ));
+        assert!(matches!(
+            PrivateNetworkConfigBuilder {
+                pre_shared_key: Some(key.clone()),
+                allowed_peers: Some(Vec::new()),
+            }
+            .finish(),
+            Err(Error::EmptyAllowList)
+        ));
+
+        let mut builder = NetworkConfigBuilder::default()
+            .with_private_network(*private_network.pre_shared_key(), Some(vec![peer_id]));
+        builder.peering.peers = Some(vec![PeerBuilder {
+            multiaddr: format!("/ip4/127.0.0.1/tcp/15600/p2p/{}", PeerId::random()),
+            alias: None,
+            group: None,
+        }]);
+
+        assert!(matches!(builder.finish(), Err(Error::StaticPeerNotAllowed(_))));
+
+        // QUIC connections can't be protected by the pre-shared key.
+        let builder = NetworkConfigBuilder::default()
+            .with_private_network(*private_network.pre_shared_key(), None::<Vec<PeerId>>)
+            .with_bind_multiaddr("/ip4/127.0.0.1/udp/15600/quic-v1".parse().unwrap())
+            .unwrap();
+
+        assert!(matches!(builder.finish(), Err(Error::QuicInPrivateNetwork(_))));
+    }
+
     #[test]
     fn create_with_builder_and_valid_ip_bind_address() {
         let _config = NetworkConfig::build()
@@ -487,6 +1076,46 @@ mod tests {
             .finish();
     }
 
+    #[test]
+    fn create_with_builder_and_dual_stack_bind_addresses() {
+        let config = NetworkConfig::build()
+            .with_bind_multiaddrs(vec![
+                "/ip4/0.0.0.0/tcp/1337".parse().unwrap(),
+                "/ip6/::/tcp/1337".parse().unwrap(),
+            ])
+            .unwrap()
+            .with_announce_multiaddr("/dns/example.com/tcp/1337".parse().unwrap())
+            .unwrap()
+            .finish()
+            .unwrap();
+
+        assert_eq!(config.bind_multiaddrs().len(), 2);
+        assert_eq!(
+            config.bind_multiaddr(),
+            &"/ip4/0.0.0.0/tcp/1337".parse::<Multiaddr>().unwrap()
+        );
+        assert_eq!(config.announce_multiaddrs().len(), 1);
+    }
+
+    #[test]
+    fn replace_port_of_all_bind_addresses() {
+        let mut config = NetworkConfig::build()
+            .with_bind_multiaddrs(vec![
+                "/ip4/0.0.0.0/tcp/1337".parse().unwrap(),
+                "/ip6/::/tcp/1337".parse().unwrap(),
+            ])
+            .unwrap()
+            .finish()
+            .unwrap();
+
+        config.replace_port(Protocol::Tcp(1338)).unwrap();
+
+        assert!(config
+            .bind_multiaddrs()
+            .iter()
+            .all(|multiaddr| multiaddr.iter().last() == Some(Protocol::Tcp(1338))));
+    }
+
     #[test]
     fn create_with_builder_and_valid_dns_bind_address() {
         let _config = NetworkConfig::build()
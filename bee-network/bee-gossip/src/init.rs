@@ -10,6 +10,8 @@ use super::{
     config::NetworkConfig,
     error::Error,
     peer::{
+        ban::{Bans, NetworkBans},
+        history::NetworkHistory,
         info::{PeerInfo, PeerRelation},
         list::{PeerList, PeerListWrapper},
     },
@@ -30,9 +32,12 @@ pub mod global {
     use super::*;
 
     static RECONNECT_INTERVAL_SECS: OnceCell<u64> = OnceCell::new();
+    static MAX_RECONNECT_INTERVAL_SECS: OnceCell<u64> = OnceCell::new();
     static NETWORK_ID: OnceCell<u64> = OnceCell::new();
     static MAX_UNKNOWN_PEERS: OnceCell<usize> = OnceCell::new();
     static MAX_DISCOVERED_PEERS: OnceCell<usize> = OnceCell::new();
+    static MAX_BANDWIDTH_BYTES_PER_SEC: OnceCell<Option<u64>> = OnceCell::new();
+    static MAX_PEER_BANDWIDTH_BYTES_PER_SEC: OnceCell<Option<u64>> = OnceCell::new();
 
     pub fn set_reconnect_interval_secs(reconnect_interval_secs: u64) {
         if cfg!(test) {
@@ -48,6 +53,20 @@ pub mod global {
         *RECONNECT_INTERVAL_SECS.get().expect("oncecell get")
     }
 
+    pub fn set_max_reconnect_interval_secs(max_reconnect_interval_secs: u64) {
+        if cfg!(test) {
+            let _ = MAX_RECONNECT_INTERVAL_SECS.set(max_reconnect_interval_secs);
+        } else {
+            MAX_RECONNECT_INTERVAL_SECS
+                .set(max_reconnect_interval_secs)
+                .expect("oncecell set");
+        }
+    }
+
+    pub fn max_reconnect_interval_secs() -> u64 {
+        *MAX_RECONNECT_INTERVAL_SECS.get().expect("oncecell get")
+    }
+
     pub fn set_network_id(network_id: u64) {
         if cfg!(test) {
             let _ = NETWORK_ID.set(network_id);
@@ -83,6 +102,34 @@ pub mod global {
     pub fn max_discovered_peers() -> usize {
         *MAX_DISCOVERED_PEERS.get().expect("oncecell get")
     }
+
+    pub fn set_max_bandwidth_bytes_per_sec(max_bandwidth_bytes_per_sec: Option<u64>) {
+        if cfg!(test) {
+            let _ = MAX_BANDWIDTH_BYTES_PER_SEC.set(max_bandwidth_bytes_per_sec);
+        } else {
+            MAX_BANDWIDTH_BYTES_PER_SEC
+                .set(max_bandwidth_bytes_per_sec)
+                .expect("oncecell set");
+        }
+    }
+
+    pub fn max_bandwidth_bytes_per_sec() -> Option<u64> {
+        *MAX_BANDWIDTH_BYTES_PER_SEC.get().expect("oncecell get")
+    }
+
+    pub fn set_max_peer_bandwidth_bytes_per_sec(max_peer_bandwidth_bytes_per_sec: Option<u64>) {
+        if cfg!(test) {
+            let _ = MAX_PEER_BANDWIDTH_BYTES_PER_SEC.set(max_peer_bandwidth_bytes_per_sec);
+        } else {
+            MAX_PEER_BANDWIDTH_BYTES_PER_SEC
+                .set(max_peer_bandwidth_bytes_per_sec)
+                .expect("oncecell set");
+        }
+    }
+
+    pub fn max_peer_bandwidth_bytes_per_sec() -> Option<u64> {
+        *MAX_PEER_BANDWIDTH_BYTES_PER_SEC.get().expect("oncecell get")
+    }
 }
 
 /// Initializes a "standalone" version of the network layer.
@@ -137,11 +184,15 @@ pub mod integrated {
     ) -> Result<(N::Builder, NetworkEventReceiver), Error> {
         let (host_config, service_config, network_command_sender, network_event_receiver) =
             super::init(config, keys, network_id)?;
+        let network_bans = NetworkBans::new(service_config.peerlist.clone());
+        let network_history = NetworkHistory::new(service_config.peerlist.clone());
 
         node_builder = node_builder
             .with_worker_cfg::<NetworkHost>(host_config)
             .with_worker_cfg::<ServiceHost>(service_config)
-            .with_resource(network_command_sender);
+            .with_resource(network_command_sender)
+            .with_resource(network_bans)
+            .with_resource(network_history);
 
         Ok((node_builder, network_event_receiver))
     }
@@ -161,17 +212,27 @@ fn init(
     Error,
 > {
     let NetworkConfig {
-        bind_multiaddr,
+        bind_multiaddrs,
+        announce_multiaddrs,
         reconnect_interval_secs,
+        max_reconnect_interval_secs,
         max_unknown_peers,
         max_discovered_peers,
+        max_bandwidth_bytes_per_sec,
+        max_peer_bandwidth_bytes_per_sec,
         static_peers: peers,
+        write_back_peers: _,
+        bans_file,
+        private_network,
     } = config;
 
     global::set_reconnect_interval_secs(reconnect_interval_secs);
+    global::set_max_reconnect_interval_secs(max_reconnect_interval_secs);
     global::set_network_id(network_id);
     global::set_max_unknown_peers(max_unknown_peers);
     global::set_max_discovered_peers(max_discovered_peers);
+    global::set_max_bandwidth_bytes_per_sec(max_bandwidth_bytes_per_sec);
+    global::set_max_peer_bandwidth_bytes_per_sec(max_peer_bandwidth_bytes_per_sec);
 
     let (command_sender, command_receiver) = command_channel();
     let (internal_command_sender, internal_command_receiver) = command_channel();
@@ -179,15 +240,37 @@ fn init(
     let (event_sender, event_receiver) = event_channel::<Event>();
     let (internal_event_sender, internal_event_receiver) = event_channel::<InternalEvent>();
 
-    let local_keys = identity::Keypair::Ed25519(keys);
+    let local_keys = identity::Keypair::from(keys);
     let local_id = PeerId::from_public_key(&local_keys.public());
 
     event_sender
         .send(Event::LocalIdCreated { local_id })
         .map_err(|_| Error::LocalIdAnnouncementFailed)?;
 
+    let bans = Bans::load(bans_file);
+
+    let (pre_shared_key, allowed_peers) = match private_network {
+        Some(private_network) => {
+            log::info!(
+                "Private network: allowing {} using the pre-shared key.",
+                private_network.allowed_peers.as_ref().map_or_else(
+                    || "all peers".to_string(),
+                    |allowed_peers| format!("{} peers", allowed_peers.len())
+                ),
+            );
+
+            (Some(private_network.pre_shared_key), private_network.allowed_peers)
+        }
+        None => (None, None),
+    };
+
     // TODO: rename to PeerStateMap.
-    let peerlist = PeerListWrapper::new(PeerList::from_peers(local_id, peers.iter().cloned().collect()));
+    let peerlist = PeerListWrapper::new(PeerList::from_peers(
+        local_id,
+        peers.iter().cloned().collect(),
+        bans,
+        allowed_peers,
+    ));
 
     // Publish which known peers were added initially.
     for peer in peers.into_iter() {
@@ -199,20 +282,22 @@ fn init(
                     address: peer.multiaddr,
                     alias: peer.alias.unwrap_or_else(|| alias!(peer_id).into()),
                     relation: PeerRelation::Known,
+                    group: peer.group,
                 },
             })
             .map_err(|_| Error::StaticPeersAnnouncementFailed)?;
     }
 
     // Create the transport layer.
-    let swarm = build_swarm(&local_keys, internal_event_sender.clone()).map_err(|_| Error::CreatingTransportFailed)?;
+    let swarm = build_swarm(&local_keys, pre_shared_key).map_err(|_| Error::CreatingTransportFailed)?;
 
     let network_host_config = NetworkHostConfig {
         internal_event_sender: internal_event_sender.clone(),
         internal_command_receiver,
         peerlist: peerlist.clone(),
         swarm,
-        bind_multiaddr,
+        bind_multiaddrs,
+        announce_multiaddrs,
     };
 
     let service_host_config = ServiceHostConfig {
@@ -13,36 +13,40 @@ mod network;
 mod peer;
 mod service;
 mod swarm;
+mod util;
 
 #[cfg(test)]
 mod tests;
 
 // Always exported
 // Exported only with "full" feature flag.
+#[doc(inline)]
+pub use libp2p_core::multiaddr::{Multiaddr, Protocol};
+#[doc(inline)]
+pub use libp2p_identity::PeerId;
 #[cfg(feature = "full")]
 #[doc(inline)]
-pub use libp2p_core::identity::{
+pub use libp2p_identity::{
     ed25519::{Keypair, SecretKey},
     PublicKey,
 };
-#[doc(inline)]
-pub use libp2p_core::{
-    multiaddr::{Multiaddr, Protocol},
-    PeerId,
-};
 
-pub use self::peer::info::{PeerInfo, PeerRelation};
+pub use self::peer::info::{PeerGroup, PeerInfo, PeerRelation, ReconnectState};
 #[cfg(feature = "full")]
 pub use crate::{
-    config::{NetworkConfig, NetworkConfigBuilder},
+    config::{NetworkConfig, NetworkConfigBuilder, PrivateNetworkConfig},
     error::Error,
     init::{integrated, standalone},
     network::host::integrated::NetworkHost,
     network::origin::Origin,
+    peer::{
+        ban::{Ban, NetworkBans},
+        history::{ConnectionEvent, ConnectionEventKind, NetworkHistory, MAX_CONNECTION_EVENTS_PER_PEER},
+    },
     service::{
         command::{Command, NetworkCommandSender},
         event::{Event, NetworkEventReceiver},
         host::integrated::ServiceHost,
     },
-    swarm::protocols::iota_gossip::{GossipReceiver, GossipSender},
+    swarm::protocols::iota_gossip::{Bandwidth, GossipReceiver, GossipSender, GossipVersion, Traffic},
 };
@@ -3,7 +3,7 @@
 
 use libp2p::{swarm::DialError, Multiaddr, PeerId};
 
-use crate::alias;
+use crate::{alias, peer::error::Error as PeerError};
 
 #[derive(Debug, thiserror::Error)]
 // TODO
@@ -15,8 +15,8 @@ pub enum Error {
     #[error("Dialing address {0} failed. Cause: {1:?}")]
     DialingAddressFailed(Multiaddr, DialError),
 
-    #[error("Dialing peer {} was denied.", alias!(.0))]
-    DialingPeerDenied(PeerId),
+    #[error("Dialing peer {} was denied. Cause: {1}", alias!(.0))]
+    DialingPeerDenied(PeerId, PeerError),
 
     #[error("Dialing peer {} failed. Cause: {1:?}", alias!(.0))]
     DialingPeerFailed(PeerId, DialError),
@@ -1,19 +1,27 @@
 // Copyright 2020-2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use std::net::IpAddr;
+
 use futures::{channel::oneshot, StreamExt};
-use libp2p::{swarm::SwarmEvent, Multiaddr, PeerId, Swarm};
+use hashbrown::HashMap;
+use libp2p::{
+    core::transport::ListenerId,
+    multiaddr::Protocol,
+    swarm::{dial_opts::DialOpts, SwarmEvent},
+    Multiaddr, PeerId, Swarm,
+};
 use log::*;
 
 use super::error::Error;
 use crate::{
     alias,
-    peer::{info::PeerInfo, list::PeerListWrapper as PeerList},
+    peer::{history::ConnectionEventKind, info::PeerInfo, list::PeerListWrapper as PeerList},
     service::{
         command::{Command, CommandReceiver},
         event::{InternalEvent, InternalEventSender},
     },
-    swarm::behaviour::SwarmBehaviour,
+    swarm::behaviour::{process_behaviour_event, SwarmBehaviour, SwarmBehaviourEvent},
 };
 
 pub struct NetworkHostConfig {
@@ -21,7 +29,8 @@ pub struct NetworkHostConfig {
     pub internal_command_receiver: CommandReceiver,
     pub peerlist: PeerList,
     pub swarm: Swarm<SwarmBehaviour>,
-    pub bind_multiaddr: Multiaddr,
+    pub bind_multiaddrs: Vec<Multiaddr>,
+    pub announce_multiaddrs: Vec<Multiaddr>,
 }
 
 pub mod integrated {
@@ -101,12 +110,24 @@ async fn network_host_processor(
         mut internal_command_receiver,
         peerlist,
         mut swarm,
-        bind_multiaddr,
+        bind_multiaddrs,
+        announce_multiaddrs,
     } = config;
 
-    // Try binding to the configured bind address.
-    info!("Binding to: {}", bind_multiaddr);
-    let _listener_id = Swarm::listen_on(&mut swarm, bind_multiaddr).map_err(|_| crate::Error::BindingAddressFailed)?;
+    // Try binding to the configured bind addresses.
+    let mut listeners = HashMap::with_capacity(bind_multiaddrs.len());
+    for bind_multiaddr in bind_multiaddrs {
+        info!("Binding to: {}", bind_multiaddr);
+        let listener_id =
+            Swarm::listen_on(&mut swarm, bind_multiaddr.clone()).map_err(|_| crate::Error::BindingAddressFailed)?;
+        listeners.insert(listener_id, bind_multiaddr);
+    }
+
+    // Announce the external addresses to other peers, alongside the bound ones.
+    for announce_multiaddr in announce_multiaddrs {
+        info!("Announcing: {}", announce_multiaddr);
+        swarm.add_external_address(announce_multiaddr);
+    }
 
     // Enter command/event loop.
     loop {
@@ -114,11 +135,11 @@ async fn network_host_processor(
             _ = &mut shutdown => break,
             event = (&mut swarm).next() => {
                 let event = event.ok_or(crate::Error::HostEventLoopError)?;
-                process_swarm_event(event, &internal_event_sender, &peerlist).await;
+                process_swarm_event(event, &internal_event_sender, &peerlist, &listeners).await;
             }
             command = (&mut internal_command_receiver).recv() => {
                 let command = command.ok_or(crate::Error::HostEventLoopError)?;
-                process_internal_command(command, &mut swarm, &peerlist).await;
+                process_internal_command(command, &mut swarm, &internal_event_sender, &peerlist).await;
             },
         }
     }
@@ -127,13 +148,19 @@ async fn network_host_processor(
 }
 
 async fn process_swarm_event(
-    event: SwarmEvent<(), impl std::error::Error>,
+    event: SwarmEvent<SwarmBehaviourEvent>,
     internal_event_sender: &InternalEventSender,
     peerlist: &PeerList,
+    listeners: &HashMap<ListenerId, Multiaddr>,
 ) {
     match event {
-        SwarmEvent::NewListenAddr { address, .. } => {
-            debug!("Swarm event: new listen address {}.", address);
+        SwarmEvent::Behaviour(event) => process_behaviour_event(event, internal_event_sender),
+        SwarmEvent::NewListenAddr { listener_id, address } => {
+            debug!(
+                "Swarm event: new listen address {} for {}.",
+                address,
+                display_listener(listeners, &listener_id)
+            );
 
             internal_event_sender
                 .send(InternalEvent::AddressBound {
@@ -144,17 +171,66 @@ async fn process_swarm_event(
             // Note: We don't care if the inserted address is a duplicate.
             let _ = peerlist.0.write().await.add_local_addr(address);
         }
+        SwarmEvent::ExpiredListenAddr { listener_id, address } => {
+            debug!(
+                "Swarm event: expired listen address {} for {}.",
+                address,
+                display_listener(listeners, &listener_id)
+            );
+
+            unbind_addr(address, internal_event_sender, peerlist).await;
+        }
+        SwarmEvent::ListenerClosed {
+            listener_id,
+            addresses,
+            reason,
+        } => {
+            match reason {
+                Ok(()) => debug!(
+                    "Swarm event: listener for {} closed.",
+                    display_listener(listeners, &listener_id)
+                ),
+                Err(e) => error!(
+                    "Swarm event: listener for {} closed: {}.",
+                    display_listener(listeners, &listener_id),
+                    e
+                ),
+            }
+
+            for address in addresses {
+                unbind_addr(address, internal_event_sender, peerlist).await;
+            }
+        }
         SwarmEvent::ConnectionEstablished { peer_id, .. } => {
             debug!("Swarm event: connection established with {}.", alias!(peer_id));
         }
         SwarmEvent::ConnectionClosed { peer_id, .. } => {
             debug!("Swarm event: connection closed with {}.", alias!(peer_id));
         }
+        SwarmEvent::OutgoingConnectionError {
+            peer_id: Some(peer_id),
+            error,
+            ..
+        } => {
+            debug!(
+                "Swarm event: outgoing connection to {} failed {}.",
+                alias!(peer_id),
+                error
+            );
+
+            internal_event_sender
+                .send(InternalEvent::DialFailed {
+                    peer_id,
+                    error: error.to_string(),
+                })
+                .expect("send error");
+        }
         SwarmEvent::ListenerError { error, .. } => {
             error!("Swarm event: listener error {}.", error);
         }
-        SwarmEvent::Dialing(peer_id) => {
-            // TODO: strange, but this event is not actually fired when dialing. (open issue?)
+        SwarmEvent::Dialing {
+            peer_id: Some(peer_id), ..
+        } => {
             debug!("Swarm event: dialing {}.", alias!(peer_id));
         }
         SwarmEvent::IncomingConnection { send_back_addr, .. } => {
@@ -164,7 +240,26 @@ async fn process_swarm_event(
     }
 }
 
-async fn process_internal_command(internal_command: Command, swarm: &mut Swarm<SwarmBehaviour>, peerlist: &PeerList) {
+async fn unbind_addr(address: Multiaddr, internal_event_sender: &InternalEventSender, peerlist: &PeerList) {
+    peerlist.0.write().await.remove_local_addr(&address);
+
+    internal_event_sender
+        .send(InternalEvent::AddressUnbound { address })
+        .expect("send error");
+}
+
+fn display_listener(listeners: &HashMap<ListenerId, Multiaddr>, listener_id: &ListenerId) -> String {
+    listeners
+        .get(listener_id)
+        .map_or_else(|| format!("{:?}", listener_id), ToString::to_string)
+}
+
+async fn process_internal_command(
+    internal_command: Command,
+    swarm: &mut Swarm<SwarmBehaviour>,
+    internal_event_sender: &InternalEventSender,
+    peerlist: &PeerList,
+) {
     match internal_command {
         Command::DialAddress { address } => {
             if let Err(e) = dial_addr(swarm, address.clone(), peerlist).await {
@@ -174,13 +269,44 @@ async fn process_internal_command(internal_command: Command, swarm: &mut Swarm<S
         Command::DialPeer { peer_id } => {
             if let Err(e) = dial_peer(swarm, peer_id, peerlist).await {
                 warn!("Dialing peer {} failed. Cause: {}", alias!(peer_id), e);
+
+                internal_event_sender
+                    .send(InternalEvent::DialFailed {
+                        peer_id,
+                        error: e.to_string(),
+                    })
+                    .expect("send error");
             }
         }
         Command::DisconnectPeer { peer_id } => hang_up(swarm, peer_id),
+        Command::AnnounceExternalIp { ip } => {
+            let external_multiaddrs = swarm
+                .listeners()
+                .filter_map(|listen_multiaddr| external_multiaddr(ip, listen_multiaddr))
+                .collect::<Vec<_>>();
+
+            for external_multiaddr in external_multiaddrs {
+                info!("Announcing: {}", external_multiaddr);
+                swarm.add_external_address(external_multiaddr);
+            }
+        }
         _ => {}
     }
 }
 
+/// Returns the listen address with its IP address replaced by an external one of the same family, if any.
+fn external_multiaddr(ip: IpAddr, listen_multiaddr: &Multiaddr) -> Option<Multiaddr> {
+    let mut protocols = listen_multiaddr.iter();
+
+    let external = match (protocols.next()?, ip) {
+        (Protocol::Ip4(_), IpAddr::V4(ip)) => Protocol::Ip4(ip),
+        (Protocol::Ip6(_), IpAddr::V6(ip)) => Protocol::Ip6(ip),
+        _ => return None,
+    };
+
+    Some(std::iter::once(external).chain(protocols).collect())
+}
+
 async fn dial_addr(swarm: &mut Swarm<SwarmBehaviour>, addr: Multiaddr, peerlist: &PeerList) -> Result<(), Error> {
     if let Err(e) = peerlist.0.read().await.allows_dialing_addr(&addr) {
         warn!("Dialing address {} denied. Cause: {:?}", addr, e);
@@ -197,7 +323,7 @@ async fn dial_addr(swarm: &mut Swarm<SwarmBehaviour>, addr: Multiaddr, peerlist:
 async fn dial_peer(swarm: &mut Swarm<SwarmBehaviour>, peer_id: PeerId, peerlist: &PeerList) -> Result<(), Error> {
     if let Err(e) = peerlist.0.read().await.allows_dialing_peer(&peer_id) {
         warn!("Dialing peer {} denied. Cause: {:?}", alias!(peer_id), e);
-        return Err(Error::DialingPeerDenied(peer_id));
+        return Err(Error::DialingPeerDenied(peer_id, e));
     }
 
     // Panic:
@@ -208,15 +334,18 @@ async fn dial_peer(swarm: &mut Swarm<SwarmBehaviour>, peer_id: PeerId, peerlist:
 
     let mut dial_attempt = 0;
 
-    peerlist
-        .0
-        .write()
-        .await
+    let mut write = peerlist.0.write().await;
+
+    write
         .update_metrics(&peer_id, |m| {
             m.num_dials += 1;
             dial_attempt = m.num_dials;
         })
         .expect("peer must exist");
+    write.record_connection_event(peer_id, ConnectionEventKind::Dialed { address: addr.clone() });
+
+    // We no longer need to hold the lock.
+    drop(write);
 
     debug!(
         "Dialing peer: {} ({}) attempt: #{}.",
@@ -225,9 +354,9 @@ async fn dial_peer(swarm: &mut Swarm<SwarmBehaviour>, peer_id: PeerId, peerlist:
         dial_attempt
     );
 
-    // TODO: We also use `Swarm::dial_addr` here (instead of `Swarm::dial`) for now. See if it's better to change
-    // that.
-    Swarm::dial(swarm, addr).map_err(|e| Error::DialingPeerFailed(peer_id, e))?;
+    // Dialing with the peer id lets failed attempts be attributed to that peer.
+    Swarm::dial(swarm, DialOpts::peer_id(peer_id).addresses(vec![addr]).build())
+        .map_err(|e| Error::DialingPeerFailed(peer_id, e))?;
 
     Ok(())
 }
@@ -237,3 +366,27 @@ fn hang_up(swarm: &mut Swarm<SwarmBehaviour>, peer_id: PeerId) {
 
     let _ = Swarm::disconnect_peer_id(swarm, peer_id);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn external_multiaddrs() {
+        let ip4: IpAddr = "198.51.100.1".parse().unwrap();
+        let ip6: IpAddr = "2001:db8::1".parse().unwrap();
+        let tcp4: Multiaddr = "/ip4/0.0.0.0/tcp/15600".parse().unwrap();
+        let ws6: Multiaddr = "/ip6/::/tcp/15601/ws".parse().unwrap();
+
+        assert_eq!(
+            external_multiaddr(ip4, &tcp4),
+            Some("/ip4/198.51.100.1/tcp/15600".parse().unwrap())
+        );
+        assert_eq!(
+            external_multiaddr(ip6, &ws6),
+            Some("/ip6/2001:db8::1/tcp/15601/ws".parse().unwrap())
+        );
+        assert_eq!(external_multiaddr(ip4, &ws6), None);
+        assert_eq!(external_multiaddr(ip6, &tcp4), None);
+    }
+}
@@ -0,0 +1,348 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+#![cfg(feature = "full")]
+
+use std::{
+    fs,
+    io::{self, ErrorKind},
+    path::PathBuf,
+    time::Duration,
+};
+
+use hashbrown::HashMap;
+use libp2p::{Multiaddr, PeerId};
+use log::warn;
+use serde::{Deserialize, Serialize};
+
+use super::list::PeerListWrapper;
+use crate::util::unix_now;
+
+/// Describes why and until when a peer or an address is banned.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Ban {
+    /// The optional reason of the ban.
+    pub reason: Option<String>,
+    /// The optional expiry of the ban, in seconds since the Unix epoch.
+    pub expires_at: Option<u64>,
+}
+
+impl Ban {
+    /// Creates a new [`Ban`] lasting for `duration`, or forever if `None`.
+    pub fn new(reason: Option<String>, duration: Option<Duration>) -> Self {
+        Self {
+            reason,
+            expires_at: duration.map(|duration| unix_now().saturating_add(duration.as_secs())),
+        }
+    }
+
+    /// Returns whether the ban has expired.
+    pub fn is_expired(&self) -> bool {
+        self.is_expired_at(unix_now())
+    }
+
+    fn is_expired_at(&self, now: u64) -> bool {
+        self.expires_at.map_or(false, |expires_at| expires_at <= now)
+    }
+}
+
+/// Allows the user to read the peers and addresses currently banned by the network layer.
+#[derive(Clone, Debug)]
+pub struct NetworkBans(PeerListWrapper);
+
+impl NetworkBans {
+    pub(crate) fn new(peerlist: PeerListWrapper) -> Self {
+        Self(peerlist)
+    }
+
+    /// Returns the banned peers.
+    pub async fn peers(&self) -> Vec<(PeerId, Ban)> {
+        self.0 .0.read().await.bans().peers().collect()
+    }
+
+    /// Returns the banned addresses.
+    pub async fn addresses(&self) -> Vec<(Multiaddr, Ban)> {
+        self.0 .0.read().await.bans().addresses().collect()
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct BansFile {
+    #[serde(default)]
+    peers: Vec<PeerBanEntry>,
+    #[serde(default)]
+    addresses: Vec<AddressBanEntry>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PeerBanEntry {
+    peer_id: String,
+    #[serde(flatten)]
+    ban: Ban,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AddressBanEntry {
+    address: String,
+    #[serde(flatten)]
+    ban: Ban,
+}
+
+/// The banned peers and addresses, persisted to a file if one is given.
+///
+/// Note: Changes are not written by [`Bans`] itself, but by writing [`Bans::persisted`], e.g. outside of a lock.
+#[derive(Debug, Default)]
+pub struct Bans {
+    path: Option<PathBuf>,
+    peers: HashMap<PeerId, Ban>,
+    addrs: HashMap<Multiaddr, Ban>,
+}
+
+impl Bans {
+    /// Loads the bans from a file, a missing or unreadable file meaning that there are no bans.
+    pub fn load(path: Option<PathBuf>) -> Self {
+        let mut bans = Self {
+            path,
+            ..Default::default()
+        };
+
+        let path = match bans.path {
+            Some(ref path) => path,
+            None => return bans,
+        };
+
+        // A corrupt bans file must not prevent the node from starting, it is overwritten by the next ban.
+        let file = match fs::read(path) {
+            Ok(content) => match serde_json::from_slice::<BansFile>(&content) {
+                Ok(file) => file,
+                Err(e) => {
+                    warn!("Invalid bans file {}, starting without bans: {}.", path.display(), e);
+                    return bans;
+                }
+            },
+            Err(e) if e.kind() == ErrorKind::NotFound => return bans,
+            Err(e) => {
+                warn!("Unreadable bans file {}, starting without bans: {}.", path.display(), e);
+                return bans;
+            }
+        };
+
+        for entry in file.peers {
+            match entry.peer_id.parse::<PeerId>() {
+                Ok(peer_id) => {
+                    bans.peers.insert(peer_id, entry.ban);
+                }
+                Err(_) => warn!("Ignoring ban of invalid peer id {}.", entry.peer_id),
+            }
+        }
+        for entry in file.addresses {
+            match entry.address.parse::<Multiaddr>() {
+                Ok(address) => {
+                    bans.addrs.insert(address, entry.ban);
+                }
+                Err(_) => warn!("Ignoring ban of invalid address {}.", entry.address),
+            }
+        }
+
+        bans.prune();
+
+        bans
+    }
+
+    /// Returns the ban of a peer, if it is banned.
+    pub fn peer(&self, peer_id: &PeerId) -> Option<&Ban> {
+        self.peers.get(peer_id).filter(|ban| !ban.is_expired())
+    }
+
+    /// Returns the ban of an address, if it is banned.
+    pub fn address(&self, addr: &Multiaddr) -> Option<&Ban> {
+        self.addrs.get(addr).filter(|ban| !ban.is_expired())
+    }
+
+    /// Returns the banned peers.
+    pub fn peers(&self) -> impl Iterator<Item = (PeerId, Ban)> + '_ {
+        self.peers
+            .iter()
+            .filter(|(_, ban)| !ban.is_expired())
+            .map(|(peer_id, ban)| (*peer_id, ban.clone()))
+    }
+
+    /// Returns the banned addresses.
+    pub fn addresses(&self) -> impl Iterator<Item = (Multiaddr, Ban)> + '_ {
+        self.addrs
+            .iter()
+            .filter(|(_, ban)| !ban.is_expired())
+            .map(|(addr, ban)| (addr.clone(), ban.clone()))
+    }
+
+    /// Bans a peer, replacing a previous ban, and returns whether it was already banned.
+    pub fn ban_peer(&mut self, peer_id: PeerId, ban: Ban) -> bool {
+        let banned = self.peer(&peer_id).is_some();
+
+        self.peers.insert(peer_id, ban);
+
+        banned
+    }
+
+    /// Bans an address, replacing a previous ban, and returns whether it was already banned.
+    pub fn ban_address(&mut self, addr: Multiaddr, ban: Ban) -> bool {
+        let banned = self.address(&addr).is_some();
+
+        self.addrs.insert(addr, ban);
+
+        banned
+    }
+
+    /// Unbans a peer, and returns whether it was banned.
+    pub fn unban_peer(&mut self, peer_id: &PeerId) -> bool {
+        let banned = self.peer(peer_id).is_some();
+
+        self.peers.remove(peer_id);
+
+        banned
+    }
+
+    /// Unbans an address, and returns whether it was banned.
+    pub fn unban_address(&mut self, addr: &Multiaddr) -> bool {
+        let banned = self.address(addr).is_some();
+
+        self.addrs.remove(addr);
+
+        banned
+    }
+
+    #[cfg(test)]
+    pub fn clear(&mut self) {
+        self.peers.clear();
+        self.addrs.clear();
+    }
+
+    fn prune(&mut self) {
+        let now = unix_now();
+
+        self.peers.retain(|_, ban| !ban.is_expired_at(now));
+        self.addrs.retain(|_, ban| !ban.is_expired_at(now));
+    }
+
+    /// Returns the serialized bans to be written to their file, if there is one.
+    pub(crate) fn persisted(&mut self) -> Option<PersistedBans> {
+        self.prune();
+
+        let path = self.path.clone()?;
+        let file = BansFile {
+            peers: self
+                .peers
+                .iter()
+                .map(|(peer_id, ban)| PeerBanEntry {
+                    peer_id: peer_id.to_string(),
+                    ban: ban.clone(),
+                })
+                .collect(),
+            addresses: self
+                .addrs
+                .iter()
+                .map(|(addr, ban)| AddressBanEntry {
+                    address: addr.to_string(),
+                    ban: ban.clone(),
+                })
+                .collect(),
+        };
+
+        // Panic: serializing strings and integers into JSON can't fail.
+        let content = serde_json::to_vec_pretty(&file).expect("serialize bans");
+
+        Some(PersistedBans { path, content })
+    }
+}
+
+/// Serialized bans, ready to be written to their file.
+pub(crate) struct PersistedBans {
+    path: PathBuf,
+    content: Vec<u8>,
+}
+
+impl PersistedBans {
+    /// Writes the bans to a temporary file that then replaces the bans file, so that it is never partially written.
+    pub(crate) fn write(self) -> io::Result<()> {
+        if let Some(parent) = self.path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut tmp_path = self.path.clone().into_os_string();
+        tmp_path.push(".tmp");
+
+        fs::write(&tmp_path, &self.content)?;
+        fs::rename(&tmp_path, &self.path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gen_addr() -> Multiaddr {
+        "/ip4/127.0.0.1/tcp/15600".parse().unwrap()
+    }
+
+    #[test]
+    fn expired_bans_are_ignored() {
+        let mut bans = Bans::default();
+        let peer_id = PeerId::random();
+        let addr = gen_addr();
+
+        bans.peers.insert(
+            peer_id,
+            Ban {
+                reason: None,
+                expires_at: Some(unix_now() - 1),
+            },
+        );
+        bans.ban_address(addr.clone(), Ban::new(None, Some(Duration::from_secs(3600))));
+
+        assert!(bans.peer(&peer_id).is_none());
+        assert!(bans.address(&addr).is_some());
+        assert!(!bans.unban_peer(&peer_id));
+        assert!(bans.unban_address(&addr));
+        assert!(bans.address(&addr).is_none());
+    }
+
+    #[test]
+    fn bans_are_persisted() {
+        let path = std::env::temp_dir().join(format!("bee-gossip-bans-{}.json", PeerId::random()));
+        let peer_id = PeerId::random();
+        let addr = gen_addr();
+
+        let mut bans = Bans::load(Some(path.clone()));
+        bans.ban_peer(peer_id, Ban::new(Some("spam".to_string()), None));
+        bans.ban_address(addr.clone(), Ban::new(None, Some(Duration::from_secs(3600))));
+        bans.persisted().unwrap().write().unwrap();
+
+        let loaded = Bans::load(Some(path.clone()));
+        let _ = fs::remove_file(path);
+
+        assert_eq!(loaded.peer(&peer_id).unwrap().reason.as_deref(), Some("spam"));
+        assert_eq!(loaded.address(&addr), bans.address(&addr));
+    }
+
+    #[test]
+    fn corrupt_bans_file_is_ignored() {
+        let path = std::env::temp_dir().join(format!("bee-gossip-bans-{}.json", PeerId::random()));
+        fs::write(&path, b"{ not json").unwrap();
+
+        let mut bans = Bans::load(Some(path.clone()));
+        assert_eq!(bans.peers().count(), 0);
+        assert_eq!(bans.addresses().count(), 0);
+
+        // The corrupt file is replaced by the next write.
+        let peer_id = PeerId::random();
+        bans.ban_peer(peer_id, Ban::new(None, None));
+        bans.persisted().unwrap().write().unwrap();
+
+        let loaded = Bans::load(Some(path.clone()));
+        let _ = fs::remove_file(path);
+
+        assert!(loaded.peer(&peer_id).is_some());
+    }
+}
@@ -48,6 +48,10 @@ pub enum Error {
     #[error("Already disconnected that peer: {0}")]
     PeerIsDisconnected(PeerId),
 
+    /// A failure due to a peer not being on the allow-list of the private network.
+    #[error("Peer is not allowed in the private network: {0}")]
+    PeerIsNotAllowed(PeerId),
+
     /// A failure due to attempting to unban a peer id twice.
     #[error("Already unbanned that peer: {0}")]
     PeerIsUnbanned(PeerId),
@@ -59,4 +63,8 @@ pub enum Error {
     /// A failure due to hitting the maximum number of allowed discovered peers.
     #[error("Tried to add more discovered peers than defined in the config ({0}).")]
     ExceedsDiscoveredPeerLimit(usize),
+
+    /// A failure due to hitting the maximum number of allowed peers of a group.
+    #[error("Tried to connect more peers of group '{0}' than defined in the config ({1}).")]
+    ExceedsGroupPeerLimit(String, usize),
 }
@@ -0,0 +1,177 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+#![cfg(feature = "full")]
+
+use std::collections::VecDeque;
+
+use hashbrown::HashMap;
+use libp2p::{Multiaddr, PeerId};
+
+use super::list::PeerListWrapper;
+use crate::{network::origin::Origin, util::unix_now};
+
+/// The number of connection events kept per peer, older ones are dropped.
+pub const MAX_CONNECTION_EVENTS_PER_PEER: usize = 64;
+/// The number of peers a connection history is kept for, the least recently active ones are dropped.
+const MAX_HISTORIES: usize = 256;
+
+/// Describes an event in the connection history of a peer.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ConnectionEventKind {
+    /// The peer was dialed.
+    Dialed {
+        /// The dialed address.
+        address: Multiaddr,
+    },
+    /// Dialing the peer failed.
+    DialFailed {
+        /// The reason of the failure.
+        error: String,
+    },
+    /// The peer was considered unreachable.
+    Unreachable {
+        /// The reason the peer was considered unreachable.
+        reason: String,
+    },
+    /// The gossip protocol was established with the peer.
+    ProtocolEstablished {
+        /// The address of the peer.
+        address: Multiaddr,
+        /// The direction of the connection.
+        origin: Origin,
+    },
+    /// The gossip protocol negotiated with the peer was rejected.
+    ProtocolRejected {
+        /// The reason of the rejection.
+        reason: String,
+    },
+    /// The gossip protocol with the peer was stopped.
+    ProtocolStopped {
+        /// The cause of the stop.
+        cause: String,
+    },
+    /// The peer was disconnected by the local node.
+    Disconnected {
+        /// The cause of the disconnection.
+        cause: String,
+    },
+}
+
+/// An event in the connection history of a peer.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConnectionEvent {
+    /// When the event happened, in seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// What happened.
+    pub kind: ConnectionEventKind,
+}
+
+/// The bounded connection histories of the peers.
+#[derive(Debug, Default)]
+pub struct ConnectionHistories {
+    histories: HashMap<PeerId, VecDeque<ConnectionEvent>>,
+}
+
+impl ConnectionHistories {
+    /// Records a connection event of a peer.
+    pub fn record(&mut self, peer_id: PeerId, kind: ConnectionEventKind) {
+        self.record_at(peer_id, kind, unix_now());
+    }
+
+    fn record_at(&mut self, peer_id: PeerId, kind: ConnectionEventKind, timestamp: u64) {
+        if !self.histories.contains_key(&peer_id) && self.histories.len() >= MAX_HISTORIES {
+            self.evict_least_recently_active();
+        }
+
+        let history = self
+            .histories
+            .entry(peer_id)
+            .or_insert_with(|| VecDeque::with_capacity(MAX_CONNECTION_EVENTS_PER_PEER));
+
+        if history.len() >= MAX_CONNECTION_EVENTS_PER_PEER {
+            history.pop_front();
+        }
+
+        history.push_back(ConnectionEvent { timestamp, kind });
+    }
+
+    /// Returns the connection history of a peer, oldest event first.
+    pub fn get(&self, peer_id: &PeerId) -> Vec<ConnectionEvent> {
+        self.histories
+            .get(peer_id)
+            .map(|history| history.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    fn evict_least_recently_active(&mut self) {
+        let least_recently_active = self
+            .histories
+            .iter()
+            .min_by_key(|(_, history)| history.back().map_or(0, |event| event.timestamp))
+            .map(|(peer_id, _)| *peer_id);
+
+        if let Some(peer_id) = least_recently_active {
+            self.histories.remove(&peer_id);
+        }
+    }
+}
+
+/// Allows the user to read the connection histories kept by the network layer.
+#[derive(Clone, Debug)]
+pub struct NetworkHistory(PeerListWrapper);
+
+impl NetworkHistory {
+    pub(crate) fn new(peerlist: PeerListWrapper) -> Self {
+        Self(peerlist)
+    }
+
+    /// Returns the connection history of a peer, oldest event first.
+    pub async fn peer(&self, peer_id: &PeerId) -> Vec<ConnectionEvent> {
+        self.0 .0.read().await.histories().get(peer_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stopped() -> ConnectionEventKind {
+        ConnectionEventKind::ProtocolStopped {
+            cause: "closed by the peer".into(),
+        }
+    }
+
+    #[test]
+    fn drop_oldest_events() {
+        let mut histories = ConnectionHistories::default();
+        let peer_id = PeerId::random();
+
+        for timestamp in 0..MAX_CONNECTION_EVENTS_PER_PEER as u64 + 10 {
+            histories.record_at(peer_id, stopped(), timestamp);
+        }
+
+        let history = histories.get(&peer_id);
+
+        assert_eq!(history.len(), MAX_CONNECTION_EVENTS_PER_PEER);
+        assert_eq!(history[0].timestamp, 10);
+        assert!(histories.get(&PeerId::random()).is_empty());
+    }
+
+    #[test]
+    fn drop_least_recently_active_peers() {
+        let mut histories = ConnectionHistories::default();
+        let peer_ids = (0..MAX_HISTORIES).map(|_| PeerId::random()).collect::<Vec<_>>();
+
+        for (timestamp, peer_id) in peer_ids.iter().enumerate() {
+            histories.record_at(*peer_id, stopped(), timestamp as u64 + 1);
+        }
+        // The first peer is active again, so the second one is dropped.
+        histories.record_at(peer_ids[0], stopped(), MAX_HISTORIES as u64 + 1);
+        histories.record_at(PeerId::random(), stopped(), MAX_HISTORIES as u64 + 2);
+
+        assert_eq!(histories.get(&peer_ids[0]).len(), 2);
+        assert!(histories.get(&peer_ids[1]).is_empty());
+        assert_eq!(histories.histories.len(), MAX_HISTORIES);
+    }
+}
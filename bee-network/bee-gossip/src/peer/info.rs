@@ -12,6 +12,21 @@ pub struct PeerInfo {
     pub alias: String,
     /// The type of relation regarding this peer.
     pub relation: PeerRelation,
+    /// The group the peer belongs to, if any.
+    pub group: Option<PeerGroup>,
+}
+
+/// A group of manually configured peers sharing the same priority class.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PeerGroup {
+    /// The name of the group.
+    pub name: String,
+    /// The maximum number of peers of the group connected at the same time, if limited.
+    pub max_peers: Option<usize>,
+    /// Whether the peers of the group are preferred when requesting messages.
+    pub preferred: bool,
+    /// Whether the peers of the group are exempt from being banned for misbehaving.
+    pub ban_exempt: bool,
 }
 
 /// Describes the relation with a peer.
@@ -63,6 +78,17 @@ impl PeerRelation {
     }
 }
 
+/// Describes the attempts to reconnect a peer.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ReconnectState {
+    /// The number of reconnect attempts since the last stable connection.
+    pub attempts: u32,
+    /// When the next reconnect attempt is due, in seconds since the Unix epoch.
+    pub next_attempt_at: Option<u64>,
+    /// The error of the last failed attempt.
+    pub last_error: Option<String>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -10,10 +10,17 @@ use libp2p::{Multiaddr, PeerId};
 use tokio::sync::RwLock;
 
 use super::{
+    ban::{Ban, Bans, PersistedBans},
     error::Error,
-    info::{PeerInfo, PeerRelation},
+    history::{ConnectionEventKind, ConnectionHistories},
+    info::{PeerGroup, PeerInfo, PeerRelation, ReconnectState},
+};
+use crate::{
+    alias,
+    config::Peer,
+    init::global,
+    swarm::protocols::iota_gossip::{Bandwidth, GossipSender},
 };
-use crate::{alias, config::Peer, init::global, swarm::protocols::iota_gossip::GossipSender};
 
 const REMOTE_PEERS_INITIAL_CAP: usize = 8;
 const LOCAL_ADDRS_INITIAL_CAP: usize = 4;
@@ -33,8 +40,10 @@ pub struct PeerList {
     local_id: PeerId,
     local_addrs: HashSet<Multiaddr>,
     peers: HashMap<PeerId, (PeerInfo, PeerState, PeerMetrics)>,
-    banned_peers: HashSet<PeerId>,
-    banned_addrs: HashSet<Multiaddr>,
+    bans: Bans,
+    histories: ConnectionHistories,
+    // The only peers allowed to connect, if the node is part of a private network.
+    allowed_peers: Option<HashSet<PeerId>>,
 }
 
 impl PeerList {
@@ -43,12 +52,13 @@ impl PeerList {
             local_id,
             local_addrs: HashSet::with_capacity(LOCAL_ADDRS_INITIAL_CAP),
             peers: HashMap::with_capacity(REMOTE_PEERS_INITIAL_CAP),
-            banned_peers: HashSet::default(),
-            banned_addrs: HashSet::default(),
+            bans: Bans::default(),
+            histories: ConnectionHistories::default(),
+            allowed_peers: None,
         }
     }
 
-    pub fn from_peers(local_id: PeerId, peers: Vec<Peer>) -> Self {
+    pub fn from_peers(local_id: PeerId, peers: Vec<Peer>, bans: Bans, allowed_peers: Option<HashSet<PeerId>>) -> Self {
         let mut p = HashMap::with_capacity(REMOTE_PEERS_INITIAL_CAP);
 
         p.extend(peers.into_iter().map(|peer| {
@@ -61,6 +71,7 @@ impl PeerList {
                         address: peer.multiaddr,
                         alias: peer.alias.unwrap_or_else(|| alias!(peer_id).to_owned()),
                         relation: PeerRelation::Known,
+                        group: peer.group,
                     },
                     PeerState::default(),
                     PeerMetrics::default(),
@@ -72,8 +83,9 @@ impl PeerList {
             local_id,
             local_addrs: HashSet::with_capacity(LOCAL_ADDRS_INITIAL_CAP),
             peers: p,
-            banned_peers: HashSet::default(),
-            banned_addrs: HashSet::default(),
+            bans,
+            histories: ConnectionHistories::default(),
+            allowed_peers,
         }
     }
 
@@ -127,6 +139,10 @@ impl PeerList {
         }
     }
 
+    pub fn remove_local_addr(&mut self, addr: &Multiaddr) -> bool {
+        self.local_addrs.remove(addr)
+    }
+
     pub fn update_info<U>(&mut self, peer_id: &PeerId, mut update: U) -> Result<(), Error>
     where
         U: FnMut(&mut PeerInfo),
@@ -217,30 +233,43 @@ impl PeerList {
     #[cfg(test)]
     pub fn clear(&mut self) {
         self.peers.clear();
-        self.banned_peers.clear();
-        self.banned_addrs.clear();
+        self.bans.clear();
     }
 
-    pub fn ban_peer(&mut self, peer_id: PeerId) -> Result<(), Error> {
-        // TODO: use storage to persist banned peers
-        if self.banned_peers.insert(peer_id) {
-            Ok(())
-        } else {
-            Err(Error::PeerIsBanned(peer_id))
-        }
+    pub fn bans(&self) -> &Bans {
+        &self.bans
     }
 
-    pub fn ban_address(&mut self, address: Multiaddr) -> Result<(), Error> {
-        // TODO: use storage to persist banned addrs
-        if self.banned_addrs.insert(address.clone()) {
-            Ok(())
-        } else {
-            Err(Error::AddressIsBanned(address))
-        }
+    pub fn histories(&self) -> &ConnectionHistories {
+        &self.histories
+    }
+
+    /// Note: The history is kept independently of the peer being in the list, so that peers that come and go can be
+    /// diagnosed as well.
+    pub fn record_connection_event(&mut self, peer_id: PeerId, kind: ConnectionEventKind) {
+        self.histories.record(peer_id, kind);
+    }
+
+    /// Note: Banning an already banned peer replaces its ban, e.g. to extend it.
+    pub fn ban_peer(&mut self, peer_id: PeerId, ban: Ban) -> Result<(), Error> {
+        self.bans.ban_peer(peer_id, ban);
+
+        Ok(())
+    }
+
+    /// Note: Banning an already banned address replaces its ban, e.g. to extend it.
+    pub fn ban_address(&mut self, address: Multiaddr, ban: Ban) -> Result<(), Error> {
+        self.bans.ban_address(address, ban);
+
+        Ok(())
+    }
+
+    pub(crate) fn persisted_bans(&mut self) -> Option<PersistedBans> {
+        self.bans.persisted()
     }
 
     pub fn unban_peer(&mut self, peer_id: &PeerId) -> Result<(), Error> {
-        if self.banned_peers.remove(peer_id) {
+        if self.bans.unban_peer(peer_id) {
             Ok(())
         } else {
             Err(Error::PeerIsUnbanned(*peer_id))
@@ -248,7 +277,7 @@ impl PeerList {
     }
 
     pub fn unban_address(&mut self, addr: &Multiaddr) -> Result<(), Error> {
-        if self.banned_addrs.remove(addr) {
+        if self.bans.unban_address(addr) {
             Ok(())
         } else {
             Err(Error::AddressIsUnbanned(addr.clone()))
@@ -256,29 +285,40 @@ impl PeerList {
     }
 
     pub fn is_peer_banned(&self, peer_id: &PeerId) -> bool {
-        self.banned_peers.contains(peer_id)
+        self.bans.peer(peer_id).is_some()
     }
 
     pub fn is_addr_banned(&self, addr: &Multiaddr) -> bool {
-        self.banned_addrs.contains(addr)
+        self.bans.address(addr).is_some()
+    }
+
+    /// Note: Outside of a private network, all peers are allowed.
+    pub fn is_peer_allowed(&self, peer_id: &PeerId) -> bool {
+        self.allowed_peers
+            .as_ref()
+            .map_or(true, |allowed_peers| allowed_peers.contains(peer_id))
     }
 
     pub fn accepts_incoming_peer(&self, peer_id: &PeerId, peer_addr: &Multiaddr) -> Result<(), Error> {
         // Checks performed are:
         // - Deny ourself as peer.
+        // - Deny peers that are not allowed in the private network.
         // - Deny one of our own addresses.
         // - Deny banned peers.
         // - Deny banned addresses.
         // - Deny already connected peers.
         // - Deny more than the configured unknown peers.
         // - Deny more than the configured discovered peers.
+        // - Deny more than the configured peers of a group.
         if peer_id == &self.local_id {
             Err(Error::PeerIsLocal(*peer_id))
+        } else if !self.is_peer_allowed(peer_id) {
+            Err(Error::PeerIsNotAllowed(*peer_id))
         } else if self.local_addrs.contains(peer_addr) {
             Err(Error::AddressIsLocal(peer_addr.clone()))
-        } else if self.banned_peers.contains(peer_id) {
+        } else if self.is_peer_banned(peer_id) {
             Err(Error::PeerIsBanned(*peer_id))
-        } else if self.banned_addrs.contains(peer_addr) {
+        } else if self.is_addr_banned(peer_addr) {
             Err(Error::AddressIsBanned(peer_addr.clone()))
         } else if self
             .satisfies(peer_id, |_, state, _| state.is_connected())
@@ -293,6 +333,8 @@ impl PeerList {
             && self.filter_count(|info, _, _| info.relation.is_discovered()) >= global::max_discovered_peers()
         {
             Err(Error::ExceedsDiscoveredPeerLimit(global::max_discovered_peers()))
+        } else if let Some(Err(e)) = self.peers.get(peer_id).map(|(info, _, _)| self.check_group_limit(info)) {
+            Err(e)
         } else {
             // All checks passed! Accept that peer.
             Ok(())
@@ -302,6 +344,7 @@ impl PeerList {
     pub fn allows_dialing_peer(&self, peer_id: &PeerId) -> Result<(), Error> {
         // Checks performed are:
         // - Deny dialing ourself as peer.
+        // - Deny dialing a peer that is not allowed in the private network.
         // - Deny dialing a peer that has not been added first. TODO: check if we might want to allow this!
         // - Deny dialing a banned peer.
         // - Deny dialing an already connected peer.
@@ -309,11 +352,14 @@ impl PeerList {
         // - Deny dialing a banned address.
         // - Deny dialing more than configured unkown peers.
         // - Deny dialing more than configured discovered peers.
+        // - Deny dialing more than configured peers of a group.
         if peer_id == &self.local_id {
             Err(Error::PeerIsLocal(*peer_id))
+        } else if !self.is_peer_allowed(peer_id) {
+            Err(Error::PeerIsNotAllowed(*peer_id))
         } else if !self.contains(peer_id) {
             Err(Error::PeerNotPresent(*peer_id))
-        } else if self.banned_peers.contains(peer_id) {
+        } else if self.is_peer_banned(peer_id) {
             Err(Error::PeerIsBanned(*peer_id))
         } else if self
             .satisfies(peer_id, |_, state, _| state.is_connected())
@@ -325,7 +371,7 @@ impl PeerList {
 
             if self.local_addrs.contains(&peer_info.address) {
                 Err(Error::AddressIsLocal(peer_info.address.clone()))
-            } else if self.banned_addrs.contains(&peer_info.address) {
+            } else if self.is_addr_banned(&peer_info.address) {
                 Err(Error::AddressIsBanned(peer_info.address.clone()))
             } else if peer_info.relation.is_unknown()
                 && self.filter_count(|info, status, _| info.relation.is_unknown() && status.is_connected())
@@ -338,8 +384,8 @@ impl PeerList {
             {
                 Err(Error::ExceedsDiscoveredPeerLimit(global::max_discovered_peers()))
             } else {
-                // All checks passed! Allow dialing that peer.
-                Ok(())
+                // All checks passed, unless the peer's group is full! Allow dialing that peer.
+                self.check_group_limit(peer_info)
             }
         }
     }
@@ -351,7 +397,7 @@ impl PeerList {
         // - Deny dialing an already connected peer (with that address).
         if self.local_addrs.contains(addr) {
             Err(Error::AddressIsLocal(addr.clone()))
-        } else if self.banned_addrs.contains(addr) {
+        } else if self.is_addr_banned(addr) {
             Err(Error::AddressIsBanned(addr.clone()))
         } else if let Some(peer_id) = self.find_peer_if_connected(addr) {
             Err(Error::PeerIsConnected(peer_id))
@@ -361,6 +407,22 @@ impl PeerList {
         }
     }
 
+    fn check_group_limit(&self, peer_info: &PeerInfo) -> Result<(), Error> {
+        match peer_info.group {
+            Some(PeerGroup {
+                ref name,
+                max_peers: Some(max_peers),
+                ..
+            }) if self.filter_count(|info, state, _| {
+                state.is_connected() && info.group.as_ref().map_or(false, |group| group.name == *name)
+            }) >= max_peers =>
+            {
+                Err(Error::ExceedsGroupPeerLimit(name.clone(), max_peers))
+            }
+            _ => Ok(()),
+        }
+    }
+
     fn find_peer_if_connected(&self, addr: &Multiaddr) -> Option<PeerId> {
         self.filter(|info, state, _| state.is_connected() && info.address == *addr)
             .next()
@@ -370,7 +432,12 @@ impl PeerList {
 
 #[cfg(test)]
 mod tests {
-    use libp2p::{identity::ed25519::Keypair, multiaddr::Protocol};
+    use std::time::Duration;
+
+    use libp2p::{
+        identity::{ed25519::Keypair, PublicKey},
+        multiaddr::Protocol,
+    };
 
     use super::*;
 
@@ -387,13 +454,12 @@ mod tests {
         let mut pl = PeerList::new(local_id);
 
         for i in 1..=3 {
-            assert!(
-                pl.add(
+            assert!(pl
+                .add(
                     gen_random_peer_id(),
                     gen_deterministic_peer_info(i, PeerRelation::Known)
                 )
-                .is_ok()
-            );
+                .is_ok());
             assert_eq!(pl.len(), i as usize);
         }
     }
@@ -438,6 +504,102 @@ mod tests {
         pl.accepts_incoming_peer(&peer_id, &peer_info.address).unwrap();
     }
 
+    #[test]
+    fn deny_banned_until_expired() {
+        global::set_max_unknown_peers(4);
+        global::set_max_discovered_peers(4);
+
+        let local_id = gen_constant_peer_id();
+        let peer_id = gen_random_peer_id();
+        let peer_info = gen_constant_peer_info();
+
+        let mut pl = PeerList::new(local_id);
+
+        pl.ban_peer(peer_id, Ban::new(None, Some(Duration::from_secs(3600))))
+            .unwrap();
+        assert!(matches!(
+            pl.accepts_incoming_peer(&peer_id, &peer_info.address),
+            Err(Error::PeerIsBanned(_))
+        ));
+
+        pl.ban_address(
+            peer_info.address.clone(),
+            Ban {
+                reason: None,
+                expires_at: Some(0),
+            },
+        )
+        .unwrap();
+        pl.unban_peer(&peer_id).unwrap();
+        pl.accepts_incoming_peer(&peer_id, &peer_info.address).unwrap();
+        pl.allows_dialing_addr(&peer_info.address).unwrap();
+    }
+
+    #[test]
+    fn deny_peers_not_allowed_in_private_network() {
+        global::set_max_unknown_peers(4);
+        global::set_max_discovered_peers(4);
+
+        let (allowed, other) = (gen_random_peer_id(), gen_random_peer_id());
+        let mut pl = PeerList::from_peers(
+            gen_constant_peer_id(),
+            Vec::new(),
+            Bans::default(),
+            Some(std::iter::once(allowed).collect()),
+        );
+
+        pl.accepts_incoming_peer(&allowed, &gen_deterministic_addr(1)).unwrap();
+        assert!(matches!(
+            pl.accepts_incoming_peer(&other, &gen_deterministic_addr(2)),
+            Err(Error::PeerIsNotAllowed(_))
+        ));
+
+        // Known peers aren't allowed either if they are not on the allow-list.
+        pl.add(other, gen_deterministic_peer_info(2, PeerRelation::Known))
+            .unwrap();
+        assert!(matches!(
+            pl.allows_dialing_peer(&other),
+            Err(Error::PeerIsNotAllowed(_))
+        ));
+    }
+
+    #[test]
+    fn deny_peers_beyond_group_limit() {
+        global::set_max_unknown_peers(4);
+        global::set_max_discovered_peers(4);
+
+        let mut pl = PeerList::new(gen_constant_peer_id());
+        // The limit applies even if the peers of the group are preferred.
+        let group = PeerGroup {
+            name: "internal".to_string(),
+            max_peers: Some(1),
+            preferred: true,
+            ban_exempt: true,
+        };
+        let (first, second) = (gen_random_peer_id(), gen_random_peer_id());
+
+        for (i, peer_id) in [first, second].iter().enumerate() {
+            let mut peer_info = gen_deterministic_peer_info(i as u16, PeerRelation::Known);
+            peer_info.group = Some(group.clone());
+            pl.add(*peer_id, peer_info).unwrap();
+        }
+
+        pl.allows_dialing_peer(&second).unwrap();
+
+        let (gossip_out, _) = crate::swarm::protocols::iota_gossip::channel();
+        pl.update_state(&first, |state| state.set_connected(gossip_out.clone()))
+            .unwrap();
+
+        assert!(matches!(
+            pl.allows_dialing_peer(&second),
+            Err(Error::ExceedsGroupPeerLimit(_, 1))
+        ));
+        assert!(matches!(
+            pl.accepts_incoming_peer(&second, &gen_deterministic_addr(1)),
+            Err(Error::ExceedsGroupPeerLimit(_, 1))
+        ));
+    }
+
     #[test]
     fn conditional_remove() {
         let local_id = gen_constant_peer_id();
@@ -463,7 +625,7 @@ mod tests {
     }
 
     pub fn gen_random_peer_id() -> PeerId {
-        PeerId::from_public_key(&libp2p_core::PublicKey::Ed25519(Keypair::generate().public()))
+        PeerId::from_public_key(&PublicKey::from(Keypair::generate().public()))
     }
 
     pub fn gen_deterministic_peer_info(port: u16, relation: PeerRelation) -> PeerInfo {
@@ -471,6 +633,7 @@ mod tests {
             address: gen_deterministic_addr(port),
             alias: port.to_string(),
             relation,
+            group: None,
         }
     }
 
@@ -479,6 +642,7 @@ mod tests {
             address: gen_deterministic_addr(1),
             alias: String::new(),
             relation: PeerRelation::Known,
+            group: None,
         }
     }
 
@@ -500,6 +664,9 @@ pub enum PeerState {
 pub struct PeerMetrics {
     pub(crate) num_dials: usize,
     pub(crate) identified_at: Option<u64>,
+    pub(crate) connected_at: Option<u64>,
+    pub(crate) reconnect: ReconnectState,
+    pub(crate) bandwidth: Arc<Bandwidth>,
 }
 
 impl Default for PeerState {
@@ -1,11 +1,13 @@
 // Copyright 2020-2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use std::net::IpAddr;
+
 use libp2p_core::{Multiaddr, PeerId};
 use tokio::sync::mpsc;
 
 use super::error::Error;
-use crate::peer::info::PeerRelation;
+use crate::peer::{ban::Ban, info::PeerRelation};
 
 pub type CommandReceiver = mpsc::UnboundedReceiver<Command>;
 pub type CommandSender = mpsc::UnboundedSender<Command>;
@@ -53,6 +55,8 @@ pub enum Command {
     BanPeer {
         /// The peer's id.
         peer_id: PeerId,
+        /// The reason and expiry of the ban.
+        ban: Ban,
     },
     /// Unbans a peer.
     UnbanPeer {
@@ -63,6 +67,8 @@ pub enum Command {
     BanAddress {
         /// The peer's address.
         address: Multiaddr,
+        /// The reason and expiry of the ban.
+        ban: Ban,
     },
     /// Unbans an address.
     UnbanAddress {
@@ -76,6 +82,11 @@ pub enum Command {
         /// The peer's new relation.
         to: PeerRelation,
     },
+    /// Announces the bound ports under an external IP address, e.g. the one perceived by other peers.
+    AnnounceExternalIp {
+        /// The external IP address.
+        ip: IpAddr,
+    },
 }
 
 /// Allows the user to send [`Command`]s to the network layer.
@@ -1,15 +1,20 @@
 // Copyright 2020-2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use libp2p::swarm::NegotiatedSubstream;
+use std::sync::Arc;
+
+use libp2p::Stream;
 use libp2p_core::{Multiaddr, PeerId};
 use tokio::sync::mpsc;
 
 use super::command::Command;
 use crate::{
     network::origin::Origin,
-    peer::{error::Error as PeerError, info::PeerInfo},
-    swarm::protocols::iota_gossip::{GossipReceiver, GossipSender},
+    peer::{
+        error::Error as PeerError,
+        info::{PeerInfo, ReconnectState},
+    },
+    swarm::protocols::iota_gossip::{Bandwidth, GossipReceiver, GossipSender, GossipVersion},
 };
 
 pub type EventSender = mpsc::UnboundedSender<Event>;
@@ -43,6 +48,12 @@ pub enum Event {
         address: Multiaddr,
     },
 
+    /// A bound address was lost, e.g. because its network interface went down.
+    AddressUnbound {
+        /// The lost bind address.
+        address: Multiaddr,
+    },
+
     /// A command failed.
     CommandFailed {
         /// The command that failed.
@@ -81,6 +92,10 @@ pub enum Event {
         gossip_in: GossipReceiver,
         /// The peer's message send channel.
         gossip_out: GossipSender,
+        /// The bytes exchanged with the peer.
+        bandwidth: Arc<Bandwidth>,
+        /// The negotiated version of the gossip protocol.
+        gossip_version: GossipVersion,
     },
 
     /// A peer was disconnected.
@@ -89,6 +104,14 @@ pub enum Event {
         peer_id: PeerId,
     },
 
+    /// The attempts to reconnect a peer were updated.
+    PeerReconnectUpdated {
+        /// The peer's id.
+        peer_id: PeerId,
+        /// The peer's reconnect state.
+        reconnect: ReconnectState,
+    },
+
     /// A peer was removed.
     PeerRemoved {
         /// The peer's id.
@@ -119,6 +142,12 @@ pub enum InternalEvent {
         address: Multiaddr,
     },
 
+    /// A bound address was lost.
+    AddressUnbound {
+        /// The lost bind address.
+        address: Multiaddr,
+    },
+
     /// The gossip protocol has been established with a peer.
     ProtocolEstablished {
         /// The peer's id.
@@ -128,19 +157,33 @@ pub enum InternalEvent {
         /// The associated connection info with that peer.
         origin: Origin,
         /// The negotiated substream the protocol is running on.
-        substream: Box<NegotiatedSubstream>,
+        substream: Box<Stream>,
+        /// The negotiated version of the protocol.
+        version: GossipVersion,
     },
 
     /// The gossip protocol with a peer was stopped.
     ProtocolStopped {
         /// The peer's id.
         peer_id: PeerId,
+        /// The cause of the stop.
+        cause: String,
     },
 
     /// A peer didn't answer our repeated calls.
     PeerUnreachable {
         /// The peer's id.
         peer_id: PeerId,
+        /// The reason the peer is considered unreachable.
+        reason: String,
+    },
+
+    /// Dialing a peer failed.
+    DialFailed {
+        /// The peer's id.
+        peer_id: PeerId,
+        /// The reason of the failure.
+        error: String,
     },
 
     /// A peer has identified itself via the `libp2p` Identify protocol.
@@ -1,8 +1,6 @@
 // Copyright 2020-2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::time::{SystemTime, UNIX_EPOCH};
-
 use bee_runtime::shutdown_stream::ShutdownStream;
 use futures::{
     channel::oneshot,
@@ -22,17 +20,23 @@ use super::{
 };
 use crate::{
     alias,
-    init::global::{self, reconnect_interval_secs},
+    init::global::{self, max_reconnect_interval_secs, reconnect_interval_secs},
     peer::{
+        ban::PersistedBans,
         error::Error as PeerError,
-        info::{PeerInfo, PeerRelation},
-        list::PeerListWrapper as PeerList,
+        history::ConnectionEventKind,
+        info::{PeerInfo, PeerRelation, ReconnectState},
+        list::{self, PeerListWrapper as PeerList},
     },
     swarm::protocols::iota_gossip,
+    util::unix_now,
 };
 
 const MAX_PEER_STATE_CHECKER_DELAY_MILLIS: u64 = 2000;
+const PEER_STATE_CHECK_INTERVAL_SECS: u64 = 1;
 const MAX_DIALS: usize = 3;
+// A connection lasting at least that long resets the reconnect attempts of a peer.
+const STABLE_CONNECTION_SECS: u64 = 60;
 
 pub struct ServiceHostConfig {
     pub local_keys: identity::Keypair,
@@ -195,7 +199,6 @@ async fn event_processor(shutdown: Shutdown, events: InternalEventReceiver, send
     debug!("Event processor stopped.");
 }
 
-// TODO: implement exponential back-off to not spam the peer with reconnect attempts.
 async fn peerstate_checker(shutdown: Shutdown, senders: Senders, peerlist: PeerList) {
     debug!("Peer checker running.");
 
@@ -206,64 +209,105 @@ async fn peerstate_checker(shutdown: Shutdown, senders: Senders, peerlist: PeerL
     let delay = Duration::from_millis(rand::thread_rng().gen_range(0u64..MAX_PEER_STATE_CHECKER_DELAY_MILLIS));
     let start = Instant::now() + delay;
 
-    // The (currently) constant interval at which peer state checks happen.
+    // The interval at which the peer states are logged.
     let period = Duration::from_secs(reconnect_interval_secs());
+    let mut next_log = start;
+
+    let mut interval = ShutdownStream::new(
+        shutdown,
+        IntervalStream::new(time::interval_at(
+            start,
+            Duration::from_secs(PEER_STATE_CHECK_INTERVAL_SECS),
+        )),
+    );
+
+    // Check, if there are any disconnected known or discovered peers, and schedule a reconnect attempt for each of
+    // those, whose backoff has elapsed.
+    while interval.next().await.is_some() {
+        let now = unix_now();
 
-    let mut interval = ShutdownStream::new(shutdown, IntervalStream::new(time::interval_at(start, period)));
+        // The due peers are collected under a read lock, the write lock is only taken if there are any.
+        let due = {
+            let read = peerlist.0.read().await;
 
-    // Check, if there are any disconnected known peers, and schedule a reconnect attempt for each
-    // of those.
-    while interval.next().await.is_some() {
-        let read = peerlist.0.read().await;
-
-        // To how many known peers are we currently connected.
-        let num_known = read.filter_count(|info, _, _| info.relation.is_known());
-        let num_connected_known = read.filter_count(|info, state, _| info.relation.is_known() && state.is_connected());
-
-        // To how many unknown peers are we currently connected.
-        let num_connected_unknown =
-            read.filter_count(|info, state, _| info.relation.is_unknown() && state.is_connected());
-
-        // To how many discovered peers are we currently connected.
-        let num_connected_discovered =
-            read.filter_count(|info, state, _| info.relation.is_discovered() && state.is_connected());
-
-        // How many peers we know of but are currently disconnected.
-        let num_disconnected = read.filter_count(|_, state, _| state.is_disconnected());
-
-        info!(
-            "Connected peers: known {}/{} unknown {}/{} discovered {}/{} - Disconnected peers: {}.",
-            num_connected_known,
-            num_known,
-            num_connected_unknown,
-            global::max_unknown_peers(),
-            num_connected_discovered,
-            global::max_discovered_peers(),
-            num_disconnected,
-        );
+            if Instant::now() >= next_log {
+                next_log += period;
 
-        // Automatically try to reconnect known **and** discovered peers. The removal of discovered peers is a decision
-        // that needs to be made in the autopeering service.
-        for (peer_id, peer_info, peer_metrics) in read.filter(|info, state, _| {
-            (info.relation.is_known() || info.relation.is_discovered()) && state.is_disconnected()
-        }) {
-            if peer_metrics.num_dials >= MAX_DIALS {
-                log::debug!("Peer {} is unreachable.", peer_id);
+                // To how many known peers are we currently connected.
+                let num_known = read.filter_count(|info, _, _| info.relation.is_known());
+                let num_connected_known =
+                    read.filter_count(|info, state, _| info.relation.is_known() && state.is_connected());
 
-                let _ = senders.events.send(Event::PeerUnreachable { peer_id, peer_info });
-                continue;
+                // To how many unknown peers are we currently connected.
+                let num_connected_unknown =
+                    read.filter_count(|info, state, _| info.relation.is_unknown() && state.is_connected());
+
+                // To how many discovered peers are we currently connected.
+                let num_connected_discovered =
+                    read.filter_count(|info, state, _| info.relation.is_discovered() && state.is_connected());
+
+                // How many peers we know of but are currently disconnected.
+                let num_disconnected = read.filter_count(|_, state, _| state.is_disconnected());
+
+                info!(
+                    "Connected peers: known {}/{} unknown {}/{} discovered {}/{} - Disconnected peers: {}.",
+                    num_connected_known,
+                    num_known,
+                    num_connected_unknown,
+                    global::max_unknown_peers(),
+                    num_connected_discovered,
+                    global::max_discovered_peers(),
+                    num_disconnected,
+                );
             }
 
-            debug!("Trying to reconnect to: {} ({}).", peer_info.alias, alias!(peer_id));
+            collect_due_peers(&read, now)
+        };
 
-            // Ignore if the command fails. We can always retry the next time.
-            let _ = senders.internal_commands.send(Command::DialPeer { peer_id });
+        if due.is_empty() {
+            continue;
+        }
+
+        let updates = update_due_peers(&mut *peerlist.0.write().await, &due, now, reconnect_delay_secs);
+
+        for (peer_id, peer_info) in due.unreachable {
+            log::debug!("Peer {} is unreachable.", peer_id);
+
+            let _ = senders.events.send(Event::PeerUnreachable { peer_id, peer_info });
+        }
+
+        for (peer_id, reconnect) in updates {
+            if let Some((_, peer_info)) = due.reconnects.iter().find(|(due_peer_id, _)| *due_peer_id == peer_id) {
+                debug!(
+                    "Trying to reconnect to: {} ({}) attempt: #{}.",
+                    peer_info.alias,
+                    alias!(peer_id),
+                    reconnect.attempts
+                );
+
+                // Ignore if the command fails. We can always retry the next time.
+                let _ = senders.internal_commands.send(Command::DialPeer { peer_id });
+            }
+
+            let _ = senders.events.send(Event::PeerReconnectUpdated { peer_id, reconnect });
         }
     }
 
     debug!("Peer checker stopped.");
 }
 
+// Note: The bans file is written on a blocking thread once the peer list lock is released. Commands are processed one
+// at a time, so successive writes can't be reordered.
+async fn persist_bans(persisted_bans: Option<PersistedBans>) {
+    if let Some(persisted_bans) = persisted_bans {
+        match tokio::task::spawn_blocking(move || persisted_bans.write()).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => warn!("Persisting bans failed: {}.", e),
+            Err(e) => warn!("Persisting bans failed: {}.", e),
+        }
+    }
+}
+
 async fn process_command(command: Command, senders: &Senders, peerlist: &PeerList) -> Result<(), Error> {
     trace!("Received {:?}.", command);
 
@@ -282,8 +326,13 @@ async fn process_command(command: Command, senders: &Senders, peerlist: &PeerLis
             let _ = senders.internal_commands.send(Command::DialPeer { peer_id });
         }
 
-        Command::BanAddress { address } => {
-            peerlist.0.write().await.ban_address(address.clone())?;
+        Command::BanAddress { address, ban } => {
+            let persisted_bans = {
+                let mut peerlist = peerlist.0.write().await;
+                peerlist.ban_address(address.clone(), ban)?;
+                peerlist.persisted_bans()
+            };
+            persist_bans(persisted_bans).await;
 
             senders
                 .events
@@ -291,8 +340,13 @@ async fn process_command(command: Command, senders: &Senders, peerlist: &PeerLis
                 .map_err(|_| Error::SendingEventFailed)?;
         }
 
-        Command::BanPeer { peer_id } => {
-            peerlist.0.write().await.ban_peer(peer_id)?;
+        Command::BanPeer { peer_id, ban } => {
+            let persisted_bans = {
+                let mut peerlist = peerlist.0.write().await;
+                peerlist.ban_peer(peer_id, ban)?;
+                peerlist.persisted_bans()
+            };
+            persist_bans(persisted_bans).await;
 
             senders
                 .events
@@ -315,6 +369,13 @@ async fn process_command(command: Command, senders: &Senders, peerlist: &PeerLis
                 .map_err(|_| Error::SendingCommandFailed)?;
         }
 
+        Command::AnnounceExternalIp { ip } => {
+            senders
+                .internal_commands
+                .send(Command::AnnounceExternalIp { ip })
+                .map_err(|_| Error::SendingCommandFailed)?;
+        }
+
         Command::DialPeer { peer_id } => {
             senders
                 .internal_commands
@@ -323,7 +384,7 @@ async fn process_command(command: Command, senders: &Senders, peerlist: &PeerLis
         }
 
         Command::DisconnectPeer { peer_id } => {
-            disconnect_peer(peer_id, senders, peerlist).await?;
+            disconnect_peer(peer_id, "disconnect requested", senders, peerlist).await?;
         }
 
         Command::RemovePeer { peer_id } => {
@@ -331,7 +392,12 @@ async fn process_command(command: Command, senders: &Senders, peerlist: &PeerLis
         }
 
         Command::UnbanAddress { address } => {
-            peerlist.0.write().await.unban_address(&address)?;
+            let persisted_bans = {
+                let mut peerlist = peerlist.0.write().await;
+                peerlist.unban_address(&address)?;
+                peerlist.persisted_bans()
+            };
+            persist_bans(persisted_bans).await;
 
             senders
                 .events
@@ -340,7 +406,12 @@ async fn process_command(command: Command, senders: &Senders, peerlist: &PeerLis
         }
 
         Command::UnbanPeer { peer_id } => {
-            peerlist.0.write().await.unban_peer(&peer_id)?;
+            let persisted_bans = {
+                let mut peerlist = peerlist.0.write().await;
+                peerlist.unban_peer(&peer_id)?;
+                peerlist.persisted_bans()
+            };
+            persist_bans(persisted_bans).await;
 
             senders
                 .events
@@ -364,13 +435,31 @@ async fn process_internal_event(
                 .send(Event::AddressBound { address })
                 .map_err(|_| Error::SendingEventFailed)?;
         }
+        InternalEvent::AddressUnbound { address } => {
+            senders
+                .events
+                .send(Event::AddressUnbound { address })
+                .map_err(|_| Error::SendingEventFailed)?;
+        }
 
-        InternalEvent::ProtocolStopped { peer_id } => {
+        InternalEvent::ProtocolStopped { peer_id, cause } => {
             let mut peerlist = peerlist.0.write().await;
 
+            peerlist.record_connection_event(peer_id, ConnectionEventKind::ProtocolStopped { cause });
+
             // Try to disconnect, but ignore errors in-case the peer was disconnected already.
             let _ = peerlist.update_state(&peer_id, |state| state.set_disconnected());
 
+            // A peer dropping an unstable connection keeps backing off, instead of being redialed right away.
+            let now = unix_now();
+            let _ = peerlist.update_metrics(&peer_id, |m| match m.connected_at.take() {
+                Some(at) if is_stable(at, now) => m.reconnect = ReconnectState::default(),
+                _ if m.reconnect.attempts > 0 => {
+                    m.reconnect.next_attempt_at = Some(now + reconnect_delay_secs(m.reconnect.attempts))
+                }
+                _ => {}
+            });
+
             // Only remove unknown peers.
             // NOTE: discovered peers should be removed manually via command if the autopeering protocol suggests it.
             let was_removed = peerlist.filter_remove(&peer_id, |peer_info, _, _| peer_info.relation.is_unknown());
@@ -404,6 +493,7 @@ async fn process_internal_event(
             peer_addr,
             origin,
             substream,
+            version,
         } => {
             let mut peerlist = peerlist.0.write().await;
             let mut peer_added = false;
@@ -414,6 +504,17 @@ async fn process_internal_event(
 
             let accepted = peerlist.accepts_incoming_peer(&peer_id, &peer_addr);
 
+            peerlist.record_connection_event(
+                peer_id,
+                match &accepted {
+                    Ok(()) => ConnectionEventKind::ProtocolEstablished {
+                        address: peer_addr.clone(),
+                        origin,
+                    },
+                    Err(e) => ConnectionEventKind::ProtocolRejected { reason: e.to_string() },
+                },
+            );
+
             if accepted.is_ok() {
                 // If the peer doesn't exist yet - but is accepted as an "unknown" peer, we insert it now.
                 if !peerlist.contains(&peer_id) {
@@ -421,6 +522,7 @@ async fn process_internal_event(
                         address: peer_addr,
                         alias: alias!(peer_id).to_string(),
                         relation: PeerRelation::Unknown,
+                        group: None,
                     };
                     peerlist.add(peer_id, peer_info).map_err(|(_, _, e)| e)?;
                     peer_added = true;
@@ -439,21 +541,33 @@ async fn process_internal_event(
                 let (inbound_gossip_tx, gossip_in) = iota_gossip::channel();
                 let (gossip_out, outbound_gossip_rx) = iota_gossip::channel();
 
+                // Panic:
+                // We made sure, that the peer id exists in the above if-branch, hence, unwrapping is fine.
+                let bandwidth = peerlist.metrics(&peer_id).unwrap().bandwidth;
+
                 iota_gossip::start_inbound_gossip_handler(
                     peer_id,
                     inbound_gossip_rx,
                     inbound_gossip_tx,
                     senders.internal_events.clone(),
+                    bandwidth.clone(),
+                    version,
                 );
                 iota_gossip::start_outbound_gossip_handler(
                     peer_id,
                     outbound_gossip_tx,
                     outbound_gossip_rx,
                     senders.internal_events.clone(),
+                    bandwidth.clone(),
+                    version,
                 );
 
                 // We store a clone of the gossip send channel in order to send a shutdown signal.
                 let _ = peerlist.update_state(&peer_id, |state| state.set_connected(gossip_out.clone()));
+                let _ = peerlist.update_metrics(&peer_id, |m| {
+                    m.connected_at = Some(unix_now());
+                    m.reconnect.next_attempt_at = None;
+                });
 
                 // We no longer need to hold the lock.
                 drop(peerlist);
@@ -472,8 +586,9 @@ async fn process_internal_event(
                 }
 
                 info!(
-                    "Established ({}) protocol with {} ({}).",
+                    "Established ({}) protocol {} with {} ({}).",
                     origin,
+                    version,
                     peer_info.alias,
                     alias!(peer_id)
                 );
@@ -485,6 +600,8 @@ async fn process_internal_event(
                         info: peer_info,
                         gossip_in,
                         gossip_out,
+                        bandwidth,
+                        gossip_version: version,
                     })
                     .map_err(|_| Error::SendingEventFailed)?;
             } else {
@@ -494,8 +611,15 @@ async fn process_internal_event(
             }
         }
 
-        InternalEvent::PeerUnreachable { peer_id } => {
-            if let Ok(peer_info) = peerlist.0.read().await.info(&peer_id) {
+        InternalEvent::PeerUnreachable { peer_id, reason } => {
+            let mut peerlist = peerlist.0.write().await;
+
+            peerlist.record_connection_event(peer_id, ConnectionEventKind::Unreachable { reason });
+
+            if let Ok(peer_info) = peerlist.info(&peer_id) {
+                // We no longer need to hold the lock.
+                drop(peerlist);
+
                 senders
                     .events
                     .send(Event::PeerUnreachable { peer_id, peer_info })
@@ -503,17 +627,35 @@ async fn process_internal_event(
             }
         }
 
+        InternalEvent::DialFailed { peer_id, error } => {
+            let mut reconnect = None;
+
+            let mut peerlist = peerlist.0.write().await;
+
+            peerlist.record_connection_event(peer_id, ConnectionEventKind::DialFailed { error: error.clone() });
+
+            let _ = peerlist.update_metrics(&peer_id, |m| {
+                m.reconnect.last_error = Some(error.clone());
+                reconnect = Some(m.reconnect.clone());
+            });
+
+            // We no longer need to hold the lock.
+            drop(peerlist);
+
+            if let Some(reconnect) = reconnect {
+                senders
+                    .events
+                    .send(Event::PeerReconnectUpdated { peer_id, reconnect })
+                    .map_err(|_| Error::SendingEventFailed)?;
+            }
+        }
+
         InternalEvent::PeerIdentified { peer_id } => {
             let _ = peerlist.0.write().await.update_metrics(&peer_id, |m| {
                 // Reset dial count.
                 m.num_dials = 0;
                 // Update Identify timestamp.
-                m.identified_at = Some(
-                    SystemTime::now()
-                        .duration_since(UNIX_EPOCH)
-                        .expect("system time")
-                        .as_secs(),
-                );
+                m.identified_at = Some(unix_now());
             });
         }
     }
@@ -533,6 +675,7 @@ async fn add_peer(
         address,
         alias,
         relation,
+        group: None,
     };
 
     let mut peerlist = peerlist.0.write().await;
@@ -607,7 +750,7 @@ async fn add_peer(
 }
 
 async fn remove_peer(peer_id: PeerId, senders: &Senders, peerlist: &PeerList) -> Result<(), Error> {
-    disconnect_peer(peer_id, senders, peerlist).await?;
+    disconnect_peer(peer_id, "peer removed", senders, peerlist).await?;
 
     let peer_removal = peerlist.0.write().await.remove(&peer_id);
 
@@ -634,12 +777,21 @@ async fn remove_peer(peer_id: PeerId, senders: &Senders, peerlist: &PeerList) ->
     }
 }
 
-async fn disconnect_peer(peer_id: PeerId, senders: &Senders, peerlist: &PeerList) -> Result<(), Error> {
-    let state_update = peerlist
-        .0
-        .write()
-        .await
-        .update_state(&peer_id, |state| state.set_disconnected());
+async fn disconnect_peer(peer_id: PeerId, cause: &str, senders: &Senders, peerlist: &PeerList) -> Result<(), Error> {
+    let mut write = peerlist.0.write().await;
+    let state_update = write.update_state(&peer_id, |state| state.set_disconnected());
+
+    if let Ok(Some(_)) = state_update {
+        write.record_connection_event(
+            peer_id,
+            ConnectionEventKind::Disconnected {
+                cause: cause.to_string(),
+            },
+        );
+    }
+
+    // We no longer need to hold the lock.
+    drop(write);
 
     match state_update {
         Ok(Some(gossip_sender)) => {
@@ -674,3 +826,172 @@ async fn disconnect_peer(peer_id: PeerId, senders: &Senders, peerlist: &PeerList
         }
     }
 }
+
+/// The peers a peer state check found to be due a change.
+#[derive(Default)]
+struct DuePeers {
+    /// Connected peers whose connection has become stable.
+    stable: Vec<PeerId>,
+    /// Disconnected peers due a reconnect attempt.
+    reconnects: Vec<(PeerId, PeerInfo)>,
+    /// Disconnected discovered peers that didn't answer too many dials. They are reported as unreachable, instead of
+    /// being dialed, whenever a reconnect attempt becomes due.
+    unreachable: Vec<(PeerId, PeerInfo)>,
+}
+
+impl DuePeers {
+    fn is_empty(&self) -> bool {
+        self.stable.is_empty() && self.reconnects.is_empty() && self.unreachable.is_empty()
+    }
+}
+
+fn collect_due_peers(peerlist: &list::PeerList, now: u64) -> DuePeers {
+    // Forget the reconnect attempts of peers whose connection has become stable.
+    let stable = peerlist
+        .filter(|_, state, metrics| {
+            state.is_connected()
+                && metrics.reconnect != ReconnectState::default()
+                && metrics.connected_at.map_or(false, |at| is_stable(at, now))
+        })
+        .map(|(peer_id, _, _)| peer_id)
+        .collect();
+
+    let mut due = DuePeers {
+        stable,
+        ..Default::default()
+    };
+
+    // Automatically try to reconnect known **and** discovered peers. The removal of discovered peers is a decision
+    // that needs to be made in the autopeering service.
+    let disconnected = peerlist.filter(|info, state, metrics| {
+        (info.relation.is_known() || info.relation.is_discovered())
+            && state.is_disconnected()
+            && metrics.reconnect.next_attempt_at.map_or(true, |at| at <= now)
+    });
+
+    for (peer_id, peer_info, peer_metrics) in disconnected {
+        if peer_info.relation.is_discovered() && peer_metrics.num_dials >= MAX_DIALS {
+            due.unreachable.push((peer_id, peer_info));
+        } else {
+            due.reconnects.push((peer_id, peer_info));
+        }
+    }
+
+    due
+}
+
+/// Forgets the reconnect attempts of the stable peers, and schedules the next reconnect attempt of the others.
+fn update_due_peers(
+    peerlist: &mut list::PeerList,
+    due: &DuePeers,
+    now: u64,
+    mut reconnect_delay_secs: impl FnMut(u32) -> u64,
+) -> Vec<(PeerId, ReconnectState)> {
+    let mut updates = Vec::new();
+
+    for peer_id in due.stable.iter() {
+        if peerlist
+            .update_metrics(peer_id, |m| m.reconnect = ReconnectState::default())
+            .is_ok()
+        {
+            updates.push((*peer_id, ReconnectState::default()));
+        }
+    }
+
+    for (peer_id, _) in due.unreachable.iter() {
+        let num_dials = peerlist.metrics(peer_id).map_or(0, |metrics| metrics.num_dials);
+
+        peerlist.record_connection_event(
+            *peer_id,
+            ConnectionEventKind::Unreachable {
+                reason: format!("no answer after {} dials", num_dials),
+            },
+        );
+    }
+
+    for (peer_id, _) in due.reconnects.iter().chain(due.unreachable.iter()) {
+        let mut reconnect = ReconnectState::default();
+
+        if peerlist
+            .update_metrics(peer_id, |m| {
+                m.reconnect.attempts += 1;
+                m.reconnect.next_attempt_at = Some(now + reconnect_delay_secs(m.reconnect.attempts));
+                reconnect = m.reconnect.clone();
+            })
+            .is_ok()
+        {
+            updates.push((*peer_id, reconnect));
+        }
+    }
+
+    updates
+}
+
+fn is_stable(connected_at: u64, now: u64) -> bool {
+    connected_at.saturating_add(STABLE_CONNECTION_SECS) <= now
+}
+
+fn reconnect_delay_secs(attempts: u32) -> u64 {
+    backoff_delay_secs(
+        attempts,
+        reconnect_interval_secs(),
+        max_reconnect_interval_secs(),
+        &mut rand::thread_rng(),
+    )
+}
+
+/// Doubles the delay with every attempt up to a maximum, and randomizes its upper half, so that peers that went down
+/// together don't get redialed in lockstep.
+fn backoff_delay_secs(attempts: u32, base: u64, max: u64, rng: &mut impl Rng) -> u64 {
+    let delay = base
+        .saturating_mul(2u64.saturating_pow(attempts.saturating_sub(1)))
+        .min(max);
+
+    delay - rng.gen_range(0..=delay / 2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unreachable_peer_is_reported_when_due() {
+        let mut peerlist = list::PeerList::new(PeerId::random());
+        let peer_id = PeerId::random();
+        let peer_info = PeerInfo {
+            address: "/ip4/127.0.0.1/tcp/15600".parse().unwrap(),
+            alias: String::new(),
+            relation: PeerRelation::Discovered,
+            group: None,
+        };
+        peerlist.add(peer_id, peer_info).unwrap();
+        peerlist.update_metrics(&peer_id, |m| m.num_dials = MAX_DIALS).unwrap();
+
+        let mut reported_at = Vec::new();
+        for now in 0..100 {
+            let due = collect_due_peers(&peerlist, now);
+            assert!(due.reconnects.is_empty());
+
+            if !due.unreachable.is_empty() {
+                reported_at.push(now);
+            }
+            update_due_peers(&mut peerlist, &due, now, |attempts| 10 * u64::from(attempts));
+        }
+
+        // Only when a reconnect attempt is due, not on every check.
+        assert_eq!(reported_at, vec![0, 10, 30, 60]);
+    }
+
+    #[test]
+    fn backoff_delay() {
+        let mut rng = rand::thread_rng();
+
+        for _ in 0..100 {
+            assert!((15..=30).contains(&backoff_delay_secs(1, 30, 600, &mut rng)));
+            assert!((30..=60).contains(&backoff_delay_secs(2, 30, 600, &mut rng)));
+            assert!((120..=240).contains(&backoff_delay_secs(4, 30, 600, &mut rng)));
+            assert!((300..=600).contains(&backoff_delay_secs(10, 30, 600, &mut rng)));
+            assert!((300..=600).contains(&backoff_delay_secs(u32::MAX, 30, 600, &mut rng)));
+        }
+    }
+}
@@ -2,11 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use libp2p::{
-    identify::{Identify, IdentifyConfig, IdentifyEvent},
-    swarm::NetworkBehaviourEventProcess,
-    NetworkBehaviour,
+    connection_limits,
+    identify::{Behaviour as Identify, Config as IdentifyConfig, Event as IdentifyEvent},
+    identity::PublicKey,
+    swarm::NetworkBehaviour,
 };
-use libp2p_core::identity::PublicKey;
 use log::*;
 
 use super::protocols::iota_gossip::{IotaGossipEvent, IotaGossipProtocol};
@@ -18,91 +18,103 @@ use crate::{
 const IOTA_PROTOCOL_VERSION: &str = "iota/0.1.0";
 
 #[derive(NetworkBehaviour)]
-#[behaviour(event_process = true)]
 pub struct SwarmBehaviour {
+    limits: connection_limits::Behaviour,
     identify: Identify,
     gossip: IotaGossipProtocol,
-    #[behaviour(ignore)]
-    internal_sender: InternalEventSender,
 }
 
 impl SwarmBehaviour {
-    pub fn new(local_pk: PublicKey, internal_sender: InternalEventSender) -> Self {
+    pub fn new(local_pk: PublicKey, limits: connection_limits::Behaviour) -> Self {
         let protocol_version = IOTA_PROTOCOL_VERSION.to_string();
         let config = IdentifyConfig::new(protocol_version, local_pk);
 
         Self {
+            limits,
             identify: Identify::new(config),
             gossip: IotaGossipProtocol::new(),
-            internal_sender,
         }
     }
 }
 
-impl NetworkBehaviourEventProcess<IdentifyEvent> for SwarmBehaviour {
-    fn inject_event(&mut self, event: IdentifyEvent) {
-        match event {
-            IdentifyEvent::Received { peer_id, info } => {
-                trace!("Received Identify response from {}: {:?}.", alias!(peer_id), info,);
+/// Forwards the relevant events of the network behaviours to the service host.
+pub fn process_behaviour_event(event: SwarmBehaviourEvent, internal_sender: &InternalEventSender) {
+    match event {
+        SwarmBehaviourEvent::Limits(_) => {}
+        SwarmBehaviourEvent::Identify(event) => process_identify_event(event, internal_sender),
+        SwarmBehaviourEvent::Gossip(event) => process_gossip_event(event, internal_sender),
+    }
+}
 
-                // Panic: we made sure that the sender (network host) is always dropped before the receiver (service
-                // host) through the worker dependencies, hence this can never panic.
-                self.internal_sender
-                    .send(InternalEvent::PeerIdentified { peer_id })
-                    .expect("send internal event");
-            }
-            IdentifyEvent::Sent { peer_id } => {
-                trace!("Sent Identify request to {}.", alias!(peer_id));
-            }
-            IdentifyEvent::Pushed { peer_id } => {
-                trace!("Pushed Identify request to {}.", alias!(peer_id));
-            }
-            IdentifyEvent::Error { peer_id, error } => {
-                debug!("Identification error with {}: Cause: {:?}.", alias!(peer_id), error);
+fn process_identify_event(event: IdentifyEvent, internal_sender: &InternalEventSender) {
+    match event {
+        IdentifyEvent::Received { peer_id, info, .. } => {
+            trace!("Received Identify response from {}: {:?}.", alias!(peer_id), info,);
 
-                // Panic: we made sure that the sender (network host) is always dropped before the receiver (service
-                // host) through the worker dependencies, hence this can never panic.
-                self.internal_sender
-                    .send(InternalEvent::PeerUnreachable { peer_id })
-                    .expect("send internal event");
-            }
+            // Panic: we made sure that the sender (network host) is always dropped before the receiver (service
+            // host) through the worker dependencies, hence this can never panic.
+            internal_sender
+                .send(InternalEvent::PeerIdentified { peer_id })
+                .expect("send internal event");
+        }
+        IdentifyEvent::Sent { peer_id, .. } => {
+            trace!("Sent Identify request to {}.", alias!(peer_id));
+        }
+        IdentifyEvent::Pushed { peer_id, .. } => {
+            trace!("Pushed Identify request to {}.", alias!(peer_id));
+        }
+        IdentifyEvent::Error { peer_id, error, .. } => {
+            debug!("Identification error with {}: Cause: {:?}.", alias!(peer_id), error);
+
+            // Panic: we made sure that the sender (network host) is always dropped before the receiver (service
+            // host) through the worker dependencies, hence this can never panic.
+            internal_sender
+                .send(InternalEvent::PeerUnreachable {
+                    peer_id,
+                    reason: format!("identification failed: {:?}", error),
+                })
+                .expect("send internal event");
         }
     }
 }
 
-impl NetworkBehaviourEventProcess<IotaGossipEvent> for SwarmBehaviour {
-    fn inject_event(&mut self, event: IotaGossipEvent) {
-        match event {
-            IotaGossipEvent::ReceivedUpgradeRequest { from } => {
-                trace!("Received IOTA gossip request from {}.", alias!(from));
-            }
-            IotaGossipEvent::SentUpgradeRequest { to } => {
-                trace!("Sent IOTA gossip request to {}.", alias!(to));
-            }
-            IotaGossipEvent::UpgradeCompleted {
-                peer_id,
-                peer_addr,
-                origin,
-                substream,
-            } => {
-                trace!("Successfully negotiated IOTA gossip protocol with {}.", alias!(peer_id));
+fn process_gossip_event(event: IotaGossipEvent, internal_sender: &InternalEventSender) {
+    match event {
+        IotaGossipEvent::ReceivedUpgradeRequest { from } => {
+            trace!("Received IOTA gossip request from {}.", alias!(from));
+        }
+        IotaGossipEvent::SentUpgradeRequest { to } => {
+            trace!("Sent IOTA gossip request to {}.", alias!(to));
+        }
+        IotaGossipEvent::UpgradeCompleted {
+            peer_id,
+            peer_addr,
+            origin,
+            substream,
+            version,
+        } => {
+            trace!(
+                "Successfully negotiated IOTA gossip protocol {} with {}.",
+                version,
+                alias!(peer_id)
+            );
 
-                self.internal_sender
-                    .send(InternalEvent::ProtocolEstablished {
-                        peer_id,
-                        peer_addr,
-                        origin,
-                        substream,
-                    })
-                    .expect("send internal event");
-            }
-            IotaGossipEvent::UpgradeError { peer_id, error } => {
-                debug!(
-                    "IOTA gossip upgrade error with {}: Cause: {:?}.",
-                    alias!(peer_id),
-                    error
-                );
-            }
+            internal_sender
+                .send(InternalEvent::ProtocolEstablished {
+                    peer_id,
+                    peer_addr,
+                    origin,
+                    substream,
+                    version,
+                })
+                .expect("send internal event");
+        }
+        IotaGossipEvent::UpgradeError { peer_id, error } => {
+            debug!(
+                "IOTA gossip upgrade error with {}: Cause: {:?}.",
+                alias!(peer_id),
+                error
+            );
         }
     }
 }
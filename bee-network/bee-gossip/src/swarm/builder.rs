@@ -3,69 +3,184 @@
 
 use std::time::Duration;
 
+use either::Either;
+use futures::future;
 use libp2p::{
+    connection_limits::{self, ConnectionLimits},
     core::{
-        connection::ConnectionLimits,
+        muxing::StreamMuxerBox,
+        transport::{Boxed, OrTransport},
         upgrade::{self, SelectUpgrade},
     },
-    dns, identity, mplex, noise,
-    swarm::SwarmBuilder,
-    tcp, yamux, Swarm, Transport,
+    dns, identity, noise,
+    pnet::{PnetConfig, PreSharedKey},
+    quic, swarm, tcp, websocket, yamux, PeerId, Swarm, Transport,
 };
 
 use super::{behaviour::SwarmBehaviour, error::Error};
-use crate::service::event::InternalEventSender;
+
+type TcpTransport = dns::tokio::Transport<tcp::tokio::Transport>;
 
 const MAX_CONNECTIONS_PER_PEER: u32 = 1;
 const DEFAULT_CONNECTION_TIMEOUT_SECS: u64 = 10;
 
 pub fn build_swarm(
     local_keys: &identity::Keypair,
-    internal_sender: InternalEventSender,
+    pre_shared_key: Option<PreSharedKey>,
 ) -> Result<Swarm<SwarmBehaviour>, Error> {
     let local_pk = local_keys.public();
     let local_id = local_pk.to_peer_id();
 
-    let noise_keys = noise::Keypair::<noise::X25519Spec>::new()
-        .into_authentic(local_keys)
-        .map_err(|_| Error::CreatingNoiseKeysFailed)?;
+    let transport = build_transport(local_keys, pre_shared_key)?;
+
+    let limits = ConnectionLimits::default().with_max_established_per_peer(Some(MAX_CONNECTIONS_PER_PEER));
+    let behaviour = SwarmBehaviour::new(local_pk, connection_limits::Behaviour::new(limits));
+
+    // We want the connection background tasks to be spawned onto the tokio runtime.
+    let swarm = Swarm::new(transport, behaviour, local_id, swarm::Config::with_tokio_executor());
 
-    let noi_config = noise::NoiseConfig::xx(noise_keys);
-    let mpx_config = mplex::MplexConfig::default();
-    let ymx_config = yamux::YamuxConfig::default();
+    Ok(swarm)
+}
+
+/// Composes the transports the swarm can dial and listen on.
+///
+/// TCP and WebSocket over TCP are supported, both optionally behind DNS, and upgraded with Noise and Yamux or Mplex.
+/// QUIC brings its own encryption and multiplexing. Tests use the in-memory transport instead.
+///
+/// In a private network, the raw TCP connections are encrypted with the pre-shared key before anything else is
+/// exchanged, so that nodes without the key can't even negotiate a protocol. QUIC connections can't be protected that
+/// way, hence QUIC is not offered in a private network.
+fn build_transport(
+    local_keys: &identity::Keypair,
+    pre_shared_key: Option<PreSharedKey>,
+) -> Result<Boxed<(PeerId, StreamMuxerBox)>, Error> {
+    let noi_config = noise::Config::new(local_keys).map_err(|_| Error::CreatingNoiseKeysFailed)?;
+    let mpx_config = libp2p_mplex::MplexConfig::default();
+    let ymx_config = yamux::Config::default();
 
     let transport = if cfg!(test) {
         use libp2p_core::transport::MemoryTransport;
 
         MemoryTransport::default()
             .upgrade(upgrade::Version::V1Lazy)
-            .authenticate(noi_config.into_authenticated())
+            .authenticate(noi_config)
             .multiplex(SelectUpgrade::new(ymx_config, mpx_config))
             .timeout(Duration::from_secs(DEFAULT_CONNECTION_TIMEOUT_SECS))
             .boxed()
     } else {
-        let tcp_config = tcp::TokioTcpConfig::new().nodelay(true).port_reuse(true);
-        let dns_config = dns::TokioDnsConfig::system(tcp_config)?;
+        let private = pre_shared_key.is_some();
+        let tcp_ws_config = build_tcp_ws_transport()?;
+        let base_transport = match pre_shared_key {
+            Some(pre_shared_key) => {
+                Either::Left(tcp_ws_config.and_then(move |socket, _| PnetConfig::new(pre_shared_key).handshake(socket)))
+            }
+            None => Either::Right(tcp_ws_config),
+        };
 
-        dns_config
+        let tcp_ws_transport = base_transport
             .upgrade(upgrade::Version::V1Lazy)
-            .authenticate(noi_config.into_authenticated())
+            .authenticate(noi_config)
             .multiplex(SelectUpgrade::new(ymx_config, mpx_config))
-            .timeout(Duration::from_secs(DEFAULT_CONNECTION_TIMEOUT_SECS))
-            .boxed()
+            .timeout(Duration::from_secs(DEFAULT_CONNECTION_TIMEOUT_SECS));
+
+        if private {
+            tcp_ws_transport.boxed()
+        } else {
+            tcp_ws_transport
+                .or_transport(build_quic_transport(local_keys))
+                .map(|output, _| match output {
+                    future::Either::Left((peer_id, muxer)) => (peer_id, StreamMuxerBox::new(muxer)),
+                    future::Either::Right((peer_id, muxer)) => (peer_id, StreamMuxerBox::new(muxer)),
+                })
+                .boxed()
+        }
     };
 
-    let behaviour = SwarmBehaviour::new(local_pk, internal_sender);
-    let limits = ConnectionLimits::default().with_max_established_per_peer(Some(MAX_CONNECTIONS_PER_PEER));
+    Ok(transport)
+}
 
-    let swarm = SwarmBuilder::new(transport, behaviour, local_id)
-        .connection_limits(limits)
-        // We want the connection background tasks to be spawned
-        // onto the tokio runtime.
-        .executor(Box::new(|fut| {
-            tokio::spawn(fut);
-        }))
-        .build();
+/// Builds the raw TCP transport, and the WebSocket transport on top of it, both behind DNS.
+fn build_tcp_ws_transport() -> Result<OrTransport<TcpTransport, websocket::WsConfig<TcpTransport>>, Error> {
+    let tcp_transport = || dns::tokio::Transport::system(tcp::tokio::Transport::new(tcp::Config::new().nodelay(true)));
 
-    Ok(swarm)
+    Ok(tcp_transport()?.or_transport(websocket::WsConfig::new(tcp_transport()?)))
+}
+
+/// Builds the QUIC transport, which authenticates peers with the local keys on its own.
+fn build_quic_transport(local_keys: &identity::Keypair) -> quic::tokio::Transport {
+    quic::tokio::Transport::new(quic::Config::new(local_keys))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::pin::Pin;
+
+    use futures::future::poll_fn;
+    use libp2p::{
+        core::{
+            transport::{DialOpts, ListenerId, PortUse, TransportEvent},
+            Endpoint,
+        },
+        multiaddr::Protocol,
+        Multiaddr,
+    };
+
+    use super::*;
+
+    async fn next_event<T: Transport + Unpin>(transport: &mut T) -> TransportEvent<T::ListenerUpgrade, T::Error> {
+        poll_fn(|cx| Pin::new(&mut *transport).poll(cx)).await
+    }
+
+    async fn listen<T: Transport + Unpin>(transport: &mut T, multiaddr: &str) -> Multiaddr {
+        transport
+            .listen_on(ListenerId::next(), multiaddr.parse().unwrap())
+            .unwrap();
+
+        match next_event(transport).await {
+            TransportEvent::NewAddress { listen_addr, .. } => listen_addr,
+            _ => panic!("expected a new listen address"),
+        }
+    }
+
+    #[tokio::test]
+    async fn listen_on_websocket() {
+        let mut transport = build_tcp_ws_transport().unwrap();
+
+        let address = listen(&mut transport, "/ip4/127.0.0.1/tcp/0/ws").await;
+
+        assert!(matches!(address.iter().last(), Some(Protocol::Ws(_))));
+    }
+
+    #[tokio::test]
+    async fn listen_and_dial_on_quic() {
+        let listener_keys = identity::Keypair::generate_ed25519();
+        let dialer_keys = identity::Keypair::generate_ed25519();
+        let mut listener = build_quic_transport(&listener_keys);
+        let mut dialer = build_quic_transport(&dialer_keys);
+
+        let address = listen(&mut listener, "/ip4/127.0.0.1/udp/0/quic-v1").await;
+        assert!(matches!(address.iter().last(), Some(Protocol::QuicV1)));
+
+        let dial = dialer
+            .dial(
+                address,
+                DialOpts {
+                    role: Endpoint::Dialer,
+                    port_use: PortUse::New,
+                },
+            )
+            .unwrap();
+        let accept = async {
+            loop {
+                if let TransportEvent::Incoming { upgrade, .. } = next_event(&mut listener).await {
+                    break upgrade.await;
+                }
+            }
+        };
+
+        let ((dialed_peer_id, _), (accepted_peer_id, _)) = futures::try_join!(dial, accept).unwrap();
+
+        assert_eq!(dialed_peer_id, listener_keys.public().to_peer_id());
+        assert_eq!(accepted_peer_id, dialer_keys.public().to_peer_id());
+    }
 }
@@ -0,0 +1,274 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use once_cell::sync::OnceCell;
+
+use super::protocol::GossipVersion;
+use crate::init::global::{max_bandwidth_bytes_per_sec, max_peer_bandwidth_bytes_per_sec};
+
+/// The bytes exchanged with a peer over a protocol or for a packet type.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Traffic {
+    /// The number of bytes received from the peer.
+    pub inbound_bytes: u64,
+    /// The number of bytes sent to the peer.
+    pub outbound_bytes: u64,
+}
+
+/// Counts the bytes exchanged with a peer over the gossip protocol.
+#[derive(Debug, Default)]
+pub struct Bandwidth {
+    inbound_bytes: AtomicU64,
+    outbound_bytes: AtomicU64,
+    protocols: Mutex<BTreeMap<GossipVersion, Traffic>>,
+    packet_types: Mutex<BTreeMap<u8, Traffic>>,
+}
+
+impl Bandwidth {
+    /// Returns the number of bytes received from the peer.
+    pub fn inbound_bytes(&self) -> u64 {
+        self.inbound_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of bytes sent to the peer.
+    pub fn outbound_bytes(&self) -> u64 {
+        self.outbound_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Returns the bytes exchanged with the peer per version of the gossip protocol.
+    pub fn protocols(&self) -> Vec<(GossipVersion, Traffic)> {
+        // Panic: the lock is never held across a panic.
+        let protocols = self.protocols.lock().expect("bandwidth protocols lock");
+
+        protocols
+            .iter()
+            .map(|(version, traffic)| (*version, *traffic))
+            .collect()
+    }
+
+    /// Returns the bytes exchanged with the peer per packet type, as reported by the protocol layer.
+    pub fn packet_types(&self) -> Vec<(u8, Traffic)> {
+        // Panic: the lock is never held across a panic.
+        let packet_types = self.packet_types.lock().expect("bandwidth packet types lock");
+
+        packet_types
+            .iter()
+            .map(|(packet_type, traffic)| (*packet_type, *traffic))
+            .collect()
+    }
+
+    /// Records a packet of `packet_type`, header included, received from the peer.
+    pub fn add_inbound_packet(&self, packet_type: u8, bytes: usize) {
+        // Panic: the lock is never held across a panic.
+        let mut packet_types = self.packet_types.lock().expect("bandwidth packet types lock");
+
+        packet_types.entry(packet_type).or_default().inbound_bytes += bytes as u64;
+    }
+
+    /// Records a packet of `packet_type`, header included, sent to the peer.
+    pub fn add_outbound_packet(&self, packet_type: u8, bytes: usize) {
+        // Panic: the lock is never held across a panic.
+        let mut packet_types = self.packet_types.lock().expect("bandwidth packet types lock");
+
+        packet_types.entry(packet_type).or_default().outbound_bytes += bytes as u64;
+    }
+
+    pub(crate) fn add_inbound_bytes(&self, version: GossipVersion, bytes: usize) {
+        self.inbound_bytes.fetch_add(bytes as u64, Ordering::Relaxed);
+
+        // Panic: the lock is never held across a panic.
+        let mut protocols = self.protocols.lock().expect("bandwidth protocols lock");
+
+        protocols.entry(version).or_default().inbound_bytes += bytes as u64;
+    }
+
+    pub(crate) fn add_outbound_bytes(&self, version: GossipVersion, bytes: usize) {
+        self.outbound_bytes.fetch_add(bytes as u64, Ordering::Relaxed);
+
+        // Panic: the lock is never held across a panic.
+        let mut protocols = self.protocols.lock().expect("bandwidth protocols lock");
+
+        protocols.entry(version).or_default().outbound_bytes += bytes as u64;
+    }
+}
+
+/// Limits a bandwidth to a number of bytes per second, allowing bursts of up to a second worth of bytes.
+#[derive(Debug)]
+pub(crate) struct TokenBucket {
+    bytes_per_sec: f64,
+    state: Mutex<TokenBucketState>,
+}
+
+#[derive(Debug)]
+struct TokenBucketState {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    pub(crate) fn new(bytes_per_sec: u64) -> Self {
+        let bytes_per_sec = bytes_per_sec.max(1) as f64;
+
+        Self {
+            bytes_per_sec,
+            state: Mutex::new(TokenBucketState {
+                tokens: bytes_per_sec,
+                refilled_at: Instant::now(),
+            }),
+        }
+    }
+
+    /// Takes `bytes` from the bucket, possibly going into debt, and returns how long to wait until the debt is paid.
+    pub(crate) fn take(&self, bytes: usize) -> Duration {
+        self.take_at(bytes, Instant::now())
+    }
+
+    fn take_at(&self, bytes: usize, now: Instant) -> Duration {
+        // Panic: the lock is never held across a panic.
+        let mut state = self.state.lock().expect("token bucket lock");
+
+        let refill = now.saturating_duration_since(state.refilled_at).as_secs_f64() * self.bytes_per_sec;
+        state.tokens = (state.tokens + refill).min(self.bytes_per_sec) - bytes as f64;
+        state.refilled_at = now;
+
+        if state.tokens < 0.0 {
+            Duration::from_secs_f64(-state.tokens / self.bytes_per_sec)
+        } else {
+            Duration::ZERO
+        }
+    }
+}
+
+struct GlobalLimits {
+    inbound: Option<TokenBucket>,
+    outbound: Option<TokenBucket>,
+}
+
+static GLOBAL_LIMITS: OnceCell<GlobalLimits> = OnceCell::new();
+
+fn global_limits() -> &'static GlobalLimits {
+    GLOBAL_LIMITS.get_or_init(|| GlobalLimits {
+        inbound: max_bandwidth_bytes_per_sec().map(TokenBucket::new),
+        outbound: max_bandwidth_bytes_per_sec().map(TokenBucket::new),
+    })
+}
+
+/// Throttles one direction of the gossip with a peer to the per-peer and the global bandwidth limits.
+pub(crate) struct Throttle {
+    peer: Option<TokenBucket>,
+    global: Option<&'static TokenBucket>,
+}
+
+impl Throttle {
+    pub(crate) fn inbound() -> Self {
+        Self {
+            peer: max_peer_bandwidth_bytes_per_sec().map(TokenBucket::new),
+            global: global_limits().inbound.as_ref(),
+        }
+    }
+
+    pub(crate) fn outbound() -> Self {
+        Self {
+            peer: max_peer_bandwidth_bytes_per_sec().map(TokenBucket::new),
+            global: global_limits().outbound.as_ref(),
+        }
+    }
+
+    /// Accounts for `bytes` and waits as long as the limits require.
+    pub(crate) async fn throttle(&self, bytes: usize) {
+        let delay = self
+            .peer
+            .iter()
+            .chain(self.global)
+            .map(|bucket| bucket.take(bytes))
+            .max()
+            .unwrap_or_default();
+
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bandwidth_per_protocol_and_packet_type() {
+        let bandwidth = Bandwidth::default();
+
+        bandwidth.add_inbound_bytes(GossipVersion::V1_0, 100);
+        bandwidth.add_inbound_bytes(GossipVersion::V1_1, 20);
+        bandwidth.add_outbound_bytes(GossipVersion::V1_1, 30);
+        bandwidth.add_inbound_packet(2, 60);
+        bandwidth.add_inbound_packet(2, 40);
+        bandwidth.add_outbound_packet(5, 30);
+
+        assert_eq!(bandwidth.inbound_bytes(), 120);
+        assert_eq!(bandwidth.outbound_bytes(), 30);
+        assert_eq!(
+            bandwidth.protocols(),
+            vec![
+                (
+                    GossipVersion::V1_0,
+                    Traffic {
+                        inbound_bytes: 100,
+                        outbound_bytes: 0
+                    }
+                ),
+                (
+                    GossipVersion::V1_1,
+                    Traffic {
+                        inbound_bytes: 20,
+                        outbound_bytes: 30
+                    }
+                ),
+            ]
+        );
+        assert_eq!(
+            bandwidth.packet_types(),
+            vec![
+                (
+                    2,
+                    Traffic {
+                        inbound_bytes: 100,
+                        outbound_bytes: 0
+                    }
+                ),
+                (
+                    5,
+                    Traffic {
+                        inbound_bytes: 0,
+                        outbound_bytes: 30
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn token_bucket_delays_beyond_burst() {
+        let bucket = TokenBucket::new(1000);
+        let now = Instant::now();
+
+        assert_eq!(bucket.take_at(1000, now), Duration::ZERO);
+        assert_eq!(bucket.take_at(500, now), Duration::from_millis(500));
+        // Half a second later, the debt is paid.
+        assert_eq!(bucket.take_at(0, now + Duration::from_millis(500)), Duration::ZERO);
+        // The bucket never holds more than a second worth of bytes.
+        assert_eq!(
+            bucket.take_at(2000, now + Duration::from_secs(10)),
+            Duration::from_secs(1)
+        );
+    }
+}
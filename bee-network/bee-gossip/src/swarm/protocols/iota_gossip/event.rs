@@ -3,11 +3,9 @@
 
 use std::io;
 
-use libp2p::{
-    swarm::{NegotiatedSubstream, ProtocolsHandlerUpgrErr},
-    Multiaddr, PeerId,
-};
+use libp2p::{swarm::StreamUpgradeError, Multiaddr, PeerId, Stream};
 
+use super::protocol::GossipVersion;
 use crate::network::origin::Origin;
 
 /// Gossip events that may occur while establishing the IOTA gossip protocol with a peer.
@@ -24,13 +22,14 @@ pub enum IotaGossipEvent {
         peer_id: PeerId,
         peer_addr: Multiaddr,
         origin: Origin,
-        substream: Box<NegotiatedSubstream>,
+        substream: Box<Stream>,
+        version: GossipVersion,
     },
 
     /// An error occured during negotiation.
     UpgradeError {
         peer_id: PeerId,
-        error: ProtocolsHandlerUpgrErr<io::Error>,
+        error: StreamUpgradeError<io::Error>,
     },
 }
 
@@ -47,11 +46,14 @@ pub enum IotaGossipHandlerEvent {
     SentUpgradeRequest { to: PeerId },
 
     /// Successfully upgraded to the IOTA gossip protocol.
-    UpgradeCompleted { substream: Box<NegotiatedSubstream> },
+    UpgradeCompleted {
+        substream: Box<Stream>,
+        version: GossipVersion,
+    },
 
     /// An errror occured during the upgrade.
     UpgradeError {
         peer_id: PeerId,
-        error: ProtocolsHandlerUpgrErr<io::Error>,
+        error: StreamUpgradeError<io::Error>,
     },
 }
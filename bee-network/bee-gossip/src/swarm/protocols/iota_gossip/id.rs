@@ -3,23 +3,53 @@
 
 use std::fmt;
 
+use super::protocol::GossipVersion;
+
 #[derive(Debug, Clone)]
-pub struct IotaGossipIdentifier(String);
+pub struct IotaGossipIdentifier {
+    id: String,
+    version: GossipVersion,
+}
 
 impl IotaGossipIdentifier {
-    pub fn new(name: impl AsRef<str>, network_id: u64, version: impl AsRef<str>) -> Self {
-        Self(format!("/{}/{}/{}", name.as_ref(), network_id, version.as_ref()))
+    pub fn new(name: impl AsRef<str>, network_id: u64, version: GossipVersion) -> Self {
+        Self {
+            id: format!("/{}/{}/{}", name.as_ref(), network_id, version),
+            version,
+        }
+    }
+
+    pub fn version(&self) -> GossipVersion {
+        self.version
     }
 }
 
 impl fmt::Display for IotaGossipIdentifier {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.0)
+        write!(f, "{}", self.id)
     }
 }
 
-impl AsRef<[u8]> for IotaGossipIdentifier {
-    fn as_ref(&self) -> &[u8] {
-        self.0.as_ref()
+impl AsRef<str> for IotaGossipIdentifier {
+    fn as_ref(&self) -> &str {
+        self.id.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn versioned_identifiers() {
+        assert_eq!(
+            IotaGossipIdentifier::new("iota-gossip", 42, GossipVersion::V1_0).to_string(),
+            "/iota-gossip/42/1.0.0"
+        );
+        assert_eq!(
+            IotaGossipIdentifier::new("iota-gossip", 42, GossipVersion::V1_1).to_string(),
+            "/iota-gossip/42/1.1.0"
+        );
+        assert!(GossipVersion::V1_0 < GossipVersion::V1_1);
     }
 }
@@ -1,15 +1,21 @@
 // Copyright 2020-2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use std::sync::Arc;
+
 use futures::{
     io::{BufReader, BufWriter, ReadHalf, WriteHalf},
     AsyncReadExt, AsyncWriteExt, StreamExt,
 };
-use libp2p::{swarm::NegotiatedSubstream, PeerId};
+use libp2p::{PeerId, Stream};
 use log::*;
 use tokio::sync::mpsc;
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
+use super::{
+    bandwidth::{Bandwidth, Throttle},
+    protocol::GossipVersion,
+};
 use crate::{
     alias,
     service::event::{InternalEvent, InternalEventSender},
@@ -30,36 +36,44 @@ pub fn channel() -> (GossipSender, GossipReceiver) {
 
 pub fn start_inbound_gossip_handler(
     peer_id: PeerId,
-    mut inbound_gossip_rx: BufReader<ReadHalf<Box<NegotiatedSubstream>>>,
+    mut inbound_gossip_rx: BufReader<ReadHalf<Box<Stream>>>,
     inbound_gossip_tx: GossipSender,
     internal_event_tx: InternalEventSender,
+    bandwidth: Arc<Bandwidth>,
+    version: GossipVersion,
 ) {
     tokio::spawn(async move {
         let mut buf = vec![0u8; MSG_BUFFER_LEN];
+        let throttle = Throttle::inbound();
 
         loop {
-            if let Some(len) = (&mut inbound_gossip_rx)
-                .read(&mut buf)
-                .await
-                .ok()
-                .filter(|len| *len > 0)
-            {
-                if inbound_gossip_tx.send(buf[..len].to_vec()).is_err() {
-                    debug!("Terminating gossip protocol with {}.", alias!(peer_id));
+            let cause = match (&mut inbound_gossip_rx).read(&mut buf).await {
+                Ok(len) if len > 0 => {
+                    bandwidth.add_inbound_bytes(version, len);
+                    // Delaying the next read pushes back on the peer once the bandwidth limits are reached.
+                    throttle.throttle(len).await;
+
+                    if inbound_gossip_tx.send(buf[..len].to_vec()).is_err() {
+                        debug!("Terminating gossip protocol with {}.", alias!(peer_id));
+
+                        break;
+                    }
 
-                    break;
+                    continue;
                 }
-            } else {
-                debug!("Peer {} terminated gossip protocol.", alias!(peer_id));
+                Ok(_) => "stream closed by the peer".to_string(),
+                Err(e) => format!("reading from the stream failed: {}", e),
+            };
 
-                // Panic: we made sure that the sender (network host) is always dropped before the receiver (service
-                // host) through the worker dependencies, hence this can never panic.
-                internal_event_tx
-                    .send(InternalEvent::ProtocolStopped { peer_id })
-                    .expect("send internal event");
+            debug!("Peer {} terminated gossip protocol.", alias!(peer_id));
 
-                break;
-            }
+            // Panic: we made sure that the sender (network host) is always dropped before the receiver (service
+            // host) through the worker dependencies, hence this can never panic.
+            internal_event_tx
+                .send(InternalEvent::ProtocolStopped { peer_id, cause })
+                .expect("send internal event");
+
+            break;
         }
 
         trace!("Dropping gossip stream reader for {}.", alias!(peer_id));
@@ -68,12 +82,15 @@ pub fn start_inbound_gossip_handler(
 
 pub fn start_outbound_gossip_handler(
     peer_id: PeerId,
-    mut outbound_gossip_tx: BufWriter<WriteHalf<Box<NegotiatedSubstream>>>,
+    mut outbound_gossip_tx: BufWriter<WriteHalf<Box<Stream>>>,
     outbound_gossip_rx: GossipReceiver,
     internal_event_tx: InternalEventSender,
+    bandwidth: Arc<Bandwidth>,
+    version: GossipVersion,
 ) {
     tokio::spawn(async move {
         let mut outbound_gossip_rx = outbound_gossip_rx.fuse();
+        let throttle = Throttle::outbound();
 
         // If the gossip sender dropped we end the connection.
         while let Some(message) = outbound_gossip_rx.next().await {
@@ -89,17 +106,26 @@ pub fn start_outbound_gossip_handler(
                 // Panic: we made sure that the sender (network host) is always dropped before the receiver (service
                 // host) through the worker dependencies, hence this can never panic.
                 internal_event_tx
-                    .send(InternalEvent::ProtocolStopped { peer_id })
+                    .send(InternalEvent::ProtocolStopped {
+                        peer_id,
+                        cause: "stream closed locally".to_string(),
+                    })
                     .expect("send internal event");
 
                 break;
-            } else if (&mut outbound_gossip_tx).write_all(&message).await.is_err()
+            }
+
+            throttle.throttle(message.len()).await;
+
+            if (&mut outbound_gossip_tx).write_all(&message).await.is_err()
                 || (&mut outbound_gossip_tx).flush().await.is_err()
             {
                 debug!("Peer {} terminated gossip protocol.", alias!(peer_id));
 
                 break;
             }
+
+            bandwidth.add_outbound_bytes(version, message.len());
         }
 
         trace!("Dropping gossip stream writer for {}.", alias!(peer_id));
@@ -3,12 +3,16 @@
 
 use std::{
     collections::{HashMap, VecDeque},
+    fmt,
     task::{Context, Poll},
 };
 
 use libp2p::{
-    core::{connection::ConnectionId, ConnectedPoint},
-    swarm::{IntoProtocolsHandler, NetworkBehaviour, NetworkBehaviourAction, NotifyHandler, PollParameters},
+    core::{transport::PortUse, Endpoint},
+    swarm::{
+        behaviour::{ConnectionClosed, ConnectionEstablished},
+        ConnectionDenied, ConnectionId, FromSwarm, NetworkBehaviour, NotifyHandler, THandlerOutEvent, ToSwarm,
+    },
     Multiaddr, PeerId,
 };
 use log::debug;
@@ -21,19 +25,38 @@ use super::{
 use crate::{alias, init::global::network_id, network::origin::Origin};
 
 const IOTA_GOSSIP_NAME: &str = "iota-gossip";
-const IOTA_GOSSIP_VERSION: &str = "1.0.0";
+// Ordered by preference, since the listener picks the first version proposed by the dialer that it supports.
+const IOTA_GOSSIP_VERSIONS: [GossipVersion; 2] = [GossipVersion::V1_1, GossipVersion::V1_0];
+
+/// Versions of the IOTA gossip protocol.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum GossipVersion {
+    /// The initial version.
+    V1_0,
+    /// Peers exchange their capabilities before gossiping.
+    V1_1,
+}
+
+impl fmt::Display for GossipVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::V1_0 => write!(f, "1.0.0"),
+            Self::V1_1 => write!(f, "1.1.0"),
+        }
+    }
+}
 
-type GossipBehaviourAction = NetworkBehaviourAction<IotaGossipEvent, GossipProtocolHandler, IotaGossipHandlerInEvent>;
+type GossipBehaviourAction = ToSwarm<IotaGossipEvent, IotaGossipHandlerInEvent>;
 
 struct ConnectionInfo {
     addr: Multiaddr,
     origin: Origin,
 }
 
-/// Substream upgrade protocol for `/iota-gossip/1.0.0`.
+/// Substream upgrade protocol for `/iota-gossip/<network id>/<version>`.
 pub struct IotaGossipProtocol {
-    /// The gossip protocol identifier.
-    id: IotaGossipIdentifier,
+    /// The supported gossip protocol identifiers.
+    ids: Vec<IotaGossipIdentifier>,
 
     /// Counts the number of handlers created.
     num_handlers: usize,
@@ -60,7 +83,10 @@ impl IotaGossipProtocol {
 impl Default for IotaGossipProtocol {
     fn default() -> Self {
         Self {
-            id: IotaGossipIdentifier::new(IOTA_GOSSIP_NAME, network_id(), IOTA_GOSSIP_VERSION),
+            ids: IOTA_GOSSIP_VERSIONS
+                .iter()
+                .map(|version| IotaGossipIdentifier::new(IOTA_GOSSIP_NAME, network_id(), *version))
+                .collect(),
             num_handlers: 0,
             num_inbounds: 0,
             num_outbounds: 0,
@@ -70,63 +96,29 @@ impl Default for IotaGossipProtocol {
     }
 }
 
-impl NetworkBehaviour for IotaGossipProtocol {
-    type ProtocolsHandler = GossipProtocolHandler;
-    type OutEvent = IotaGossipEvent;
-
-    /// **libp2p docs**:
-    ///
-    /// Creates a new `ProtocolsHandler` for a connection with a peer.
-    ///
-    /// Every time an incoming connection is opened, and every time we start dialing a node, this
-    /// method is called.
-    ///
-    /// The returned object is a handler for that specific connection, and will be moved to a
-    /// background task dedicated to that connection.
-    ///
-    /// The network behaviour (ie. the implementation of this trait) and the handlers it has
-    /// spawned (ie. the objects returned by `new_handler`) can communicate by passing messages.
-    /// Messages sent from the handler to the behaviour are injected with `inject_event`, and
-    /// the behaviour can send a message to the handler by making `poll` return `SendEvent`.
-    fn new_handler(&mut self) -> Self::ProtocolsHandler {
+impl IotaGossipProtocol {
+    fn new_handler(&mut self) -> GossipProtocolHandler {
         self.num_handlers += 1;
         debug!("gossip protocol: new handler ({}).", self.num_handlers);
 
-        GossipProtocolHandler::new(self.id.clone())
+        GossipProtocolHandler::new(self.ids.clone())
     }
 
-    /// **libp2p docs**:
-    ///
-    /// Addresses that this behaviour is aware of for this specific peer, and that may allow
-    /// reaching the peer.
-    ///
-    /// The addresses will be tried in the order returned by this function, which means that they
-    /// should be ordered by decreasing likelihood of reachability. In other words, the first
-    /// address should be the most likely to be reachable.
-    fn addresses_of_peer(&mut self, peer_id: &PeerId) -> Vec<Multiaddr> {
-        let addrs = self
-            .peers
-            .get(peer_id)
-            .map_or(Vec::new(), |conn_info| vec![conn_info.addr.clone()]);
-
-        debug!("gossip protocol: addresses of peer {}: {:?}.", alias!(peer_id), addrs);
-
-        addrs
-    }
-
-    /// **libp2p docs**:
-    ///
-    /// Informs the behaviour about a newly established connection to a peer.
-    fn inject_connection_established(
+    fn on_connection_established(
         &mut self,
-        peer_id: &PeerId,
-        conn_id: &ConnectionId,
-        endpoint: &ConnectedPoint,
-        _failed_addresses: Option<&Vec<Multiaddr>>,
+        ConnectionEstablished {
+            peer_id,
+            connection_id,
+            endpoint,
+            other_established,
+            ..
+        }: ConnectionEstablished,
     ) {
-        let (peer_addr, origin) = match endpoint {
-            ConnectedPoint::Dialer { address } => (address.clone(), Origin::Outbound),
-            ConnectedPoint::Listener { send_back_addr, .. } => (send_back_addr.clone(), Origin::Inbound),
+        let peer_addr = endpoint.get_remote_address().clone();
+        let origin = if endpoint.is_dialer() {
+            Origin::Outbound
+        } else {
+            Origin::Inbound
         };
 
         match origin {
@@ -138,7 +130,11 @@ impl NetworkBehaviour for IotaGossipProtocol {
             self.num_inbounds, self.num_outbounds
         );
 
-        self.peers.insert(*peer_id, {
+        if other_established == 0 {
+            debug!("gossip protocol: {} connected.", alias!(peer_id));
+        }
+
+        self.peers.insert(peer_id, {
             ConnectionInfo {
                 addr: peer_addr,
                 origin,
@@ -147,56 +143,112 @@ impl NetworkBehaviour for IotaGossipProtocol {
 
         let handler_event = IotaGossipHandlerInEvent { origin };
 
-        let notify_handler = NetworkBehaviourAction::NotifyHandler {
-            peer_id: *peer_id,
-            handler: NotifyHandler::One(*conn_id), // TODO: maybe better use ::Any ??
+        let notify_handler = ToSwarm::NotifyHandler {
+            peer_id,
+            handler: NotifyHandler::One(connection_id),
             event: handler_event,
         };
 
         self.events.push_back(notify_handler);
     }
 
+    fn on_connection_closed(
+        &mut self,
+        ConnectionClosed {
+            peer_id,
+            remaining_established,
+            ..
+        }: ConnectionClosed,
+    ) {
+        debug!("gossip behaviour: connection with {} closed.", alias!(peer_id));
+
+        if remaining_established == 0 {
+            debug!("gossip behaviour: {} disconnected.", alias!(peer_id));
+        }
+    }
+}
+
+impl NetworkBehaviour for IotaGossipProtocol {
+    type ConnectionHandler = GossipProtocolHandler;
+    type ToSwarm = IotaGossipEvent;
+
     /// **libp2p docs**:
     ///
-    /// Indicate to the behaviour that we connected to the node with the given peer id.
+    /// Callback that is invoked for every established inbound connection.
     ///
-    /// This node now has a handler (as spawned by `new_handler`) running in the background.
+    /// This is invoked once another peer has successfully dialed us.
+    fn handle_established_inbound_connection(
+        &mut self,
+        _: ConnectionId,
+        _: PeerId,
+        _: &Multiaddr,
+        _: &Multiaddr,
+    ) -> Result<GossipProtocolHandler, ConnectionDenied> {
+        Ok(self.new_handler())
+    }
+
+    /// **libp2p docs**:
     ///
-    /// This method is only called when the first connection to the peer is established, preceded by
-    /// [`inject_connection_established`](NetworkBehaviour::inject_connection_established).
-    fn inject_connected(&mut self, peer_id: &PeerId) {
-        debug!("gossip protocol: {} connected.", alias!(peer_id));
+    /// Callback that is invoked for every established outbound connection.
+    ///
+    /// This is invoked once we have successfully dialed a peer.
+    fn handle_established_outbound_connection(
+        &mut self,
+        _: ConnectionId,
+        _: PeerId,
+        _: &Multiaddr,
+        _: Endpoint,
+        _: PortUse,
+    ) -> Result<GossipProtocolHandler, ConnectionDenied> {
+        Ok(self.new_handler())
     }
 
     /// **libp2p docs**:
     ///
-    /// Informs the behaviour about an event generated by the handler dedicated to the peer identified by `peer_id`.
-    /// for the behaviour.
+    /// Informs the behaviour about an event from the [`Swarm`](libp2p::Swarm).
+    fn on_swarm_event(&mut self, event: FromSwarm) {
+        match event {
+            FromSwarm::ConnectionEstablished(connection_established) => {
+                self.on_connection_established(connection_established)
+            }
+            FromSwarm::ConnectionClosed(connection_closed) => self.on_connection_closed(connection_closed),
+            FromSwarm::AddressChange(address_change) => {
+                debug!(
+                    "gossip behaviour: address of {} changed.",
+                    alias!(address_change.peer_id)
+                );
+            }
+            _ => {}
+        }
+    }
+
+    /// **libp2p docs**:
     ///
-    /// The `peer_id` is guaranteed to be in a connected state. In other words, `inject_connected`
-    /// has previously been called with this `PeerId`.
-    fn inject_event(&mut self, peer_id: PeerId, _: ConnectionId, event: IotaGossipHandlerEvent) {
+    /// Informs the behaviour about an event generated by the [`ConnectionHandler`](libp2p::swarm::ConnectionHandler)
+    /// dedicated to the peer identified by `peer_id`.
+    fn on_connection_handler_event(&mut self, peer_id: PeerId, _: ConnectionId, event: THandlerOutEvent<Self>) {
         debug!("gossip protocol: handler event: {:?}", event);
 
         // Propagate events to the behaviour.
         let ev = match event {
             IotaGossipHandlerEvent::SentUpgradeRequest { to } => {
-                NetworkBehaviourAction::GenerateEvent(IotaGossipEvent::SentUpgradeRequest { to })
+                ToSwarm::GenerateEvent(IotaGossipEvent::SentUpgradeRequest { to })
             }
-            IotaGossipHandlerEvent::UpgradeCompleted { substream } => {
+            IotaGossipHandlerEvent::UpgradeCompleted { substream, version } => {
                 if let Some(conn_info) = self.peers.remove(&peer_id) {
-                    NetworkBehaviourAction::GenerateEvent(IotaGossipEvent::UpgradeCompleted {
+                    ToSwarm::GenerateEvent(IotaGossipEvent::UpgradeCompleted {
                         peer_id,
                         peer_addr: conn_info.addr,
                         origin: conn_info.origin,
                         substream,
+                        version,
                     })
                 } else {
                     return;
                 }
             }
             IotaGossipHandlerEvent::UpgradeError { peer_id, error } => {
-                NetworkBehaviourAction::GenerateEvent(IotaGossipEvent::UpgradeError { peer_id, error })
+                ToSwarm::GenerateEvent(IotaGossipEvent::UpgradeError { peer_id, error })
             }
             _ => return,
         };
@@ -204,50 +256,7 @@ impl NetworkBehaviour for IotaGossipProtocol {
         self.events.push_back(ev);
     }
 
-    /// **libp2p docs**:
-    ///
-    /// Informs the behaviour about a closed connection to a peer.
-    ///
-    /// A call to this method is always paired with an earlier call to
-    /// `inject_connection_established` with the same peer ID, connection ID and
-    /// endpoint.
-    fn inject_connection_closed(
-        &mut self,
-        peer_id: &PeerId,
-        _: &ConnectionId,
-        _: &ConnectedPoint,
-        _: <Self::ProtocolsHandler as IntoProtocolsHandler>::Handler,
-    ) {
-        debug!("gossip behaviour: connection with {} closed.", alias!(peer_id));
-    }
-
-    /// **libp2p docs**:
-    ///
-    /// Indicates to the behaviour that we disconnected from the node with the given peer id.
-    ///
-    /// There is no handler running anymore for this node. Any event that has been sent to it may
-    /// or may not have been processed by the handler.
-    ///
-    /// This method is only called when the last established connection to the peer is closed,
-    /// preceded by [`inject_connection_closed`](NetworkBehaviour::inject_connection_closed).
-    fn inject_disconnected(&mut self, peer_id: &PeerId) {
-        debug!("gossip behaviour: {} disconnected.", alias!(peer_id));
-    }
-
-    /// **libp2p docs**:
-    ///
-    /// Informs the behaviour that the [`ConnectedPoint`] of an existing connection has changed.
-    fn inject_address_change(
-        &mut self,
-        peer_id: &PeerId,
-        _: &ConnectionId,
-        _old: &ConnectedPoint,
-        _new: &ConnectedPoint,
-    ) {
-        debug!("gossip behaviour: address of {} changed.", alias!(peer_id));
-    }
-
-    fn poll(&mut self, _: &mut Context<'_>, _: &mut impl PollParameters) -> Poll<GossipBehaviourAction> {
+    fn poll(&mut self, _: &mut Context<'_>) -> Poll<GossipBehaviourAction> {
         if let Some(event) = self.events.pop_front() {
             Poll::Ready(event)
         } else {
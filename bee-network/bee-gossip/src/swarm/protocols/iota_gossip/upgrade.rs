@@ -1,33 +1,33 @@
 // Copyright 2020-2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{io, iter};
+use std::{io, vec};
 
 use futures::{future, AsyncRead, AsyncWrite};
-use libp2p::{core::UpgradeInfo, InboundUpgrade, OutboundUpgrade};
+use libp2p::core::{InboundUpgrade, OutboundUpgrade, UpgradeInfo};
 use log::*;
 
-use super::id::IotaGossipIdentifier;
+use super::{id::IotaGossipIdentifier, protocol::GossipVersion};
 
 #[derive(Debug, Clone)]
 pub struct IotaGossipProtocolUpgrade {
-    id: IotaGossipIdentifier,
+    ids: Vec<IotaGossipIdentifier>,
 }
 
 impl IotaGossipProtocolUpgrade {
-    pub fn new(id: IotaGossipIdentifier) -> Self {
-        Self { id }
+    pub fn new(ids: Vec<IotaGossipIdentifier>) -> Self {
+        Self { ids }
     }
 }
 
 impl UpgradeInfo for IotaGossipProtocolUpgrade {
     type Info = IotaGossipIdentifier;
-    type InfoIter = iter::Once<Self::Info>;
+    type InfoIter = vec::IntoIter<Self::Info>;
 
     fn protocol_info(&self) -> Self::InfoIter {
-        trace!("gossip upgrade: protocol info query: {}", self.id);
+        trace!("gossip upgrade: protocol info query: {:?}", self.ids);
 
-        iter::once(self.id.clone())
+        self.ids.clone().into_iter()
     }
 }
 
@@ -35,14 +35,14 @@ impl<S> InboundUpgrade<S> for IotaGossipProtocolUpgrade
 where
     S: AsyncWrite + AsyncWrite + Unpin + Send,
 {
-    type Output = S;
+    type Output = (S, GossipVersion);
     type Error = io::Error;
     type Future = future::Ready<Result<Self::Output, Self::Error>>;
 
     fn upgrade_inbound(self, stream: S, info: Self::Info) -> Self::Future {
         debug!("gossip upgrade: inbound: {}", info);
 
-        future::ok(stream)
+        future::ok((stream, info.version()))
     }
 }
 
@@ -50,13 +50,13 @@ impl<S> OutboundUpgrade<S> for IotaGossipProtocolUpgrade
 where
     S: AsyncRead + AsyncWrite + Unpin + Send,
 {
-    type Output = S;
+    type Output = (S, GossipVersion);
     type Error = io::Error;
     type Future = future::Ready<Result<Self::Output, Self::Error>>;
 
     fn upgrade_outbound(self, stream: S, info: Self::Info) -> Self::Future {
         debug!("gossip upgrade: outbound: {}", info);
 
-        future::ok(stream)
+        future::ok((stream, info.version()))
     }
 }
@@ -12,7 +12,7 @@ mod full {
 
     use std::iter::repeat;
 
-    use libp2p_core::identity::{
+    use libp2p_identity::{
         ed25519::{Keypair, SecretKey},
         PublicKey,
     };
@@ -20,12 +20,12 @@ mod full {
     use super::*;
 
     pub fn gen_random_peer_id() -> PeerId {
-        PeerId::from_public_key(&libp2p_core::PublicKey::Ed25519(Keypair::generate().public()))
+        PeerId::from_public_key(&PublicKey::from(Keypair::generate().public()))
     }
 
     pub fn gen_deterministic_peer_id(gen: impl ToString) -> PeerId {
         let keys = gen_deterministic_keys(gen);
-        PeerId::from_public_key(&PublicKey::Ed25519(keys.public()))
+        PeerId::from_public_key(&PublicKey::from(keys.public()))
     }
 
     pub fn gen_deterministic_keys(gen: impl ToString) -> Keypair {
@@ -43,7 +43,7 @@ mod full {
         // The input consists only of valid hex chars and the length for the secret key
         // is also correct. Hence, the `unwrap`s are fine.
         let mut hex_sk = hex::decode(identity_sk).expect("invalid generated secret key");
-        let sk = SecretKey::from_bytes(&mut hex_sk).unwrap();
+        let sk = SecretKey::try_from_bytes(&mut hex_sk).unwrap();
         sk.into()
     }
 
@@ -54,7 +54,7 @@ mod full {
         // Unwraps below are fine because we checked `identity_kp` for its validity.
         let mut hex_kp = hex::decode(identity_kp).unwrap();
 
-        Keypair::decode(&mut hex_kp[..]).unwrap()
+        Keypair::try_from_bytes(&mut hex_kp[..]).unwrap()
     }
 
     pub fn gen_random_keys() -> Keypair {
@@ -11,6 +11,8 @@ pub fn get_network_config_with_port(port: u16) -> NetworkConfig {
     config
 }
 
+/// Note: The in-memory transport keeps a port taken even after the network is shut down, so every test needs its own
+/// ports.
 pub fn get_in_memory_network_config(port: u64) -> NetworkConfig {
     NetworkConfig::build_in_memory()
         .with_bind_multiaddr({
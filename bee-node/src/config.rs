@@ -7,7 +7,10 @@
 //! All node types use a common config file (e.g. config.json), and simply ignore
 //! those parameters they don't actually require.
 
-use std::{fs, path::Path};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
 use bee_autopeering::config::{AutopeeringConfig, AutopeeringConfigBuilder};
 use bee_gossip::{NetworkConfig, NetworkConfigBuilder};
@@ -19,13 +22,17 @@ use bee_protocol::workers::config::{ProtocolConfig, ProtocolConfigBuilder};
 use bee_rest_api::endpoints::config::{RestApiConfig, RestApiConfigBuilder};
 use bee_tangle::config::{TangleConfig, TangleConfigBuilder};
 use fern_logger::{LoggerConfig, LoggerConfigBuilder, LOGGER_STDOUT_NAME};
-use serde::Deserialize;
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::Value;
 
 #[cfg(feature = "dashboard")]
 use crate::plugins::dashboard::config::{DashboardConfig, DashboardConfigBuilder};
 use crate::{
     cli::ClArgs,
-    plugins::mqtt::config::{MqttConfig, MqttConfigBuilder},
+    plugins::{
+        mqtt::config::{MqttConfig, MqttConfigBuilder},
+        nat::config::{NatConfig, NatConfigBuilder},
+    },
     storage::NodeStorageBackend,
     util, BECH32_HRP_DEFAULT, NETWORK_NAME_DEFAULT,
 };
@@ -43,10 +50,21 @@ pub enum NodeConfigError {
     JsonConfigBuilderDeserialization(#[from] serde_json::Error),
     #[error("deserializing the toml config builder failed: {0}")]
     TomlConfigBuilderDeserialization(#[from] toml::de::Error),
+    #[error("writing the config file failed: {0}")]
+    FileWrite(std::io::Error),
+    #[error("serializing the json config failed: {0}")]
+    JsonConfigSerialization(serde_json::Error),
+    #[error("serializing the toml config failed: {0}")]
+    TomlConfigSerialization(#[from] toml::ser::Error),
+    #[error("editing the toml config failed: {0}")]
+    TomlConfigEdit(#[from] toml_edit::TomlError),
+    #[error("the config file contains an invalid peering section")]
+    InvalidPeeringSection,
 }
 
 /// Entails all data that can be stored in a Bee config file.
 pub struct NodeConfig<S: NodeStorageBackend> {
+    pub(crate) config_path: Option<PathBuf>,
     pub(crate) alias: String,
     pub(crate) network_spec: NetworkSpec,
     pub(crate) logger: LoggerConfig,
@@ -59,6 +77,7 @@ pub struct NodeConfig<S: NodeStorageBackend> {
     pub(crate) storage: S::Config,
     pub(crate) tangle: TangleConfig,
     pub(crate) mqtt: MqttConfig,
+    pub(crate) nat: NatConfig,
     #[cfg(feature = "dashboard")]
     pub(crate) dashboard: DashboardConfig,
 }
@@ -90,6 +109,9 @@ pub struct NodeConfigBuilder<S: NodeStorageBackend> {
     #[deprecated(since = "0.3.0")]
     #[serde(alias = "identity")]
     _identity: Option<String>,
+    // The file the builder was deserialized from, if any.
+    #[serde(skip)]
+    pub(crate) config_path: Option<PathBuf>,
     pub(crate) alias: Option<String>,
     #[serde(alias = "bech32Hrp")]
     pub(crate) bech32_hrp: Option<String>,
@@ -106,6 +128,7 @@ pub struct NodeConfigBuilder<S: NodeStorageBackend> {
     pub(crate) storage: Option<S::ConfigBuilder>,
     pub(crate) tangle: Option<TangleConfigBuilder>,
     pub(crate) mqtt: Option<MqttConfigBuilder>,
+    pub(crate) nat: Option<NatConfigBuilder>,
     #[cfg(feature = "dashboard")]
     pub(crate) dashboard: Option<DashboardConfigBuilder>,
 }
@@ -121,6 +144,8 @@ where
         // We destructure `Self` so this implementation does not fail silently if fields are added or removed.
         let Self {
             _identity: self_identity,
+            // Where the config comes from is irrelevant to its equality.
+            config_path: _,
             alias: self_alias,
             bech32_hrp: self_bech32_hrp,
             network_id: self_network_id,
@@ -134,6 +159,7 @@ where
             storage: self_storage,
             tangle: self_tangle,
             mqtt: self_mqtt,
+            nat: self_nat,
             #[cfg(feature = "dashboard")]
                 dashboard: self_dashboard,
         } = self;
@@ -151,7 +177,8 @@ where
             && (self_pruning == &other.pruning)
             && (self_storage == &other.storage)
             && (self_tangle == &other.tangle)
-            && (self_mqtt == &other.mqtt);
+            && (self_mqtt == &other.mqtt)
+            && (self_nat == &other.nat);
 
         #[cfg(feature = "dashboard")]
         return cmp && (self_dashboard == &other.dashboard);
@@ -166,18 +193,11 @@ where
 {
     /// Creates a node config builder from a local config file.
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, NodeConfigError> {
-        match fs::read_to_string(&path) {
-            Ok(string) => match path.as_ref().extension().and_then(|e| e.to_str()) {
-                Some("json") => {
-                    serde_json::from_str::<Self>(&string).map_err(NodeConfigError::JsonConfigBuilderDeserialization)
-                }
-                Some("toml") => {
-                    toml::from_str::<Self>(&string).map_err(NodeConfigError::TomlConfigBuilderDeserialization)
-                }
-                _ => Err(NodeConfigError::UnsupportedConfigType),
-            },
-            Err(e) => Err(NodeConfigError::FileRead(e)),
-        }
+        let mut builder = deserialize_file::<Self, _>(&path)?;
+
+        builder.config_path = Some(path.as_ref().to_path_buf());
+
+        Ok(builder)
     }
 
     /// Applies commandline arguments to the builder.
@@ -221,6 +241,7 @@ where
             #[allow(deprecated)]
             self._identity,
             NodeConfig {
+                config_path: self.config_path,
                 alias: self.alias.unwrap_or_else(|| ALIAS_DEFAULT.to_owned()),
                 network_spec,
                 logger: self.logger.unwrap_or_default().finish(),
@@ -237,6 +258,7 @@ where
                 storage: self.storage.unwrap_or_default().into(),
                 tangle: self.tangle.unwrap_or_default().finish(),
                 mqtt: self.mqtt.unwrap_or_default().finish(),
+                nat: self.nat.unwrap_or_default().finish(),
                 #[cfg(feature = "dashboard")]
                 dashboard: self.dashboard.unwrap_or_default().finish(),
             },
@@ -244,6 +266,164 @@ where
     }
 }
 
+fn deserialize_file<T: DeserializeOwned, P: AsRef<Path>>(path: P) -> Result<T, NodeConfigError> {
+    match fs::read_to_string(&path) {
+        Ok(string) => match path.as_ref().extension().and_then(|e| e.to_str()) {
+            Some("json") => {
+                serde_json::from_str::<T>(&string).map_err(NodeConfigError::JsonConfigBuilderDeserialization)
+            }
+            Some("toml") => toml::from_str::<T>(&string).map_err(NodeConfigError::TomlConfigBuilderDeserialization),
+            _ => Err(NodeConfigError::UnsupportedConfigType),
+        },
+        Err(e) => Err(NodeConfigError::FileRead(e)),
+    }
+}
+
+/// The network section of a config file, all other sections are ignored.
+#[derive(Deserialize)]
+struct NetworkSection {
+    network: Option<NetworkConfigBuilder>,
+}
+
+/// Reads the network config builder from a local config file.
+pub(crate) fn network_config_from_file<P: AsRef<Path>>(path: P) -> Result<NetworkConfigBuilder, NodeConfigError> {
+    Ok(deserialize_file::<NetworkSection, _>(path)?.network.unwrap_or_default())
+}
+
+/// Edits the manually configured peers of a local config file, as they appear in the file.
+///
+/// ## Note
+/// In a TOML file, only the `[[network.peering.peers]]` tables are touched, and the ones that are kept keep their
+/// comments. The new content is written to a temporary file that then replaces the config file, so that it is never
+/// left half-written.
+pub(crate) fn edit_peers_in_file<P: AsRef<Path>>(
+    path: P,
+    edit: impl FnOnce(&mut Vec<Value>),
+) -> Result<(), NodeConfigError> {
+    let path = path.as_ref();
+    let is_toml = match path.extension().and_then(|e| e.to_str()) {
+        Some("json") => false,
+        Some("toml") => true,
+        _ => return Err(NodeConfigError::UnsupportedConfigType),
+    };
+
+    let string = fs::read_to_string(path).map_err(NodeConfigError::FileRead)?;
+    let string = if is_toml {
+        edit_peers_in_toml(&string, edit)?
+    } else {
+        edit_peers_in_json(&string, edit)?
+    };
+
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+
+    fs::write(&tmp_path, string)
+        .and_then(|()| fs::rename(&tmp_path, path))
+        .map_err(NodeConfigError::FileWrite)
+}
+
+fn edit_peers_in_json(string: &str, edit: impl FnOnce(&mut Vec<Value>)) -> Result<String, NodeConfigError> {
+    let mut document =
+        serde_json::from_str::<Value>(string).map_err(NodeConfigError::JsonConfigBuilderDeserialization)?;
+
+    edit(json_peers_mut(&mut document).ok_or(NodeConfigError::InvalidPeeringSection)?);
+
+    serde_json::to_string_pretty(&document).map_err(NodeConfigError::JsonConfigSerialization)
+}
+
+fn json_peers_mut(document: &mut Value) -> Option<&mut Vec<Value>> {
+    let mut value = document;
+
+    for key in ["network", "peering"] {
+        value = value
+            .as_object_mut()?
+            .entry(key)
+            .or_insert_with(|| Value::Object(Default::default()));
+    }
+
+    value
+        .as_object_mut()?
+        .entry("peers")
+        .or_insert_with(|| Value::Array(Vec::new()))
+        .as_array_mut()
+}
+
+fn edit_peers_in_toml(string: &str, edit: impl FnOnce(&mut Vec<Value>)) -> Result<String, NodeConfigError> {
+    let mut document = string.parse::<toml_edit::Document>()?;
+
+    let peering = toml_peering_mut(&mut document).ok_or(NodeConfigError::InvalidPeeringSection)?;
+    let peering_position = peering.position();
+    let peers = peering
+        .entry("peers")
+        .or_insert(toml_edit::Item::ArrayOfTables(Default::default()))
+        .as_array_of_tables_mut()
+        .ok_or(NodeConfigError::InvalidPeeringSection)?;
+
+    // New tables are placed after the existing ones, or else right after the peering table.
+    let position = peers
+        .iter()
+        .filter_map(toml_edit::Table::position)
+        .last()
+        .or(peering_position);
+
+    let mut tables = peers
+        .iter()
+        .map(|table| Ok((toml_table_to_json(table)?, table.clone())))
+        .collect::<Result<Vec<_>, NodeConfigError>>()?;
+    let mut entries = tables.iter().map(|(entry, _)| entry.clone()).collect();
+
+    edit(&mut entries);
+
+    peers.clear();
+    for entry in entries {
+        // Unchanged entries keep their table, including comments and formatting.
+        let table = match tables.iter().position(|(table_entry, _)| *table_entry == entry) {
+            Some(index) => tables.remove(index).1,
+            None => {
+                let mut table = json_to_toml_table(&entry)?;
+                if let Some(position) = position {
+                    table.set_position(position);
+                }
+                table
+            }
+        };
+        peers.push(table);
+    }
+
+    Ok(document.to_string())
+}
+
+fn toml_peering_mut(document: &mut toml_edit::Document) -> Option<&mut toml_edit::Table> {
+    let mut table = document.as_table_mut();
+
+    for key in ["network", "peering"] {
+        table = table
+            .entry(key)
+            .or_insert_with(|| {
+                let mut table = toml_edit::Table::new();
+                table.set_implicit(true);
+                toml_edit::Item::Table(table)
+            })
+            .as_table_mut()?;
+    }
+
+    Some(table)
+}
+
+fn toml_table_to_json(table: &toml_edit::Table) -> Result<Value, NodeConfigError> {
+    let mut document = toml_edit::Document::new();
+    *document.as_table_mut() = table.clone();
+
+    serde_json::to_value(toml::from_str::<toml::Value>(&document.to_string())?)
+        .map_err(NodeConfigError::JsonConfigSerialization)
+}
+
+fn json_to_toml_table(entry: &Value) -> Result<toml_edit::Table, NodeConfigError> {
+    let document = toml::to_string(entry)?.parse::<toml_edit::Document>()?;
+
+    Ok(document.as_table().clone())
+}
+
 /// Represents an IOTA network specification. It consists of:
 /// * a name, e.g. "chrysalis-mainnet";
 /// * an id number (hash of the name);
@@ -275,6 +455,7 @@ impl NetworkSpec {
 #[cfg(test)]
 mod test {
 
+    use bee_gossip::PeerId;
     #[cfg(feature = "rocksdb")]
     use bee_storage_rocksdb::storage::Storage;
     #[cfg(all(feature = "sled", not(feature = "rocksdb")))]
@@ -308,4 +489,135 @@ mod test {
 
         Ok(())
     }
+
+    fn edit_peers_round_trip(extension: &str, content: &str) -> String {
+        let path = std::env::temp_dir().join(format!("bee-node-peers-{}.{}", std::process::id(), extension));
+        let (existing, added) = (PeerId::random(), PeerId::random());
+        fs::write(&path, content.replace("EXISTING", &existing.to_string())).unwrap();
+
+        edit_peers_in_file(&path, |entries| {
+            entries.push(serde_json::json!({
+                "address": format!("/ip4/127.0.0.1/tcp/15601/p2p/{}", added),
+                "alias": "added",
+            }));
+        })
+        .unwrap();
+
+        let peers = network_config_from_file(&path)
+            .unwrap()
+            .finish()
+            .unwrap()
+            .static_peers()
+            .clone();
+        assert_eq!(peers.len(), 2);
+        assert!(peers
+            .iter()
+            .any(|peer| peer.peer_id == added && peer.alias.as_deref() == Some("added")));
+
+        edit_peers_in_file(&path, |entries| {
+            entries.retain(|entry| entry.get("alias").and_then(Value::as_str) != Some("added"));
+        })
+        .unwrap();
+
+        let peers = network_config_from_file(&path)
+            .unwrap()
+            .finish()
+            .unwrap()
+            .static_peers()
+            .clone();
+        let written = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(peers.len(), 1);
+        assert_eq!(peers.iter().next().unwrap().peer_id, existing);
+        // The other sections are kept.
+        assert!(written.contains("my-node"));
+
+        written
+    }
+
+    #[test]
+    fn edit_peers_in_json_file() {
+        edit_peers_round_trip(
+            "json",
+            r#"{
+                "alias": "my-node",
+                "network": {
+                    "peering": {
+                        "peers": [
+                            {
+                                "address": "/ip4/127.0.0.1/tcp/15600/p2p/EXISTING",
+                                "alias": "existing"
+                            }
+                        ]
+                    }
+                }
+            }"#,
+        );
+    }
+
+    #[test]
+    fn edit_peers_in_toml_file() {
+        let written = edit_peers_round_trip(
+            "toml",
+            r#"# The node.
+alias = "my-node"
+
+[network]
+# Where to listen.
+bind_address = "/ip4/0.0.0.0/tcp/15600"
+
+[network.peering]
+# A friendly node.
+[[network.peering.peers]]
+address = "/ip4/127.0.0.1/tcp/15600/p2p/EXISTING" # Next door.
+alias = "existing"
+
+# Keep this section.
+[protocol]
+minimum_pow_score = 4000
+"#,
+        );
+
+        // Comments and the order of the sections survive the edits.
+        let comments = [
+            "# The node.",
+            "# Where to listen.",
+            "# A friendly node.",
+            "# Next door.",
+            "# Keep this section.",
+        ];
+        let offsets = comments
+            .iter()
+            .map(|comment| written.find(comment).unwrap())
+            .collect::<Vec<_>>();
+        assert!(offsets.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(written.find("bind_address").unwrap() < written.find("[network.peering]").unwrap());
+    }
+
+    #[test]
+    fn add_peer_to_toml_file_without_peering_section() {
+        let path = std::env::temp_dir().join(format!("bee-node-peering-{}.toml", std::process::id()));
+        let added = PeerId::random();
+        fs::write(&path, "alias = \"my-node\"\n\n[protocol]\nminimum_pow_score = 4000\n").unwrap();
+
+        edit_peers_in_file(&path, |entries| {
+            entries.push(serde_json::json!({
+                "address": format!("/ip4/127.0.0.1/tcp/15601/p2p/{}", added),
+            }));
+        })
+        .unwrap();
+
+        let peers = network_config_from_file(&path)
+            .unwrap()
+            .finish()
+            .unwrap()
+            .static_peers()
+            .clone();
+        let written = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(peers.iter().next().unwrap().peer_id, added);
+        assert!(written.contains("minimum_pow_score = 4000"));
+    }
 }
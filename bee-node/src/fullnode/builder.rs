@@ -3,7 +3,8 @@
 
 use std::{
     any::{type_name, Any, TypeId},
-    collections::HashMap,
+    collections::{HashMap, HashSet},
+    net::IpAddr,
 };
 
 use async_trait::async_trait;
@@ -11,7 +12,7 @@ use bee_autopeering::{
     stores::{Options as RocksDbPeerStoreConfigOptions, RocksDbPeerStore, RocksDbPeerStoreConfig},
     NeighborValidator, ServiceProtocol, AUTOPEERING_SERVICE_NAME,
 };
-use bee_gossip::{Keypair, NetworkEventReceiver, Protocol};
+use bee_gossip::{Keypair, Multiaddr, NetworkConfig, NetworkEventReceiver, PeerId, Protocol};
 use bee_runtime::{
     event::Bus,
     node::{Node, NodeBuilder},
@@ -26,7 +27,12 @@ use crate::plugins::Dashboard;
 use crate::{
     config::NetworkSpec,
     core::{Core, CoreError, ResourceRegister, TopologicalOrder, WorkerStart, WorkerStop},
-    plugins::{self, Mqtt, VersionChecker},
+    plugins::{
+        self,
+        nat::{self, PortProtocol},
+        peer_reloader::PeerReloaderConfig,
+        Mqtt, PeerReloader, PortMapper, VersionChecker,
+    },
     shutdown,
     storage::NodeStorageBackend,
     util, AUTOPEERING_VERSION,
@@ -146,8 +152,9 @@ impl<S: NodeStorageBackend> NodeBuilder<FullNode<S>> for FullNodeBuilder<S> {
         let builder = add_node_resources(builder)?;
 
         // Initialize everything.
+        let (external_ports, builder) = initialize_port_mapping(builder).await;
         let (gossip_rx, builder) = initialize_gossip_layer(builder)?;
-        let (autopeering_rx, builder) = initialize_autopeering(builder).await?;
+        let (autopeering_rx, builder) = initialize_autopeering(builder, external_ports).await?;
         let builder = initialize_ledger(builder);
         let builder = initialize_protocol(builder, gossip_rx, autopeering_rx);
         let builder = initialize_api(builder);
@@ -156,6 +163,19 @@ impl<S: NodeStorageBackend> NodeBuilder<FullNode<S>> for FullNodeBuilder<S> {
         // Start the version checker.
         let builder = builder.with_worker::<VersionChecker>();
 
+        // Start reloading the peers from the config file (if it is known).
+        let builder = if let Some(config_path) = builder.config().config_path.clone() {
+            let network_cfg = &builder.config().network;
+            let peer_reloader_cfg = PeerReloaderConfig {
+                config_path,
+                peers: network_cfg.static_peers().iter().map(|peer| peer.peer_id).collect(),
+                write_back: network_cfg.write_back_peers(),
+            };
+            builder.with_worker_cfg::<PeerReloader>(peer_reloader_cfg)
+        } else {
+            builder
+        };
+
         // Start the MQTT broker.
         let mqtt_cfg = builder.config().mqtt.clone();
         let builder = builder.with_worker_cfg::<Mqtt>(mqtt_cfg);
@@ -245,6 +265,101 @@ fn add_node_resources<S: NodeStorageBackend>(builder: FullNodeBuilder<S>) -> Res
     Ok(builder)
 }
 
+/// The external ports the gossip and autopeering ports are mapped to on the gateway.
+#[derive(Default)]
+struct ExternalPorts {
+    gossip: Option<u16>,
+    autopeering: Option<u16>,
+}
+
+/// Maps the gossip and autopeering ports on the gateway (if enabled).
+async fn initialize_port_mapping<S: NodeStorageBackend>(
+    mut builder: FullNodeBuilder<S>,
+) -> (ExternalPorts, FullNodeBuilder<S>) {
+    let config = builder.config();
+
+    if !config.nat.enabled() {
+        return (ExternalPorts::default(), builder);
+    }
+
+    log::info!("Mapping ports on the gateway...");
+
+    // Every bind address is mapped. Without announce addresses, the external port of the first one is the gossip port
+    // announced via autopeering.
+    let mut gossip_ports = Vec::new();
+    for port in config.network.bind_multiaddrs().iter().filter_map(tcp_port) {
+        if !gossip_ports.contains(&port) {
+            gossip_ports.push(port);
+        }
+    }
+    let autopeering_port = if config.autopeering.enabled() {
+        config.autopeering.bind_addr_v4().map(|bind_addr| bind_addr.port())
+    } else {
+        None
+    };
+
+    let ports = gossip_ports
+        .iter()
+        .map(|port| (PortProtocol::Tcp, *port))
+        .chain(autopeering_port.map(|port| (PortProtocol::Udp, port)))
+        .collect::<Vec<_>>();
+
+    let port_mapper_cfg = match nat::map_ports(&config.nat, &ports).await {
+        Ok(port_mapper_cfg) => port_mapper_cfg,
+        Err(e) => {
+            log::warn!("Mapping ports on the gateway failed: {}.", e);
+            return (ExternalPorts::default(), builder);
+        }
+    };
+
+    let external_ports = ExternalPorts {
+        gossip: gossip_ports
+            .first()
+            .and_then(|port| port_mapper_cfg.external_port(PortProtocol::Tcp, *port)),
+        autopeering: autopeering_port.and_then(|port| port_mapper_cfg.external_port(PortProtocol::Udp, port)),
+    };
+
+    // Announce the external gossip addresses to the peers. They are added after the configured announce addresses,
+    // which therefore take precedence.
+    if let Some(external_ip) = port_mapper_cfg.external_ip() {
+        let external_multiaddrs = builder
+            .config
+            .network
+            .bind_multiaddrs()
+            .iter()
+            .filter_map(|bind_multiaddr| {
+                let external_port = port_mapper_cfg.external_port(PortProtocol::Tcp, tcp_port(bind_multiaddr)?)?;
+
+                Some(external_gossip_multiaddr(bind_multiaddr, external_ip, external_port))
+            })
+            .collect::<Vec<_>>();
+
+        for multiaddr in external_multiaddrs {
+            if let Err(e) = builder.config.network.add_announce_multiaddr(multiaddr) {
+                log::warn!("Announcing the external gossip address failed: {}.", e);
+            }
+        }
+    }
+
+    (external_ports, builder.with_worker_cfg::<PortMapper>(port_mapper_cfg))
+}
+
+/// Returns the address under which a bind address is reachable through the gateway, keeping the protocols that follow
+/// the TCP port (e.g. `/ws`).
+fn external_gossip_multiaddr(bind_multiaddr: &Multiaddr, external_ip: IpAddr, external_port: u16) -> Multiaddr {
+    let mut multiaddr = Multiaddr::empty();
+    multiaddr.push(Protocol::from(external_ip));
+
+    for protocol in bind_multiaddr.iter().skip(1) {
+        match protocol {
+            Protocol::Tcp(_) => multiaddr.push(Protocol::Tcp(external_port)),
+            protocol => multiaddr.push(protocol),
+        }
+    }
+
+    multiaddr
+}
+
 /// Initializes the gossip layer.
 fn initialize_gossip_layer<S: NodeStorageBackend>(
     builder: FullNodeBuilder<S>,
@@ -307,10 +422,20 @@ fn initialize_protocol<S: NodeStorageBackend>(
 /// Initializes the (optional) autopeering service.
 async fn initialize_autopeering<S: NodeStorageBackend>(
     builder: FullNodeBuilder<S>,
+    external_ports: ExternalPorts,
 ) -> Result<(Option<bee_autopeering::event::EventRx>, FullNodeBuilder<S>), FullNodeError> {
     let config = builder.config();
 
     if !config.autopeering.enabled() {
+        Ok((None, builder))
+    } else if config
+        .network
+        .private_network()
+        .map_or(false, |private_network| private_network.allowed_peers().is_none())
+    {
+        // Without an allow-list, there is no telling which discovered peers belong to the private network.
+        log::info!("Private network without an allow-list: autopeering is disabled.");
+
         Ok((None, builder))
     } else {
         log::info!("Initializing autopeering...");
@@ -318,8 +443,19 @@ async fn initialize_autopeering<S: NodeStorageBackend>(
         let autopeering_cfg = config.autopeering.clone();
         let network_name = config.network_spec().name().to_string();
 
+        // In a private network, only peers on the allow-list are chosen as neighbors.
+        let allowed_peers = config
+            .network
+            .private_network()
+            .and_then(|private_network| private_network.allowed_peers())
+            .map(|allowed_peers| {
+                log::info!("Private network: restricting autopeering to the allowed peers.");
+
+                allowed_peers.iter().copied().collect()
+            });
+
         // The neighbor validator that includes/excludes certain peers by applying custom criteria.
-        let neighbor_validator = FullNodeNeighborValidator::new(network_name.clone());
+        let neighbor_validator = FullNodeNeighborValidator::new(network_name.clone(), allowed_peers);
 
         // The peer store for persisting discovered peers.
         let mut peerstore_options = RocksDbPeerStoreConfigOptions::default();
@@ -329,7 +465,7 @@ async fn initialize_autopeering<S: NodeStorageBackend>(
 
         // A local entity that can sign outgoing messages, and announce services.
         let keypair = config.local().keypair().clone();
-        let local = create_local_autopeering_entity(keypair, config);
+        let local = create_local_autopeering_entity(keypair, config, external_ports)?;
 
         let quit_signal = tokio::signal::ctrl_c();
 
@@ -353,10 +489,13 @@ async fn initialize_autopeering<S: NodeStorageBackend>(
 fn create_local_autopeering_entity<S: NodeStorageBackend>(
     keypair: Keypair,
     config: &FullNodeConfig<S>,
-) -> bee_autopeering::Local {
+    external_ports: ExternalPorts,
+) -> Result<bee_autopeering::Local, FullNodeError> {
     let local = bee_autopeering::Local::from_keypair(keypair).expect("failed to create local entity");
 
-    let port = if let Some(bind_addr) = config.autopeering.bind_addr_v4() {
+    let port = if let Some(port) = external_ports.autopeering {
+        port
+    } else if let Some(bind_addr) = config.autopeering.bind_addr_v4() {
         bind_addr.port()
     } else if let Some(bind_addr) = config.autopeering.bind_addr_v6() {
         bind_addr.port()
@@ -364,19 +503,39 @@ fn create_local_autopeering_entity<S: NodeStorageBackend>(
         unreachable!("config validation ensures, that one bind address is available.");
     };
 
-    // Announce the autopeering service.
+    // Announce the autopeering service, preferably on the port mapped on the gateway.
     local.add_service(AUTOPEERING_SERVICE_NAME, ServiceProtocol::Udp, port);
 
-    // Announce the gossip service.
-    // TODO: Make the bind address a SocketAddr instead of a Multiaddr
-    let mut bind_addr = config.network.bind_multiaddr().clone();
-    if let Some(Protocol::Tcp(port)) = bind_addr.pop() {
-        local.add_service(config.network_spec().name(), ServiceProtocol::Tcp, port);
-    } else {
-        panic!("invalid gossip bind address");
-    }
+    // Announce the gossip service on the port of an announce address, or else on the port mapped on the gateway.
+    local.add_service(
+        config.network_spec().name(),
+        ServiceProtocol::Tcp,
+        gossip_service_port(&config.network, external_ports.gossip)?,
+    );
+
+    Ok(local)
+}
 
-    local
+/// Returns the TCP port of a gossip address, regardless of the protocols that follow it (e.g. `/ws`).
+fn tcp_port(multiaddr: &Multiaddr) -> Option<u16> {
+    multiaddr.iter().find_map(|protocol| match protocol {
+        Protocol::Tcp(port) => Some(port),
+        _ => None,
+    })
+}
+
+/// Returns the port of the gossip service that is announced via autopeering.
+///
+/// The first announce address wins, which is a configured one if there is any, and otherwise the external address of
+/// a port mapped on the gateway. Without announce addresses, the external port is used, and lastly the bind port.
+fn gossip_service_port(network: &NetworkConfig, external_port: Option<u16>) -> Result<u16, FullNodeError> {
+    let multiaddr = match (network.announce_multiaddrs().first(), external_port) {
+        (Some(multiaddr), _) => multiaddr,
+        (None, Some(port)) => return Ok(port),
+        (None, None) => network.bind_multiaddr(),
+    };
+
+    tcp_port(multiaddr).ok_or_else(|| FullNodeError::InvalidGossipAddress(multiaddr.clone()))
 }
 
 /// Initializes the API.
@@ -411,16 +570,96 @@ fn initialize_tangle<S: NodeStorageBackend>(builder: FullNodeBuilder<S>) -> Full
 #[derive(Clone)]
 struct FullNodeNeighborValidator {
     network_name: String,
+    allowed_peers: Option<HashSet<PeerId>>,
 }
 
 impl FullNodeNeighborValidator {
-    pub fn new(network_name: String) -> Self {
-        Self { network_name }
+    pub fn new(network_name: String, allowed_peers: Option<HashSet<PeerId>>) -> Self {
+        Self {
+            network_name,
+            allowed_peers,
+        }
     }
 }
 
 impl NeighborValidator for FullNodeNeighborValidator {
     fn is_valid<P: AsRef<bee_autopeering::Peer>>(&self, peer: P) -> bool {
-        peer.as_ref().has_service(&self.network_name)
+        let peer = peer.as_ref();
+
+        peer.has_service(&self.network_name)
+            && self.allowed_peers.as_ref().map_or(true, |allowed_peers| {
+                allowed_peers.contains(&peer.peer_id().libp2p_peer_id())
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gossip_service_port_of_websocket_bind_address() {
+        let network = NetworkConfig::build()
+            .with_bind_multiaddr("/ip4/0.0.0.0/tcp/15601/ws".parse().unwrap())
+            .unwrap()
+            .finish()
+            .unwrap();
+
+        assert_eq!(gossip_service_port(&network, None).unwrap(), 15601);
+        assert_eq!(gossip_service_port(&network, Some(15700)).unwrap(), 15700);
+    }
+
+    #[test]
+    fn gossip_service_port_of_websocket_announce_address() {
+        let network = NetworkConfig::build()
+            .with_bind_multiaddr("/ip4/0.0.0.0/tcp/15600".parse().unwrap())
+            .unwrap()
+            .with_announce_multiaddr("/dns/node.example.com/tcp/443/wss".parse().unwrap())
+            .unwrap()
+            .finish()
+            .unwrap();
+
+        assert_eq!(gossip_service_port(&network, None).unwrap(), 443);
+    }
+
+    #[test]
+    fn external_gossip_multiaddr_keeps_websocket() {
+        let bind_multiaddr = "/ip4/0.0.0.0/tcp/15601/ws".parse().unwrap();
+
+        assert_eq!(
+            external_gossip_multiaddr(&bind_multiaddr, [203, 0, 113, 7].into(), 15700),
+            "/ip4/203.0.113.7/tcp/15700/ws".parse().unwrap()
+        );
+    }
+
+    #[test]
+    fn configured_announce_address_wins_over_external_address() {
+        let mut network = NetworkConfig::build()
+            .with_bind_multiaddr("/ip4/0.0.0.0/tcp/15600".parse().unwrap())
+            .unwrap()
+            .with_announce_multiaddr("/dns/node.example.com/tcp/443/wss".parse().unwrap())
+            .unwrap()
+            .finish()
+            .unwrap();
+        network
+            .add_announce_multiaddr("/ip4/203.0.113.7/tcp/15700".parse().unwrap())
+            .unwrap();
+
+        assert_eq!(network.announce_multiaddrs().len(), 2);
+        assert_eq!(gossip_service_port(&network, Some(15700)).unwrap(), 443);
+    }
+
+    #[test]
+    fn external_address_announced_without_configured_announce_address() {
+        let mut network = NetworkConfig::build()
+            .with_bind_multiaddr("/ip4/0.0.0.0/tcp/15601/ws".parse().unwrap())
+            .unwrap()
+            .finish()
+            .unwrap();
+        let external_multiaddr = external_gossip_multiaddr(network.bind_multiaddr(), [203, 0, 113, 7].into(), 15700);
+        network.add_announce_multiaddr(external_multiaddr.clone()).unwrap();
+
+        assert_eq!(network.announce_multiaddrs().first(), Some(&external_multiaddr));
+        assert_eq!(gossip_service_port(&network, Some(15700)).unwrap(), 15700);
     }
 }
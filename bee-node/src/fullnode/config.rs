@@ -1,6 +1,8 @@
 // Copyright 2020-2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use std::path::PathBuf;
+
 use bee_autopeering::config::AutopeeringConfig;
 use bee_gossip::NetworkConfig;
 use bee_ledger::workers::{pruning::config::PruningConfig, snapshot::config::SnapshotConfig};
@@ -12,11 +14,17 @@ use fern_logger::LoggerConfig;
 #[cfg(feature = "dashboard")]
 use crate::plugins::dashboard::config::DashboardConfig;
 use crate::{
-    config::NetworkSpec, local::Local, plugins::mqtt::config::MqttConfig, storage::NodeStorageBackend, NodeConfig,
+    config::NetworkSpec,
+    local::Local,
+    plugins::{mqtt::config::MqttConfig, nat::config::NatConfig},
+    storage::NodeStorageBackend,
+    NodeConfig,
 };
 
 /// The config of a Bee full node.
 pub struct FullNodeConfig<S: NodeStorageBackend> {
+    /// The file the config was loaded from, if any.
+    pub config_path: Option<PathBuf>,
     /// The node alias.
     pub alias: String,
     /// The local entity.
@@ -43,6 +51,8 @@ pub struct FullNodeConfig<S: NodeStorageBackend> {
     pub tangle: TangleConfig,
     /// MQTT broker.
     pub mqtt: MqttConfig,
+    /// Port mapping on the gateway.
+    pub nat: NatConfig,
     /// Node dashboard.
     #[cfg(feature = "dashboard")]
     pub dashboard: DashboardConfig,
@@ -66,6 +76,7 @@ impl<S: NodeStorageBackend> FullNodeConfig<S> {
 
     pub fn from(local: Local, node_cfg: NodeConfig<S>) -> Self {
         Self {
+            config_path: node_cfg.config_path,
             alias: node_cfg.alias,
             local,
             network_spec: node_cfg.network_spec,
@@ -79,6 +90,7 @@ impl<S: NodeStorageBackend> FullNodeConfig<S> {
             storage: node_cfg.storage,
             tangle: node_cfg.tangle,
             mqtt: node_cfg.mqtt,
+            nat: node_cfg.nat,
             #[cfg(feature = "dashboard")]
             dashboard: node_cfg.dashboard,
         }
@@ -88,6 +100,7 @@ impl<S: NodeStorageBackend> FullNodeConfig<S> {
 impl<S: NodeStorageBackend> Clone for FullNodeConfig<S> {
     fn clone(&self) -> Self {
         Self {
+            config_path: self.config_path.clone(),
             alias: self.alias.clone(),
             local: self.local.clone(),
             network_spec: self.network_spec.clone(),
@@ -101,6 +114,7 @@ impl<S: NodeStorageBackend> Clone for FullNodeConfig<S> {
             storage: self.storage.clone(),
             tangle: self.tangle.clone(),
             mqtt: self.mqtt.clone(),
+            nat: self.nat.clone(),
             #[cfg(feature = "dashboard")]
             dashboard: self.dashboard.clone(),
         }
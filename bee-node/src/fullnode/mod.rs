@@ -28,6 +28,8 @@ pub enum FullNodeError {
     GossipLayerInitialization(#[from] bee_gossip::Error),
     #[error("{0}")]
     AutopeeringInitialization(Box<dyn std::error::Error>),
+    #[error("the gossip address {0} has no TCP port")]
+    InvalidGossipAddress(bee_gossip::Multiaddr),
     #[error("{0}")]
     Core(#[from] CoreError),
 }
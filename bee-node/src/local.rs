@@ -19,8 +19,8 @@ pub struct Local {
 
 impl Local {
     pub fn from_keypair(keypair: Keypair) -> Self {
-        let encoded = hex::encode(keypair.encode());
-        let peer_id = PeerId::from_public_key(&PublicKey::Ed25519(keypair.public()));
+        let encoded = hex::encode(keypair.to_bytes());
+        let peer_id = PeerId::from_public_key(&PublicKey::from(keypair.public()));
 
         Self {
             keypair,
@@ -126,7 +126,7 @@ fn migrate_keypair(encoded: String) -> Result<Keypair, IdentityMigrationError> {
         hex::decode_to_slice(&encoded[..], &mut decoded).map_err(|_| IdentityMigrationError::DecodeHex)?;
 
         // Decode the keypair from bytes.
-        Keypair::decode(&mut decoded).map_err(|_| IdentityMigrationError::DecodeKeypair)
+        Keypair::try_from_bytes(&mut decoded).map_err(|_| IdentityMigrationError::DecodeKeypair)
     } else {
         Err(IdentityMigrationError::InvalidKeypair)
     }
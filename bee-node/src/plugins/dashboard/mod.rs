@@ -18,8 +18,11 @@ use std::{
 use async_trait::async_trait;
 use bee_ledger::workers::event::MilestoneConfirmed;
 use bee_protocol::workers::{
-    event::{MessageSolidified, MpsMetricsUpdated, TipAdded, TipRemoved, VertexCreated},
-    MetricsWorker, PeerManagerResWorker,
+    event::{
+        AverageSpamMetricsUpdated, MessageOrphaned, MessageSolidified, MpsMetricsUpdated, SpamMetricsUpdated, TipAdded,
+        TipRemoved, VertexCreated, WarpSyncCompleted, WarpSyncProgressed,
+    },
+    MetricsWorker, MpsWorker, PeerManagerResWorker,
 };
 use bee_runtime::{node::Node, shutdown_stream::ShutdownStream, worker::Worker};
 use bee_tangle::{event::LatestMilestoneChanged, Tangle, TangleWorker};
@@ -100,6 +103,7 @@ where
         vec![
             TypeId::of::<TangleWorker>(),
             TypeId::of::<MetricsWorker>(),
+            TypeId::of::<MpsWorker>(),
             TypeId::of::<PeerManagerResWorker>(),
         ]
         .leak()
@@ -139,6 +143,20 @@ where
             false,
             <WsEvent as From<MpsMetricsUpdated>>::from,
         );
+        topic_handler(
+            node,
+            "SpamMetrics",
+            &users,
+            false,
+            <WsEvent as From<SpamMetricsUpdated>>::from,
+        );
+        topic_handler(
+            node,
+            "AverageSpamMetrics",
+            &users,
+            false,
+            <WsEvent as From<AverageSpamMetricsUpdated>>::from,
+        );
         topic_handler(node, "Milestone", &users, false, milestone::forward);
         topic_handler(
             node,
@@ -158,6 +176,27 @@ where
         );
         topic_handler(node, "TipInfo", &users, true, <WsEvent as From<TipAdded>>::from);
         topic_handler(node, "TipInfo", &users, true, <WsEvent as From<TipRemoved>>::from);
+        topic_handler(
+            node,
+            "OrphanedMessage",
+            &users,
+            true,
+            <WsEvent as From<MessageOrphaned>>::from,
+        );
+        topic_handler(
+            node,
+            "WarpSync",
+            &users,
+            false,
+            <WsEvent as From<WarpSyncProgressed>>::from,
+        );
+        topic_handler(
+            node,
+            "WarpSync",
+            &users,
+            false,
+            <WsEvent as From<WarpSyncCompleted>>::from,
+        );
 
         // run sub-workers
         confirmed_ms_metrics_worker(node, &users);
@@ -1,6 +1,7 @@
 // Copyright 2020-2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use bee_tangle::milestone_stats::LatencyStats;
 use serde::Serialize;
 
 use crate::plugins::dashboard::{
@@ -18,6 +19,8 @@ pub(crate) struct ConfirmedMilestoneMetricsResponse {
     rmps: u64,
     referenced_rate: f64,
     time_since_last_ms: u64,
+    confirmation_latency: LatencyStats,
+    solidification_delay: LatencyStats,
 }
 
 impl From<ConfirmedMilestoneMetrics> for WsEvent {
@@ -37,6 +40,8 @@ impl From<ConfirmedMilestoneMetrics> for ConfirmedMilestoneMetricsResponse {
             rmps: val.rmps,
             referenced_rate: val.referenced_rate,
             time_since_last_ms: val.time_since_last_ms,
+            confirmation_latency: val.confirmation_latency,
+            solidification_delay: val.solidification_delay,
         }
     }
 }
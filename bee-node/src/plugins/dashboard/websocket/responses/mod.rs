@@ -8,12 +8,15 @@ pub(crate) mod milestone;
 pub(crate) mod milestone_info;
 pub(crate) mod mps_metrics_updated;
 pub(crate) mod node_status;
+pub(crate) mod orphaned_message;
 pub(crate) mod peer_metric;
 pub(crate) mod public_node_status;
 pub(crate) mod solid_info;
+pub(crate) mod spam_metrics;
 pub(crate) mod sync_status;
 pub(crate) mod tip_info;
 pub(crate) mod vertex;
+pub(crate) mod warp_sync;
 
 use bee_rest_api::types::responses::PeersResponse;
 use serde::Serialize;
@@ -23,8 +26,10 @@ use crate::plugins::dashboard::websocket::{
         confirmed_info::ConfirmedInfoResponse, confirmed_milestone_metrics::ConfirmedMilestoneMetricsResponse,
         database_size_metrics::DatabaseSizeMetricsResponse, milestone::MilestoneResponse,
         milestone_info::MilestoneInfoResponse, mps_metrics_updated::MpsMetricsUpdatedResponse,
-        node_status::NodeStatusResponse, public_node_status::PublicNodeStatusResponse, solid_info::SolidInfoResponse,
+        node_status::NodeStatusResponse, orphaned_message::OrphanedMessageResponse,
+        public_node_status::PublicNodeStatusResponse, solid_info::SolidInfoResponse, spam_metrics::SpamMetricsResponse,
         sync_status::SyncStatusResponse, tip_info::TipInfoResponse, vertex::VertexResponse,
+        warp_sync::WarpSyncResponse,
     },
     topics::WsTopic,
 };
@@ -59,4 +64,7 @@ pub(crate) enum WsEventInner {
     PublicNodeStatus(PublicNodeStatusResponse),
     NodeStatus(Box<NodeStatusResponse>), // `NodeStatusResponse` is much larger than the rest.
     PeerMetric(PeersResponse),
+    OrphanedMessage(OrphanedMessageResponse),
+    SpamMetrics(SpamMetricsResponse),
+    WarpSync(WarpSyncResponse),
 }
@@ -0,0 +1,23 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use bee_protocol::workers::event::MessageOrphaned;
+use bee_rest_api::types::dtos::OrphanedMessageDto;
+use serde::Serialize;
+
+use crate::plugins::dashboard::websocket::{
+    responses::{WsEvent, WsEventInner},
+    topics::WsTopic,
+};
+
+#[derive(Clone, Debug, Serialize)]
+pub(crate) struct OrphanedMessageResponse(pub OrphanedMessageDto);
+
+impl From<MessageOrphaned> for WsEvent {
+    fn from(event: MessageOrphaned) -> Self {
+        Self::new(
+            WsTopic::OrphanedMessage,
+            WsEventInner::OrphanedMessage(OrphanedMessageResponse(OrphanedMessageDto::from(&event))),
+        )
+    }
+}
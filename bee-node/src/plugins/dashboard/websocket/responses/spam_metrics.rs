@@ -0,0 +1,63 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use bee_protocol::{
+    types::metrics::SpamMetrics,
+    workers::event::{AverageSpamMetricsUpdated, SpamMetricsUpdated},
+};
+use serde::Serialize;
+
+use crate::plugins::dashboard::websocket::{
+    responses::{WsEvent, WsEventInner},
+    topics::WsTopic,
+};
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SpamMetricsResponse {
+    pub indexation: f64,
+    pub transaction: f64,
+    pub milestone: f64,
+    pub no_payload: f64,
+    pub top_tags: Vec<SpamTagDto>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub(crate) struct SpamTagDto {
+    pub tag: String,
+    pub mps: f64,
+}
+
+impl From<SpamMetricsUpdated> for WsEvent {
+    fn from(event: SpamMetricsUpdated) -> Self {
+        Self::new(WsTopic::SpamMetrics, WsEventInner::SpamMetrics(event.metrics.into()))
+    }
+}
+
+impl From<AverageSpamMetricsUpdated> for WsEvent {
+    fn from(event: AverageSpamMetricsUpdated) -> Self {
+        Self::new(
+            WsTopic::AverageSpamMetrics,
+            WsEventInner::SpamMetrics(event.metrics.into()),
+        )
+    }
+}
+
+impl From<SpamMetrics> for SpamMetricsResponse {
+    fn from(metrics: SpamMetrics) -> Self {
+        Self {
+            indexation: metrics.indexation,
+            transaction: metrics.transaction,
+            milestone: metrics.milestone,
+            no_payload: metrics.no_payload,
+            top_tags: metrics
+                .top_tags
+                .into_iter()
+                .map(|(tag, mps)| SpamTagDto {
+                    tag: hex::encode(tag),
+                    mps,
+                })
+                .collect(),
+        }
+    }
+}
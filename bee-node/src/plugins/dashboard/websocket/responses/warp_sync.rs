@@ -0,0 +1,56 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use bee_protocol::workers::event::{WarpSyncCompleted, WarpSyncProgressed};
+use serde::Serialize;
+
+use crate::plugins::dashboard::websocket::{
+    responses::{WsEvent, WsEventInner},
+    topics::WsTopic,
+};
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WarpSyncResponse {
+    pub start_index: u32,
+    pub current_index: u32,
+    pub target_index: u32,
+    pub milestones_per_second: Option<f64>,
+    pub eta_secs: Option<u64>,
+    pub duration_secs: Option<u64>,
+    pub completed: bool,
+}
+
+impl From<WarpSyncProgressed> for WsEvent {
+    fn from(event: WarpSyncProgressed) -> Self {
+        Self::new(
+            WsTopic::WarpSync,
+            WsEventInner::WarpSync(WarpSyncResponse {
+                start_index: *event.start_index,
+                current_index: *event.current_index,
+                target_index: *event.target_index,
+                milestones_per_second: Some(event.milestones_per_second),
+                eta_secs: event.eta_secs,
+                duration_secs: None,
+                completed: false,
+            }),
+        )
+    }
+}
+
+impl From<WarpSyncCompleted> for WsEvent {
+    fn from(event: WarpSyncCompleted) -> Self {
+        Self::new(
+            WsTopic::WarpSync,
+            WsEventInner::WarpSync(WarpSyncResponse {
+                start_index: *event.start_index,
+                current_index: *event.target_index,
+                target_index: *event.target_index,
+                milestones_per_second: None,
+                eta_secs: None,
+                duration_secs: Some(event.duration_secs),
+                completed: true,
+            }),
+        )
+    }
+}
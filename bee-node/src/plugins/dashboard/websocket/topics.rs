@@ -23,6 +23,8 @@ pub(crate) enum WsTopic {
     DatabaseCleanupEvent = 14,
     SpamMetrics = 15,
     AverageSpamMetrics = 16,
+    OrphanedMessage = 17,
+    WarpSync = 18,
 }
 
 impl TryFrom<u8> for WsTopic {
@@ -47,6 +49,8 @@ impl TryFrom<u8> for WsTopic {
             14 => Ok(WsTopic::DatabaseCleanupEvent),
             15 => Ok(WsTopic::SpamMetrics),
             16 => Ok(WsTopic::AverageSpamMetrics),
+            17 => Ok(WsTopic::OrphanedMessage),
+            18 => Ok(WsTopic::WarpSync),
             _ => Err(val),
         }
     }
@@ -66,6 +70,7 @@ impl WsTopic {
                 | WsTopic::ConfirmedInfo
                 | WsTopic::MilestoneInfo
                 | WsTopic::TipInfo
+                | WsTopic::WarpSync
         )
     }
 }
@@ -4,6 +4,7 @@
 use bee_ledger::workers::event::MilestoneConfirmed;
 use bee_protocol::types::metrics::NodeMetrics;
 use bee_runtime::{node::Node, shutdown_stream::ShutdownStream};
+use bee_tangle::{milestone_stats::LatencyStats, Tangle};
 use futures::StreamExt;
 use log::{debug, error};
 use tokio::sync::mpsc;
@@ -20,6 +21,7 @@ where
     N::Backend: NodeStorageBackend,
 {
     let metrics = node.resource::<NodeMetrics>();
+    let tangle = node.resource::<Tangle<N::Backend>>();
     let bus = node.bus();
     let users = users.clone();
     let (tx, rx) = mpsc::unbounded_channel::<MilestoneConfirmed>();
@@ -48,12 +50,21 @@ where
 
                 // to avoid division by zero in case two milestones do have the same timestamp
                 if time_diff > 0 {
+                    let stats = tangle.get_milestone_stats(event.index).await;
+
                     let metrics = ConfirmedMilestoneMetrics {
                         ms_index: *event.index,
                         mps: new_msg_diff / time_diff,
                         rmps: event.referenced_messages as u64 / time_diff,
                         referenced_rate,
                         time_since_last_ms: time_diff,
+                        confirmation_latency: stats
+                            .as_ref()
+                            .map(|stats| stats.confirmation_latency)
+                            .unwrap_or_default(),
+                        solidification_delay: stats
+                            .map(|stats| stats.solidification_delay)
+                            .unwrap_or_default(),
                     };
                     broadcast(metrics.into(), &users).await;
                 }  else {
@@ -84,4 +95,6 @@ pub struct ConfirmedMilestoneMetrics {
     pub rmps: u64,
     pub referenced_rate: f64,
     pub time_since_last_ms: u64,
+    pub confirmation_latency: LatencyStats,
+    pub solidification_delay: LatencyStats,
 }
@@ -5,6 +5,8 @@
 pub mod dashboard;
 pub mod mps;
 pub mod mqtt;
+pub mod nat;
+pub mod peer_reloader;
 pub mod version_checker;
 
 use std::{any::type_name, error::Error, fmt};
@@ -14,7 +16,7 @@ use bee_runtime::{event::Bus, node::Node, worker::Worker};
 
 #[cfg(feature = "dashboard")]
 pub use self::dashboard::Dashboard;
-pub use self::{mps::Mps, mqtt::Mqtt, version_checker::VersionChecker};
+pub use self::{mps::Mps, mqtt::Mqtt, nat::PortMapper, peer_reloader::PeerReloader, version_checker::VersionChecker};
 
 #[async_trait]
 pub trait Plugin: Sized + Send + Sync + 'static {
@@ -9,6 +9,8 @@ pub mod config;
 use std::{any::Any, convert::Infallible};
 
 use async_trait::async_trait;
+use bee_protocol::workers::event::MessageOrphaned;
+use bee_rest_api::types::dtos::OrphanedMessageDto;
 use bee_runtime::{node::Node, shutdown_stream::ShutdownStream, worker::Worker};
 use bee_tangle::event::{LatestMilestoneChanged, SolidMilestoneChanged};
 use futures::stream::StreamExt;
@@ -74,6 +76,12 @@ impl<N: Node> Worker<N> for Mqtt {
                 // topic_handler(node, _TOPIC_MESSAGES_REFERENCED, |_event: &_| {
                 //     (_TOPIC_MESSAGES_REFERENCED, "")
                 // });
+                topic_handler(node, TOPIC_MESSAGES_ORPHANED, |event: &MessageOrphaned| {
+                    (
+                        TOPIC_MESSAGES_ORPHANED,
+                        serde_json::to_string(&OrphanedMessageDto::from(event)).unwrap_or_default(),
+                    )
+                });
                 // topic_handler(node, _TOPIC_MESSAGES_INDEXATION, |_event: &_| {
                 //     (_TOPIC_MESSAGES_INDEXATION, "")
                 // });
@@ -5,6 +5,7 @@ pub(crate) const TOPIC_MILESTONES_LATEST: &str = "milestones/latest";
 pub(crate) const TOPIC_MILESTONES_SOLID: &str = "milestones/solid";
 pub(crate) const _TOPIC_MESSAGES: &str = "messages";
 pub(crate) const _TOPIC_MESSAGES_REFERENCED: &str = "messages/referenced";
+pub(crate) const TOPIC_MESSAGES_ORPHANED: &str = "messages/orphaned";
 pub(crate) const _TOPIC_MESSAGES_INDEXATION: &str = "messages/indexation/{index}";
 pub(crate) const _TOPIC_MESSAGES_METADATA: &str = "messages/{messageId}/metadata";
 pub(crate) const _TOPIC_OUTPUTS: &str = "outputs/{outputId}";
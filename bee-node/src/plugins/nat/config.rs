@@ -0,0 +1,76 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::IpAddr;
+
+use serde::Deserialize;
+
+const DEFAULT_LEASE_SECS: u32 = 3600;
+// Mappings are renewed halfway through their lease, so it shouldn't be too short.
+const MIN_LEASE_SECS: u32 = 120;
+
+/// The protocol used to ask the gateway for port mappings.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum NatProtocol {
+    /// Tries UPnP first, then NAT-PMP.
+    Auto,
+    /// UPnP Internet Gateway Device.
+    Upnp,
+    /// NAT Port Mapping Protocol.
+    NatPmp,
+}
+
+#[derive(Default, Deserialize, PartialEq)]
+pub struct NatConfigBuilder {
+    enabled: Option<bool>,
+    protocol: Option<NatProtocol>,
+    gateway: Option<IpAddr>,
+    #[serde(alias = "leaseSecs")]
+    lease_secs: Option<u32>,
+}
+
+impl NatConfigBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn finish(self) -> NatConfig {
+        NatConfig {
+            enabled: self.enabled.unwrap_or(false),
+            protocol: self.protocol.unwrap_or(NatProtocol::Auto),
+            gateway: self.gateway,
+            lease_secs: self.lease_secs.unwrap_or(DEFAULT_LEASE_SECS).max(MIN_LEASE_SECS),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct NatConfig {
+    enabled: bool,
+    protocol: NatProtocol,
+    gateway: Option<IpAddr>,
+    lease_secs: u32,
+}
+
+impl NatConfig {
+    /// Returns whether the gossip and autopeering ports are mapped on the gateway.
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Returns the protocol used to ask the gateway for port mappings.
+    pub fn protocol(&self) -> NatProtocol {
+        self.protocol
+    }
+
+    /// Returns the address of the NAT-PMP gateway, if it isn't taken from the routing table.
+    pub fn gateway(&self) -> Option<IpAddr> {
+        self.gateway
+    }
+
+    /// Returns the requested lifetime of the port mappings.
+    pub fn lease_secs(&self) -> u32 {
+        self.lease_secs
+    }
+}
@@ -0,0 +1,256 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Maps the gossip and autopeering ports on the gateway of a node running behind NAT, e.g. a home router, so that
+//! other peers can reach it.
+//!
+//! Both UPnP (Internet Gateway Device) and NAT-PMP gateways are supported. The mappings are requested when the node
+//! starts, renewed halfway through their lease and removed again when the node shuts down.
+
+pub mod config;
+pub mod natpmp;
+pub mod upnp;
+
+use std::{
+    convert::Infallible,
+    fmt,
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use bee_runtime::{node::Node, shutdown_stream::ShutdownStream, worker::Worker};
+use futures::StreamExt;
+use log::{info, warn};
+use tokio::time::{interval_at, Instant};
+use tokio_stream::wrappers::IntervalStream;
+
+use self::{
+    config::{NatConfig, NatProtocol},
+    natpmp::NatPmpGateway,
+    upnp::UpnpGateway,
+};
+
+/// Errors that occur while talking to the gateway.
+#[derive(Debug, thiserror::Error)]
+pub enum NatError {
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+    #[error("{0}")]
+    Http(#[from] reqwest::Error),
+    #[error("the gateway did not respond in time")]
+    Timeout,
+    #[error("no gateway found")]
+    NoGateway,
+    #[error("invalid response from the gateway: {0}")]
+    InvalidResponse(String),
+    #[error("the gateway refused the request: {0}")]
+    Refused(String),
+}
+
+/// The transport protocol of a mapped port.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PortProtocol {
+    Tcp,
+    Udp,
+}
+
+impl fmt::Display for PortProtocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tcp => write!(f, "TCP"),
+            Self::Udp => write!(f, "UDP"),
+        }
+    }
+}
+
+/// A port mapped on the gateway.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PortMapping {
+    pub protocol: PortProtocol,
+    pub internal_port: u16,
+    pub external_port: u16,
+}
+
+/// A gateway that can map ports.
+#[async_trait]
+pub trait Gateway: Send + Sync {
+    /// Returns the name of the protocol used to talk to the gateway.
+    fn name(&self) -> &'static str;
+
+    /// Returns the external IP address of the gateway.
+    async fn external_ip(&self) -> Result<IpAddr, NatError>;
+
+    /// Maps a port, preferably to the given external port, for the given lease.
+    async fn map_port(
+        &self,
+        protocol: PortProtocol,
+        internal_port: u16,
+        external_port: u16,
+        lease_secs: u32,
+    ) -> Result<PortMapping, NatError>;
+
+    /// Removes a port mapping.
+    async fn unmap_port(&self, mapping: &PortMapping) -> Result<(), NatError>;
+}
+
+/// Looks for a gateway speaking the configured protocol.
+pub async fn discover_gateway(config: &NatConfig) -> Result<Arc<dyn Gateway>, NatError> {
+    let natpmp = || async {
+        let gateway_ip = config
+            .gateway()
+            .or_else(natpmp::default_gateway)
+            .ok_or(NatError::NoGateway)?;
+        let gateway = NatPmpGateway::new(SocketAddr::new(gateway_ip, natpmp::NATPMP_PORT));
+        // Make sure that the gateway actually speaks NAT-PMP.
+        gateway.external_ip().await?;
+
+        Ok::<_, NatError>(Arc::new(gateway) as Arc<dyn Gateway>)
+    };
+
+    match config.protocol() {
+        NatProtocol::Upnp => Ok(Arc::new(UpnpGateway::discover().await?)),
+        NatProtocol::NatPmp => natpmp().await,
+        NatProtocol::Auto => match UpnpGateway::discover().await {
+            Ok(gateway) => Ok(Arc::new(gateway)),
+            Err(e) => {
+                info!("No UPnP gateway found: {}. Trying NAT-PMP.", e);
+                natpmp().await
+            }
+        },
+    }
+}
+
+/// Maps the given ports on the gateway, preferably to the same external ports.
+///
+/// Ports that can't be mapped are skipped, only failing to find a gateway is an error.
+pub async fn map_ports(config: &NatConfig, ports: &[(PortProtocol, u16)]) -> Result<PortMapperConfig, NatError> {
+    let gateway = discover_gateway(config).await?;
+
+    info!("Found {} gateway.", gateway.name());
+
+    let external_ip = match gateway.external_ip().await {
+        Ok(external_ip) => Some(external_ip),
+        Err(e) => {
+            warn!("Getting the external IP address from the gateway failed: {}.", e);
+            None
+        }
+    };
+
+    let mut mappings = Vec::with_capacity(ports.len());
+
+    for (protocol, port) in ports {
+        match gateway.map_port(*protocol, *port, *port, config.lease_secs()).await {
+            Ok(mapping) => {
+                info!(
+                    "Mapped {} port {} to external port {}.",
+                    mapping.protocol, mapping.internal_port, mapping.external_port
+                );
+                mappings.push(mapping);
+            }
+            Err(e) => warn!("Mapping {} port {} failed: {}.", protocol, port, e),
+        }
+    }
+
+    Ok(PortMapperConfig {
+        gateway,
+        external_ip,
+        mappings,
+        lease_secs: config.lease_secs(),
+    })
+}
+
+/// The config of the [`PortMapper`], i.e. the ports already mapped on the gateway.
+pub struct PortMapperConfig {
+    gateway: Arc<dyn Gateway>,
+    external_ip: Option<IpAddr>,
+    mappings: Vec<PortMapping>,
+    lease_secs: u32,
+}
+
+impl PortMapperConfig {
+    /// Returns the external IP address of the gateway, if it is known.
+    pub fn external_ip(&self) -> Option<IpAddr> {
+        self.external_ip
+    }
+
+    /// Returns the external port an internal port is mapped to, if any.
+    pub fn external_port(&self, protocol: PortProtocol, internal_port: u16) -> Option<u16> {
+        self.mappings
+            .iter()
+            .find(|mapping| mapping.protocol == protocol && mapping.internal_port == internal_port)
+            .map(|mapping| mapping.external_port)
+    }
+}
+
+/// A worker that renews the port mappings and removes them on shutdown.
+#[derive(Default)]
+pub struct PortMapper {}
+
+#[async_trait]
+impl<N: Node> Worker<N> for PortMapper {
+    type Config = PortMapperConfig;
+    type Error = Infallible;
+
+    async fn start(node: &mut N, config: Self::Config) -> Result<Self, Self::Error> {
+        node.spawn::<Self, _, _>(|shutdown| async move {
+            info!("Running.");
+
+            let PortMapperConfig {
+                gateway,
+                mut mappings,
+                lease_secs,
+                ..
+            } = config;
+
+            let renew_interval = Duration::from_secs(u64::from(lease_secs / 2));
+            let mut ticker = ShutdownStream::new(
+                shutdown,
+                IntervalStream::new(interval_at(Instant::now() + renew_interval, renew_interval)),
+            );
+
+            while ticker.next().await.is_some() {
+                for mapping in mappings.iter_mut() {
+                    match gateway
+                        .map_port(
+                            mapping.protocol,
+                            mapping.internal_port,
+                            mapping.external_port,
+                            lease_secs,
+                        )
+                        .await
+                    {
+                        Ok(renewed) if renewed.external_port != mapping.external_port => {
+                            // The old port was already announced, the node needs to be restarted to announce the new
+                            // one.
+                            warn!(
+                                "{} port {} is now mapped to external port {} instead of {}.",
+                                mapping.protocol, mapping.internal_port, renewed.external_port, mapping.external_port
+                            );
+                            *mapping = renewed;
+                        }
+                        Ok(_) => {}
+                        Err(e) => warn!(
+                            "Renewing the mapping of {} port {} failed: {}.",
+                            mapping.protocol, mapping.internal_port, e
+                        ),
+                    }
+                }
+            }
+
+            for mapping in mappings.iter() {
+                if let Err(e) = gateway.unmap_port(mapping).await {
+                    warn!(
+                        "Removing the mapping of {} port {} failed: {}.",
+                        mapping.protocol, mapping.internal_port, e
+                    );
+                }
+            }
+
+            info!("Stopped.");
+        });
+
+        Ok(Self::default())
+    }
+}
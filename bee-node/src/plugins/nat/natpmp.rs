@@ -0,0 +1,227 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! A minimal NAT Port Mapping Protocol client, see RFC 6886.
+
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use tokio::{net::UdpSocket, time::timeout};
+
+use super::{Gateway, NatError, PortMapping, PortProtocol};
+
+/// The port NAT-PMP gateways listen on.
+pub const NATPMP_PORT: u16 = 5351;
+
+const VERSION: u8 = 0;
+const OPCODE_EXTERNAL_ADDRESS: u8 = 0;
+const OPCODE_MAP_UDP: u8 = 1;
+const OPCODE_MAP_TCP: u8 = 2;
+// Responses carry the opcode of the request plus 128.
+const OPCODE_RESPONSE: u8 = 128;
+// The RFC recommends to double the timeout up to 9 times, but a gateway that didn't respond after a few seconds most
+// likely won't respond at all.
+const INITIAL_TIMEOUT: Duration = Duration::from_millis(250);
+const MAX_ATTEMPTS: u32 = 4;
+
+/// A gateway speaking NAT-PMP.
+pub struct NatPmpGateway {
+    address: SocketAddr,
+}
+
+impl NatPmpGateway {
+    /// Creates a client of the NAT-PMP gateway at the given address.
+    pub fn new(address: SocketAddr) -> Self {
+        Self { address }
+    }
+
+    async fn request(&self, request: &[u8], response_len: usize) -> Result<Vec<u8>, NatError> {
+        let socket = UdpSocket::bind(SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 0)).await?;
+        socket.connect(self.address).await?;
+
+        let mut response = vec![0u8; response_len];
+        let mut wait = INITIAL_TIMEOUT;
+
+        for _ in 0..MAX_ATTEMPTS {
+            socket.send(request).await?;
+
+            if let Ok(len) = timeout(wait, socket.recv(&mut response)).await {
+                let len = len?;
+
+                if len < response_len {
+                    return Err(NatError::InvalidResponse(format!(
+                        "{} bytes instead of {}",
+                        len, response_len
+                    )));
+                }
+
+                check_response(&response, request[1])?;
+
+                return Ok(response);
+            }
+
+            wait *= 2;
+        }
+
+        Err(NatError::Timeout)
+    }
+}
+
+#[async_trait]
+impl Gateway for NatPmpGateway {
+    fn name(&self) -> &'static str {
+        "NAT-PMP"
+    }
+
+    async fn external_ip(&self) -> Result<IpAddr, NatError> {
+        let response = self.request(&[VERSION, OPCODE_EXTERNAL_ADDRESS], 12).await?;
+
+        Ok(Ipv4Addr::new(response[8], response[9], response[10], response[11]).into())
+    }
+
+    async fn map_port(
+        &self,
+        protocol: PortProtocol,
+        internal_port: u16,
+        external_port: u16,
+        lease_secs: u32,
+    ) -> Result<PortMapping, NatError> {
+        let response = self
+            .request(&map_request(protocol, internal_port, external_port, lease_secs), 16)
+            .await?;
+
+        Ok(PortMapping {
+            protocol,
+            internal_port,
+            external_port: u16::from_be_bytes([response[10], response[11]]),
+        })
+    }
+
+    async fn unmap_port(&self, mapping: &PortMapping) -> Result<(), NatError> {
+        // A mapping is removed by requesting it again with a lifetime of 0.
+        self.request(&map_request(mapping.protocol, mapping.internal_port, 0, 0), 16)
+            .await
+            .map(drop)
+    }
+}
+
+fn map_request(protocol: PortProtocol, internal_port: u16, external_port: u16, lease_secs: u32) -> Vec<u8> {
+    let opcode = match protocol {
+        PortProtocol::Udp => OPCODE_MAP_UDP,
+        PortProtocol::Tcp => OPCODE_MAP_TCP,
+    };
+
+    let mut request = vec![VERSION, opcode, 0, 0];
+    request.extend_from_slice(&internal_port.to_be_bytes());
+    request.extend_from_slice(&external_port.to_be_bytes());
+    request.extend_from_slice(&lease_secs.to_be_bytes());
+    request
+}
+
+fn check_response(response: &[u8], opcode: u8) -> Result<(), NatError> {
+    if response[0] != VERSION || response[1] != OPCODE_RESPONSE + opcode {
+        return Err(NatError::InvalidResponse(format!(
+            "version {} and opcode {}",
+            response[0], response[1]
+        )));
+    }
+
+    match u16::from_be_bytes([response[2], response[3]]) {
+        0 => Ok(()),
+        1 => Err(NatError::Refused("unsupported version".to_string())),
+        2 => Err(NatError::Refused("not authorized".to_string())),
+        3 => Err(NatError::Refused("network failure".to_string())),
+        4 => Err(NatError::Refused("out of resources".to_string())),
+        5 => Err(NatError::Refused("unsupported opcode".to_string())),
+        code => Err(NatError::Refused(format!("result code {}", code))),
+    }
+}
+
+/// Returns the default IPv4 gateway from the routing table.
+#[cfg(target_os = "linux")]
+pub fn default_gateway() -> Option<IpAddr> {
+    // The destination and gateway are hex encoded in host byte order.
+    std::fs::read_to_string("/proc/net/route")
+        .ok()?
+        .lines()
+        .skip(1)
+        .map(|line| line.split_whitespace().collect::<Vec<_>>())
+        .find(|fields| fields.len() > 2 && fields[1] == "00000000")
+        .and_then(|fields| u32::from_str_radix(fields[2], 16).ok())
+        .map(|gateway| Ipv4Addr::from(gateway.to_ne_bytes()).into())
+}
+
+/// Returns the default IPv4 gateway from the routing table.
+#[cfg(not(target_os = "linux"))]
+pub fn default_gateway() -> Option<IpAddr> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXTERNAL_IP: [u8; 4] = [203, 0, 113, 7];
+
+    // Answers NAT-PMP requests like a gateway that maps every port to the next one.
+    async fn mock_gateway() -> SocketAddr {
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let address = socket.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            let mut request = [0u8; 12];
+
+            while let Ok((len, from)) = socket.recv_from(&mut request).await {
+                let mut response = vec![VERSION, OPCODE_RESPONSE + request[1], 0, 0, 0, 0, 0, 1];
+
+                match (len, request[1]) {
+                    (2, OPCODE_EXTERNAL_ADDRESS) => response.extend_from_slice(&EXTERNAL_IP),
+                    (12, OPCODE_MAP_UDP | OPCODE_MAP_TCP) => {
+                        let external_port = u16::from_be_bytes([request[6], request[7]]);
+                        let external_port = if external_port == 0 { 0 } else { external_port + 1 };
+
+                        response.extend_from_slice(&request[4..6]);
+                        response.extend_from_slice(&external_port.to_be_bytes());
+                        response.extend_from_slice(&request[8..12]);
+                    }
+                    _ => response[3] = 5,
+                }
+
+                socket.send_to(&response, from).await.unwrap();
+            }
+        });
+
+        address
+    }
+
+    #[tokio::test]
+    async fn map_ports_on_mock_gateway() {
+        let gateway = NatPmpGateway::new(mock_gateway().await);
+
+        assert_eq!(gateway.external_ip().await.unwrap(), IpAddr::from(EXTERNAL_IP));
+
+        let mapping = gateway.map_port(PortProtocol::Tcp, 15600, 15600, 3600).await.unwrap();
+
+        assert_eq!(
+            mapping,
+            PortMapping {
+                protocol: PortProtocol::Tcp,
+                internal_port: 15600,
+                external_port: 15601,
+            }
+        );
+        gateway.unmap_port(&mapping).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn time_out_without_gateway() {
+        // Nothing answers on this socket.
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let gateway = NatPmpGateway::new(socket.local_addr().unwrap());
+
+        assert!(matches!(gateway.external_ip().await, Err(NatError::Timeout)));
+    }
+}
@@ -0,0 +1,415 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! A minimal UPnP Internet Gateway Device client.
+//!
+//! The gateway is discovered via SSDP, its description tells where to send the SOAP requests of the WAN connection
+//! service to.
+
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use reqwest::{header::CONTENT_TYPE, Client, RequestBuilder, Url};
+use tokio::{
+    net::{lookup_host, UdpSocket},
+    time::timeout,
+};
+
+use super::{Gateway, NatError, PortMapping, PortProtocol};
+
+const SSDP_ADDRESS: &str = "239.255.255.250:1900";
+const SEARCH_TARGET: &str = "urn:schemas-upnp-org:device:InternetGatewayDevice:1";
+const SERVICE_TYPES: [&str; 3] = [
+    "urn:schemas-upnp-org:service:WANIPConnection:2",
+    "urn:schemas-upnp-org:service:WANIPConnection:1",
+    "urn:schemas-upnp-org:service:WANPPPConnection:1",
+];
+const DISCOVERY_TIMEOUT: Duration = Duration::from_secs(3);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+const MAPPING_DESCRIPTION: &str = "bee";
+// The error code of gateways that don't support mappings that expire.
+const ONLY_PERMANENT_LEASES_SUPPORTED: &str = "725";
+
+/// A gateway speaking UPnP.
+pub struct UpnpGateway {
+    client: Client,
+    control_url: Url,
+    service_type: &'static str,
+    // The address of the node in the network of the gateway.
+    local_ip: IpAddr,
+}
+
+impl UpnpGateway {
+    /// Searches the local network for a gateway.
+    pub async fn discover() -> Result<Self, NatError> {
+        let socket = UdpSocket::bind(SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 0)).await?;
+        let search = format!(
+            "M-SEARCH * HTTP/1.1\r\nHOST: {}\r\nST: {}\r\nMAN: \"ssdp:discover\"\r\nMX: 2\r\n\r\n",
+            SSDP_ADDRESS, SEARCH_TARGET
+        );
+
+        socket.send_to(search.as_bytes(), SSDP_ADDRESS).await?;
+
+        let mut response = vec![0u8; 2048];
+        let len = timeout(DISCOVERY_TIMEOUT, socket.recv(&mut response))
+            .await
+            .map_err(|_| NatError::NoGateway)??;
+        let response = String::from_utf8_lossy(&response[..len]);
+
+        let location =
+            header(&response, "location").ok_or_else(|| NatError::InvalidResponse("missing location".to_string()))?;
+
+        Self::from_location(location).await
+    }
+
+    /// Creates a client of the gateway whose description is at the given URL.
+    pub async fn from_location(location: &str) -> Result<Self, NatError> {
+        let invalid_url = |url: &str| NatError::InvalidResponse(format!("invalid URL '{}'", url));
+
+        let location = Url::parse(location.trim()).map_err(|_| invalid_url(location))?;
+        let client = Client::builder().timeout(REQUEST_TIMEOUT).build()?;
+        let (status, description) = send(client.get(location.clone())).await?;
+
+        if status != 200 {
+            return Err(NatError::InvalidResponse(format!(
+                "status {} for the description",
+                status
+            )));
+        }
+
+        let (service_type, control_url) = description
+            .split("<service>")
+            .skip(1)
+            .find_map(|service| {
+                let service_type = element(service, "serviceType")?;
+                let service_type = SERVICE_TYPES.iter().find(|known| **known == service_type)?;
+
+                Some((*service_type, element(service, "controlURL")?))
+            })
+            .ok_or_else(|| NatError::InvalidResponse("no WAN connection service".to_string()))?;
+
+        // The control URL is either absolute or relative to the location of the description.
+        let control_url = location.join(control_url).map_err(|_| invalid_url(control_url))?;
+        let local_ip = local_ip(&control_url).await?;
+
+        Ok(Self {
+            client,
+            control_url,
+            service_type,
+            local_ip,
+        })
+    }
+
+    async fn soap(&self, action: &str, arguments: &[(&str, String)]) -> Result<String, NatError> {
+        let arguments = arguments
+            .iter()
+            .map(|(name, value)| format!("<{0}>{1}</{0}>", name, value))
+            .collect::<String>();
+        let body = format!(
+            "<?xml version=\"1.0\"?>\r\n<s:Envelope xmlns:s=\"http://schemas.xmlsoap.org/soap/envelope/\" \
+             s:encodingStyle=\"http://schemas.xmlsoap.org/soap/encoding/\"><s:Body><u:{0} xmlns:u=\"{1}\">{2}</u:{0}>\
+             </s:Body></s:Envelope>",
+            action, self.service_type, arguments
+        );
+        let request = self
+            .client
+            .post(self.control_url.clone())
+            .header(CONTENT_TYPE, "text/xml; charset=\"utf-8\"")
+            .header("SOAPAction", format!("\"{}#{}\"", self.service_type, action))
+            .body(body);
+
+        let (status, response) = send(request).await?;
+
+        if status == 200 {
+            Ok(response)
+        } else {
+            let code = element(&response, "errorCode").unwrap_or_default();
+            let description = element(&response, "errorDescription").unwrap_or("unknown error");
+
+            Err(NatError::Refused(format!("{} {}", code, description)))
+        }
+    }
+
+    async fn add_port_mapping(
+        &self,
+        protocol: PortProtocol,
+        internal_port: u16,
+        external_port: u16,
+        lease_secs: u32,
+    ) -> Result<(), NatError> {
+        self.soap(
+            "AddPortMapping",
+            &[
+                ("NewRemoteHost", String::new()),
+                ("NewExternalPort", external_port.to_string()),
+                ("NewProtocol", protocol.to_string()),
+                ("NewInternalPort", internal_port.to_string()),
+                ("NewInternalClient", self.local_ip.to_string()),
+                ("NewEnabled", "1".to_string()),
+                ("NewPortMappingDescription", MAPPING_DESCRIPTION.to_string()),
+                ("NewLeaseDuration", lease_secs.to_string()),
+            ],
+        )
+        .await
+        .map(drop)
+    }
+}
+
+#[async_trait]
+impl Gateway for UpnpGateway {
+    fn name(&self) -> &'static str {
+        "UPnP"
+    }
+
+    async fn external_ip(&self) -> Result<IpAddr, NatError> {
+        let response = self.soap("GetExternalIPAddress", &[]).await?;
+
+        element(&response, "NewExternalIPAddress")
+            .and_then(|ip| ip.trim().parse().ok())
+            .ok_or_else(|| NatError::InvalidResponse("missing external IP address".to_string()))
+    }
+
+    async fn map_port(
+        &self,
+        protocol: PortProtocol,
+        internal_port: u16,
+        external_port: u16,
+        lease_secs: u32,
+    ) -> Result<PortMapping, NatError> {
+        match self
+            .add_port_mapping(protocol, internal_port, external_port, lease_secs)
+            .await
+        {
+            // Permanent mappings are still removed on shutdown.
+            Err(NatError::Refused(e)) if e.starts_with(ONLY_PERMANENT_LEASES_SUPPORTED) => {
+                self.add_port_mapping(protocol, internal_port, external_port, 0).await
+            }
+            result => result,
+        }?;
+
+        Ok(PortMapping {
+            protocol,
+            internal_port,
+            external_port,
+        })
+    }
+
+    async fn unmap_port(&self, mapping: &PortMapping) -> Result<(), NatError> {
+        self.soap(
+            "DeletePortMapping",
+            &[
+                ("NewRemoteHost", String::new()),
+                ("NewExternalPort", mapping.external_port.to_string()),
+                ("NewProtocol", mapping.protocol.to_string()),
+            ],
+        )
+        .await
+        .map(drop)
+    }
+}
+
+/// Sends an HTTP request, and returns the status and body of the response.
+async fn send(request: RequestBuilder) -> Result<(u16, String), NatError> {
+    let exchange = async {
+        let response = request.send().await?;
+        let status = response.status().as_u16();
+
+        Ok::<_, reqwest::Error>((status, response.text().await?))
+    };
+
+    exchange.await.map_err(|e| {
+        if e.is_timeout() {
+            NatError::Timeout
+        } else {
+            NatError::Http(e)
+        }
+    })
+}
+
+/// Returns the IP address of the node in the network of the gateway, as the source address of packets to the given URL.
+async fn local_ip(url: &Url) -> Result<IpAddr, NatError> {
+    let invalid_url = || NatError::InvalidResponse(format!("invalid URL '{}'", url));
+
+    let host = url.host_str().ok_or_else(invalid_url)?;
+    let port = url.port_or_known_default().ok_or_else(invalid_url)?;
+    let address = lookup_host((host, port)).await?.next().ok_or_else(invalid_url)?;
+
+    // Connecting a UDP socket sends nothing, it only picks the route and therefore the local address.
+    let socket = UdpSocket::bind(SocketAddr::new(
+        match address {
+            SocketAddr::V4(_) => Ipv4Addr::UNSPECIFIED.into(),
+            SocketAddr::V6(_) => Ipv6Addr::UNSPECIFIED.into(),
+        },
+        0,
+    ))
+    .await?;
+    socket.connect(address).await?;
+
+    Ok(socket.local_addr()?.ip())
+}
+
+/// Returns the value of a header of an HTTP response.
+fn header<'a>(response: &'a str, name: &str) -> Option<&'a str> {
+    response.lines().find_map(|line| {
+        let (header, value) = line.split_once(':')?;
+
+        if header.trim().eq_ignore_ascii_case(name) {
+            Some(value.trim())
+        } else {
+            None
+        }
+    })
+}
+
+/// Returns the text of the first element with the given name, regardless of its namespace.
+fn element<'a>(xml: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = xml;
+
+    loop {
+        let start = rest.find('<')?;
+        rest = &rest[start + 1..];
+        let end = rest.find('>')?;
+        let tag = &rest[..end];
+        rest = &rest[end + 1..];
+
+        let tag_name = tag.split_whitespace().next().unwrap_or_default();
+        let local_name = tag_name.rsplit(':').next().unwrap_or_default();
+
+        if local_name == name && !tag.ends_with('/') {
+            let close = rest.find("</")?;
+
+            return Some(rest[..close].trim());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::*;
+
+    const DESCRIPTION: &str = "<?xml version=\"1.0\"?><root xmlns=\"urn:schemas-upnp-org:device-1-0\"><device>\
+        <serviceList><service><serviceType>urn:schemas-upnp-org:service:Layer3Forwarding:1</serviceType>\
+        <controlURL>/ctl/L3F</controlURL></service></serviceList><deviceList><device><serviceList><service>\
+        <serviceType>urn:schemas-upnp-org:service:WANIPConnection:1</serviceType><controlURL>/ctl/IPConn</controlURL>\
+        </service></serviceList></device></deviceList></device></root>";
+
+    fn soap_response(action: &str, body: &str) -> String {
+        format!(
+            "<s:Envelope xmlns:s=\"http://schemas.xmlsoap.org/soap/envelope/\"><s:Body><u:{0}Response \
+             xmlns:u=\"urn:schemas-upnp-org:service:WANIPConnection:1\">{1}</u:{0}Response></s:Body></s:Envelope>",
+            action, body
+        )
+    }
+
+    fn soap_error(code: &str, description: &str) -> String {
+        format!(
+            "<s:Envelope><s:Body><s:Fault><detail><UPnPError><errorCode>{}</errorCode><errorDescription>{}\
+             </errorDescription></UPnPError></detail></s:Fault></s:Body></s:Envelope>",
+            code, description
+        )
+    }
+
+    // Serves the description and answers SOAP requests like a gateway that only supports permanent mappings.
+    async fn mock_gateway() -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = Vec::new();
+                let mut buffer = [0u8; 4096];
+
+                // Read until the whole body, as announced by the content length, is there.
+                loop {
+                    let len = stream.read(&mut buffer).await.unwrap();
+                    request.extend_from_slice(&buffer[..len]);
+
+                    let request = String::from_utf8_lossy(&request);
+                    if let Some((head, body)) = request.split_once("\r\n\r\n") {
+                        let content_length = header(head, "content-length").map_or(0, |len| len.parse().unwrap());
+                        if body.len() >= content_length {
+                            break;
+                        }
+                    }
+                }
+
+                let request = String::from_utf8_lossy(&request).into_owned();
+                let (status, body) = if request.starts_with("GET /rootDesc.xml") {
+                    (200, DESCRIPTION.to_string())
+                } else if !request.starts_with("POST /ctl/IPConn") {
+                    (404, String::new())
+                } else if request.contains("#GetExternalIPAddress") {
+                    (
+                        200,
+                        soap_response(
+                            "GetExternalIPAddress",
+                            "<NewExternalIPAddress>203.0.113.7</NewExternalIPAddress>",
+                        ),
+                    )
+                } else if request.contains("#AddPortMapping") {
+                    if element(&request, "NewLeaseDuration") == Some("0")
+                        && element(&request, "NewInternalClient") == Some("127.0.0.1")
+                    {
+                        (200, soap_response("AddPortMapping", ""))
+                    } else {
+                        (500, soap_error("725", "OnlyPermanentLeasesSupported"))
+                    }
+                } else if request.contains("#DeletePortMapping") {
+                    (200, soap_response("DeletePortMapping", ""))
+                } else {
+                    (500, soap_error("401", "Invalid Action"))
+                };
+
+                let response = format!(
+                    "HTTP/1.1 {} OK\r\nContent-Type: text/xml\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        address
+    }
+
+    #[tokio::test]
+    async fn map_ports_on_mock_gateway() {
+        let address = mock_gateway().await;
+        let gateway = UpnpGateway::from_location(&format!("http://{}/rootDesc.xml", address))
+            .await
+            .unwrap();
+
+        assert_eq!(gateway.control_url.path(), "/ctl/IPConn");
+        assert_eq!(gateway.external_ip().await.unwrap(), IpAddr::from([203, 0, 113, 7]));
+
+        let mapping = gateway.map_port(PortProtocol::Udp, 14626, 14626, 3600).await.unwrap();
+
+        assert_eq!(mapping.external_port, 14626);
+        gateway.unmap_port(&mapping).await.unwrap();
+    }
+
+    #[test]
+    fn find_elements() {
+        let xml = "<a:Envelope><b:Value attr=\"1\"> 42 </b:Value><Empty/><Other>x</Other></a:Envelope>";
+
+        assert_eq!(element(xml, "Value"), Some("42"));
+        assert_eq!(element(xml, "Other"), Some("x"));
+        assert_eq!(element(xml, "Missing"), None);
+        assert_eq!(
+            header(
+                "HTTP/1.1 200 OK\r\nLOCATION: http://192.168.1.1:5000/rootDesc.xml\r\n",
+                "location"
+            ),
+            Some("http://192.168.1.1:5000/rootDesc.xml")
+        );
+    }
+}
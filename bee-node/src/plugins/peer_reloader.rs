@@ -0,0 +1,395 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Reloads the manually configured peers from the config file at runtime.
+//!
+//! On Unix platforms, sending `SIGHUP` to the node makes it read the peers of the config file again and apply the
+//! difference to the current peers:
+//! * configured peers that are missing are added as known peers;
+//! * configured peers that are present with another relation become known peers;
+//! * configured peers whose address changed are added again with the new address;
+//! * peers that were removed from the config file since it was last read are removed.
+//!
+//! Peers that were added by other means, e.g. via the REST API, are left alone. If enabled, known peers that are added
+//! or removed at runtime are in turn written back to the config file.
+//!
+//! Changes to the peer groups only take effect after a restart.
+
+use std::{any::TypeId, collections::HashSet, convert::Infallible, path::PathBuf};
+
+use async_trait::async_trait;
+use bee_gossip::{Command, Multiaddr, NetworkCommandSender, PeerId, PeerRelation, Protocol, ServiceHost};
+use bee_protocol::workers::{
+    event::{PeerAdded, PeerRemoved},
+    PeerManager, PeerManagerResWorker,
+};
+use bee_runtime::{node::Node, shutdown_stream::ShutdownStream, worker::Worker};
+use futures::{
+    stream::{self, BoxStream},
+    StreamExt,
+};
+use log::{info, warn};
+use serde_json::Value;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+
+use crate::config::{edit_peers_in_file, network_config_from_file};
+
+/// The config of the [`PeerReloader`].
+pub struct PeerReloaderConfig {
+    /// The config file the peers are read from.
+    pub config_path: PathBuf,
+    /// The peers that were read from the config file at startup.
+    pub peers: HashSet<PeerId>,
+    /// Whether known peers added or removed at runtime are written back to the config file.
+    pub write_back: bool,
+}
+
+enum PeerChange {
+    Added(PeerAdded),
+    Removed(PeerRemoved),
+}
+
+enum Trigger {
+    Reload,
+    Change(PeerChange),
+}
+
+/// A worker that reloads the manually configured peers from the config file.
+#[derive(Default)]
+pub struct PeerReloader {}
+
+#[async_trait]
+impl<N: Node> Worker<N> for PeerReloader {
+    type Config = PeerReloaderConfig;
+    type Error = Infallible;
+
+    fn dependencies() -> &'static [TypeId] {
+        vec![TypeId::of::<ServiceHost>(), TypeId::of::<PeerManagerResWorker>()].leak()
+    }
+
+    async fn start(node: &mut N, config: Self::Config) -> Result<Self, Self::Error> {
+        let gossip_command_tx = node.resource::<NetworkCommandSender>();
+        let peer_manager = node.resource::<PeerManager>();
+        let (tx, rx) = mpsc::unbounded_channel();
+
+        if config.write_back {
+            let bus = node.bus();
+            let added_tx = tx.clone();
+
+            bus.add_listener::<Self, _, _>(move |event: &PeerAdded| {
+                if added_tx.send(PeerChange::Added(event.clone())).is_err() {
+                    warn!("Sending added peer to the peer reloader failed.");
+                }
+            });
+            bus.add_listener::<Self, _, _>(move |event: &PeerRemoved| {
+                if tx.send(PeerChange::Removed(event.clone())).is_err() {
+                    warn!("Sending removed peer to the peer reloader failed.");
+                }
+            });
+        }
+
+        node.spawn::<Self, _, _>(|shutdown| async move {
+            info!("Running.");
+
+            let mut state = PeerReloaderState {
+                config_path: config.config_path,
+                peers: config.peers,
+                write_back: config.write_back,
+                readded: HashSet::new(),
+            };
+
+            let triggers = stream::select(
+                reload_requests().map(|()| Trigger::Reload),
+                UnboundedReceiverStream::new(rx).map(Trigger::Change),
+            );
+            let mut triggers = ShutdownStream::new(shutdown, triggers);
+
+            while let Some(trigger) = triggers.next().await {
+                match trigger {
+                    Trigger::Reload => state.reload_peers(&peer_manager, &gossip_command_tx),
+                    Trigger::Change(change) => state.write_back_peer(change).await,
+                }
+            }
+
+            info!("Stopped.");
+        });
+
+        Ok(Self::default())
+    }
+}
+
+#[cfg(unix)]
+fn reload_requests() -> BoxStream<'static, ()> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    match signal(SignalKind::hangup()) {
+        Ok(signal) => stream::unfold(signal, |mut signal| async move {
+            signal.recv().await.map(|()| ((), signal))
+        })
+        .boxed(),
+        Err(e) => {
+            warn!("Listening to SIGHUP failed: {}. Peers can't be reloaded.", e);
+            stream::pending().boxed()
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn reload_requests() -> BoxStream<'static, ()> {
+    stream::pending().boxed()
+}
+
+struct PeerReloaderState {
+    config_path: PathBuf,
+    // The peers currently in the config file, as far as the node knows.
+    peers: HashSet<PeerId>,
+    write_back: bool,
+    // The peers that are removed and added again because their address changed.
+    readded: HashSet<PeerId>,
+}
+
+impl PeerReloaderState {
+    fn reload_peers(&mut self, peer_manager: &PeerManager, gossip_command_tx: &NetworkCommandSender) {
+        info!("Reloading peers from {}.", self.config_path.display());
+
+        let network_config = match network_config_from_file(&self.config_path).map(|builder| builder.finish()) {
+            Ok(Ok(network_config)) => network_config,
+            Ok(Err(e)) => {
+                warn!("Reloading peers failed: {}.", e);
+                return;
+            }
+            Err(e) => {
+                warn!("Reloading peers failed: {}.", e);
+                return;
+            }
+        };
+
+        let reloaded = network_config
+            .static_peers()
+            .iter()
+            .map(|peer| (peer.peer_id, peer.multiaddr.clone(), peer.alias.clone()));
+        let diff = diff_peers(reloaded, &self.peers, |peer_id| {
+            peer_manager.get_map(peer_id, |peer| (peer.0.relation(), peer.0.address().clone()))
+        });
+
+        if self.write_back {
+            self.readded.extend(diff.readded);
+        }
+
+        for command in diff.commands {
+            if let Err(e) = gossip_command_tx.send(command) {
+                warn!("Sending command to update peers failed: {:?}.", e);
+            }
+        }
+
+        self.peers = diff.peers;
+    }
+
+    async fn write_back_peer(&mut self, change: PeerChange) {
+        let edit = match change {
+            // Peers that are already in the config file, e.g. because they were just reloaded, need no write back.
+            PeerChange::Added(event) if event.relation.is_known() && !self.peers.contains(&event.peer_id) => {
+                let mut address = event.address;
+                address.push(Protocol::P2p(event.peer_id));
+
+                PeersEdit::Add(
+                    event.peer_id,
+                    serde_json::json!({
+                        "address": address.to_string(),
+                        "alias": event.alias,
+                    }),
+                )
+            }
+            PeerChange::Removed(event) if self.readded.remove(&event.peer_id) => return,
+            PeerChange::Removed(event) if self.peers.contains(&event.peer_id) => PeersEdit::Remove(event.peer_id),
+            _ => return,
+        };
+
+        let config_path = self.config_path.clone();
+        let (peer_id, added) = match &edit {
+            PeersEdit::Add(peer_id, _) => (*peer_id, true),
+            PeersEdit::Remove(peer_id) => (*peer_id, false),
+        };
+
+        // Reading and writing the file blocks, which must not happen on the runtime threads.
+        match tokio::task::spawn_blocking(move || edit_peers_in_file(&config_path, |entries| edit.apply(entries))).await
+        {
+            Ok(Ok(())) => {
+                if added {
+                    self.peers.insert(peer_id);
+                } else {
+                    self.peers.remove(&peer_id);
+                }
+            }
+            Ok(Err(e)) => warn!("Writing peers back to {} failed: {}.", self.config_path.display(), e),
+            Err(e) => warn!("Writing peers back to {} failed: {}.", self.config_path.display(), e),
+        }
+    }
+}
+
+// The result of diffing the reloaded peers against the current ones.
+struct PeersDiff {
+    // The commands that apply the reloaded peers.
+    commands: Vec<Command>,
+    // The peers that are removed and added again because their address changed.
+    readded: Vec<PeerId>,
+    // The peers of the config file.
+    peers: HashSet<PeerId>,
+}
+
+// Diffs the `reloaded` peers, given by identifier, address and alias, against the `previous` peers of the config file
+// and the `current` relation and address of the peers known to the node.
+fn diff_peers(
+    reloaded: impl IntoIterator<Item = (PeerId, Multiaddr, Option<String>)>,
+    previous: &HashSet<PeerId>,
+    current: impl Fn(&PeerId) -> Option<(PeerRelation, Multiaddr)>,
+) -> PeersDiff {
+    let mut diff = PeersDiff {
+        commands: Vec::new(),
+        readded: Vec::new(),
+        peers: HashSet::new(),
+    };
+
+    for (peer_id, multiaddr, alias) in reloaded {
+        diff.peers.insert(peer_id);
+
+        match current(&peer_id) {
+            Some((_, address)) if address != multiaddr => {
+                diff.readded.push(peer_id);
+                diff.commands.push(Command::RemovePeer { peer_id });
+                diff.commands.push(add_peer_command(peer_id, &multiaddr, &alias));
+            }
+            Some((relation, _)) if !relation.is_known() => {
+                diff.commands.push(Command::ChangeRelation {
+                    peer_id,
+                    to: PeerRelation::Known,
+                });
+            }
+            Some(_) => {}
+            None => diff.commands.push(add_peer_command(peer_id, &multiaddr, &alias)),
+        }
+    }
+
+    for peer_id in previous.difference(&diff.peers) {
+        if current(peer_id).is_some() {
+            diff.commands.push(Command::RemovePeer { peer_id: *peer_id });
+        }
+    }
+
+    diff
+}
+
+// An edit of the peers of the config file.
+enum PeersEdit {
+    Add(PeerId, Value),
+    Remove(PeerId),
+}
+
+impl PeersEdit {
+    fn apply(self, entries: &mut Vec<Value>) {
+        match self {
+            Self::Add(_, entry) => entries.push(entry),
+            Self::Remove(peer_id) => entries.retain(|entry| entry_peer_id(entry) != Some(peer_id)),
+        }
+    }
+}
+
+fn add_peer_command(peer_id: PeerId, multiaddr: &Multiaddr, alias: &Option<String>) -> Command {
+    Command::AddPeer {
+        peer_id,
+        multiaddr: multiaddr.clone(),
+        alias: alias.clone(),
+        relation: PeerRelation::Known,
+    }
+}
+
+fn entry_peer_id(entry: &Value) -> Option<PeerId> {
+    let address = entry.get("address").or_else(|| entry.get("multiaddr"))?.as_str()?;
+
+    match address.parse::<Multiaddr>().ok()?.pop()? {
+        Protocol::P2p(peer_id) => Some(peer_id),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn address(port: u16) -> Multiaddr {
+        format!("/ip4/127.0.0.1/tcp/{}", port).parse().unwrap()
+    }
+
+    fn add(peer_id: PeerId, port: u16) -> Command {
+        add_peer_command(peer_id, &address(port), &None)
+    }
+
+    #[test]
+    fn diff_reloaded_peers() {
+        let (unchanged, added, moved, discovered, removed, gone) = (
+            PeerId::random(),
+            PeerId::random(),
+            PeerId::random(),
+            PeerId::random(),
+            PeerId::random(),
+            PeerId::random(),
+        );
+        let current = HashMap::from([
+            (unchanged, (PeerRelation::Known, address(1))),
+            (moved, (PeerRelation::Known, address(3))),
+            (discovered, (PeerRelation::Discovered, address(4))),
+            (removed, (PeerRelation::Known, address(5))),
+        ]);
+        let reloaded = vec![
+            (unchanged, address(1), None),
+            (added, address(2), None),
+            (moved, address(33), None),
+            (discovered, address(4), None),
+        ];
+        // `gone` was in the config file but is not known to the node anymore, e.g. because it was removed via the API.
+        let previous = HashSet::from([unchanged, moved, removed, gone]);
+
+        let diff = diff_peers(reloaded, &previous, |peer_id| current.get(peer_id).cloned());
+
+        assert_eq!(
+            diff.commands,
+            vec![
+                add(added, 2),
+                Command::RemovePeer { peer_id: moved },
+                add(moved, 33),
+                Command::ChangeRelation {
+                    peer_id: discovered,
+                    to: PeerRelation::Known,
+                },
+                Command::RemovePeer { peer_id: removed },
+            ]
+        );
+        assert_eq!(diff.readded, vec![moved]);
+        assert_eq!(diff.peers, HashSet::from([unchanged, added, moved, discovered]));
+    }
+
+    #[test]
+    fn peer_id_of_entries() {
+        let peer_id = PeerId::random();
+        let mut address = address(1);
+        address.push(Protocol::P2p(peer_id));
+
+        assert_eq!(
+            entry_peer_id(&serde_json::json!({ "address": address.to_string() })),
+            Some(peer_id)
+        );
+        assert_eq!(
+            entry_peer_id(&serde_json::json!({ "multiaddr": address.to_string(), "alias": "peer" })),
+            Some(peer_id)
+        );
+        assert_eq!(
+            entry_peer_id(&serde_json::json!({ "address": "/ip4/127.0.0.1/tcp/1" })),
+            None
+        );
+        assert_eq!(entry_peer_id(&serde_json::json!({ "address": "not an address" })), None);
+        assert_eq!(entry_peer_id(&serde_json::json!({ "alias": "peer" })), None);
+    }
+}
@@ -3,11 +3,14 @@
 
 mod ed25519;
 mod password;
+mod peer_history;
 #[cfg(feature = "rocksdb")]
 mod rocksdb;
 #[cfg(feature = "sled")]
 mod sled;
 mod snapshot_info;
+#[cfg(any(feature = "rocksdb", feature = "sled"))]
+mod solid_entry_points;
 
 use structopt::StructOpt;
 use thiserror::Error;
@@ -25,8 +28,13 @@ pub enum Tool {
     Sled(sled::SledTool),
     /// Outputs information about a snapshot file.
     SnapshotInfo(snapshot_info::SnapshotInfoTool),
+    /// Lists and inspects the solid entry points of a database.
+    #[cfg(any(feature = "rocksdb", feature = "sled"))]
+    SolidEntryPoints(solid_entry_points::SolidEntryPointsTool),
     /// Generates password salt and hash.
     Password(password::PasswordTool),
+    /// Outputs the connection history of a peer, as recorded by a running node.
+    PeerHistory(peer_history::PeerHistoryTool),
 }
 
 #[derive(Debug, Error)]
@@ -41,8 +49,13 @@ pub enum ToolError {
     Sled(#[from] sled::SledError),
     #[error("{0}")]
     SnapshotInfo(#[from] snapshot_info::SnapshotInfoError),
+    #[cfg(any(feature = "rocksdb", feature = "sled"))]
+    #[error("{0}")]
+    SolidEntryPoints(#[from] solid_entry_points::SolidEntryPointsError),
     #[error("{0}")]
     Password(#[from] password::PasswordError),
+    #[error("{0}")]
+    PeerHistory(#[from] peer_history::PeerHistoryError),
 }
 
 pub fn exec(tool: &Tool) -> Result<(), ToolError> {
@@ -53,7 +66,10 @@ pub fn exec(tool: &Tool) -> Result<(), ToolError> {
         #[cfg(feature = "sled")]
         Tool::Sled(tool) => sled::exec(tool)?,
         Tool::SnapshotInfo(tool) => snapshot_info::exec(tool)?,
+        #[cfg(any(feature = "rocksdb", feature = "sled"))]
+        Tool::SolidEntryPoints(tool) => solid_entry_points::exec(tool)?,
         Tool::Password(tool) => password::exec(tool)?,
+        Tool::PeerHistory(tool) => peer_history::exec(tool)?,
     }
 
     Ok(())
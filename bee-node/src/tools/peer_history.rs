@@ -0,0 +1,81 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use bee_rest_api::types::{
+    body::SuccessBody,
+    dtos::{ConnectionEventDto, ConnectionEventKindDto},
+    responses::PeerHistoryResponse,
+};
+use chrono::{offset::TimeZone, Utc};
+use structopt::StructOpt;
+use thiserror::Error;
+
+const REST_API_ADDRESS_DEFAULT: &str = "127.0.0.1:14265";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Error)]
+pub enum PeerHistoryError {
+    #[error("Request failed: {0}")]
+    RequestFailed(#[from] reqwest::Error),
+}
+
+#[derive(Clone, Debug, StructOpt)]
+pub struct PeerHistoryTool {
+    /// Id of the peer.
+    peer_id: String,
+    /// Address of the REST API of the node, which has to allow the local IP address.
+    #[structopt(long, default_value = REST_API_ADDRESS_DEFAULT)]
+    api: String,
+}
+
+fn print_event(event: &ConnectionEventDto) {
+    let (kind, details) = match &event.kind {
+        ConnectionEventKindDto::Dialed { address } => ("dialed", address.clone()),
+        ConnectionEventKindDto::DialFailed { reason } => ("dial failed", reason.clone()),
+        ConnectionEventKindDto::Unreachable { reason } => ("unreachable", reason.clone()),
+        ConnectionEventKindDto::ProtocolEstablished { address, origin } => {
+            ("protocol established", format!("{}, {}", address, origin))
+        }
+        ConnectionEventKindDto::ProtocolRejected { reason } => ("protocol rejected", reason.clone()),
+        ConnectionEventKindDto::ProtocolStopped { reason } => ("protocol stopped", reason.clone()),
+        ConnectionEventKindDto::Disconnected { reason } => ("disconnected", reason.clone()),
+    };
+
+    println!(
+        "{}\t{:<20}\t{}",
+        Utc.timestamp(event.timestamp as i64, 0).format("%d-%m-%Y %H:%M:%S"),
+        kind,
+        details
+    );
+}
+
+async fn fetch_history(tool: &PeerHistoryTool) -> Result<Vec<ConnectionEventDto>, reqwest::Error> {
+    Ok(reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()?
+        .get(format!("http://{}/api/v1/peers/{}/history", tool.api, tool.peer_id))
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<SuccessBody<PeerHistoryResponse>>()
+        .await?
+        .data
+        .0)
+}
+
+pub fn exec(tool: &PeerHistoryTool) -> Result<(), PeerHistoryError> {
+    // Tools are executed synchronously from within the runtime of the node binary.
+    let history = tokio::task::block_in_place(|| tokio::runtime::Handle::current().block_on(fetch_history(tool)))?;
+
+    if history.is_empty() {
+        println!("No connection events recorded for {}.", tool.peer_id);
+    }
+
+    for event in history.iter() {
+        print_event(event);
+    }
+
+    Ok(())
+}
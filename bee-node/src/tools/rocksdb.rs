@@ -26,6 +26,7 @@ use bee_storage_rocksdb::{
 };
 use bee_tangle::{
     metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    urts::TipMetadata,
 };
 use structopt::StructOpt;
 use thiserror::Error;
@@ -345,6 +346,22 @@ fn exec_inner(tool: &RocksdbTool, storage: &Storage) -> Result<(), RocksdbError>
                 }
             }
         },
+        CF_MESSAGE_ID_TO_TIP_METADATA => match &tool.command {
+            RocksdbCommand::Fetch { key } => {
+                let key = MessageId::from_str(key).map_err(|_| RocksdbError::InvalidKey(key.clone()))?;
+                let value = Fetch::<MessageId, TipMetadata>::fetch(storage, &key)?;
+
+                println!("Key: {:?}\nValue: {:?}\n", key, value);
+            }
+            RocksdbCommand::Iterator => {
+                let iterator = AsIterator::<MessageId, TipMetadata>::iter(storage)?;
+
+                for result in iterator {
+                    let (key, value) = result?;
+                    println!("Key: {:?}\nValue: {:?}\n", key, value);
+                }
+            }
+        },
 
         _ => return Err(RocksdbError::UnknownColumnFamily(tool.column_family[..].to_owned())),
     }
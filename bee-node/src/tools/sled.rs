@@ -25,6 +25,7 @@ use bee_storage_sled::{
 };
 use bee_tangle::{
     metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    urts::TipMetadata,
 };
 use structopt::StructOpt;
 use thiserror::Error;
@@ -327,6 +328,22 @@ fn exec_inner(tool: &SledTool, storage: &Storage) -> Result<(), SledError> {
                 }
             }
         },
+        TREE_MESSAGE_ID_TO_TIP_METADATA => match &tool.command {
+            SledCommand::Fetch { key } => {
+                let key = MessageId::from_str(key).map_err(|_| SledError::InvalidKey(key.clone()))?;
+                let value = Fetch::<MessageId, TipMetadata>::fetch(storage, &key)?;
+
+                println!("Key: {:?}\nValue: {:?}\n", key, value);
+            }
+            SledCommand::Iterator => {
+                let iterator = AsIterator::<MessageId, TipMetadata>::iter(storage)?;
+
+                for result in iterator {
+                    let (key, value) = result?;
+                    println!("Key: {:?}\nValue: {:?}\n", key, value);
+                }
+            }
+        },
 
         _ => return Err(SledError::UnknownTree(tool.tree[..].to_owned())),
     }
@@ -0,0 +1,93 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+
+use bee_message::{milestone::MilestoneIndex, MessageId};
+use bee_storage::{
+    access::{AsIterator, Fetch},
+    backend::StorageBackend,
+};
+#[cfg(feature = "rocksdb")]
+use bee_storage_rocksdb::{config::RocksDbConfigBuilder, error::Error as BackendError, storage::Storage};
+#[cfg(all(feature = "sled", not(feature = "rocksdb")))]
+use bee_storage_sled::{
+    config::SledConfigBuilder,
+    storage::{Error as BackendError, Storage},
+};
+use bee_tangle::{metadata::MessageMetadata, solid_entry_point::SolidEntryPoint};
+use structopt::StructOpt;
+use thiserror::Error;
+
+#[derive(Clone, Debug, StructOpt)]
+pub enum SolidEntryPointsCommand {
+    /// Lists all solid entry points, ordered by milestone index.
+    List,
+    /// Inspects a solid entry point.
+    Inspect { message_id: String },
+}
+
+#[derive(Debug, Error)]
+pub enum SolidEntryPointsError {
+    #[error("Storage backend error: {0}")]
+    StorageBackend(#[from] BackendError),
+    #[error("Invalid message id: {0}")]
+    InvalidMessageId(String),
+    #[error("Not a solid entry point: {0}")]
+    NotASolidEntryPoint(MessageId),
+}
+
+#[derive(Clone, Debug, StructOpt)]
+pub struct SolidEntryPointsTool {
+    path: String,
+    #[structopt(subcommand)]
+    command: SolidEntryPointsCommand,
+}
+
+fn exec_inner(tool: &SolidEntryPointsTool, storage: &Storage) -> Result<(), SolidEntryPointsError> {
+    match &tool.command {
+        SolidEntryPointsCommand::List => {
+            let mut seps =
+                AsIterator::<SolidEntryPoint, MilestoneIndex>::iter(storage)?.collect::<Result<Vec<_>, _>>()?;
+
+            seps.sort_unstable_by_key(|(_, index)| *index);
+
+            for (sep, index) in &seps {
+                println!("{}\t{}", sep.message_id(), **index);
+            }
+            println!("Count:\t\t\t\t{}", seps.len());
+        }
+        SolidEntryPointsCommand::Inspect { message_id } => {
+            let message_id = MessageId::from_str(message_id)
+                .map_err(|_| SolidEntryPointsError::InvalidMessageId(message_id.clone()))?;
+            let index = Fetch::<SolidEntryPoint, MilestoneIndex>::fetch(storage, &SolidEntryPoint::from(message_id))?
+                .ok_or(SolidEntryPointsError::NotASolidEntryPoint(message_id))?;
+            let metadata = Fetch::<MessageId, MessageMetadata>::fetch(storage, &message_id)?;
+            let approvers = Fetch::<MessageId, Vec<MessageId>>::fetch(storage, &message_id)?.unwrap_or_default();
+
+            println!("Message ID:\t\t\t{}", message_id);
+            println!("Milestone index:\t\t{}", *index);
+            println!("Stored:\t\t\t\t{}", metadata.is_some());
+            if let Some(referenced_index) = metadata.and_then(|metadata| metadata.milestone_index()) {
+                println!("Referenced by milestone:\t{}", *referenced_index);
+            }
+            println!("Approvers count:\t\t{}", approvers.len());
+        }
+    }
+
+    Ok(())
+}
+
+pub fn exec(tool: &SolidEntryPointsTool) -> Result<(), SolidEntryPointsError> {
+    #[cfg(feature = "rocksdb")]
+    let config = RocksDbConfigBuilder::default().with_path(tool.path.clone()).finish();
+    #[cfg(all(feature = "sled", not(feature = "rocksdb")))]
+    let config = SledConfigBuilder::default().with_path(tool.path.clone()).finish();
+
+    let storage = Storage::start(config)?;
+    let res = exec_inner(tool, &storage);
+
+    storage.shutdown()?;
+
+    res
+}
@@ -0,0 +1,45 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use bee_pow::score::{PoWBatchScorer, PoWScorer};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+fn messages(count: usize) -> Vec<Vec<u8>> {
+    (0..count)
+        .map(|i| (0..256).map(|j| (i * 31 + j * 7) as u8).collect())
+        .collect()
+}
+
+fn serial_score(messages: &[Vec<u8>]) {
+    let mut pow = PoWScorer::new();
+
+    for message in messages {
+        pow.score(message);
+    }
+}
+
+fn batch_score(messages: &[Vec<u8>]) {
+    let mut pow = PoWBatchScorer::new();
+
+    pow.score(messages);
+}
+
+fn bench_score(c: &mut Criterion) {
+    let mut group = c.benchmark_group("PoW score");
+
+    for count in [PoWBatchScorer::BATCH_SIZE, PoWBatchScorer::BATCH_SIZE * 16].iter() {
+        let messages = messages(*count);
+
+        group.throughput(Throughput::Elements(*count as u64));
+        group.bench_with_input(BenchmarkId::new("Serial", count), &messages, |b, m| {
+            b.iter(|| serial_score(m))
+        });
+        group.bench_with_input(BenchmarkId::new("Batched", count), &messages, |b, m| {
+            b.iter(|| batch_score(m))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_score);
+criterion_main!(benches);
@@ -6,7 +6,10 @@
 use bee_ternary::{b1t6, Btrit, T1B1Buf, TritBuf, Trits, T1B1};
 use crypto::hashes::{
     blake2b::Blake2b256,
-    ternary::{curl_p::CurlP, HASH_LENGTH},
+    ternary::{
+        curl_p::{CurlP, CurlPBatchHasher, BATCH_SIZE},
+        HASH_LENGTH,
+    },
     Digest,
 };
 
@@ -58,6 +61,70 @@ impl PoWScorer {
     }
 }
 
+/// Encapsulates the different steps that are used for scoring Proof of Work of several messages at once, hashing up to
+/// `BATCH_SIZE` of them in a single run of a batched Curl-P.
+pub struct PoWBatchScorer {
+    blake2b: Blake2b256,
+    hasher: CurlPBatchHasher<T1B1Buf>,
+}
+
+impl PoWBatchScorer {
+    /// The number of messages that are hashed in a single batch.
+    pub const BATCH_SIZE: usize = BATCH_SIZE;
+
+    /// Creates an new `PoWBatchScorer` that holds the required hash functions as internal state.
+    pub fn new() -> Self {
+        Self {
+            blake2b: Blake2b256::new(),
+            hasher: CurlPBatchHasher::new(HASH_LENGTH),
+        }
+    }
+
+    /// Computes the Proof of Work scores of given messages bytes, in the same order.
+    /// Panic: expects at least 8 bytes for every message.
+    pub fn score<B: AsRef<[u8]>>(&mut self, messages: &[B]) -> Vec<f64> {
+        let mut scores = Vec::with_capacity(messages.len());
+
+        for batch in messages.chunks(BATCH_SIZE) {
+            for bytes in batch {
+                let pow_input = pow_input(&mut self.blake2b, bytes.as_ref());
+                self.hasher.add(pow_input);
+            }
+
+            scores.extend(
+                self.hasher
+                    .hash()
+                    .zip(batch)
+                    .map(|(pow_hash, bytes)| pow_score_for_hash(&pow_hash, bytes.as_ref().len())),
+            );
+        }
+
+        scores
+    }
+}
+
+// Returns the 243 trits Curl-P input of given bytes: the Blake2b-256 hash of the message, excluding the nonce, followed
+// by the nonce.
+fn pow_input(blake2b: &mut Blake2b256, bytes: &[u8]) -> TritBuf<T1B1Buf> {
+    debug_assert!(bytes.len() >= std::mem::size_of::<u64>());
+
+    let length = bytes.len() - std::mem::size_of::<u64>();
+    let (head, tail) = bytes.split_at(length);
+    blake2b.update(head);
+    let pow_digest = blake2b.finalize_reset();
+
+    let mut pow_input = TritBuf::<T1B1Buf>::with_capacity(HASH_LENGTH);
+    pow_input.append(b1t6::encode::<T1B1Buf>(&pow_digest).as_slice());
+    pow_input.append(b1t6::encode::<T1B1Buf>(tail).as_slice());
+
+    // Pad to 243 trits
+    pow_input.push(Btrit::Zero);
+    pow_input.push(Btrit::Zero);
+    pow_input.push(Btrit::Zero);
+
+    pow_input
+}
+
 /// Returns the Proof of Work hash of given bytes.
 /// Panic: expects at least 8 bytes.
 #[deprecated(note = "Use `PoWScorer::hash` instead.")]
@@ -90,3 +157,9 @@ impl Default for PoWScorer {
         Self::new()
     }
 }
+
+impl Default for PoWBatchScorer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
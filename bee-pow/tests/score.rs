@@ -1,7 +1,7 @@
 // Copyright 2020-2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use bee_pow::score::PoWScorer;
+use bee_pow::score::{PoWBatchScorer, PoWScorer};
 
 // Tests are from:
 // https://github.com/Wollac/protocol-rfcs/blob/message-pow/text/0024-message-pow/0024-message-pow.md#example
@@ -34,3 +34,27 @@ fn score() {
 
     assert!((pow.score(&message) - 3u128.pow(0) as f64 / 10000_f64).abs() < f64::EPSILON);
 }
+
+#[test]
+fn batch_score() {
+    let mut pow = PoWScorer::new();
+    let mut batch_pow = PoWBatchScorer::new();
+
+    // More messages than fit in a single batch, of various lengths.
+    let messages = (0..PoWBatchScorer::BATCH_SIZE * 2 + 3)
+        .map(|i| (0..8 + i).map(|j| (i * 31 + j * 7) as u8).collect::<Vec<u8>>())
+        .collect::<Vec<_>>();
+
+    let scores = batch_pow.score(&messages);
+
+    assert_eq!(scores.len(), messages.len());
+
+    for (message, score) in messages.iter().zip(scores) {
+        assert!((pow.score(message) - score).abs() < f64::EPSILON);
+    }
+}
+
+#[test]
+fn batch_score_empty() {
+    assert!(PoWBatchScorer::new().score::<Vec<u8>>(&[]).is_empty());
+}
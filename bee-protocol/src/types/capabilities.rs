@@ -0,0 +1,134 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! A module that provides a type describing the protocol capabilities of a node.
+
+/// Version of the protocol spoken by peers that don't send a handshake.
+pub const LEGACY_PROTOCOL_VERSION: u8 = 1;
+/// Version of the protocol spoken by this node.
+pub const PROTOCOL_VERSION: u8 = 2;
+
+/// Packet types understood by peers that don't send a handshake: milestone request, message, message request and
+/// heartbeat.
+const LEGACY_PACKET_TYPES: u32 = 0b1_1110;
+/// Packet types understood by this node: the legacy ones, the handshake and the compressed packet.
+const PACKET_TYPES: u32 = LEGACY_PACKET_TYPES | 0b110_0000;
+
+/// Bit flags of the compression algorithms a node supports.
+pub mod compression {
+    /// Zstandard compression.
+    pub const ZSTD: u8 = 1 << 0;
+    /// LZ4 compression.
+    pub const LZ4: u8 = 1 << 1;
+}
+
+/// Compression algorithms supported by this node.
+const COMPRESSIONS: u8 = compression::ZSTD | compression::LZ4;
+
+/// The capabilities of a node: protocol version, supported packet types, compression algorithms and packet size.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ProtocolCapabilities {
+    /// Version of the protocol.
+    pub version: u8,
+    /// Supported packet types, as a bit set indexed by packet identifier.
+    pub packet_types: u32,
+    /// Supported compression algorithms, as a bit set of `compression` flags.
+    pub compressions: u8,
+    /// Maximum size of a packet.
+    pub max_packet_size: u32,
+}
+
+impl ProtocolCapabilities {
+    /// Returns the capabilities assumed for peers that don't send a handshake.
+    pub const fn legacy() -> Self {
+        Self {
+            version: LEGACY_PROTOCOL_VERSION,
+            packet_types: LEGACY_PACKET_TYPES,
+            compressions: 0,
+            max_packet_size: u16::MAX as u32,
+        }
+    }
+
+    /// Returns the capabilities of this node.
+    pub const fn local() -> Self {
+        Self {
+            version: PROTOCOL_VERSION,
+            packet_types: PACKET_TYPES,
+            compressions: COMPRESSIONS,
+            max_packet_size: u16::MAX as u32,
+        }
+    }
+
+    /// Returns the capabilities both `self` and `other` agree on.
+    pub fn negotiate(&self, other: &Self) -> Self {
+        Self {
+            version: self.version.min(other.version),
+            packet_types: self.packet_types & other.packet_types,
+            compressions: self.compressions & other.compressions,
+            max_packet_size: self.max_packet_size.min(other.max_packet_size),
+        }
+    }
+
+    /// Returns whether a packet type is supported.
+    pub fn supports_packet_type(&self, packet_type: u8) -> bool {
+        packet_type < 32 && self.packet_types & (1 << packet_type) != 0
+    }
+
+    /// Returns whether a compression algorithm is supported.
+    pub fn supports_compression(&self, compression: u8) -> bool {
+        self.compressions & compression != 0
+    }
+
+    /// Returns the preferred supported compression algorithm, zstd over lz4, if any.
+    pub fn preferred_compression(&self) -> Option<u8> {
+        [compression::ZSTD, compression::LZ4]
+            .iter()
+            .copied()
+            .find(|compression| self.supports_compression(*compression))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legacy_packet_types() {
+        let legacy = ProtocolCapabilities::legacy();
+
+        assert!(!legacy.supports_packet_type(0));
+        for packet_type in 1..=4 {
+            assert!(legacy.supports_packet_type(packet_type));
+        }
+        assert!(!legacy.supports_packet_type(5));
+        assert!(!legacy.supports_packet_type(255));
+    }
+
+    #[test]
+    fn negotiate() {
+        let remote = ProtocolCapabilities {
+            version: 7,
+            packet_types: u32::MAX,
+            compressions: compression::ZSTD | compression::LZ4,
+            max_packet_size: 1024,
+        };
+        let negotiated = ProtocolCapabilities::local().negotiate(&remote);
+
+        assert_eq!(negotiated.version, PROTOCOL_VERSION);
+        assert_eq!(negotiated.packet_types, PACKET_TYPES);
+        assert_eq!(negotiated.compressions, COMPRESSIONS);
+        assert_eq!(negotiated.max_packet_size, 1024);
+        assert_eq!(negotiated, remote.negotiate(&ProtocolCapabilities::local()));
+    }
+
+    #[test]
+    fn preferred_compression() {
+        let mut capabilities = ProtocolCapabilities::local();
+        assert_eq!(capabilities.preferred_compression(), Some(compression::ZSTD));
+
+        capabilities.compressions = compression::LZ4;
+        assert_eq!(capabilities.preferred_compression(), Some(compression::LZ4));
+
+        assert_eq!(ProtocolCapabilities::legacy().preferred_compression(), None);
+    }
+}
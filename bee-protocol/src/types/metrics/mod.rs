@@ -5,5 +5,10 @@
 
 pub mod node;
 pub mod peer;
+pub mod spam;
 
-pub use self::{node::NodeMetrics, peer::PeerMetrics};
+pub use self::{
+    node::NodeMetrics,
+    peer::PeerMetrics,
+    spam::{SpamCounts, SpamMetrics},
+};
@@ -20,10 +20,23 @@ pub struct NodeMetrics {
     message_requests_sent: AtomicU64,
     heartbeats_sent: AtomicU64,
 
+    compressed_bytes_received: AtomicU64,
+    decompressed_bytes_received: AtomicU64,
+    compressed_bytes_sent: AtomicU64,
+    uncompressed_bytes_sent: AtomicU64,
+
     invalid_messages: AtomicU64,
+    filtered_messages: AtomicU64,
     new_messages: AtomicU64,
     known_messages: AtomicU64,
     messages_average_latency: AtomicU64,
+    messages_latency_count: AtomicU64,
+    messages_latency_sum: AtomicU64,
+
+    dropped_messages: AtomicU64,
+    hasher_queue_depth: AtomicU64,
+    processor_queue_depth: AtomicU64,
+    payload_queue_depth: AtomicU64,
 
     referenced_messages: AtomicU64,
     excluded_no_transaction_messages: AtomicU64,
@@ -42,6 +55,14 @@ pub struct NodeMetrics {
     prunings: AtomicU64,
 }
 
+fn compression_ratio(compressed: u64, uncompressed: u64) -> f64 {
+    if compressed == 0 {
+        1.0
+    } else {
+        uncompressed as f64 / compressed as f64
+    }
+}
+
 impl NodeMetrics {
     /// Creates a new `NodeMetrics`.
     pub fn new() -> Self {
@@ -140,6 +161,56 @@ impl NodeMetrics {
         self.heartbeats_sent.fetch_add(1, Ordering::SeqCst)
     }
 
+    /// Returns the number of compressed bytes received by the `NodeMetrics`.
+    pub fn compressed_bytes_received(&self) -> u64 {
+        self.compressed_bytes_received.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of compressed bytes received by the `NodeMetrics`.
+    pub fn compressed_bytes_received_inc(&self, value: u64) -> u64 {
+        self.compressed_bytes_received.fetch_add(value, Ordering::SeqCst)
+    }
+
+    /// Returns the number of bytes the compressed packets received by the `NodeMetrics` decompressed to.
+    pub fn decompressed_bytes_received(&self) -> u64 {
+        self.decompressed_bytes_received.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of bytes the compressed packets received by the `NodeMetrics` decompressed to.
+    pub fn decompressed_bytes_received_inc(&self, value: u64) -> u64 {
+        self.decompressed_bytes_received.fetch_add(value, Ordering::SeqCst)
+    }
+
+    /// Returns the number of compressed bytes sent by the `NodeMetrics`.
+    pub fn compressed_bytes_sent(&self) -> u64 {
+        self.compressed_bytes_sent.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of compressed bytes sent by the `NodeMetrics`.
+    pub fn compressed_bytes_sent_inc(&self, value: u64) -> u64 {
+        self.compressed_bytes_sent.fetch_add(value, Ordering::SeqCst)
+    }
+
+    /// Returns the number of bytes the compressed packets sent by the `NodeMetrics` had before compression.
+    pub fn uncompressed_bytes_sent(&self) -> u64 {
+        self.uncompressed_bytes_sent.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of bytes the compressed packets sent by the `NodeMetrics` had before compression.
+    pub fn uncompressed_bytes_sent_inc(&self, value: u64) -> u64 {
+        self.uncompressed_bytes_sent.fetch_add(value, Ordering::SeqCst)
+    }
+
+    /// Returns the compression ratio of the packets received by the `NodeMetrics`, 1 if none was compressed.
+    pub fn compression_ratio_received(&self) -> f64 {
+        compression_ratio(self.compressed_bytes_received(), self.decompressed_bytes_received())
+    }
+
+    /// Returns the compression ratio of the packets sent by the `NodeMetrics`, 1 if none was compressed.
+    pub fn compression_ratio_sent(&self) -> f64 {
+        compression_ratio(self.compressed_bytes_sent(), self.uncompressed_bytes_sent())
+    }
+
     /// Returns the number of invalid messages of the `NodeMetrics`.
     pub fn invalid_messages(&self) -> u64 {
         self.invalid_messages.load(Ordering::Relaxed)
@@ -150,6 +221,16 @@ impl NodeMetrics {
         self.invalid_messages.fetch_add(1, Ordering::SeqCst)
     }
 
+    /// Returns the number of valid messages rejected by a message filter of the `NodeMetrics`.
+    pub fn filtered_messages(&self) -> u64 {
+        self.filtered_messages.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of valid messages rejected by a message filter of the `NodeMetrics`.
+    pub fn filtered_messages_inc(&self) -> u64 {
+        self.filtered_messages.fetch_add(1, Ordering::SeqCst)
+    }
+
     /// Returns the number of new messages of the `NodeMetrics`.
     pub fn new_messages(&self) -> u64 {
         self.new_messages.load(Ordering::Relaxed)
@@ -180,6 +261,69 @@ impl NodeMetrics {
         self.messages_average_latency.store(val, Ordering::Relaxed)
     }
 
+    /// Records the latency of a requested message and updates the average messages latency of the `NodeMetrics`.
+    pub fn messages_latency_record(&self, latency: u64) {
+        let count = self.messages_latency_count.fetch_add(1, Ordering::SeqCst) + 1;
+        let sum = self.messages_latency_sum.fetch_add(latency, Ordering::SeqCst) + latency;
+
+        self.messages_average_latency_set(sum / count);
+    }
+
+    /// Returns the number of messages dropped because a queue was full of the `NodeMetrics`.
+    pub fn dropped_messages(&self) -> u64 {
+        self.dropped_messages.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of messages dropped because a queue was full of the `NodeMetrics`.
+    pub fn dropped_messages_inc(&self) -> u64 {
+        self.dropped_messages.fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// Returns the number of messages waiting in the hasher queue of the `NodeMetrics`.
+    pub fn hasher_queue_depth(&self) -> u64 {
+        self.hasher_queue_depth.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of messages waiting in the hasher queue of the `NodeMetrics`.
+    pub fn hasher_queue_depth_inc(&self, value: u64) -> u64 {
+        self.hasher_queue_depth.fetch_add(value, Ordering::SeqCst)
+    }
+
+    /// Decrements the number of messages waiting in the hasher queue of the `NodeMetrics`.
+    pub fn hasher_queue_depth_dec(&self, value: u64) -> u64 {
+        self.hasher_queue_depth.fetch_sub(value, Ordering::SeqCst)
+    }
+
+    /// Returns the number of messages waiting in the processor queue of the `NodeMetrics`.
+    pub fn processor_queue_depth(&self) -> u64 {
+        self.processor_queue_depth.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of messages waiting in the processor queue of the `NodeMetrics`.
+    pub fn processor_queue_depth_inc(&self, value: u64) -> u64 {
+        self.processor_queue_depth.fetch_add(value, Ordering::SeqCst)
+    }
+
+    /// Decrements the number of messages waiting in the processor queue of the `NodeMetrics`.
+    pub fn processor_queue_depth_dec(&self, value: u64) -> u64 {
+        self.processor_queue_depth.fetch_sub(value, Ordering::SeqCst)
+    }
+
+    /// Returns the number of messages waiting in the payload queue of the `NodeMetrics`.
+    pub fn payload_queue_depth(&self) -> u64 {
+        self.payload_queue_depth.load(Ordering::Relaxed)
+    }
+
+    /// Increments the number of messages waiting in the payload queue of the `NodeMetrics`.
+    pub fn payload_queue_depth_inc(&self, value: u64) -> u64 {
+        self.payload_queue_depth.fetch_add(value, Ordering::SeqCst)
+    }
+
+    /// Decrements the number of messages waiting in the payload queue of the `NodeMetrics`.
+    pub fn payload_queue_depth_dec(&self, value: u64) -> u64 {
+        self.payload_queue_depth.fetch_sub(value, Ordering::SeqCst)
+    }
+
     /// Returns the number of referenced messages of the `NodeMetrics`.
     pub fn referenced_messages(&self) -> u64 {
         self.referenced_messages.load(Ordering::Relaxed)
@@ -319,10 +463,21 @@ mod tests {
         assert_eq!(metrics.messages_sent(), 0);
         assert_eq!(metrics.message_requests_sent(), 0);
         assert_eq!(metrics.heartbeats_sent(), 0);
+        assert_eq!(metrics.compressed_bytes_received(), 0);
+        assert_eq!(metrics.decompressed_bytes_received(), 0);
+        assert_eq!(metrics.compressed_bytes_sent(), 0);
+        assert_eq!(metrics.uncompressed_bytes_sent(), 0);
+        assert_eq!(metrics.compression_ratio_received(), 1.0);
+        assert_eq!(metrics.compression_ratio_sent(), 1.0);
         assert_eq!(metrics.invalid_messages(), 0);
+        assert_eq!(metrics.filtered_messages(), 0);
         assert_eq!(metrics.new_messages(), 0);
         assert_eq!(metrics.known_messages(), 0);
         assert_eq!(metrics.messages_average_latency(), 0);
+        assert_eq!(metrics.dropped_messages(), 0);
+        assert_eq!(metrics.hasher_queue_depth(), 0);
+        assert_eq!(metrics.processor_queue_depth(), 0);
+        assert_eq!(metrics.payload_queue_depth(), 0);
         assert_eq!(metrics.referenced_messages(), 0);
         assert_eq!(metrics.excluded_no_transaction_messages(), 0);
         assert_eq!(metrics.excluded_conflicting_messages(), 0);
@@ -345,10 +500,20 @@ mod tests {
         metrics.messages_sent_inc();
         metrics.message_requests_sent_inc();
         metrics.heartbeats_sent_inc();
+        metrics.compressed_bytes_received_inc(100);
+        metrics.decompressed_bytes_received_inc(250);
+        metrics.compressed_bytes_sent_inc(100);
+        metrics.uncompressed_bytes_sent_inc(400);
         metrics.invalid_messages_inc();
+        metrics.filtered_messages_inc();
         metrics.new_messages_inc();
         metrics.known_messages_inc();
         metrics.messages_average_latency_set(42);
+        metrics.dropped_messages_inc();
+        metrics.hasher_queue_depth_inc(3);
+        metrics.hasher_queue_depth_dec(2);
+        metrics.processor_queue_depth_inc(1);
+        metrics.payload_queue_depth_inc(1);
         metrics.referenced_messages_inc(1);
         metrics.excluded_no_transaction_messages_inc(1);
         metrics.excluded_conflicting_messages_inc(1);
@@ -371,10 +536,21 @@ mod tests {
         assert_eq!(metrics.messages_sent(), 1);
         assert_eq!(metrics.message_requests_sent(), 1);
         assert_eq!(metrics.heartbeats_sent(), 1);
+        assert_eq!(metrics.compressed_bytes_received(), 100);
+        assert_eq!(metrics.decompressed_bytes_received(), 250);
+        assert_eq!(metrics.compressed_bytes_sent(), 100);
+        assert_eq!(metrics.uncompressed_bytes_sent(), 400);
+        assert_eq!(metrics.compression_ratio_received(), 2.5);
+        assert_eq!(metrics.compression_ratio_sent(), 4.0);
         assert_eq!(metrics.invalid_messages(), 1);
+        assert_eq!(metrics.filtered_messages(), 1);
         assert_eq!(metrics.new_messages(), 1);
         assert_eq!(metrics.known_messages(), 1);
         assert_eq!(metrics.messages_average_latency(), 42);
+        assert_eq!(metrics.dropped_messages(), 1);
+        assert_eq!(metrics.hasher_queue_depth(), 1);
+        assert_eq!(metrics.processor_queue_depth(), 1);
+        assert_eq!(metrics.payload_queue_depth(), 1);
         assert_eq!(metrics.referenced_messages(), 1);
         assert_eq!(metrics.excluded_no_transaction_messages(), 1);
         assert_eq!(metrics.excluded_conflicting_messages(), 1);
@@ -388,4 +564,15 @@ mod tests {
         assert_eq!(metrics.snapshots(), 1);
         assert_eq!(metrics.prunings(), 1);
     }
+
+    #[test]
+    fn node_metrics_messages_latency() {
+        let metrics = NodeMetrics::default();
+
+        metrics.messages_latency_record(10);
+        metrics.messages_latency_record(20);
+        metrics.messages_latency_record(60);
+
+        assert_eq!(metrics.messages_average_latency(), 30);
+    }
 }
@@ -0,0 +1,250 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Module that provides types to break down the messages per second by payload kind and indexation tag.
+
+use std::collections::HashMap;
+
+use bee_message::{
+    payload::{indexation::IndexationPayload, transaction::Essence, Payload},
+    Message,
+};
+
+/// Maximum number of distinct indexation tags counted. When it is reached, a new tag replaces the least used one.
+const MAX_TRACKED_TAGS: usize = 1000;
+
+/// Counts of new messages by payload kind and indexation tag.
+#[derive(Clone, Debug, Default)]
+pub struct SpamCounts {
+    indexation: u64,
+    transaction: u64,
+    milestone: u64,
+    no_payload: u64,
+    tags: HashMap<Vec<u8>, u64>,
+}
+
+impl SpamCounts {
+    /// Counts a new message by its payload kind, and by the tag of its indexation payload, including the one of a
+    /// transaction.
+    pub fn record(&mut self, message: &Message) {
+        match message.payload() {
+            Some(Payload::Indexation(_)) => self.indexation += 1,
+            Some(Payload::Transaction(_)) => self.transaction += 1,
+            Some(Payload::Milestone(_)) => self.milestone += 1,
+            // Other payloads can't be found in new messages.
+            Some(_) => {}
+            None => self.no_payload += 1,
+        }
+
+        if let Some(indexation) = indexation_of(message) {
+            self.count_tag(indexation.index(), 1);
+        }
+    }
+
+    /// Adds the counts of another `SpamCounts`.
+    pub fn merge(&mut self, other: &SpamCounts) {
+        self.indexation += other.indexation;
+        self.transaction += other.transaction;
+        self.milestone += other.milestone;
+        self.no_payload += other.no_payload;
+
+        for (tag, count) in other.tags.iter() {
+            self.count_tag(tag, *count);
+        }
+    }
+
+    /// Turns the counts over a number of seconds into `SpamMetrics` keeping the `top` most used tags.
+    pub fn to_metrics(&self, seconds: f64, top: usize) -> SpamMetrics {
+        let seconds = seconds.max(f64::MIN_POSITIVE);
+        let mut tags = self.tags.iter().collect::<Vec<_>>();
+
+        // Ties are broken by tag to keep the order stable between updates.
+        tags.sort_unstable_by(|(a_tag, a_count), (b_tag, b_count)| b_count.cmp(a_count).then(a_tag.cmp(b_tag)));
+
+        SpamMetrics {
+            indexation: self.indexation as f64 / seconds,
+            transaction: self.transaction as f64 / seconds,
+            milestone: self.milestone as f64 / seconds,
+            no_payload: self.no_payload as f64 / seconds,
+            top_tags: tags
+                .into_iter()
+                .take(top)
+                .map(|(tag, count)| (tag.clone(), *count as f64 / seconds))
+                .collect(),
+        }
+    }
+
+    fn count_tag(&mut self, tag: &[u8], count: u64) {
+        if let Some(total) = self.tags.get_mut(tag) {
+            *total += count;
+        } else if self.tags.len() < MAX_TRACKED_TAGS {
+            self.tags.insert(tag.to_vec(), count);
+        } else if let Some((least_used, least_count)) = self
+            .tags
+            .iter()
+            .min_by_key(|(_, count)| **count)
+            .map(|(tag, count)| (tag.clone(), *count))
+        {
+            // The new tag inherits the count of the tag it replaces, so that a frequent tag showing up late still makes
+            // it to the top, at the cost of overestimating it by at most that count.
+            self.tags.remove(&least_used);
+            self.tags.insert(tag.to_vec(), least_count + count);
+        }
+    }
+}
+
+/// Returns the indexation payload of a message, be it its own payload or the one of its transaction.
+pub(crate) fn indexation_of(message: &Message) -> Option<&IndexationPayload> {
+    match message.payload() {
+        Some(Payload::Indexation(indexation)) => Some(indexation),
+        Some(Payload::Transaction(transaction)) => {
+            let Essence::Regular(essence) = transaction.essence();
+
+            match essence.payload() {
+                Some(Payload::Indexation(indexation)) => Some(indexation),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Messages per second broken down by payload kind and by the most used indexation tags.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SpamMetrics {
+    /// Messages per second with an indexation payload.
+    pub indexation: f64,
+    /// Messages per second with a transaction payload.
+    pub transaction: f64,
+    /// Messages per second with a milestone payload.
+    pub milestone: f64,
+    /// Messages per second without payload.
+    pub no_payload: f64,
+    /// The most used indexation tags and their messages per second, most used first.
+    pub top_tags: Vec<(Vec<u8>, f64)>,
+}
+
+#[cfg(test)]
+mod tests {
+    use bee_message::{
+        parents::Parents,
+        payload::{indexation::IndexationPayload, transaction::RegularEssence},
+        prelude::*,
+        MessageBuilder, MessageId,
+    };
+
+    use super::*;
+
+    fn message(index: Option<&[u8]>) -> Message {
+        let builder = MessageBuilder::<u64>::new()
+            .with_network_id(0)
+            .with_parents(Parents::new(vec![MessageId::null()]).unwrap())
+            .with_nonce_provider(0, 0.0);
+
+        match index {
+            Some(index) => builder.with_payload(Payload::Indexation(Box::new(
+                IndexationPayload::new(index, &[]).unwrap(),
+            ))),
+            None => builder,
+        }
+        .finish()
+        .unwrap()
+    }
+
+    fn transaction_message(index: &[u8]) -> Message {
+        let input = Input::Utxo(UtxoInput::new(TransactionId::new([0; 32]), 0).unwrap());
+        let address = Address::from(Ed25519Address::new([0; 32]));
+        let output = Output::SignatureLockedSingle(SignatureLockedSingleOutput::new(address, 1_000_000).unwrap());
+        let essence = RegularEssence::builder()
+            .with_inputs(vec![input])
+            .with_outputs(vec![output])
+            .with_payload(Payload::Indexation(Box::new(
+                IndexationPayload::new(index, &[]).unwrap(),
+            )))
+            .finish()
+            .unwrap();
+        let signature = Ed25519Signature::new([0; 32], [0; 64]);
+        let unlock_blocks =
+            UnlockBlocks::new(vec![UnlockBlock::Signature(SignatureUnlock::Ed25519(signature))]).unwrap();
+        let transaction = TransactionPayload::builder()
+            .with_essence(Essence::Regular(essence))
+            .with_unlock_blocks(unlock_blocks)
+            .finish()
+            .unwrap();
+
+        MessageBuilder::<u64>::new()
+            .with_network_id(0)
+            .with_parents(Parents::new(vec![MessageId::null()]).unwrap())
+            .with_payload(Payload::Transaction(Box::new(transaction)))
+            .with_nonce_provider(0, 0.0)
+            .finish()
+            .unwrap()
+    }
+
+    #[test]
+    fn record_tag_of_transaction() {
+        let mut counts = SpamCounts::default();
+        counts.record(&transaction_message(b"spam"));
+        counts.record(&message(Some(b"spam")));
+
+        let metrics = counts.to_metrics(1.0, 1);
+
+        assert_eq!(metrics.indexation, 1.0);
+        assert_eq!(metrics.transaction, 1.0);
+        assert_eq!(metrics.top_tags, vec![(b"spam".to_vec(), 2.0)]);
+    }
+
+    #[test]
+    fn record_and_merge() {
+        let mut first = SpamCounts::default();
+        first.record(&message(Some(b"spam")));
+        first.record(&message(Some(b"spam")));
+        first.record(&message(Some(b"ham")));
+        first.record(&message(None));
+
+        let mut second = SpamCounts::default();
+        second.record(&message(Some(b"spam")));
+        second.record(&message(Some(b"eggs")));
+
+        first.merge(&second);
+
+        let metrics = first.to_metrics(2.0, 2);
+
+        assert_eq!(metrics.indexation, 2.5);
+        assert_eq!(metrics.transaction, 0.0);
+        assert_eq!(metrics.milestone, 0.0);
+        assert_eq!(metrics.no_payload, 0.5);
+        assert_eq!(metrics.top_tags, vec![(b"spam".to_vec(), 1.5), (b"eggs".to_vec(), 0.5)]);
+    }
+
+    #[test]
+    fn tracked_tags_are_bounded() {
+        let mut counts = SpamCounts::default();
+
+        for i in 0..(MAX_TRACKED_TAGS + 10) {
+            counts.record(&message(Some(format!("tag{}", i).as_bytes())));
+        }
+
+        assert_eq!(counts.tags.len(), MAX_TRACKED_TAGS);
+        assert_eq!(counts.indexation, (MAX_TRACKED_TAGS + 10) as u64);
+    }
+
+    #[test]
+    fn frequent_late_tag_replaces_least_used_tag() {
+        let mut counts = SpamCounts::default();
+
+        for i in 0..MAX_TRACKED_TAGS {
+            counts.record(&message(Some(format!("tag{}", i).as_bytes())));
+        }
+        counts.record(&message(Some(b"tag0")));
+        for _ in 0..3 {
+            counts.record(&message(Some(b"late")));
+        }
+
+        let metrics = counts.to_metrics(1.0, 2);
+
+        assert_eq!(counts.tags.len(), MAX_TRACKED_TAGS);
+        assert_eq!(metrics.top_tags[0].0, b"late".to_vec());
+        assert_eq!(metrics.top_tags[1], (b"tag0".to_vec(), 2.0));
+    }
+}
@@ -3,10 +3,80 @@
 
 //! A module that provides a milestone key range manager type.
 
+use core::fmt;
+
 use bee_message::milestone::MilestoneIndex;
 
 use crate::types::milestone_key_range::MilestoneKeyRange;
 
+// Length of a hex encoded Ed25519 public key.
+const PUBLIC_KEY_HEX_LENGTH: usize = 64;
+
+/// Errors occurring when validating `MilestoneKeyRange`s.
+#[derive(Debug, Eq, PartialEq)]
+pub enum MilestoneKeyManagerError {
+    /// A public key is not a hex encoded Ed25519 public key.
+    InvalidPublicKey(String),
+    /// A key range ends before it starts.
+    InvalidBounds {
+        /// The public key of the key range.
+        public_key: String,
+        /// The start index of the key range.
+        start: MilestoneIndex,
+        /// The end index of the key range.
+        end: MilestoneIndex,
+    },
+    /// The same public key is used by overlapping key ranges.
+    OverlappingPublicKey(String),
+    /// Fewer public keys than the minimum threshold are applicable for a milestone index.
+    InsufficientPublicKeys {
+        /// The milestone index.
+        index: MilestoneIndex,
+        /// The number of applicable public keys.
+        found: usize,
+        /// The minimum threshold.
+        required: usize,
+    },
+}
+
+impl fmt::Display for MilestoneKeyManagerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidPublicKey(public_key) => write!(f, "invalid public key {}", public_key),
+            Self::InvalidBounds { public_key, start, end } => {
+                write!(
+                    f,
+                    "key range of {} ends at {} before starting at {}",
+                    public_key, end, start
+                )
+            }
+            Self::OverlappingPublicKey(public_key) => {
+                write!(f, "public key {} is used by overlapping key ranges", public_key)
+            }
+            Self::InsufficientPublicKeys { index, found, required } => write!(
+                f,
+                "only {} public keys are applicable for milestone {} but {} are required",
+                found, index, required
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MilestoneKeyManagerError {}
+
+// Returns the end of a key range as an exclusive bound, `None` if the key range never ends.
+fn exclusive_end(key_range: &MilestoneKeyRange) -> Option<u32> {
+    if *key_range.end() == 0 {
+        None
+    } else {
+        Some(key_range.end().saturating_add(1))
+    }
+}
+
+fn overlap(a: &MilestoneKeyRange, b: &MilestoneKeyRange) -> bool {
+    exclusive_end(a).map_or(true, |end| *b.start() < end) && exclusive_end(b).map_or(true, |end| *a.start() < end)
+}
+
 /// A key manager is managing a set of `MilestoneKeyRange`s.
 #[derive(Clone)]
 pub struct MilestoneKeyManager {
@@ -25,11 +95,86 @@ impl MilestoneKeyManager {
         }
     }
 
+    /// Creates a new `MilestoneKeyManager` after validating its key ranges.
+    ///
+    /// Key ranges may overlap, e.g. during a key rotation, as long as a public key is not used by overlapping key
+    /// ranges and at least `min_threshold` public keys are applicable from the first covered milestone index on.
+    pub fn try_new(
+        min_threshold: usize,
+        key_ranges: Box<[MilestoneKeyRange]>,
+    ) -> Result<Self, MilestoneKeyManagerError> {
+        let key_manager = Self::new(min_threshold, key_ranges);
+
+        key_manager.validate()?;
+
+        Ok(key_manager)
+    }
+
+    fn validate(&self) -> Result<(), MilestoneKeyManagerError> {
+        for (i, key_range) in self.key_ranges.iter().enumerate() {
+            if key_range.public_key().len() != PUBLIC_KEY_HEX_LENGTH
+                || !key_range.public_key().chars().all(|c| c.is_ascii_hexdigit())
+            {
+                return Err(MilestoneKeyManagerError::InvalidPublicKey(
+                    key_range.public_key().clone(),
+                ));
+            }
+
+            if exclusive_end(key_range).map_or(false, |end| end <= *key_range.start()) {
+                return Err(MilestoneKeyManagerError::InvalidBounds {
+                    public_key: key_range.public_key().clone(),
+                    start: key_range.start(),
+                    end: key_range.end(),
+                });
+            }
+
+            if self.key_ranges[i + 1..]
+                .iter()
+                .any(|other| other.public_key() == key_range.public_key() && overlap(key_range, other))
+            {
+                return Err(MilestoneKeyManagerError::OverlappingPublicKey(
+                    key_range.public_key().clone(),
+                ));
+            }
+        }
+
+        // The number of applicable public keys only changes where a key range starts or ends.
+        let last_end = self
+            .key_ranges
+            .iter()
+            .map(exclusive_end)
+            .try_fold(0, |last, end| end.map(|end| last.max(end)));
+        let boundaries = self
+            .key_ranges
+            .iter()
+            .flat_map(|key_range| core::iter::once(*key_range.start()).chain(exclusive_end(key_range)))
+            .filter(|index| last_end.map_or(true, |last_end| *index < last_end));
+
+        for index in boundaries {
+            let found = self.applicable_key_ranges(MilestoneIndex(index)).count();
+
+            if found < self.min_threshold {
+                return Err(MilestoneKeyManagerError::InsufficientPublicKeys {
+                    index: MilestoneIndex(index),
+                    found,
+                    required: self.min_threshold,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
     /// Returns the minimum threshold of the `MilestoneKeyManager`.
     pub fn min_threshold(&self) -> usize {
         self.min_threshold
     }
 
+    /// Returns the key ranges of the `MilestoneKeyManager`, sorted by start index.
+    pub fn key_ranges(&self) -> &[MilestoneKeyRange] {
+        &self.key_ranges
+    }
+
     /// Returns the key ranges that are applicable for a given milestone index.
     pub fn applicable_key_ranges(&self, index: MilestoneIndex) -> impl Iterator<Item = &MilestoneKeyRange> {
         self.key_ranges
@@ -102,4 +247,90 @@ mod tests {
         assert!(public_keys.contains("kr5"));
         assert!(public_keys.contains("kr6"));
     }
+
+    fn key(n: u8) -> String {
+        format!("{:02x}", n).repeat(32)
+    }
+
+    #[test]
+    fn try_new_rotation() {
+        let krs = vec![
+            MilestoneKeyRange::new(key(0), 0.into(), 100.into()),
+            MilestoneKeyRange::new(key(1), 0.into(), 150.into()),
+            // Overlaps with the previous keys during the rotation.
+            MilestoneKeyRange::new(key(2), 90.into(), 0.into()),
+            MilestoneKeyRange::new(key(3), 140.into(), 0.into()),
+            // A key can be used again once its previous key range is over.
+            MilestoneKeyRange::new(key(0), 200.into(), 0.into()),
+        ];
+
+        assert!(MilestoneKeyManager::try_new(2, krs.into_boxed_slice()).is_ok());
+    }
+
+    #[test]
+    fn try_new_invalid_public_key() {
+        let krs = vec![MilestoneKeyRange::new("kr0".to_string(), 0.into(), 0.into())];
+
+        assert_eq!(
+            MilestoneKeyManager::try_new(1, krs.into_boxed_slice()).err(),
+            Some(MilestoneKeyManagerError::InvalidPublicKey("kr0".to_string()))
+        );
+    }
+
+    #[test]
+    fn try_new_invalid_bounds() {
+        let krs = vec![MilestoneKeyRange::new(key(0), 50.into(), 10.into())];
+
+        assert_eq!(
+            MilestoneKeyManager::try_new(1, krs.into_boxed_slice()).err(),
+            Some(MilestoneKeyManagerError::InvalidBounds {
+                public_key: key(0),
+                start: 50.into(),
+                end: 10.into()
+            })
+        );
+    }
+
+    #[test]
+    fn try_new_overlapping_public_key() {
+        let krs = vec![
+            MilestoneKeyRange::new(key(0), 0.into(), 100.into()),
+            MilestoneKeyRange::new(key(0), 100.into(), 0.into()),
+        ];
+
+        assert_eq!(
+            MilestoneKeyManager::try_new(1, krs.into_boxed_slice()).err(),
+            Some(MilestoneKeyManagerError::OverlappingPublicKey(key(0)))
+        );
+    }
+
+    #[test]
+    fn try_new_insufficient_public_keys() {
+        // The new keys start one milestone too late, leaving a single key at index 101.
+        let krs = vec![
+            MilestoneKeyRange::new(key(0), 0.into(), 100.into()),
+            MilestoneKeyRange::new(key(1), 0.into(), 200.into()),
+            MilestoneKeyRange::new(key(2), 102.into(), 0.into()),
+        ];
+
+        assert_eq!(
+            MilestoneKeyManager::try_new(2, krs.into_boxed_slice()).err(),
+            Some(MilestoneKeyManagerError::InsufficientPublicKeys {
+                index: 101.into(),
+                found: 1,
+                required: 2
+            })
+        );
+    }
+
+    #[test]
+    fn try_new_expired_key_ranges() {
+        // Milestones after the last key range can't be validated anyway and are not considered.
+        let krs = vec![
+            MilestoneKeyRange::new(key(0), 0.into(), 100.into()),
+            MilestoneKeyRange::new(key(1), 0.into(), 100.into()),
+        ];
+
+        assert!(MilestoneKeyManager::try_new(2, krs.into_boxed_slice()).is_ok());
+    }
 }
@@ -3,7 +3,9 @@
 
 //! A module that provides primitive types for the IOTA protocol.
 
+pub mod capabilities;
 pub mod metrics;
 pub mod milestone_key_manager;
 pub mod milestone_key_range;
 pub mod peer;
+pub mod reputation;
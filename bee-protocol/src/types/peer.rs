@@ -4,14 +4,17 @@
 //! A module that provides a type describing peers.
 
 use std::{
-    sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicU8, Ordering},
-    time::{SystemTime, UNIX_EPOCH},
+    sync::{
+        atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicU8, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
-use bee_gossip::{Multiaddr, PeerId, PeerInfo, PeerRelation};
+use bee_gossip::{Bandwidth, Multiaddr, PeerGroup, PeerId, PeerInfo, PeerRelation, ReconnectState};
 use bee_message::milestone::MilestoneIndex;
 
-use crate::types::metrics::PeerMetrics;
+use crate::types::{capabilities::ProtocolCapabilities, metrics::PeerMetrics, reputation::PeerReputation};
 
 const SYNCED_THRESHOLD: u32 = 2;
 
@@ -21,6 +24,7 @@ pub struct Peer {
     info: PeerInfo,
     connected: AtomicBool,
     metrics: PeerMetrics,
+    reputation: PeerReputation,
     solid_milestone_index: AtomicU32,
     pruned_index: AtomicU32,
     latest_milestone_index: AtomicU32,
@@ -28,6 +32,14 @@ pub struct Peer {
     synced_peers: AtomicU8,
     heartbeat_sent_timestamp: AtomicU64,
     heartbeat_received_timestamp: AtomicU64,
+    requests_in_flight: AtomicU32,
+    request_latency: AtomicU64,
+    protocol_version: AtomicU8,
+    packet_types: AtomicU32,
+    compressions: AtomicU8,
+    max_packet_size: AtomicU32,
+    reconnect: Mutex<ReconnectState>,
+    bandwidth: Mutex<Arc<Bandwidth>>,
 }
 
 impl Peer {
@@ -38,6 +50,7 @@ impl Peer {
             info,
             connected: AtomicBool::new(false),
             metrics: PeerMetrics::default(),
+            reputation: PeerReputation::default(),
             solid_milestone_index: AtomicU32::new(0),
             pruned_index: AtomicU32::new(0),
             latest_milestone_index: AtomicU32::new(0),
@@ -45,6 +58,14 @@ impl Peer {
             synced_peers: AtomicU8::new(0),
             heartbeat_sent_timestamp: AtomicU64::new(0),
             heartbeat_received_timestamp: AtomicU64::new(0),
+            requests_in_flight: AtomicU32::new(0),
+            request_latency: AtomicU64::new(0),
+            protocol_version: AtomicU8::new(ProtocolCapabilities::legacy().version),
+            packet_types: AtomicU32::new(ProtocolCapabilities::legacy().packet_types),
+            compressions: AtomicU8::new(ProtocolCapabilities::legacy().compressions),
+            max_packet_size: AtomicU32::new(ProtocolCapabilities::legacy().max_packet_size),
+            reconnect: Mutex::new(ReconnectState::default()),
+            bandwidth: Mutex::new(Arc::new(Bandwidth::default())),
         }
     }
 
@@ -68,6 +89,23 @@ impl Peer {
         self.info.relation
     }
 
+    /// Returns the group of the `Peer`, if any.
+    pub fn group(&self) -> Option<&PeerGroup> {
+        self.info.group.as_ref()
+    }
+
+    /// Returns whether the `Peer` is preferred when requesting messages.
+    pub fn is_preferred(&self) -> bool {
+        self.group().map_or(false, |group| group.preferred)
+    }
+
+    /// Returns whether the `Peer` is exempt from being banned for misbehaving.
+    pub fn is_ban_exempt(&self) -> bool {
+        // Manually configured peers are exempt unless their group says otherwise, the operator explicitly chose them.
+        self.group()
+            .map_or_else(|| self.relation().is_known(), |group| group.ban_exempt)
+    }
+
     /// Returns whether the `Peer` is connected or not.
     pub fn set_connected(&self, connected: bool) {
         self.connected.store(connected, Ordering::Relaxed);
@@ -83,6 +121,11 @@ impl Peer {
         &self.metrics
     }
 
+    /// Returns the reputation of the `Peer`.
+    pub fn reputation(&self) -> &PeerReputation {
+        &self.reputation
+    }
+
     /// Sets the solid milestone index of the `Peer`.
     pub fn set_solid_milestone_index(&self, index: MilestoneIndex) {
         self.solid_milestone_index.store(*index, Ordering::Relaxed);
@@ -165,6 +208,105 @@ impl Peer {
         self.heartbeat_received_timestamp.load(Ordering::Relaxed)
     }
 
+    /// Returns the capabilities agreed on with the `Peer`, legacy ones until a handshake is received.
+    pub fn capabilities(&self) -> ProtocolCapabilities {
+        ProtocolCapabilities {
+            version: self.protocol_version.load(Ordering::Relaxed),
+            packet_types: self.packet_types.load(Ordering::Relaxed),
+            compressions: self.compressions.load(Ordering::Relaxed),
+            max_packet_size: self.max_packet_size.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Sets the capabilities agreed on with the `Peer`.
+    pub fn set_capabilities(&self, capabilities: &ProtocolCapabilities) {
+        self.protocol_version.store(capabilities.version, Ordering::Relaxed);
+        self.packet_types.store(capabilities.packet_types, Ordering::Relaxed);
+        self.compressions.store(capabilities.compressions, Ordering::Relaxed);
+        self.max_packet_size
+            .store(capabilities.max_packet_size, Ordering::Relaxed);
+    }
+
+    /// Returns the state of the attempts to reconnect the `Peer`.
+    pub fn reconnect(&self) -> ReconnectState {
+        // Panic: the lock is never held while panicking.
+        self.reconnect.lock().expect("reconnect lock").clone()
+    }
+
+    /// Sets the state of the attempts to reconnect the `Peer`.
+    pub fn set_reconnect(&self, reconnect: ReconnectState) {
+        // Panic: the lock is never held while panicking.
+        *self.reconnect.lock().expect("reconnect lock") = reconnect;
+    }
+
+    /// Returns the bytes exchanged with the `Peer`.
+    pub fn bandwidth(&self) -> Arc<Bandwidth> {
+        // Panic: the lock is never held while panicking.
+        self.bandwidth.lock().expect("bandwidth lock").clone()
+    }
+
+    /// Sets the bytes exchanged with the `Peer`, as counted by the network layer.
+    pub fn set_bandwidth(&self, bandwidth: Arc<Bandwidth>) {
+        // Panic: the lock is never held while panicking.
+        *self.bandwidth.lock().expect("bandwidth lock") = bandwidth;
+    }
+
+    /// Returns the number of requests sent to the `Peer` that are still waiting for a response.
+    pub fn requests_in_flight(&self) -> u32 {
+        self.requests_in_flight.load(Ordering::Relaxed)
+    }
+
+    /// Returns the smoothed response latency of the `Peer`, if any request has been completed yet.
+    pub fn request_latency(&self) -> Option<Duration> {
+        match self.request_latency.load(Ordering::Relaxed) {
+            0 => None,
+            latency => Some(Duration::from_micros(latency)),
+        }
+    }
+
+    /// Records that a request has been sent to the `Peer`.
+    pub fn request_sent(&self) {
+        self.requests_in_flight.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Records that a request sent to the `Peer` has been answered after `latency`.
+    pub fn request_answered(&self, latency: Duration) {
+        self.request_done();
+        self.update_request_latency(latency);
+    }
+
+    /// Records that a request sent to the `Peer` has not been answered after `elapsed`, which also counts as a latency
+    /// sample.
+    pub fn request_expired(&self, elapsed: Duration) {
+        self.request_done();
+        self.update_request_latency(elapsed);
+    }
+
+    /// Records that a request sent to the `Peer` is not needed anymore, e.g. because another peer answered it.
+    pub fn request_cancelled(&self) {
+        self.request_done();
+    }
+
+    fn request_done(&self) {
+        let _ = self
+            .requests_in_flight
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1));
+    }
+
+    fn update_request_latency(&self, latency: Duration) {
+        // Exponentially weighted moving average with a 1/8 weight for the new sample; 0 means no sample yet.
+        let sample = (latency.as_micros() as u64).max(1);
+        let _ = self
+            .request_latency
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |average| {
+                Some(if average == 0 {
+                    sample
+                } else {
+                    (average * 7 + sample) / 8
+                })
+            });
+    }
+
     /// Returns whether the `Peer` is synced or not.
     pub fn is_synced(&self) -> bool {
         self.is_synced_threshold(SYNCED_THRESHOLD)
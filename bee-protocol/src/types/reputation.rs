@@ -0,0 +1,163 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! A module that provides types to score the behaviour of peers and to rate limit them.
+
+use std::{
+    sync::atomic::{AtomicU32, Ordering},
+    time::Instant,
+};
+
+/// The different kinds of misbehaviour a peer can be penalized for.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Misbehaviour {
+    /// The peer sent a packet that could not be parsed. Packets of unknown types are skipped without a penalty.
+    InvalidPacket,
+    /// The peer sent a message that was rejected, e.g. because of a malformed structure or an insufficient PoW score.
+    InvalidMessage,
+    /// The peer sent almost only messages that were already known.
+    UselessDuplicates,
+    /// The peer exceeded the rate limit of a packet type.
+    Flooding,
+    /// The peer did not send a heartbeat for too long.
+    StaleHeartbeat,
+}
+
+impl Misbehaviour {
+    /// Returns the penalty associated with the `Misbehaviour`.
+    pub fn penalty(&self) -> u32 {
+        match self {
+            Self::InvalidPacket => 20,
+            Self::InvalidMessage => 10,
+            Self::UselessDuplicates => 20,
+            Self::Flooding => 1,
+            Self::StaleHeartbeat => 50,
+        }
+    }
+}
+
+/// Holds the misbehaviour score of a peer, the higher the worse.
+#[derive(Default)]
+pub struct PeerReputation {
+    score: AtomicU32,
+}
+
+impl PeerReputation {
+    /// Returns the current misbehaviour score of the `PeerReputation`.
+    pub fn score(&self) -> u32 {
+        self.score.load(Ordering::Relaxed)
+    }
+
+    /// Penalizes the `PeerReputation` for a misbehaviour and returns the new score.
+    pub fn penalize(&self, misbehaviour: Misbehaviour) -> u32 {
+        let penalty = misbehaviour.penalty();
+
+        self.score
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |score| {
+                Some(score.saturating_add(penalty))
+            })
+            // Panic: the closure always returns `Some`.
+            .unwrap()
+            .saturating_add(penalty)
+    }
+
+    /// Lowers the misbehaviour score of the `PeerReputation` by `amount` and returns the new score.
+    pub fn decay(&self, amount: u32) -> u32 {
+        self.score
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |score| {
+                Some(score.saturating_sub(amount))
+            })
+            // Panic: the closure always returns `Some`.
+            .unwrap()
+            .saturating_sub(amount)
+    }
+
+    /// Resets the misbehaviour score of the `PeerReputation`.
+    pub fn reset(&self) {
+        self.score.store(0, Ordering::Relaxed);
+    }
+}
+
+/// A token bucket rate limiter.
+///
+/// The bucket holds up to `burst` tokens and is refilled with `rate` tokens per second; every accepted packet consumes
+/// a token.
+#[derive(Clone, Debug)]
+pub struct TokenBucket {
+    rate: f64,
+    burst: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    /// Creates a new, full, `TokenBucket`.
+    pub fn new(rate: f64, burst: f64) -> Self {
+        Self {
+            rate,
+            burst,
+            tokens: burst,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Tries to take a token from the `TokenBucket`, returns whether it succeeded.
+    pub fn try_take(&mut self) -> bool {
+        self.try_take_at(Instant::now())
+    }
+
+    fn try_take_at(&mut self, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.burst);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn peer_reputation_penalize_decay() {
+        let reputation = PeerReputation::default();
+
+        assert_eq!(reputation.score(), 0);
+        assert_eq!(reputation.penalize(Misbehaviour::InvalidMessage), 10);
+        assert_eq!(reputation.penalize(Misbehaviour::Flooding), 11);
+        assert_eq!(reputation.decay(5), 6);
+        assert_eq!(reputation.decay(10), 0);
+        assert_eq!(reputation.score(), 0);
+    }
+
+    #[test]
+    fn token_bucket_burst_refill() {
+        let mut bucket = TokenBucket::new(2.0, 3.0);
+        let start = bucket.last_refill;
+
+        assert!(bucket.try_take_at(start));
+        assert!(bucket.try_take_at(start));
+        assert!(bucket.try_take_at(start));
+        assert!(!bucket.try_take_at(start));
+
+        // Half a second refills a single token.
+        assert!(bucket.try_take_at(start + Duration::from_millis(500)));
+        assert!(!bucket.try_take_at(start + Duration::from_millis(500)));
+
+        // Refilling never exceeds the burst size.
+        let later = start + Duration::from_secs(60);
+        assert!(bucket.try_take_at(later));
+        assert!(bucket.try_take_at(later));
+        assert!(bucket.try_take_at(later));
+        assert!(!bucket.try_take_at(later));
+    }
+}
@@ -1,17 +1,36 @@
 // Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use std::path::PathBuf;
+
 use bee_message::milestone::MilestoneIndex;
 use serde::Deserialize;
 
-use crate::types::milestone_key_range::MilestoneKeyRange;
+use crate::{types::milestone_key_range::MilestoneKeyRange, workers::IpNetwork};
 
 const DEFAULT_MINIMUM_POW_SCORE: f64 = 4000.0;
 const DEFAULT_COO_PUBLIC_KEY_COUNT: usize = 2;
 const DEFAULT_COO_PUBLIC_KEY_RANGES: [(&str, MilestoneIndex, MilestoneIndex); 0] = [];
+const DEFAULT_COO_PUBLIC_KEY_RANGES_RELOAD_INTERVAL: u64 = 10;
 const DEFAULT_MESSAGE_WORKER_CACHE: usize = 10000;
 const DEFAULT_STATUS_INTERVAL: u64 = 10;
 const DEFAULT_MILESTONE_SYNC_COUNT: u32 = 200;
+// Warp sync is opt-in.
+const DEFAULT_WARP_SYNC_WINDOW: u32 = 0;
+const DEFAULT_MESSAGE_PROCESSOR_WORKERS: usize = 16;
+const DEFAULT_HASHER_QUEUE_SIZE: usize = 10000;
+const DEFAULT_PROCESSOR_QUEUE_SIZE: usize = 10000;
+const DEFAULT_PAYLOAD_QUEUE_SIZE: usize = 10000;
+const DEFAULT_REPUTATION_DISCONNECT_THRESHOLD: u32 = 100;
+const DEFAULT_REPUTATION_BAN_THRESHOLD: u32 = 300;
+const DEFAULT_REPUTATION_DECAY: u32 = 5;
+const DEFAULT_REPUTATION_CHECK_INTERVAL: u64 = 10;
+const DEFAULT_REPUTATION_BAN_DURATION: u64 = 3600;
+const DEFAULT_REPUTATION_STALE_HEARTBEAT: u64 = 120;
+const DEFAULT_MESSAGE_RATE_LIMIT: PacketRateLimit = PacketRateLimit::new(500.0, 1000.0);
+const DEFAULT_MESSAGE_REQUEST_RATE_LIMIT: PacketRateLimit = PacketRateLimit::new(200.0, 400.0);
+const DEFAULT_MILESTONE_REQUEST_RATE_LIMIT: PacketRateLimit = PacketRateLimit::new(50.0, 250.0);
+const DEFAULT_HEARTBEAT_RATE_LIMIT: PacketRateLimit = PacketRateLimit::new(1.0, 5.0);
 
 #[derive(Default, Deserialize, PartialEq)]
 #[must_use]
@@ -20,6 +39,10 @@ struct ProtocolCoordinatorConfigBuilder {
     public_key_count: Option<usize>,
     #[serde(alias = "publicKeyRanges")]
     public_key_ranges: Option<Vec<MilestoneKeyRange>>,
+    #[serde(alias = "publicKeyRangesFile")]
+    public_key_ranges_file: Option<PathBuf>,
+    #[serde(alias = "publicKeyRangesReloadInterval")]
+    public_key_ranges_reload_interval: Option<u64>,
 }
 
 #[derive(Default, Deserialize, PartialEq)]
@@ -31,6 +54,72 @@ struct ProtocolWorkersConfigBuilder {
     status_interval: Option<u64>,
     #[serde(alias = "milestoneSyncCount")]
     milestone_sync_count: Option<u32>,
+    #[serde(alias = "messageHasherWorkers")]
+    message_hasher_workers: Option<usize>,
+    #[serde(alias = "warpSyncWindow")]
+    warp_sync_window: Option<u32>,
+    #[serde(alias = "messageProcessorWorkers")]
+    message_processor_workers: Option<usize>,
+    #[serde(alias = "hasherQueueSize")]
+    hasher_queue_size: Option<usize>,
+    #[serde(alias = "processorQueueSize")]
+    processor_queue_size: Option<usize>,
+    #[serde(alias = "payloadQueueSize")]
+    payload_queue_size: Option<usize>,
+}
+
+/// A token bucket rate limit: `rate` packets per second with bursts of up to `burst` packets.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+pub struct PacketRateLimit {
+    pub(crate) rate: f64,
+    pub(crate) burst: f64,
+}
+
+impl PacketRateLimit {
+    /// Creates a new `PacketRateLimit`.
+    pub const fn new(rate: f64, burst: f64) -> Self {
+        Self { rate, burst }
+    }
+}
+
+#[derive(Default, Deserialize, PartialEq)]
+#[must_use]
+struct ProtocolRateLimitsConfigBuilder {
+    message: Option<PacketRateLimit>,
+    #[serde(alias = "messageRequest")]
+    message_request: Option<PacketRateLimit>,
+    #[serde(alias = "milestoneRequest")]
+    milestone_request: Option<PacketRateLimit>,
+    heartbeat: Option<PacketRateLimit>,
+}
+
+#[derive(Default, Deserialize, PartialEq)]
+#[must_use]
+struct ProtocolReputationConfigBuilder {
+    #[serde(alias = "disconnectThreshold")]
+    disconnect_threshold: Option<u32>,
+    #[serde(alias = "banThreshold")]
+    ban_threshold: Option<u32>,
+    decay: Option<u32>,
+    #[serde(alias = "checkInterval")]
+    check_interval: Option<u64>,
+    #[serde(alias = "staleHeartbeat")]
+    stale_heartbeat: Option<u64>,
+    #[serde(alias = "banDuration")]
+    ban_duration: Option<u64>,
+    #[serde(default, alias = "rateLimits")]
+    rate_limits: ProtocolRateLimitsConfigBuilder,
+}
+
+#[derive(Default, Deserialize, PartialEq)]
+#[must_use]
+struct ProtocolFiltersConfigBuilder {
+    #[serde(alias = "indexationMaxSize")]
+    indexation_max_size: Option<usize>,
+    #[serde(alias = "indexationTags")]
+    indexation_tags: Option<Vec<String>>,
+    #[serde(default, alias = "deniedNetworks")]
+    denied_networks: Vec<IpNetwork>,
 }
 
 /// Builder for a `ProtocolConfig`.
@@ -41,6 +130,10 @@ pub struct ProtocolConfigBuilder {
     minimum_pow_score: Option<f64>,
     coordinator: ProtocolCoordinatorConfigBuilder,
     workers: ProtocolWorkersConfigBuilder,
+    #[serde(default)]
+    reputation: ProtocolReputationConfigBuilder,
+    #[serde(default)]
+    filters: ProtocolFiltersConfigBuilder,
 }
 
 impl ProtocolConfigBuilder {
@@ -67,6 +160,22 @@ impl ProtocolConfigBuilder {
         self
     }
 
+    /// Sets the file the coordinator public key ranges are reloaded from when it changes.
+    pub fn coo_public_key_ranges_file(mut self, coo_public_key_ranges_file: PathBuf) -> Self {
+        self.coordinator
+            .public_key_ranges_file
+            .replace(coo_public_key_ranges_file);
+        self
+    }
+
+    /// Sets the interval, in seconds, at which the coordinator public key ranges file is checked for changes.
+    pub fn coo_public_key_ranges_reload_interval(mut self, coo_public_key_ranges_reload_interval: u64) -> Self {
+        self.coordinator
+            .public_key_ranges_reload_interval
+            .replace(coo_public_key_ranges_reload_interval);
+        self
+    }
+
     /// Sets the message worker cache of the `ProtocolConfigBuilder`.
     pub fn message_worker_cache(mut self, message_worker_cache: usize) -> Self {
         self.workers.message_worker_cache.replace(message_worker_cache);
@@ -85,6 +194,122 @@ impl ProtocolConfigBuilder {
         self
     }
 
+    /// Sets the number of message hasher workers of the `ProtocolConfigBuilder`.
+    pub fn message_hasher_workers(mut self, message_hasher_workers: usize) -> Self {
+        self.workers.message_hasher_workers.replace(message_hasher_workers);
+        self
+    }
+
+    /// Sets the warp sync window of the `ProtocolConfigBuilder`, 0 disables warp sync.
+    pub fn warp_sync_window(mut self, warp_sync_window: u32) -> Self {
+        self.workers.warp_sync_window.replace(warp_sync_window);
+        self
+    }
+
+    /// Sets the number of message processor workers of the `ProtocolConfigBuilder`.
+    pub fn message_processor_workers(mut self, message_processor_workers: usize) -> Self {
+        self.workers
+            .message_processor_workers
+            .replace(message_processor_workers);
+        self
+    }
+
+    /// Sets the capacity of the hasher queue, received messages are dropped when it is full.
+    pub fn hasher_queue_size(mut self, hasher_queue_size: usize) -> Self {
+        self.workers.hasher_queue_size.replace(hasher_queue_size);
+        self
+    }
+
+    /// Sets the capacity of the processor queue, hashed messages are dropped when it is full.
+    pub fn processor_queue_size(mut self, processor_queue_size: usize) -> Self {
+        self.workers.processor_queue_size.replace(processor_queue_size);
+        self
+    }
+
+    /// Sets the capacity of the payload queue, processors wait when it is full.
+    pub fn payload_queue_size(mut self, payload_queue_size: usize) -> Self {
+        self.workers.payload_queue_size.replace(payload_queue_size);
+        self
+    }
+
+    /// Sets the misbehaviour score above which peers are disconnected.
+    pub fn reputation_disconnect_threshold(mut self, disconnect_threshold: u32) -> Self {
+        self.reputation.disconnect_threshold.replace(disconnect_threshold);
+        self
+    }
+
+    /// Sets the misbehaviour score above which peers are banned.
+    pub fn reputation_ban_threshold(mut self, ban_threshold: u32) -> Self {
+        self.reputation.ban_threshold.replace(ban_threshold);
+        self
+    }
+
+    /// Sets the amount by which the misbehaviour score of peers decays at every check.
+    pub fn reputation_decay(mut self, decay: u32) -> Self {
+        self.reputation.decay.replace(decay);
+        self
+    }
+
+    /// Sets the interval, in seconds, at which the reputation of peers is checked.
+    pub fn reputation_check_interval(mut self, check_interval: u64) -> Self {
+        self.reputation.check_interval.replace(check_interval);
+        self
+    }
+
+    /// Sets the delay, in seconds, after which a peer that didn't send a heartbeat is penalized.
+    pub fn reputation_stale_heartbeat(mut self, stale_heartbeat: u64) -> Self {
+        self.reputation.stale_heartbeat.replace(stale_heartbeat);
+        self
+    }
+
+    /// Sets the duration, in seconds, for which peers are banned because of their misbehaviour score.
+    pub fn reputation_ban_duration(mut self, ban_duration: u64) -> Self {
+        self.reputation.ban_duration.replace(ban_duration);
+        self
+    }
+
+    /// Sets the rate limit of message packets.
+    pub fn message_rate_limit(mut self, rate_limit: PacketRateLimit) -> Self {
+        self.reputation.rate_limits.message.replace(rate_limit);
+        self
+    }
+
+    /// Sets the rate limit of message request packets.
+    pub fn message_request_rate_limit(mut self, rate_limit: PacketRateLimit) -> Self {
+        self.reputation.rate_limits.message_request.replace(rate_limit);
+        self
+    }
+
+    /// Sets the rate limit of milestone request packets.
+    pub fn milestone_request_rate_limit(mut self, rate_limit: PacketRateLimit) -> Self {
+        self.reputation.rate_limits.milestone_request.replace(rate_limit);
+        self
+    }
+
+    /// Sets the rate limit of heartbeat packets.
+    pub fn heartbeat_rate_limit(mut self, rate_limit: PacketRateLimit) -> Self {
+        self.reputation.rate_limits.heartbeat.replace(rate_limit);
+        self
+    }
+
+    /// Sets the maximum size of the data of indexation payloads, larger ones are filtered out.
+    pub fn filter_indexation_max_size(mut self, max_size: usize) -> Self {
+        self.filters.indexation_max_size.replace(max_size);
+        self
+    }
+
+    /// Sets the allowed indexation tags, indexation payloads with other tags are filtered out.
+    pub fn filter_indexation_tags(mut self, tags: Vec<String>) -> Self {
+        self.filters.indexation_tags.replace(tags);
+        self
+    }
+
+    /// Sets the networks whose peers' messages are filtered out.
+    pub fn filter_denied_networks(mut self, networks: Vec<IpNetwork>) -> Self {
+        self.filters.denied_networks = networks;
+        self
+    }
+
     /// Finishes the `ProtocolConfigBuilder` into a `ProtocolConfig`.
     #[must_use]
     pub fn finish(self) -> ProtocolConfig {
@@ -101,6 +326,12 @@ impl ProtocolConfigBuilder {
                         .map(|(public_key, start, end)| MilestoneKeyRange::new(public_key.to_string(), *start, *end))
                         .collect()
                 }),
+                public_key_ranges_file: self.coordinator.public_key_ranges_file,
+                public_key_ranges_reload_interval: self
+                    .coordinator
+                    .public_key_ranges_reload_interval
+                    .filter(|interval| *interval > 0)
+                    .unwrap_or(DEFAULT_COO_PUBLIC_KEY_RANGES_RELOAD_INTERVAL),
             },
             workers: ProtocolWorkersConfig {
                 message_worker_cache: self
@@ -112,6 +343,84 @@ impl ProtocolConfigBuilder {
                     .workers
                     .milestone_sync_count
                     .unwrap_or(DEFAULT_MILESTONE_SYNC_COUNT),
+                message_hasher_workers: self
+                    .workers
+                    .message_hasher_workers
+                    .filter(|workers| *workers > 0)
+                    .unwrap_or_else(num_cpus::get),
+                warp_sync_window: self.workers.warp_sync_window.unwrap_or(DEFAULT_WARP_SYNC_WINDOW),
+                message_processor_workers: self
+                    .workers
+                    .message_processor_workers
+                    .filter(|workers| *workers > 0)
+                    .unwrap_or(DEFAULT_MESSAGE_PROCESSOR_WORKERS),
+                hasher_queue_size: self
+                    .workers
+                    .hasher_queue_size
+                    .filter(|size| *size > 0)
+                    .unwrap_or(DEFAULT_HASHER_QUEUE_SIZE),
+                processor_queue_size: self
+                    .workers
+                    .processor_queue_size
+                    .filter(|size| *size > 0)
+                    .unwrap_or(DEFAULT_PROCESSOR_QUEUE_SIZE),
+                payload_queue_size: self
+                    .workers
+                    .payload_queue_size
+                    .filter(|size| *size > 0)
+                    .unwrap_or(DEFAULT_PAYLOAD_QUEUE_SIZE),
+            },
+            reputation: ProtocolReputationConfig {
+                disconnect_threshold: self
+                    .reputation
+                    .disconnect_threshold
+                    .unwrap_or(DEFAULT_REPUTATION_DISCONNECT_THRESHOLD),
+                ban_threshold: self
+                    .reputation
+                    .ban_threshold
+                    .unwrap_or(DEFAULT_REPUTATION_BAN_THRESHOLD),
+                decay: self.reputation.decay.unwrap_or(DEFAULT_REPUTATION_DECAY),
+                check_interval: self
+                    .reputation
+                    .check_interval
+                    .filter(|interval| *interval > 0)
+                    .unwrap_or(DEFAULT_REPUTATION_CHECK_INTERVAL),
+                stale_heartbeat: self
+                    .reputation
+                    .stale_heartbeat
+                    .unwrap_or(DEFAULT_REPUTATION_STALE_HEARTBEAT),
+                ban_duration: self
+                    .reputation
+                    .ban_duration
+                    .filter(|duration| *duration > 0)
+                    .unwrap_or(DEFAULT_REPUTATION_BAN_DURATION),
+                rate_limits: ProtocolRateLimitsConfig {
+                    message: self
+                        .reputation
+                        .rate_limits
+                        .message
+                        .unwrap_or(DEFAULT_MESSAGE_RATE_LIMIT),
+                    message_request: self
+                        .reputation
+                        .rate_limits
+                        .message_request
+                        .unwrap_or(DEFAULT_MESSAGE_REQUEST_RATE_LIMIT),
+                    milestone_request: self
+                        .reputation
+                        .rate_limits
+                        .milestone_request
+                        .unwrap_or(DEFAULT_MILESTONE_REQUEST_RATE_LIMIT),
+                    heartbeat: self
+                        .reputation
+                        .rate_limits
+                        .heartbeat
+                        .unwrap_or(DEFAULT_HEARTBEAT_RATE_LIMIT),
+                },
+            },
+            filters: ProtocolFiltersConfig {
+                indexation_max_size: self.filters.indexation_max_size,
+                indexation_tags: self.filters.indexation_tags,
+                denied_networks: self.filters.denied_networks,
             },
         }
     }
@@ -122,6 +431,8 @@ impl ProtocolConfigBuilder {
 pub struct ProtocolCoordinatorConfig {
     pub(crate) public_key_count: usize,
     pub(crate) public_key_ranges: Vec<MilestoneKeyRange>,
+    pub(crate) public_key_ranges_file: Option<PathBuf>,
+    pub(crate) public_key_ranges_reload_interval: u64,
 }
 
 /// Configuration for the protocol workers.
@@ -130,6 +441,41 @@ pub struct ProtocolWorkersConfig {
     pub(crate) message_worker_cache: usize,
     pub(crate) status_interval: u64,
     pub(crate) milestone_sync_count: u32,
+    pub(crate) message_hasher_workers: usize,
+    pub(crate) warp_sync_window: u32,
+    pub(crate) message_processor_workers: usize,
+    pub(crate) hasher_queue_size: usize,
+    pub(crate) processor_queue_size: usize,
+    pub(crate) payload_queue_size: usize,
+}
+
+/// Configuration for the per packet type rate limits.
+#[derive(Clone)]
+pub struct ProtocolRateLimitsConfig {
+    pub(crate) message: PacketRateLimit,
+    pub(crate) message_request: PacketRateLimit,
+    pub(crate) milestone_request: PacketRateLimit,
+    pub(crate) heartbeat: PacketRateLimit,
+}
+
+/// Configuration for the peer reputation.
+#[derive(Clone)]
+pub struct ProtocolReputationConfig {
+    pub(crate) disconnect_threshold: u32,
+    pub(crate) ban_threshold: u32,
+    pub(crate) decay: u32,
+    pub(crate) check_interval: u64,
+    pub(crate) stale_heartbeat: u64,
+    pub(crate) ban_duration: u64,
+    pub(crate) rate_limits: ProtocolRateLimitsConfig,
+}
+
+/// Configuration for the message filters.
+#[derive(Clone)]
+pub struct ProtocolFiltersConfig {
+    pub(crate) indexation_max_size: Option<usize>,
+    pub(crate) indexation_tags: Option<Vec<String>>,
+    pub(crate) denied_networks: Vec<IpNetwork>,
 }
 
 /// Configuration for the protocol.
@@ -138,6 +484,8 @@ pub struct ProtocolConfig {
     pub(crate) minimum_pow_score: f64,
     pub(crate) coordinator: ProtocolCoordinatorConfig,
     pub(crate) workers: ProtocolWorkersConfig,
+    pub(crate) reputation: ProtocolReputationConfig,
+    pub(crate) filters: ProtocolFiltersConfig,
 }
 
 impl ProtocolConfig {
@@ -156,3 +504,56 @@ impl ProtocolConfig {
         &self.coordinator
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_reputation_check_interval_falls_back_to_default() {
+        let config = ProtocolConfigBuilder::default().reputation_check_interval(0).finish();
+
+        assert_eq!(config.reputation.check_interval, DEFAULT_REPUTATION_CHECK_INTERVAL);
+    }
+
+    #[test]
+    fn zero_public_key_ranges_reload_interval_falls_back_to_default() {
+        let config = ProtocolConfigBuilder::default()
+            .coo_public_key_ranges_reload_interval(0)
+            .finish();
+
+        assert_eq!(
+            config.coordinator.public_key_ranges_reload_interval,
+            DEFAULT_COO_PUBLIC_KEY_RANGES_RELOAD_INTERVAL
+        );
+    }
+
+    #[test]
+    fn reputation_bans_are_temporary() {
+        assert_eq!(
+            ProtocolConfigBuilder::default().finish().reputation.ban_duration,
+            DEFAULT_REPUTATION_BAN_DURATION
+        );
+        assert_eq!(
+            ProtocolConfigBuilder::default()
+                .reputation_ban_duration(0)
+                .finish()
+                .reputation
+                .ban_duration,
+            DEFAULT_REPUTATION_BAN_DURATION
+        );
+    }
+
+    #[test]
+    fn warp_sync_disabled_by_default() {
+        assert_eq!(ProtocolConfigBuilder::default().finish().workers.warp_sync_window, 0);
+        assert_eq!(
+            ProtocolConfigBuilder::default()
+                .warp_sync_window(50)
+                .finish()
+                .workers
+                .warp_sync_window,
+            50
+        );
+    }
+}
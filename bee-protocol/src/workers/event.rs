@@ -1,7 +1,10 @@
 // Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use bee_message::MessageId;
+use bee_gossip::{Multiaddr, PeerId, PeerRelation};
+use bee_message::{milestone::MilestoneIndex, MessageId};
+
+use crate::types::metrics::SpamMetrics;
 
 /// An event that indicates that a message was processed.
 #[derive(Clone)]
@@ -32,6 +35,20 @@ pub struct MpsMetricsUpdated {
     pub outgoing: u64,
 }
 
+/// An event that indicates that the spam metrics of the last second were updated.
+#[derive(Clone)]
+pub struct SpamMetricsUpdated {
+    /// Messages per second by payload kind and indexation tag.
+    pub metrics: SpamMetrics,
+}
+
+/// An event that indicates that the spam metrics averaged over the last minute were updated.
+#[derive(Clone)]
+pub struct AverageSpamMetricsUpdated {
+    /// Messages per second by payload kind and indexation tag.
+    pub metrics: SpamMetrics,
+}
+
 /// An event that indicates that a vertex was created.
 #[derive(Clone)]
 pub struct VertexCreated {
@@ -66,3 +83,63 @@ pub struct TipRemoved {
     /// Message identifier of the removed tip.
     pub message_id: MessageId,
 }
+
+/// An event that indicates that a message was declared orphaned, i.e. it remained unreferenced beyond the below max
+/// depth.
+#[derive(Clone, Debug)]
+pub struct MessageOrphaned {
+    /// Message identifier of the orphaned message.
+    pub message_id: MessageId,
+    /// Latest milestone index at the arrival of the orphaned message.
+    pub milestone_index: MilestoneIndex,
+    /// Arrival timestamp of the orphaned message, in milliseconds from the unix epoch.
+    pub arrival_timestamp: u64,
+    /// Identifier of the peer that delivered the orphaned message, if it did not originate from this node.
+    pub peer_id: Option<PeerId>,
+}
+
+/// An event that indicates the progress of a warp sync.
+#[derive(Clone, Debug)]
+pub struct WarpSyncProgressed {
+    /// Solid milestone index at the start of the warp sync.
+    pub start_index: MilestoneIndex,
+    /// Current solid milestone index.
+    pub current_index: MilestoneIndex,
+    /// Latest milestone index, the warp sync is done once it is solid.
+    pub target_index: MilestoneIndex,
+    /// Average number of milestones solidified per second since the start of the warp sync.
+    pub milestones_per_second: f64,
+    /// Estimated time until the warp sync is done, in seconds, if it can be estimated yet.
+    pub eta_secs: Option<u64>,
+}
+
+/// An event that indicates that a warp sync is done.
+#[derive(Clone, Debug)]
+pub struct WarpSyncCompleted {
+    /// Solid milestone index at the start of the warp sync.
+    pub start_index: MilestoneIndex,
+    /// Solid milestone index at the end of the warp sync.
+    pub target_index: MilestoneIndex,
+    /// Duration of the warp sync, in seconds.
+    pub duration_secs: u64,
+}
+
+/// An event that indicates that a peer was added.
+#[derive(Clone, Debug)]
+pub struct PeerAdded {
+    /// Identifier of the added peer.
+    pub peer_id: PeerId,
+    /// Address of the added peer.
+    pub address: Multiaddr,
+    /// Alias of the added peer.
+    pub alias: String,
+    /// Relation with the added peer.
+    pub relation: PeerRelation,
+}
+
+/// An event that indicates that a peer was removed.
+#[derive(Clone, Debug)]
+pub struct PeerRemoved {
+    /// Identifier of the removed peer.
+    pub peer_id: PeerId,
+}
@@ -0,0 +1,269 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Hooks to reject valid messages before they are stored and broadcast.
+
+use std::{collections::HashSet, convert::TryFrom, net::IpAddr, str::FromStr};
+
+use bee_gossip::{Multiaddr, Protocol};
+use bee_message::{Message, MessageId};
+use parking_lot::RwLock;
+use serde::Deserialize;
+
+use crate::{
+    types::{metrics::spam::indexation_of, peer::Peer},
+    workers::config::ProtocolFiltersConfig,
+};
+
+/// A filter consulted for every new message that was not requested by the node, after it has been unpacked and
+/// checked, and before it is stored and broadcast.
+///
+/// Messages requested by the node are needed for solidification and are never filtered.
+pub trait MessageFilter: Send + Sync + 'static {
+    /// Returns the reason to reject a message, if it should be.
+    ///
+    /// `peer` is the peer the message was received from, `None` if the message was submitted to the node.
+    fn filter(&self, message_id: &MessageId, message: &Message, peer: Option<&Peer>) -> Result<(), String>;
+}
+
+/// The filters consulted for new messages.
+#[derive(Default)]
+pub struct MessageFilters(RwLock<Vec<Box<dyn MessageFilter>>>);
+
+impl MessageFilters {
+    pub(crate) fn new(config: &ProtocolFiltersConfig) -> Self {
+        let filters = Self::default();
+
+        if let Some(max_size) = config.indexation_max_size {
+            filters.add(IndexationSizeFilter { max_size });
+        }
+        if let Some(ref tags) = config.indexation_tags {
+            filters.add(IndexationTagFilter {
+                tags: tags.iter().map(|tag| tag.as_bytes().to_vec()).collect(),
+            });
+        }
+        if !config.denied_networks.is_empty() {
+            filters.add(PeerNetworkFilter {
+                denied: config.denied_networks.clone(),
+            });
+        }
+
+        filters
+    }
+
+    /// Adds a filter, messages are then only accepted if every filter accepts them.
+    pub fn add(&self, filter: impl MessageFilter) {
+        self.0.write().push(Box::new(filter));
+    }
+
+    /// Returns the number of filters.
+    pub fn len(&self) -> usize {
+        self.0.read().len()
+    }
+
+    /// Returns whether there are no filters.
+    pub fn is_empty(&self) -> bool {
+        self.0.read().is_empty()
+    }
+
+    pub(crate) fn filter(&self, message_id: &MessageId, message: &Message, peer: Option<&Peer>) -> Result<(), String> {
+        self.0
+            .read()
+            .iter()
+            .try_for_each(|filter| filter.filter(message_id, message, peer))
+    }
+}
+
+/// Rejects indexation payloads, including the ones of transactions, with data larger than a maximum size.
+pub struct IndexationSizeFilter {
+    /// Maximum size of the data of an indexation payload.
+    pub max_size: usize,
+}
+
+impl MessageFilter for IndexationSizeFilter {
+    fn filter(&self, _message_id: &MessageId, message: &Message, _peer: Option<&Peer>) -> Result<(), String> {
+        match indexation_of(message) {
+            Some(indexation) if indexation.data().len() > self.max_size => Err(format!(
+                "Indexation payload of {} bytes exceeds {} bytes.",
+                indexation.data().len(),
+                self.max_size
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Rejects indexation payloads, including the ones of transactions, whose index is not allowed.
+pub struct IndexationTagFilter {
+    /// The allowed indexes.
+    pub tags: HashSet<Vec<u8>>,
+}
+
+impl MessageFilter for IndexationTagFilter {
+    fn filter(&self, _message_id: &MessageId, message: &Message, _peer: Option<&Peer>) -> Result<(), String> {
+        match indexation_of(message) {
+            Some(indexation) if !self.tags.contains(indexation.index()) => Err(format!(
+                "Indexation tag {} is not allowed.",
+                hex::encode(indexation.index())
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Rejects messages received from peers whose IP address belongs to a denied network.
+pub struct PeerNetworkFilter {
+    /// The denied networks.
+    pub denied: Vec<IpNetwork>,
+}
+
+impl MessageFilter for PeerNetworkFilter {
+    fn filter(&self, _message_id: &MessageId, _message: &Message, peer: Option<&Peer>) -> Result<(), String> {
+        match peer.and_then(|peer| ip_of(peer.address())) {
+            Some(ip) if self.denied.iter().any(|network| network.contains(&ip)) => {
+                Err(format!("Peer address {} belongs to a denied network.", ip))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+fn ip_of(address: &Multiaddr) -> Option<IpAddr> {
+    address.iter().find_map(|protocol| match protocol {
+        Protocol::Ip4(ip) => Some(IpAddr::V4(ip)),
+        Protocol::Ip6(ip) => Some(IpAddr::V6(ip)),
+        _ => None,
+    })
+}
+
+/// An IP network in CIDR notation, e.g. `10.0.0.0/8`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct IpNetwork {
+    address: IpAddr,
+    prefix: u8,
+}
+
+impl IpNetwork {
+    /// Returns whether an IP address belongs to the `IpNetwork`.
+    pub fn contains(&self, ip: &IpAddr) -> bool {
+        match (self.address, ip) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                let mask = u32::MAX.checked_shl(32 - self.prefix as u32).unwrap_or(0);
+                u32::from(network) & mask == u32::from(*ip) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                let mask = u128::MAX.checked_shl(128 - self.prefix as u32).unwrap_or(0);
+                u128::from(network) & mask == u128::from(*ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for IpNetwork {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (address, prefix) = match s.split_once('/') {
+            Some((address, prefix)) => (address, Some(prefix)),
+            None => (s, None),
+        };
+        let address = address
+            .parse::<IpAddr>()
+            .map_err(|e| format!("invalid network {}: {}", s, e))?;
+        let max_prefix = if address.is_ipv4() { 32 } else { 128 };
+        let prefix = match prefix {
+            Some(prefix) => prefix
+                .parse::<u8>()
+                .ok()
+                .filter(|prefix| *prefix <= max_prefix)
+                .ok_or_else(|| format!("invalid network {}: invalid prefix length", s))?,
+            None => max_prefix,
+        };
+
+        Ok(Self { address, prefix })
+    }
+}
+
+impl TryFrom<String> for IpNetwork {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bee_message::{
+        parents::Parents,
+        payload::{indexation::IndexationPayload, Payload},
+        MessageBuilder,
+    };
+
+    use super::*;
+
+    fn indexation_message(index: &[u8], data: Vec<u8>) -> Message {
+        MessageBuilder::<u64>::new()
+            .with_network_id(0)
+            .with_parents(Parents::new(vec![MessageId::null()]).unwrap())
+            .with_payload(Payload::Indexation(Box::new(
+                IndexationPayload::new(index, &data).unwrap(),
+            )))
+            .with_nonce_provider(0, 0.0)
+            .finish()
+            .unwrap()
+    }
+
+    #[test]
+    fn indexation_size() {
+        let filters = MessageFilters::default();
+        filters.add(IndexationSizeFilter { max_size: 10 });
+
+        let small = indexation_message(b"tag", vec![0; 10]);
+        let large = indexation_message(b"tag", vec![0; 11]);
+
+        assert!(filters.filter(&small.id().0, &small, None).is_ok());
+        assert!(filters.filter(&large.id().0, &large, None).is_err());
+    }
+
+    #[test]
+    fn indexation_tag() {
+        let filters = MessageFilters::default();
+        filters.add(IndexationTagFilter {
+            tags: vec![b"allowed".to_vec()].into_iter().collect(),
+        });
+
+        let allowed = indexation_message(b"allowed", vec![]);
+        let denied = indexation_message(b"denied", vec![]);
+
+        assert!(filters.filter(&allowed.id().0, &allowed, None).is_ok());
+        assert!(filters.filter(&denied.id().0, &denied, None).is_err());
+    }
+
+    #[test]
+    fn ip_network() {
+        let network = "10.1.0.0/16".parse::<IpNetwork>().unwrap();
+
+        assert!(network.contains(&"10.1.2.3".parse().unwrap()));
+        assert!(!network.contains(&"10.2.2.3".parse().unwrap()));
+        assert!(!network.contains(&"::1".parse().unwrap()));
+
+        let network = "fd00::/8".parse::<IpNetwork>().unwrap();
+
+        assert!(network.contains(&"fd12::1".parse().unwrap()));
+        assert!(!network.contains(&"fe80::1".parse().unwrap()));
+
+        assert!("0.0.0.0/0"
+            .parse::<IpNetwork>()
+            .unwrap()
+            .contains(&"1.2.3.4".parse().unwrap()));
+        assert_eq!(
+            "1.2.3.4".parse::<IpNetwork>().unwrap(),
+            "1.2.3.4/32".parse::<IpNetwork>().unwrap()
+        );
+        assert!("1.2.3.4/33".parse::<IpNetwork>().is_err());
+        assert!("1.2.3/8".parse::<IpNetwork>().is_err());
+    }
+}
@@ -8,21 +8,24 @@ use bee_gossip::PeerId;
 use bee_message::MessageId;
 use bee_pow::score;
 use bee_runtime::{node::Node, shutdown_stream::ShutdownStream, worker::Worker};
+use crypto::hashes::{blake2b::Blake2b256, Digest};
 use futures::{channel::oneshot::Sender, StreamExt};
 use log::{error, info, trace, warn};
-use tokio::sync::mpsc;
-use tokio_stream::wrappers::UnboundedReceiverStream;
+use tokio::sync::mpsc::{self, error::TrySendError};
+use tokio_stream::wrappers::ReceiverStream;
 
 use crate::{
-    types::metrics::NodeMetrics,
+    types::{metrics::NodeMetrics, reputation::Misbehaviour},
     workers::{
         config::ProtocolConfig,
         message::{
-            submitter::notify_invalid_message, HashCache, MessageSubmitterError, ProcessorWorker, ProcessorWorkerEvent,
+            submitter::{notify_dropped_message, notify_invalid_message},
+            HashCache, MessageSubmitterError, ProcessorWorker, ProcessorWorkerEvent,
         },
         packets::MessagePacket,
+        reputation::penalize_peer,
         storage::StorageBackend,
-        MetricsWorker, PeerManager, PeerManagerResWorker,
+        MessageRequesterWorker, MetricsWorker, PeerManager, PeerManagerResWorker, RequestedMessages,
     },
 };
 
@@ -33,7 +36,62 @@ pub(crate) struct HasherWorkerEvent {
 }
 
 pub(crate) struct HasherWorker {
-    pub(crate) tx: mpsc::UnboundedSender<HasherWorkerEvent>,
+    pub(crate) tx: mpsc::Sender<HasherWorkerEvent>,
+}
+
+/// Enqueues a message to the hasher, dropping it if the hasher queue is full.
+pub(crate) fn enqueue_message(
+    hasher: &mpsc::Sender<HasherWorkerEvent>,
+    event: HasherWorkerEvent,
+    metrics: &NodeMetrics,
+) {
+    // Incremented beforehand so that the depth doesn't underflow if the event is dequeued before being accounted.
+    metrics.hasher_queue_depth_inc(1);
+
+    if let Err(e) = hasher.try_send(event) {
+        metrics.hasher_queue_depth_dec(1);
+
+        match e {
+            TrySendError::Full(event) => {
+                notify_dropped_message("Hasher queue is full.".to_string(), metrics, event.notifier)
+            }
+            TrySendError::Closed(_) => warn!("Sending event to the hasher worker failed: channel closed."),
+        }
+    }
+}
+
+/// Sends a message to the processor, dropping it if the processor queue is full unless it was requested.
+async fn send_to_processor(
+    processor: &mpsc::Sender<ProcessorWorkerEvent>,
+    event: ProcessorWorkerEvent,
+    requested_messages: &RequestedMessages,
+    metrics: &NodeMetrics,
+) {
+    // Incremented beforehand so that the depth doesn't underflow if the event is dequeued before being accounted.
+    metrics.processor_queue_depth_inc(1);
+
+    let event = match processor.try_send(event) {
+        Ok(()) => return,
+        Err(TrySendError::Full(event)) => event,
+        Err(TrySendError::Closed(_)) => {
+            metrics.processor_queue_depth_dec(1);
+            warn!("Sending event to the processor worker failed: channel closed.");
+            return;
+        }
+    };
+
+    // Requested messages are needed for solidification, they wait for the processor instead of being dropped.
+    let message_id = MessageId::new(Blake2b256::digest(&event.message_packet.bytes).into());
+
+    if requested_messages.contains(&message_id) {
+        if processor.send(event).await.is_err() {
+            metrics.processor_queue_depth_dec(1);
+            warn!("Sending event to the processor worker failed: channel closed.");
+        }
+    } else {
+        metrics.processor_queue_depth_dec(1);
+        notify_dropped_message("Processor queue is full.".to_string(), metrics, event.notifier);
+    }
 }
 
 #[async_trait]
@@ -47,6 +105,7 @@ where
     fn dependencies() -> &'static [TypeId] {
         vec![
             TypeId::of::<ProcessorWorker>(),
+            TypeId::of::<MessageRequesterWorker>(),
             TypeId::of::<MetricsWorker>(),
             TypeId::of::<PeerManagerResWorker>(),
         ]
@@ -54,8 +113,9 @@ where
     }
 
     async fn start(node: &mut N, config: Self::Config) -> Result<Self, Self::Error> {
-        let (tx, rx) = mpsc::unbounded_channel();
+        let (tx, rx) = mpsc::channel::<HasherWorkerEvent>(config.workers.hasher_queue_size);
         let processor_worker = node.worker::<ProcessorWorker>().unwrap().tx.clone();
+        let requested_messages = node.resource::<RequestedMessages>();
         let metrics = node.resource::<NodeMetrics>();
         let peer_manager = node.resource::<PeerManager>();
 
@@ -64,57 +124,132 @@ where
         let mut cache = HashCache::new(config.workers.message_worker_cache);
 
         node.spawn::<Self, _, _>(|shutdown| async move {
-            let mut receiver = ShutdownStream::new(shutdown, UnboundedReceiverStream::new(rx));
-            let mut pow = score::PoWScorer::new();
-
             info!("Running.");
 
-            while let Some(HasherWorkerEvent {
-                from,
-                message_packet,
-                notifier,
-            }) = receiver.next().await
-            {
-                if !cache.insert(&message_packet.bytes) {
-                    // If the message was already received, we skip it and poll again.
-                    trace!("Message already received.");
-
-                    if let Some(notifier) = notifier {
-                        if let Err(e) =
-                            notifier.send(Err(MessageSubmitterError("message already received".to_string())))
+            let mut receiver =
+                ShutdownStream::new(shutdown, ReceiverStream::new(rx)).ready_chunks(score::PoWBatchScorer::BATCH_SIZE);
+
+            // Bounded by the number of hasher workers so that the hasher queue fills up when they can't keep up.
+            let (tx, rx) = async_channel::bounded::<Vec<HasherWorkerEvent>>(config.workers.message_hasher_workers);
+
+            let mut hasher_workers = Vec::with_capacity(config.workers.message_hasher_workers);
+
+            for _ in 0..config.workers.message_hasher_workers {
+                let rx = rx.clone();
+                let processor_worker = processor_worker.clone();
+                let requested_messages = requested_messages.clone();
+                let metrics = metrics.clone();
+                let peer_manager = peer_manager.clone();
+
+                hasher_workers.push(tokio::spawn(async move {
+                    let mut pow = score::PoWBatchScorer::new();
+
+                    while let Ok(batch) = rx.recv().await {
+                        // Scoring is CPU bound, it is moved off the runtime threads.
+                        let (batch, pow_scores) = match tokio::task::spawn_blocking(move || {
+                            let pow_scores = pow.score(
+                                &batch
+                                    .iter()
+                                    .map(|event| &event.message_packet.bytes)
+                                    .collect::<Vec<_>>(),
+                            );
+
+                            (pow, batch, pow_scores)
+                        })
+                        .await
                         {
-                            error!("failed to send error: {:?}.", e);
+                            Ok((scorer, batch, pow_scores)) => {
+                                pow = scorer;
+                                (batch, pow_scores)
+                            }
+                            Err(e) => {
+                                error!("Scoring a batch of messages failed: {}.", e);
+                                break;
+                            }
+                        };
+
+                        for (
+                            HasherWorkerEvent {
+                                from,
+                                message_packet,
+                                notifier,
+                            },
+                            pow_score,
+                        ) in batch.into_iter().zip(pow_scores)
+                        {
+                            if pow_score < minimum_pow_score {
+                                notify_invalid_message(
+                                    format!("Insufficient pow score: {} < {}.", pow_score, minimum_pow_score),
+                                    &metrics,
+                                    notifier,
+                                );
+                                if let Some(peer_id) = from {
+                                    penalize_peer(&peer_manager, &peer_id, Misbehaviour::InvalidMessage);
+                                }
+                                continue;
+                            }
+
+                            send_to_processor(
+                                &processor_worker,
+                                ProcessorWorkerEvent {
+                                    from,
+                                    message_packet,
+                                    notifier,
+                                },
+                                &requested_messages,
+                                &metrics,
+                            )
+                            .await;
                         }
                     }
+                }));
+            }
+
+            while let Some(events) = receiver.next().await {
+                metrics.hasher_queue_depth_dec(events.len() as u64);
+
+                let mut batch = Vec::with_capacity(events.len());
+
+                for event in events {
+                    if !cache.insert(&event.message_packet.bytes) {
+                        // If the message was already received, we skip it.
+                        trace!("Message already received.");
 
-                    metrics.known_messages_inc();
-                    if let Some(peer_id) = from {
-                        peer_manager
-                            .get_map(&peer_id, |peer| {
-                                peer.0.metrics().known_messages_inc();
-                            })
-                            .unwrap_or_default();
+                        if let Some(notifier) = event.notifier {
+                            if let Err(e) =
+                                notifier.send(Err(MessageSubmitterError("message already received".to_string())))
+                            {
+                                error!("failed to send error: {:?}.", e);
+                            }
+                        }
+
+                        metrics.known_messages_inc();
+                        if let Some(peer_id) = event.from {
+                            peer_manager
+                                .get_map(&peer_id, |peer| {
+                                    peer.0.metrics().known_messages_inc();
+                                })
+                                .unwrap_or_default();
+                        }
+                        continue;
                     }
-                    continue;
-                }
 
-                let pow_score = pow.score(&message_packet.bytes);
+                    batch.push(event);
+                }
 
-                if pow_score < minimum_pow_score {
-                    notify_invalid_message(
-                        format!("Insufficient pow score: {} < {}.", pow_score, minimum_pow_score),
-                        &metrics,
-                        notifier,
-                    );
-                    continue;
+                // Batches are scored by whichever hasher worker is free first, in arrival order.
+                if !batch.is_empty() {
+                    if let Err(e) = tx.send(batch).await {
+                        warn!("Sending batch to a hasher worker failed: {}.", e);
+                    }
                 }
+            }
 
-                if let Err(e) = processor_worker.send(ProcessorWorkerEvent {
-                    from,
-                    message_packet,
-                    notifier,
-                }) {
-                    warn!("Sending event to the processor worker failed: {}.", e);
+            // Let the hasher workers finish the batches they already received.
+            drop(tx);
+            for hasher_worker in hasher_workers {
+                if let Err(e) = hasher_worker.await {
+                    error!("Hasher worker failed: {}.", e);
                 }
             }
 
@@ -124,3 +259,83 @@ where
         Ok(Self { tx })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message_packet(n: u8) -> MessagePacket {
+        MessagePacket::new(vec![n; 100])
+    }
+
+    fn processor_event(n: u8) -> ProcessorWorkerEvent {
+        ProcessorWorkerEvent {
+            from: None,
+            message_packet: message_packet(n),
+            notifier: None,
+        }
+    }
+
+    #[test]
+    fn enqueue_message_drops_when_full() {
+        let (tx, mut rx) = mpsc::channel(1);
+        let metrics = NodeMetrics::new();
+
+        for n in 0..2 {
+            enqueue_message(
+                &tx,
+                HasherWorkerEvent {
+                    from: None,
+                    message_packet: message_packet(n),
+                    notifier: None,
+                },
+                &metrics,
+            );
+        }
+
+        assert_eq!(metrics.dropped_messages(), 1);
+        assert_eq!(metrics.hasher_queue_depth(), 1);
+        assert_eq!(rx.try_recv().unwrap().message_packet.bytes, message_packet(0).bytes);
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn send_to_processor_drops_when_full() {
+        let (tx, mut rx) = mpsc::channel(1);
+        let requested_messages = RequestedMessages::default();
+        let metrics = NodeMetrics::new();
+
+        for n in 0..2 {
+            send_to_processor(&tx, processor_event(n), &requested_messages, &metrics).await;
+        }
+
+        assert_eq!(metrics.dropped_messages(), 1);
+        assert_eq!(metrics.processor_queue_depth(), 1);
+        assert_eq!(rx.try_recv().unwrap().message_packet.bytes, message_packet(0).bytes);
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn send_to_processor_waits_for_requested_when_full() {
+        let (tx, mut rx) = mpsc::channel(1);
+        let requested_messages = RequestedMessages::default();
+        let metrics = NodeMetrics::new();
+
+        requested_messages.insert(
+            MessageId::new(Blake2b256::digest(&message_packet(1).bytes).into()),
+            0.into(),
+            None,
+        );
+
+        send_to_processor(&tx, processor_event(0), &requested_messages, &metrics).await;
+        let (_, first) = tokio::join!(
+            send_to_processor(&tx, processor_event(1), &requested_messages, &metrics),
+            rx.recv()
+        );
+
+        assert_eq!(metrics.dropped_messages(), 0);
+        assert_eq!(metrics.processor_queue_depth(), 2);
+        assert_eq!(first.unwrap().message_packet.bytes, message_packet(0).bytes);
+        assert_eq!(rx.try_recv().unwrap().message_packet.bytes, message_packet(1).bytes);
+    }
+}
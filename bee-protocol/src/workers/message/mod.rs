@@ -1,17 +1,24 @@
 // Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+mod filter;
 mod hash_cache;
 mod hasher;
+mod orphan_detector;
 mod payload;
 mod processor;
 mod submitter;
 mod unreferenced_inserter;
 
-pub use self::submitter::{MessageSubmitterError, MessageSubmitterWorker, MessageSubmitterWorkerEvent};
+pub use self::{
+    filter::{IndexationSizeFilter, IndexationTagFilter, IpNetwork, MessageFilter, MessageFilters, PeerNetworkFilter},
+    orphan_detector::OrphanedMessages,
+    submitter::{MessageSubmitterError, MessageSubmitterWorker, MessageSubmitterWorkerEvent},
+};
 pub(crate) use self::{
     hash_cache::HashCache,
-    hasher::{HasherWorker, HasherWorkerEvent},
+    hasher::{enqueue_message, HasherWorker, HasherWorkerEvent},
+    orphan_detector::OrphanDetectorWorker,
     payload::{
         IndexationPayloadWorker, IndexationPayloadWorkerEvent, MilestonePayloadWorker, PayloadWorker,
         PayloadWorkerEvent, TransactionPayloadWorker,
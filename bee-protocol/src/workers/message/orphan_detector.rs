@@ -0,0 +1,253 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    any::TypeId,
+    collections::{BTreeMap, VecDeque},
+    convert::Infallible,
+};
+
+use async_trait::async_trait;
+use bee_gossip::PeerId;
+use bee_ledger::workers::event::MilestoneConfirmed;
+use bee_message::{milestone::MilestoneIndex, MessageId};
+use bee_runtime::{node::Node, shutdown_stream::ShutdownStream, worker::Worker};
+use bee_tangle::{metadata::MessageMetadata, Tangle, TangleWorker};
+use futures::StreamExt;
+use log::{debug, info, warn};
+use parking_lot::{Mutex, RwLock};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+
+use crate::workers::{event::MessageOrphaned, storage::StorageBackend};
+
+const ORPHANED_MESSAGES_CAPACITY: usize = 1000;
+
+type UnreferencedMessages = BTreeMap<MilestoneIndex, Vec<(MessageId, Option<PeerId>)>>;
+
+/// Keeps track of the unreferenced messages and reports the ones that became orphaned.
+///
+/// Tracking is kept in memory only: messages that arrived before a restart are never reported.
+#[derive(Default)]
+pub struct OrphanedMessages {
+    unreferenced: Mutex<UnreferencedMessages>,
+    orphaned: RwLock<VecDeque<MessageOrphaned>>,
+}
+
+impl OrphanedMessages {
+    pub(crate) fn track(&self, message_id: MessageId, index: MilestoneIndex, peer_id: Option<PeerId>) {
+        self.unreferenced
+            .lock()
+            .entry(index)
+            .or_default()
+            .push((message_id, peer_id));
+    }
+
+    fn untrack_below(&self, index: MilestoneIndex) -> UnreferencedMessages {
+        let mut unreferenced = self.unreferenced.lock();
+        let kept = unreferenced.split_off(&index);
+
+        std::mem::replace(&mut *unreferenced, kept)
+    }
+
+    /// Stops tracking the messages that can no longer be selected as tips now that the given milestone is confirmed,
+    /// and reports the ones that remained unreferenced.
+    fn detect(
+        &self,
+        confirmed_index: MilestoneIndex,
+        below_max_depth: u32,
+        get_metadata: impl Fn(&MessageId) -> Option<MessageMetadata>,
+    ) -> Vec<MessageOrphaned> {
+        // A message is orphaned once the confirmed milestone index went beyond the below max depth of the latest
+        // milestone index at its arrival, since it can no longer be selected as a tip.
+        if *confirmed_index <= below_max_depth {
+            return Vec::new();
+        }
+
+        let mut orphaned_messages = Vec::new();
+
+        for (index, messages) in self.untrack_below(MilestoneIndex(*confirmed_index - below_max_depth)) {
+            for (message_id, peer_id) in messages {
+                let metadata = match get_metadata(&message_id) {
+                    Some(metadata) => metadata,
+                    None => continue,
+                };
+
+                if metadata.flags().is_referenced() {
+                    continue;
+                }
+
+                let orphaned = MessageOrphaned {
+                    message_id,
+                    milestone_index: index,
+                    arrival_timestamp: metadata.arrival_timestamp(),
+                    peer_id,
+                };
+
+                self.report(orphaned.clone());
+                orphaned_messages.push(orphaned);
+            }
+        }
+
+        orphaned_messages
+    }
+
+    fn report(&self, orphaned: MessageOrphaned) {
+        let mut report = self.orphaned.write();
+
+        if report.len() == ORPHANED_MESSAGES_CAPACITY {
+            report.pop_front();
+        }
+        report.push_back(orphaned);
+    }
+
+    /// Returns the most recently orphaned messages, oldest first.
+    pub fn orphaned(&self) -> Vec<MessageOrphaned> {
+        self.orphaned.read().iter().cloned().collect()
+    }
+}
+
+pub(crate) struct OrphanDetectorWorker {}
+
+#[async_trait]
+impl<N: Node> Worker<N> for OrphanDetectorWorker
+where
+    N::Backend: StorageBackend,
+{
+    type Config = ();
+    type Error = Infallible;
+
+    fn dependencies() -> &'static [TypeId] {
+        vec![TypeId::of::<TangleWorker>()].leak()
+    }
+
+    async fn start(node: &mut N, _config: Self::Config) -> Result<Self, Self::Error> {
+        let (tx, rx) = mpsc::unbounded_channel::<MilestoneIndex>();
+
+        node.register_resource(OrphanedMessages::default());
+
+        let orphaned_messages = node.resource::<OrphanedMessages>();
+        let tangle = node.resource::<Tangle<N::Backend>>();
+        let bus = node.bus();
+
+        node.spawn::<Self, _, _>(|shutdown| async move {
+            info!("Running.");
+
+            let mut receiver = ShutdownStream::new(shutdown, UnboundedReceiverStream::new(rx));
+            let below_max_depth = tangle.config().below_max_depth();
+
+            while let Some(index) = receiver.next().await {
+                let orphaned =
+                    orphaned_messages.detect(index, below_max_depth, |message_id| tangle.get_metadata(message_id));
+
+                if !orphaned.is_empty() {
+                    debug!("Declared {} messages orphaned.", orphaned.len());
+                }
+
+                for orphaned in orphaned {
+                    bus.dispatch(orphaned);
+                }
+            }
+
+            info!("Stopped.");
+        });
+
+        node.bus().add_listener::<Self, MilestoneConfirmed, _>(move |event| {
+            if tx.send(event.index).is_err() {
+                warn!("Sending confirmed milestone index to orphan detector failed.");
+            }
+        });
+
+        Ok(Self {})
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata(is_referenced: bool) -> MessageMetadata {
+        let mut metadata = MessageMetadata::arrived();
+
+        if is_referenced {
+            metadata.reference(0);
+        }
+
+        metadata
+    }
+
+    #[test]
+    fn detect_unreferenced_messages_beyond_below_max_depth() {
+        let orphaned_messages = OrphanedMessages::default();
+        let referenced = MessageId::new([1; 32]);
+        let unreferenced = MessageId::new([2; 32]);
+        let unknown = MessageId::new([3; 32]);
+        let recent = MessageId::new([4; 32]);
+
+        orphaned_messages.track(referenced, MilestoneIndex(10), None);
+        orphaned_messages.track(unreferenced, MilestoneIndex(10), None);
+        orphaned_messages.track(unknown, MilestoneIndex(11), None);
+        orphaned_messages.track(recent, MilestoneIndex(20), None);
+
+        let get_metadata = |message_id: &MessageId| match *message_id {
+            id if id == referenced => Some(metadata(true)),
+            id if id == unknown => None,
+            _ => Some(metadata(false)),
+        };
+
+        // Nothing can be orphaned before the confirmed milestone index goes beyond the below max depth.
+        assert!(orphaned_messages
+            .detect(MilestoneIndex(15), 15, get_metadata)
+            .is_empty());
+        assert!(orphaned_messages
+            .detect(MilestoneIndex(25), 15, get_metadata)
+            .is_empty());
+
+        let orphaned = orphaned_messages.detect(MilestoneIndex(30), 15, get_metadata);
+
+        assert_eq!(orphaned.len(), 1);
+        assert_eq!(orphaned[0].message_id, unreferenced);
+        assert_eq!(orphaned[0].milestone_index, MilestoneIndex(10));
+
+        // Messages are only checked once.
+        assert!(orphaned_messages
+            .detect(MilestoneIndex(30), 15, get_metadata)
+            .is_empty());
+
+        let orphaned = orphaned_messages.detect(MilestoneIndex(36), 15, get_metadata);
+
+        assert_eq!(orphaned.len(), 1);
+        assert_eq!(orphaned[0].message_id, recent);
+        assert_eq!(
+            orphaned_messages
+                .orphaned()
+                .iter()
+                .map(|orphaned| orphaned.message_id)
+                .collect::<Vec<_>>(),
+            vec![unreferenced, recent]
+        );
+    }
+
+    #[test]
+    fn expire_oldest_orphaned_messages() {
+        let orphaned_messages = OrphanedMessages::default();
+
+        for i in 0..ORPHANED_MESSAGES_CAPACITY + 2 {
+            orphaned_messages.report(MessageOrphaned {
+                message_id: MessageId::new([0; 32]),
+                milestone_index: MilestoneIndex(i as u32),
+                arrival_timestamp: 0,
+                peer_id: None,
+            });
+        }
+
+        let orphaned = orphaned_messages.orphaned();
+
+        assert_eq!(orphaned.len(), ORPHANED_MESSAGES_CAPACITY);
+        assert_eq!(orphaned[0].milestone_index, MilestoneIndex(2));
+        assert_eq!(
+            orphaned[ORPHANED_MESSAGES_CAPACITY - 1].milestone_index,
+            MilestoneIndex(ORPHANED_MESSAGES_CAPACITY as u32 + 1)
+        );
+    }
+}
@@ -22,9 +22,9 @@ use tokio_stream::wrappers::UnboundedReceiverStream;
 use crate::{
     types::{metrics::NodeMetrics, milestone_key_manager::MilestoneKeyManager},
     workers::{
-        config::ProtocolConfig, heartbeater::broadcast_heartbeat, peer::PeerManager, storage::StorageBackend,
-        MetricsWorker, MilestoneRequesterWorker, MilestoneSolidifierWorker, MilestoneSolidifierWorkerEvent,
-        PeerManagerResWorker, RequestedMilestones,
+        heartbeater::broadcast_heartbeat, peer::PeerManager, requester::peer_selection, storage::StorageBackend,
+        MetricsWorker, MilestoneKeys, MilestoneKeysWorker, MilestoneRequesterWorker, MilestoneSolidifierWorker,
+        MilestoneSolidifierWorkerEvent, PeerManagerResWorker, RequestedMilestones,
     },
 };
 
@@ -76,7 +76,7 @@ fn process<B: StorageBackend>(
     metrics: &NodeMetrics,
     requested_milestones: &RequestedMilestones,
     milestone_solidifier: &mpsc::UnboundedSender<MilestoneSolidifierWorkerEvent>,
-    key_manager: &MilestoneKeyManager,
+    milestone_keys: &MilestoneKeys,
     bus: &Bus<'static>,
 ) {
     if let Some(Payload::Milestone(milestone)) = message.payload() {
@@ -88,7 +88,7 @@ fn process<B: StorageBackend>(
             return;
         }
 
-        match validate(message_id, &message, milestone, key_manager) {
+        match validate(message_id, &message, milestone, &milestone_keys.get()) {
             Ok(milestone) => {
                 tangle.add_milestone(index, milestone.clone());
                 if index > tangle.get_latest_milestone_index() {
@@ -102,7 +102,11 @@ fn process<B: StorageBackend>(
                     debug!("New milestone {} {}.", *index, milestone.message_id());
                 }
 
-                requested_milestones.remove(&index);
+                if let Some(request) = requested_milestones.remove(&index) {
+                    // The sender of the milestone is not known at this point, the milestone is assumed to be the
+                    // answer of the peer it was requested from.
+                    peer_selection::request_fulfilled(peer_manager, &request, request.peer_id);
+                }
 
                 if let Err(e) = milestone_solidifier.send(MilestoneSolidifierWorkerEvent(index)) {
                     error!("Sending solidification event failed: {}.", e);
@@ -123,11 +127,12 @@ impl<N: Node> Worker<N> for MilestonePayloadWorker
 where
     N::Backend: StorageBackend,
 {
-    type Config = ProtocolConfig;
+    type Config = ();
     type Error = Infallible;
 
     fn dependencies() -> &'static [TypeId] {
         vec![
+            TypeId::of::<MilestoneKeysWorker>(),
             TypeId::of::<MilestoneSolidifierWorker>(),
             TypeId::of::<TangleWorker>(),
             TypeId::of::<MilestoneRequesterWorker>(),
@@ -137,16 +142,13 @@ where
         .leak()
     }
 
-    async fn start(node: &mut N, config: Self::Config) -> Result<Self, Self::Error> {
+    async fn start(node: &mut N, _config: Self::Config) -> Result<Self, Self::Error> {
         let milestone_solidifier = node.worker::<MilestoneSolidifierWorker>().unwrap().tx.clone();
         let tangle = node.resource::<Tangle<N::Backend>>();
         let requested_milestones = node.resource::<RequestedMilestones>();
         let peer_manager = node.resource::<PeerManager>();
         let metrics = node.resource::<NodeMetrics>();
-        let key_manager = MilestoneKeyManager::new(
-            config.coordinator.public_key_count,
-            config.coordinator.public_key_ranges.into_boxed_slice(),
-        );
+        let milestone_keys = node.resource::<MilestoneKeys>();
         let bus = node.bus();
         let (tx, rx) = mpsc::unbounded_channel();
 
@@ -164,7 +166,7 @@ where
                     &metrics,
                     &requested_milestones,
                     &milestone_solidifier,
-                    &key_manager,
+                    &milestone_keys,
                     &bus,
                 );
             }
@@ -184,7 +186,7 @@ where
                     &metrics,
                     &requested_milestones,
                     &milestone_solidifier,
-                    &key_manager,
+                    &milestone_keys,
                     &bus,
                 );
                 count += 1;
@@ -198,3 +200,131 @@ where
         Ok(Self { tx })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bee_message::{
+        milestone::MilestoneIndex,
+        parents::Parents,
+        payload::milestone::{MilestonePayloadEssence, MILESTONE_MERKLE_PROOF_LENGTH},
+        MessageBuilder,
+    };
+    use crypto::signatures::ed25519::SecretKey;
+
+    use super::*;
+    use crate::types::milestone_key_range::MilestoneKeyRange;
+
+    // Rotation boundary: the old keys are valid up to this index included, the new keys from 10 milestones before it.
+    const ROTATION: u32 = 100;
+
+    fn generate_key() -> SecretKey {
+        SecretKey::from_bytes(rand::random())
+    }
+
+    fn public_key(key: &SecretKey) -> String {
+        hex::encode(key.public_key().to_bytes())
+    }
+
+    // Builds a milestone message signed by `signers`, like the coordinator would.
+    fn milestone(index: u32, signers: &[&SecretKey]) -> (MessageId, Message, MilestonePayload) {
+        let parents = Parents::new(vec![MessageId::new(rand::random())]).unwrap();
+        let mut signers = signers.to_vec();
+        signers.sort_by_key(|key| key.public_key().to_bytes());
+
+        let essence = MilestonePayloadEssence::new(
+            MilestoneIndex(index),
+            0,
+            parents.clone(),
+            [0; MILESTONE_MERKLE_PROOF_LENGTH],
+            0,
+            0,
+            signers.iter().map(|key| key.public_key().to_bytes()).collect(),
+            None,
+        )
+        .unwrap();
+        let hash = essence.hash();
+        let signatures = signers.iter().map(|key| key.sign(&hash).to_bytes()).collect();
+        let payload = MilestonePayload::new(essence, signatures).unwrap();
+        let message = MessageBuilder::<u64>::new()
+            .with_network_id(0)
+            .with_parents(parents)
+            .with_payload(Payload::Milestone(Box::new(payload.clone())))
+            .with_nonce_provider(0, 0.0)
+            .finish()
+            .unwrap();
+
+        (message.id().0, message, payload)
+    }
+
+    fn is_valid(index: u32, signers: &[&SecretKey], key_manager: &MilestoneKeyManager) -> bool {
+        let (message_id, message, payload) = milestone(index, signers);
+
+        validate(message_id, &message, &payload, key_manager).is_ok()
+    }
+
+    #[test]
+    fn key_rotation() {
+        let old = [generate_key(), generate_key()];
+        let new = [generate_key(), generate_key()];
+        let key_manager = MilestoneKeyManager::try_new(
+            2,
+            vec![
+                MilestoneKeyRange::new(public_key(&old[0]), 0.into(), ROTATION.into()),
+                MilestoneKeyRange::new(public_key(&old[1]), 0.into(), ROTATION.into()),
+                MilestoneKeyRange::new(public_key(&new[0]), (ROTATION - 10).into(), 0.into()),
+                MilestoneKeyRange::new(public_key(&new[1]), (ROTATION - 10).into(), 0.into()),
+            ]
+            .into_boxed_slice(),
+        )
+        .unwrap();
+
+        // Before the overlap, only the old keys are applicable.
+        assert!(is_valid(ROTATION - 11, &[&old[0], &old[1]], &key_manager));
+        assert!(!is_valid(ROTATION - 11, &[&new[0], &new[1]], &key_manager));
+
+        // During the overlap, both sets of keys are applicable.
+        for index in ROTATION - 10..=ROTATION {
+            assert!(is_valid(index, &[&old[0], &old[1]], &key_manager));
+            assert!(is_valid(index, &[&new[0], &new[1]], &key_manager));
+            assert!(is_valid(index, &[&old[0], &new[1]], &key_manager));
+        }
+
+        // After the rotation, only the new keys are applicable.
+        assert!(!is_valid(ROTATION + 1, &[&old[0], &old[1]], &key_manager));
+        assert!(!is_valid(ROTATION + 1, &[&old[0], &new[1]], &key_manager));
+        assert!(is_valid(ROTATION + 1, &[&new[0], &new[1]], &key_manager));
+
+        // Not enough signatures.
+        assert!(!is_valid(ROTATION + 1, &[&new[0]], &key_manager));
+    }
+
+    #[test]
+    fn key_rotation_reload() {
+        let old = [generate_key(), generate_key()];
+        let new = [generate_key(), generate_key()];
+        let milestone_keys = MilestoneKeys::new(MilestoneKeyManager::new(
+            2,
+            vec![
+                MilestoneKeyRange::new(public_key(&old[0]), 0.into(), 0.into()),
+                MilestoneKeyRange::new(public_key(&old[1]), 0.into(), 0.into()),
+            ]
+            .into_boxed_slice(),
+        ));
+
+        assert!(!is_valid(ROTATION + 1, &[&new[0], &new[1]], &milestone_keys.get()));
+
+        // The coordinator announces the rotation, the old key ranges are closed and the new ones added.
+        milestone_keys
+            .reload(vec![
+                MilestoneKeyRange::new(public_key(&old[0]), 0.into(), ROTATION.into()),
+                MilestoneKeyRange::new(public_key(&old[1]), 0.into(), ROTATION.into()),
+                MilestoneKeyRange::new(public_key(&new[0]), (ROTATION + 1).into(), 0.into()),
+                MilestoneKeyRange::new(public_key(&new[1]), (ROTATION + 1).into(), 0.into()),
+            ])
+            .unwrap();
+
+        assert!(is_valid(ROTATION, &[&old[0], &old[1]], &milestone_keys.get()));
+        assert!(!is_valid(ROTATION + 1, &[&old[0], &old[1]], &milestone_keys.get()));
+        assert!(is_valid(ROTATION + 1, &[&new[0], &new[1]], &milestone_keys.get()));
+    }
+}
@@ -13,14 +13,17 @@ use bee_runtime::{node::Node, shutdown_stream::ShutdownStream, worker::Worker};
 use futures::{future::FutureExt, stream::StreamExt};
 use log::{debug, error, info};
 use tokio::sync::mpsc;
-use tokio_stream::wrappers::UnboundedReceiverStream;
+use tokio_stream::wrappers::ReceiverStream;
 
 pub(crate) use self::{
     indexation::{IndexationPayloadWorker, IndexationPayloadWorkerEvent},
     milestone::{MilestonePayloadWorker, MilestonePayloadWorkerEvent},
     transaction::{TransactionPayloadWorker, TransactionPayloadWorkerEvent},
 };
-use crate::workers::storage::StorageBackend;
+use crate::{
+    types::metrics::NodeMetrics,
+    workers::{config::ProtocolWorkersConfig, storage::StorageBackend, MetricsWorker},
+};
 
 pub(crate) struct PayloadWorkerEvent {
     pub(crate) message_id: MessageId,
@@ -28,7 +31,7 @@ pub(crate) struct PayloadWorkerEvent {
 }
 
 pub(crate) struct PayloadWorker {
-    pub(crate) tx: mpsc::UnboundedSender<PayloadWorkerEvent>,
+    pub(crate) tx: mpsc::Sender<PayloadWorkerEvent>,
 }
 
 fn process(
@@ -73,7 +76,7 @@ where
     N: Node,
     N::Backend: StorageBackend,
 {
-    type Config = ();
+    type Config = ProtocolWorkersConfig;
     type Error = Infallible;
 
     fn dependencies() -> &'static [TypeId] {
@@ -81,22 +84,25 @@ where
             TypeId::of::<TransactionPayloadWorker>(),
             TypeId::of::<MilestonePayloadWorker>(),
             TypeId::of::<IndexationPayloadWorker>(),
+            TypeId::of::<MetricsWorker>(),
         ]
         .leak()
     }
 
-    async fn start(node: &mut N, _config: Self::Config) -> Result<Self, Self::Error> {
+    async fn start(node: &mut N, config: Self::Config) -> Result<Self, Self::Error> {
         let transaction_payload_worker = node.worker::<TransactionPayloadWorker>().unwrap().tx.clone();
         let milestone_payload_worker = node.worker::<MilestonePayloadWorker>().unwrap().tx.clone();
         let indexation_payload_worker = node.worker::<IndexationPayloadWorker>().unwrap().tx.clone();
-        let (tx, rx) = mpsc::unbounded_channel();
+        let metrics = node.resource::<NodeMetrics>();
+        let (tx, rx) = mpsc::channel(config.payload_queue_size);
 
         node.spawn::<Self, _, _>(|shutdown| async move {
             info!("Running.");
 
-            let mut receiver = ShutdownStream::new(shutdown, UnboundedReceiverStream::new(rx));
+            let mut receiver = ShutdownStream::new(shutdown, ReceiverStream::new(rx));
 
             while let Some(PayloadWorkerEvent { message_id, message }) = receiver.next().await {
+                metrics.payload_queue_depth_dec(1);
                 process(
                     message_id,
                     message,
@@ -113,6 +119,7 @@ where
             let mut count: usize = 0;
 
             while let Some(Some(PayloadWorkerEvent { message_id, message })) = receiver.next().now_or_never() {
+                metrics.payload_queue_depth_dec(1);
                 process(
                     message_id,
                     message,
@@ -1,7 +1,7 @@
 // Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{any::TypeId, convert::Infallible, time::Instant};
+use std::{any::TypeId, collections::HashSet, convert::Infallible, sync::Arc, time::Instant};
 
 use async_trait::async_trait;
 use bee_common::packable::Packable;
@@ -10,22 +10,30 @@ use bee_message::{Message, MessageId};
 use bee_runtime::{node::Node, shutdown_stream::ShutdownStream, worker::Worker};
 use bee_tangle::{metadata::MessageMetadata, Tangle, TangleWorker};
 use futures::{channel::oneshot::Sender, stream::StreamExt};
+use fxhash::FxBuildHasher;
 use log::{error, info, trace};
+use parking_lot::Mutex;
 use tokio::sync::mpsc;
-use tokio_stream::wrappers::UnboundedReceiverStream;
+use tokio_stream::wrappers::ReceiverStream;
 
 use crate::{
-    types::metrics::NodeMetrics,
+    types::{metrics::NodeMetrics, reputation::Misbehaviour},
     workers::{
+        config::ProtocolConfig,
         event::{MessageProcessed, VertexCreated},
-        message::submitter::{notify_invalid_message, notify_message},
+        message::{
+            submitter::{notify_filtered_message, notify_invalid_message, notify_message},
+            MessageFilters,
+        },
         packets::MessagePacket,
         peer::PeerManager,
-        requester::request_message,
+        reputation::penalize_peer,
+        requester::{peer_selection, request_message},
         storage::StorageBackend,
         BroadcasterWorker, BroadcasterWorkerEvent, MessageRequesterWorker, MessageSubmitterError, MetricsWorker,
-        PayloadWorker, PayloadWorkerEvent, PeerManagerResWorker, PropagatorWorker, PropagatorWorkerEvent,
-        RequestedMessages, UnreferencedMessageInserterWorker, UnreferencedMessageInserterWorkerEvent,
+        MpsWorker, OrphanDetectorWorker, OrphanedMessages, PayloadWorker, PayloadWorkerEvent, PeerManagerResWorker,
+        PropagatorWorker, PropagatorWorkerEvent, RequestedMessages, SpamTracker, UnreferencedMessageInserterWorker,
+        UnreferencedMessageInserterWorkerEvent,
     },
 };
 
@@ -35,8 +43,38 @@ pub(crate) struct ProcessorWorkerEvent {
     pub(crate) notifier: Option<Sender<Result<MessageId, MessageSubmitterError>>>,
 }
 
+/// Identifiers of the messages being processed, so that a message received twice concurrently is only processed once.
+#[derive(Default)]
+struct ProcessingMessages(Mutex<HashSet<MessageId, FxBuildHasher>>);
+
+impl ProcessingMessages {
+    /// Starts processing a message, returns `None` if it is already being processed.
+    fn start(&self, message_id: MessageId) -> Option<ProcessingGuard<'_>> {
+        if self.0.lock().insert(message_id) {
+            Some(ProcessingGuard {
+                processing: self,
+                message_id,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// Stops the processing of a message when dropped.
+struct ProcessingGuard<'a> {
+    processing: &'a ProcessingMessages,
+    message_id: MessageId,
+}
+
+impl Drop for ProcessingGuard<'_> {
+    fn drop(&mut self) {
+        self.processing.0.lock().remove(&self.message_id);
+    }
+}
+
 pub(crate) struct ProcessorWorker {
-    pub(crate) tx: mpsc::UnboundedSender<ProcessorWorkerEvent>,
+    pub(crate) tx: mpsc::Sender<ProcessorWorkerEvent>,
 }
 
 #[async_trait]
@@ -44,7 +82,7 @@ impl<N: Node> Worker<N> for ProcessorWorker
 where
     N::Backend: StorageBackend,
 {
-    type Config = u64;
+    type Config = (u64, ProtocolConfig);
     type Error = Infallible;
 
     fn dependencies() -> &'static [TypeId] {
@@ -54,15 +92,20 @@ where
             TypeId::of::<BroadcasterWorker>(),
             TypeId::of::<MessageRequesterWorker>(),
             TypeId::of::<MetricsWorker>(),
+            TypeId::of::<MpsWorker>(),
             TypeId::of::<PeerManagerResWorker>(),
             TypeId::of::<PayloadWorker>(),
+            TypeId::of::<OrphanDetectorWorker>(),
             TypeId::of::<UnreferencedMessageInserterWorker>(),
         ]
         .leak()
     }
 
     async fn start(node: &mut N, config: Self::Config) -> Result<Self, Self::Error> {
-        let (tx, rx) = mpsc::unbounded_channel();
+        let (network_id, config) = config;
+        let (tx, rx) = mpsc::channel(config.workers.processor_queue_size);
+
+        node.register_resource(MessageFilters::new(&config.filters));
 
         let propagator = node.worker::<PropagatorWorker>().unwrap().tx.clone();
         let broadcaster = node.worker::<BroadcasterWorker>().unwrap().tx.clone();
@@ -72,20 +115,23 @@ where
 
         let tangle = node.resource::<Tangle<N::Backend>>();
         let requested_messages = node.resource::<RequestedMessages>();
+        let orphaned_messages = node.resource::<OrphanedMessages>();
         let metrics = node.resource::<NodeMetrics>();
         let peer_manager = node.resource::<PeerManager>();
+        let message_filters = node.resource::<MessageFilters>();
+        let spam_tracker = node.resource::<SpamTracker>();
         let bus = node.bus();
 
         node.spawn::<Self, _, _>(|shutdown| async move {
             info!("Running.");
 
-            let mut latency_num: u64 = 0;
-            let mut latency_sum: u64 = 0;
-            let mut receiver = ShutdownStream::new(shutdown, UnboundedReceiverStream::new(rx));
+            let processing_messages = Arc::new(ProcessingMessages::default());
+            let mut receiver = ShutdownStream::new(shutdown, ReceiverStream::new(rx));
 
-            let (tx, rx) = async_channel::unbounded();
+            // Bounded by the number of processor workers so that the processor queue fills up when they can't keep up.
+            let (tx, rx) = async_channel::bounded(config.workers.message_processor_workers);
 
-            for _ in 0..16 {
+            for _ in 0..config.workers.message_processor_workers {
                 let rx = rx.clone();
                 let propagator = propagator.clone();
                 let broadcaster = broadcaster.clone();
@@ -94,10 +140,13 @@ where
                 let unreferenced_inserted_worker = unreferenced_inserted_worker.clone();
                 let tangle = tangle.clone();
                 let requested_messages = requested_messages.clone();
+                let orphaned_messages = orphaned_messages.clone();
                 let metrics = metrics.clone();
                 let peer_manager = peer_manager.clone();
+                let message_filters = message_filters.clone();
+                let spam_counter = spam_tracker.counter();
                 let bus = bus.clone();
-                let network_id = config;
+                let processing_messages = processing_messages.clone();
 
                 tokio::spawn(async move {
                     while let Ok(ProcessorWorkerEvent {
@@ -106,12 +155,17 @@ where
                         notifier,
                     }) = rx.recv().await
                     {
+                        metrics.processor_queue_depth_dec(1);
+
                         trace!("Processing received message...");
 
                         let message = match Message::unpack(&mut &message_packet.bytes[..]) {
                             Ok(message) => message,
                             Err(e) => {
                                 notify_invalid_message(format!("Invalid message: {:?}.", e), &metrics, notifier);
+                                if let Some(peer_id) = from {
+                                    penalize_peer(&peer_manager, &peer_id, Misbehaviour::InvalidMessage);
+                                }
                                 continue;
                             }
                         };
@@ -122,33 +176,45 @@ where
                                 &metrics,
                                 notifier,
                             );
+                            if let Some(peer_id) = from {
+                                penalize_peer(&peer_manager, &peer_id, Misbehaviour::InvalidMessage);
+                            }
                             continue;
                         }
 
                         let (message_id, _) = message.id();
 
-                        if tangle.contains(&message_id) {
-                            metrics.known_messages_inc();
-                            if let Some(ref peer_id) = from {
-                                peer_manager
-                                    .get_map(peer_id, |peer| {
-                                        (*peer).0.metrics().known_messages_inc();
-                                    })
-                                    .unwrap_or_default();
+                        // The guard is held until the message is inserted in the tangle, a concurrent copy of the
+                        // message is then either rejected here or found in the tangle.
+                        let _processing = match processing_messages.start(message_id) {
+                            Some(guard) if !tangle.contains(&message_id) => guard,
+                            _ => {
+                                metrics.known_messages_inc();
+                                if let Some(ref peer_id) = from {
+                                    peer_manager
+                                        .get_map(peer_id, |peer| {
+                                            (*peer).0.metrics().known_messages_inc();
+                                        })
+                                        .unwrap_or_default();
+                                }
+                                continue;
+                            }
+                        };
+
+                        // Requested messages are needed for solidification, they are never filtered.
+                        if !message_filters.is_empty() && !requested_messages.contains(&message_id) {
+                            let peer = from.and_then(|peer_id| peer_manager.get_map(&peer_id, |peer| peer.0.clone()));
+
+                            if let Err(e) = message_filters.filter(&message_id, &message, peer.as_deref()) {
+                                // Filtered messages are valid, the peer is not penalized for sending them.
+                                notify_filtered_message(e, &metrics, notifier);
+                                continue;
                             }
-                            continue;
-                        } else {
-                            let metadata = MessageMetadata::arrived();
-                            // There is no data race here even if the `Message` and
-                            // `MessageMetadata` are inserted between the call to `tangle.contains`
-                            // and here because:
-                            // - Both `Message`s are the same because they have the same hash.
-                            // - `MessageMetadata` is not overwritten.
-                            // - Some extra code is executing due to not calling `continue` but
-                            // this does not create inconsistencies.
-                            tangle.insert(&message, &message_id, &metadata);
                         }
 
+                        let metadata = MessageMetadata::arrived();
+                        tangle.insert(&message, &message_id, &metadata);
+
                         // Send the propagation event ASAP to allow the propagator to do its thing
                         if let Err(e) = propagator.send(PropagatorWorkerEvent(message_id)) {
                             error!("Failed to send message id {} to propagator: {:?}.", message_id, e);
@@ -156,10 +222,10 @@ where
 
                         match requested_messages.remove(&message_id) {
                             // Message was requested.
-                            Some((index, instant)) => {
-                                latency_num += 1;
-                                latency_sum += (Instant::now() - instant).as_millis() as u64;
-                                metrics.messages_average_latency_set(latency_sum / latency_num);
+                            Some((index, request)) => {
+                                metrics
+                                    .messages_latency_record((Instant::now() - request.timestamp).as_millis() as u64);
+                                peer_selection::request_fulfilled(&peer_manager, &request, from);
 
                                 for parent in message.parents().iter() {
                                     request_message(&tangle, &message_requester, &*requested_messages, *parent, index)
@@ -174,20 +240,29 @@ where
                                 }) {
                                     error!("Broadcasting message failed: {}.", e);
                                 }
-                                if let Err(e) =
-                                    unreferenced_inserted_worker.send(UnreferencedMessageInserterWorkerEvent(
-                                        message_id,
-                                        tangle.get_latest_milestone_index(),
-                                    ))
+                                let index = tangle.get_latest_milestone_index();
+                                if let Err(e) = unreferenced_inserted_worker
+                                    .send(UnreferencedMessageInserterWorkerEvent(message_id, index))
                                 {
                                     error!("Sending message to unreferenced inserter failed: {}.", e);
                                 }
+                                orphaned_messages.track(message_id, index, from);
                             }
                         };
 
+                        spam_counter.record(&message);
+
                         let parent_message_ids = message.parents().to_vec();
 
-                        if payload_worker.send(PayloadWorkerEvent { message_id, message }).is_err() {
+                        // The message is already stored, waiting for the payload worker back-pressures the processor
+                        // queue rather than losing its payload.
+                        metrics.payload_queue_depth_inc(1);
+                        if payload_worker
+                            .send(PayloadWorkerEvent { message_id, message })
+                            .await
+                            .is_err()
+                        {
+                            metrics.payload_queue_depth_dec(1);
                             error!("Sending message {} to payload worker failed.", message_id);
                         }
 
@@ -221,4 +296,29 @@ where
 
         Ok(Self { tx })
     }
+
+    async fn stop(self, node: &mut N) -> Result<(), Self::Error> {
+        node.remove_resource::<MessageFilters>();
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn processing_messages() {
+        let processing = ProcessingMessages::default();
+        let message_id = MessageId::new([1; 32]);
+
+        let guard = processing.start(message_id);
+        assert!(guard.is_some());
+        assert!(processing.start(message_id).is_none());
+        assert!(processing.start(MessageId::new([2; 32])).is_some());
+
+        drop(guard);
+        assert!(processing.start(message_id).is_some());
+    }
 }
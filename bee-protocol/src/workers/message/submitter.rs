@@ -17,7 +17,10 @@ use tokio_stream::wrappers::UnboundedReceiverStream;
 
 use crate::{
     types::metrics::NodeMetrics,
-    workers::{packets::MessagePacket, storage::StorageBackend, HasherWorker, HasherWorkerEvent},
+    workers::{
+        message::enqueue_message, packets::MessagePacket, storage::StorageBackend, HasherWorker, HasherWorkerEvent,
+        MetricsWorker,
+    },
 };
 
 pub(crate) fn notify_invalid_message(
@@ -35,6 +38,36 @@ pub(crate) fn notify_invalid_message(
     }
 }
 
+pub(crate) fn notify_filtered_message(
+    error: String,
+    metrics: &NodeMetrics,
+    notifier: Option<Sender<Result<MessageId, MessageSubmitterError>>>,
+) {
+    trace!("Filtered message: {}", error);
+    metrics.filtered_messages_inc();
+
+    if let Some(notifier) = notifier {
+        if let Err(e) = notifier.send(Err(MessageSubmitterError(error))) {
+            error!("Failed to send error: {:?}.", e);
+        }
+    }
+}
+
+pub(crate) fn notify_dropped_message(
+    error: String,
+    metrics: &NodeMetrics,
+    notifier: Option<Sender<Result<MessageId, MessageSubmitterError>>>,
+) {
+    trace!("Dropped message: {}", error);
+    metrics.dropped_messages_inc();
+
+    if let Some(notifier) = notifier {
+        if let Err(e) = notifier.send(Err(MessageSubmitterError(error))) {
+            error!("Failed to send error: {:?}.", e);
+        }
+    }
+}
+
 pub(crate) fn notify_message(
     message_id: MessageId,
     notifier: Option<Sender<Result<MessageId, MessageSubmitterError>>>,
@@ -73,13 +106,14 @@ where
     type Error = WorkerError;
 
     fn dependencies() -> &'static [TypeId] {
-        vec![TypeId::of::<HasherWorker>()].leak()
+        vec![TypeId::of::<HasherWorker>(), TypeId::of::<MetricsWorker>()].leak()
     }
 
     async fn start(node: &mut N, _config: Self::Config) -> Result<Self, Self::Error> {
         let (tx, rx) = mpsc::unbounded_channel();
 
         let hasher = node.worker::<HasherWorker>().unwrap().tx.clone();
+        let metrics = node.resource::<NodeMetrics>();
 
         node.spawn::<Self, _, _>(|shutdown| async move {
             info!("Running.");
@@ -92,9 +126,7 @@ where
                     message_packet: MessagePacket::new(message),
                     notifier: Some(notifier),
                 };
-                if let Err(e) = hasher.send(event) {
-                    error!("Sending HasherWorkerEvent failed: {}.", e);
-                }
+                enqueue_message(&hasher, event, &metrics);
             }
 
             info!("Stopped.");
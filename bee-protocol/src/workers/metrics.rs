@@ -55,6 +55,11 @@ impl<N: Node> Worker<N> for MetricsWorker {
 
             while ticker.next().await.is_some() {
                 info!("{:?}", *metrics);
+                info!(
+                    "Compression ratio: {:.2} received, {:.2} sent.",
+                    metrics.compression_ratio_received(),
+                    metrics.compression_ratio_sent()
+                );
             }
 
             info!("Stopped.");
@@ -0,0 +1,196 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    path::Path,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use async_trait::async_trait;
+use bee_runtime::{node::Node, shutdown_stream::ShutdownStream, worker::Worker};
+use futures::StreamExt;
+use log::{info, warn};
+use parking_lot::RwLock;
+use tokio::time::interval;
+use tokio_stream::wrappers::IntervalStream;
+
+use crate::{
+    types::{
+        milestone_key_manager::{MilestoneKeyManager, MilestoneKeyManagerError},
+        milestone_key_range::MilestoneKeyRange,
+    },
+    workers::config::ProtocolCoordinatorConfig,
+};
+
+/// Holds the `MilestoneKeyManager` used to validate milestones, whose key ranges can be reloaded at runtime.
+pub struct MilestoneKeys(RwLock<Arc<MilestoneKeyManager>>);
+
+impl MilestoneKeys {
+    pub(crate) fn new(key_manager: MilestoneKeyManager) -> Self {
+        Self(RwLock::new(Arc::new(key_manager)))
+    }
+
+    /// Returns the current `MilestoneKeyManager`.
+    pub fn get(&self) -> Arc<MilestoneKeyManager> {
+        self.0.read().clone()
+    }
+
+    /// Replaces the key ranges, keeping the minimum threshold, if they are valid.
+    pub fn reload(&self, key_ranges: Vec<MilestoneKeyRange>) -> Result<(), MilestoneKeyManagerError> {
+        let mut guard = self.0.write();
+        let key_manager = MilestoneKeyManager::try_new(guard.min_threshold(), key_ranges.into_boxed_slice())?;
+
+        *guard = Arc::new(key_manager);
+
+        Ok(())
+    }
+}
+
+fn reload_from_file(milestone_keys: &MilestoneKeys, path: &Path) -> Result<(), String> {
+    let bytes = std::fs::read(path).map_err(|e| e.to_string())?;
+    let key_ranges = serde_json::from_slice::<Vec<MilestoneKeyRange>>(&bytes).map_err(|e| e.to_string())?;
+    let count = key_ranges.len();
+
+    milestone_keys.reload(key_ranges).map_err(|e| e.to_string())?;
+
+    info!(
+        "Reloaded {} coordinator public key ranges from {}.",
+        count,
+        path.display()
+    );
+
+    Ok(())
+}
+
+#[derive(Default)]
+pub struct MilestoneKeysWorker {}
+
+#[async_trait]
+impl<N: Node> Worker<N> for MilestoneKeysWorker {
+    type Config = ProtocolCoordinatorConfig;
+    type Error = MilestoneKeyManagerError;
+
+    async fn start(node: &mut N, config: Self::Config) -> Result<Self, Self::Error> {
+        let key_manager =
+            MilestoneKeyManager::try_new(config.public_key_count, config.public_key_ranges.into_boxed_slice())?;
+
+        node.register_resource(MilestoneKeys::new(key_manager));
+
+        if let Some(path) = config.public_key_ranges_file {
+            let milestone_keys = node.resource::<MilestoneKeys>();
+
+            node.spawn::<Self, _, _>(|shutdown| async move {
+                info!("Running.");
+
+                let mut ticker = ShutdownStream::new(
+                    shutdown,
+                    IntervalStream::new(interval(Duration::from_secs(config.public_key_ranges_reload_interval))),
+                );
+                let mut last_modified: Option<SystemTime> = None;
+
+                while ticker.next().await.is_some() {
+                    let modified = match std::fs::metadata(&path).and_then(|metadata| metadata.modified()) {
+                        Ok(modified) => modified,
+                        // The file may not exist yet, the configured key ranges are used in the meantime.
+                        Err(_) => continue,
+                    };
+
+                    if last_modified == Some(modified) {
+                        continue;
+                    }
+                    last_modified = Some(modified);
+
+                    if let Err(e) = reload_from_file(&milestone_keys, &path) {
+                        warn!(
+                            "Reloading coordinator public key ranges from {} failed, keeping the current ones: {}.",
+                            path.display(),
+                            e
+                        );
+                    }
+                }
+
+                info!("Stopped.");
+            });
+        }
+
+        Ok(Self::default())
+    }
+
+    async fn stop(self, node: &mut N) -> Result<(), Self::Error> {
+        node.remove_resource::<MilestoneKeys>();
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(n: u8) -> String {
+        hex::encode([n; 32])
+    }
+
+    #[test]
+    fn reload() {
+        let milestone_keys = MilestoneKeys::new(MilestoneKeyManager::new(
+            1,
+            vec![MilestoneKeyRange::new(key(0), 0.into(), 0.into())].into_boxed_slice(),
+        ));
+        let before = milestone_keys.get();
+
+        milestone_keys
+            .reload(vec![
+                MilestoneKeyRange::new(key(0), 0.into(), 100.into()),
+                MilestoneKeyRange::new(key(1), 100.into(), 0.into()),
+            ])
+            .unwrap();
+
+        // Readers holding the previous key manager are not affected.
+        assert_eq!(before.key_ranges().len(), 1);
+        assert_eq!(milestone_keys.get().key_ranges().len(), 2);
+        assert_eq!(milestone_keys.get().min_threshold(), 1);
+    }
+
+    #[test]
+    fn reload_invalid() {
+        let milestone_keys = MilestoneKeys::new(MilestoneKeyManager::new(
+            1,
+            vec![MilestoneKeyRange::new(key(0), 0.into(), 0.into())].into_boxed_slice(),
+        ));
+
+        // A gap between the two key ranges.
+        assert!(milestone_keys
+            .reload(vec![
+                MilestoneKeyRange::new(key(0), 0.into(), 100.into()),
+                MilestoneKeyRange::new(key(1), 102.into(), 0.into()),
+            ])
+            .is_err());
+        assert_eq!(milestone_keys.get().key_ranges().len(), 1);
+    }
+
+    #[test]
+    fn reload_from_invalid_file() {
+        let milestone_keys = MilestoneKeys::new(MilestoneKeyManager::new(
+            1,
+            vec![MilestoneKeyRange::new(key(0), 0.into(), 0.into())].into_boxed_slice(),
+        ));
+        let path = std::env::temp_dir().join(format!("bee-coordinator-keys-{}.json", std::process::id()));
+
+        std::fs::write(
+            &path,
+            format!(
+                r#"[{{"publicKey":"{}","start":0,"end":100}},{{"publicKey":"{}","start":102,"end":0}}]"#,
+                key(0),
+                key(1)
+            ),
+        )
+        .unwrap();
+        let result = reload_from_file(&milestone_keys, &path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(result.is_err());
+        assert_eq!(milestone_keys.get().key_ranges().len(), 1);
+    }
+}
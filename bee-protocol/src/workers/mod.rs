@@ -10,15 +10,18 @@ mod heartbeater;
 mod index_updater;
 mod message;
 mod metrics;
+mod milestone_keys;
 mod mps;
 mod packets;
 mod peer;
 mod propagator;
+mod reputation;
 mod requester;
 mod responder;
 mod sender;
 mod solidifier;
 mod status;
+mod warp_sync;
 
 use bee_autopeering::event::EventRx as AutopeeringEventRx;
 use bee_gossip::NetworkEventReceiver as NetworkEventRx;
@@ -31,12 +34,12 @@ pub(crate) use self::{
     index_updater::{IndexUpdaterWorker, IndexUpdaterWorkerEvent},
     message::{
         HasherWorker, HasherWorkerEvent, IndexationPayloadWorker, IndexationPayloadWorkerEvent, MilestonePayloadWorker,
-        PayloadWorker, PayloadWorkerEvent, ProcessorWorker, TransactionPayloadWorker,
+        OrphanDetectorWorker, PayloadWorker, PayloadWorkerEvent, ProcessorWorker, TransactionPayloadWorker,
         UnreferencedMessageInserterWorker, UnreferencedMessageInserterWorkerEvent,
     },
-    mps::MpsWorker,
     peer::{PeerManagerWorker, PeerWorker},
     propagator::{PropagatorWorker, PropagatorWorkerEvent},
+    reputation::ReputationWorker,
     requester::{MilestoneRequesterWorker, MilestoneRequesterWorkerEvent},
     responder::{
         MessageResponderWorker, MessageResponderWorkerEvent, MilestoneResponderWorker, MilestoneResponderWorkerEvent,
@@ -45,8 +48,13 @@ pub(crate) use self::{
     status::StatusWorker,
 };
 pub use self::{
-    message::{MessageSubmitterError, MessageSubmitterWorker, MessageSubmitterWorkerEvent},
+    message::{
+        IndexationSizeFilter, IndexationTagFilter, IpNetwork, MessageFilter, MessageFilters, MessageSubmitterError,
+        MessageSubmitterWorker, MessageSubmitterWorkerEvent, OrphanedMessages, PeerNetworkFilter,
+    },
     metrics::MetricsWorker,
+    milestone_keys::{MilestoneKeys, MilestoneKeysWorker},
+    mps::{MpsWorker, SpamTracker},
     peer::{PeerManager, PeerManagerResWorker},
     requester::{request_message, MessageRequesterWorker, RequestedMessages, RequestedMilestones},
 };
@@ -68,25 +76,29 @@ where
             network_rx: network_events,
             peering_rx: autopeering_events,
             network_name: network_id.0,
+            rate_limits: config.reputation.rate_limits.clone(),
         })
         .with_worker_cfg::<HasherWorker>(config.clone())
-        .with_worker_cfg::<ProcessorWorker>(network_id.1)
+        .with_worker_cfg::<ProcessorWorker>((network_id.1, config.clone()))
         .with_worker::<MessageResponderWorker>()
         .with_worker::<MilestoneResponderWorker>()
         .with_worker::<MessageRequesterWorker>()
         .with_worker::<MilestoneRequesterWorker>()
-        .with_worker::<PayloadWorker>()
+        .with_worker_cfg::<PayloadWorker>(config.workers.clone())
         .with_worker::<TransactionPayloadWorker>()
-        .with_worker_cfg::<MilestonePayloadWorker>(config.clone())
+        .with_worker_cfg::<MilestoneKeysWorker>(config.coordinator.clone())
+        .with_worker::<MilestonePayloadWorker>()
         .with_worker::<IndexationPayloadWorker>()
-        .with_worker::<PayloadWorker>()
+        .with_worker_cfg::<PayloadWorker>(config.workers.clone())
         .with_worker::<BroadcasterWorker>()
         .with_worker::<PropagatorWorker>()
         .with_worker::<MpsWorker>()
-        .with_worker_cfg::<MilestoneSolidifierWorker>(config.workers.milestone_sync_count)
+        .with_worker_cfg::<MilestoneSolidifierWorker>(config.workers.clone())
         .with_worker::<IndexUpdaterWorker>()
         .with_worker_cfg::<StatusWorker>(config.workers.status_interval)
         .with_worker::<HeartbeaterWorker>()
         .with_worker::<MessageSubmitterWorker>()
         .with_worker::<UnreferencedMessageInserterWorker>()
+        .with_worker::<OrphanDetectorWorker>()
+        .with_worker_cfg::<ReputationWorker>(config.reputation.clone())
 }
@@ -1,24 +1,83 @@
 // Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{any::TypeId, convert::Infallible, time::Duration};
+use std::{any::TypeId, collections::VecDeque, convert::Infallible, sync::Arc, time::Duration};
 
 use async_trait::async_trait;
+use bee_message::Message;
 use bee_runtime::{node::Node, shutdown_stream::ShutdownStream, worker::Worker};
 use futures::StreamExt;
 use log::info;
+use parking_lot::{Mutex, RwLock};
 use tokio::time::interval;
 use tokio_stream::wrappers::IntervalStream;
 
 use crate::{
-    types::metrics::NodeMetrics,
-    workers::{event::MpsMetricsUpdated, MetricsWorker},
+    types::metrics::{NodeMetrics, SpamCounts, SpamMetrics},
+    workers::{
+        event::{AverageSpamMetricsUpdated, MpsMetricsUpdated, SpamMetricsUpdated},
+        MetricsWorker,
+    },
 };
 
 const MPS_INTERVAL: Duration = Duration::from_secs(1);
+// Number of `MPS_INTERVAL`s the average spam metrics are computed over.
+const SPAM_WINDOW: usize = 60;
+const SPAM_TOP_TAGS: usize = 10;
 
+/// Counts new messages by payload kind and indexation tag, and holds the latest spam metrics.
 #[derive(Default)]
-pub(crate) struct MpsWorker {}
+pub struct SpamTracker {
+    counters: Mutex<Vec<Arc<Mutex<SpamCounts>>>>,
+    metrics: RwLock<(SpamMetrics, SpamMetrics)>,
+}
+
+impl SpamTracker {
+    /// Returns a counter for a single task. The counters are only merged on every metrics update, so that the tasks
+    /// don't contend for a lock on every message.
+    pub(crate) fn counter(&self) -> SpamCounter {
+        let counts = Arc::new(Mutex::new(SpamCounts::default()));
+
+        self.counters.lock().push(counts.clone());
+
+        SpamCounter(counts)
+    }
+
+    fn take_counts(&self) -> SpamCounts {
+        let mut counters = self.counters.lock();
+        let mut total = SpamCounts::default();
+
+        for counts in counters.iter() {
+            total.merge(&std::mem::take(&mut *counts.lock()));
+        }
+        // The counters of the tasks that have finished are not needed anymore.
+        counters.retain(|counts| Arc::strong_count(counts) > 1);
+
+        total
+    }
+
+    /// Returns the spam metrics of the last second.
+    pub fn latest(&self) -> SpamMetrics {
+        self.metrics.read().0.clone()
+    }
+
+    /// Returns the spam metrics averaged over the last minute.
+    pub fn average(&self) -> SpamMetrics {
+        self.metrics.read().1.clone()
+    }
+}
+
+/// Counts the new messages of a single task, see [`SpamTracker::counter`].
+pub(crate) struct SpamCounter(Arc<Mutex<SpamCounts>>);
+
+impl SpamCounter {
+    pub(crate) fn record(&self, message: &Message) {
+        self.0.lock().record(message);
+    }
+}
+
+#[derive(Default)]
+pub struct MpsWorker {}
 
 #[async_trait]
 impl<N: Node> Worker<N> for MpsWorker {
@@ -30,8 +89,11 @@ impl<N: Node> Worker<N> for MpsWorker {
     }
 
     async fn start(node: &mut N, _config: Self::Config) -> Result<Self, Self::Error> {
+        node.register_resource(SpamTracker::default());
+
         let bus = node.bus();
         let metrics = node.resource::<NodeMetrics>();
+        let spam_tracker = node.resource::<SpamTracker>();
 
         node.spawn::<Self, _, _>(|shutdown| async move {
             info!("Running.");
@@ -43,6 +105,7 @@ impl<N: Node> Worker<N> for MpsWorker {
             let mut total_known = 0u64;
             let mut total_invalid = 0u64;
             let mut total_outgoing = 0u64;
+            let mut spam_window = VecDeque::with_capacity(SPAM_WINDOW);
 
             while ticker.next().await.is_some() {
                 let incoming = metrics.messages_received();
@@ -64,6 +127,27 @@ impl<N: Node> Worker<N> for MpsWorker {
                 total_known = known;
                 total_invalid = invalid;
                 total_outgoing = outgoing;
+
+                let counts = spam_tracker.take_counts();
+                let latest = counts.to_metrics(MPS_INTERVAL.as_secs_f64(), SPAM_TOP_TAGS);
+
+                if spam_window.len() == SPAM_WINDOW {
+                    spam_window.pop_front();
+                }
+                spam_window.push_back(counts);
+
+                let average = spam_window
+                    .iter()
+                    .fold(SpamCounts::default(), |mut total, counts| {
+                        total.merge(counts);
+                        total
+                    })
+                    .to_metrics(MPS_INTERVAL.as_secs_f64() * spam_window.len() as f64, SPAM_TOP_TAGS);
+
+                *spam_tracker.metrics.write() = (latest.clone(), average.clone());
+
+                bus.dispatch(SpamMetricsUpdated { metrics: latest });
+                bus.dispatch(AverageSpamMetricsUpdated { metrics: average });
             }
 
             info!("Stopped.");
@@ -71,4 +155,40 @@ impl<N: Node> Worker<N> for MpsWorker {
 
         Ok(Self::default())
     }
+
+    async fn stop(self, node: &mut N) -> Result<(), Self::Error> {
+        node.remove_resource::<SpamTracker>();
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bee_message::{parents::Parents, MessageBuilder, MessageId};
+
+    use super::*;
+
+    #[test]
+    fn merge_task_counters() {
+        let message = MessageBuilder::<u64>::new()
+            .with_network_id(0)
+            .with_parents(Parents::new(vec![MessageId::null()]).unwrap())
+            .with_nonce_provider(0, 0.0)
+            .finish()
+            .unwrap();
+        let tracker = SpamTracker::default();
+        let (first, second) = (tracker.counter(), tracker.counter());
+
+        first.record(&message);
+        second.record(&message);
+        drop(second);
+
+        assert_eq!(tracker.take_counts().to_metrics(1.0, 0).no_payload, 2.0);
+        assert_eq!(tracker.counters.lock().len(), 1);
+
+        first.record(&message);
+
+        assert_eq!(tracker.take_counts().to_metrics(1.0, 0).no_payload, 1.0);
+    }
 }
@@ -0,0 +1,192 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Compressed packet of the protocol.
+
+use std::ops::Range;
+
+use crate::{
+    types::capabilities::compression,
+    workers::packets::{HeaderPacket, Packet},
+};
+
+const COMPRESSION_SIZE: usize = 1;
+const PACKET_TYPE_SIZE: usize = 1;
+const CONSTANT_SIZE: usize = COMPRESSION_SIZE + PACKET_TYPE_SIZE;
+const MAX_SIZE: usize = u16::MAX as usize;
+// Compression level of zstd, favouring speed since packets are compressed on the fly.
+const ZSTD_LEVEL: i32 = 1;
+
+#[derive(Debug)]
+pub(crate) enum CompressionError {
+    UnsupportedCompression(u8),
+    Decompression(std::io::Error),
+    Lz4Decompression(lz4_flex::block::DecompressError),
+    TooLarge(usize),
+}
+
+/// A packet that wraps another packet compressed with an algorithm both peers agreed on during the handshake.
+#[derive(Clone)]
+pub(crate) struct CompressedPacket {
+    /// Compression algorithm, one of the `compression` flags.
+    pub(crate) compression: u8,
+    /// Type of the wrapped packet.
+    pub(crate) packet_type: u8,
+    /// Compressed bytes of the wrapped packet.
+    pub(crate) bytes: Vec<u8>,
+}
+
+impl CompressedPacket {
+    /// Compresses a packet, returns `None` if the algorithm is not supported or if compressing doesn't save space.
+    pub(crate) fn compress<P: Packet>(packet: &P, compression: u8) -> Option<Self> {
+        let mut raw = vec![0u8; packet.size()];
+        packet.to_bytes(&mut raw);
+
+        let bytes = match compression {
+            compression::ZSTD => zstd::block::compress(&raw, ZSTD_LEVEL).ok()?,
+            compression::LZ4 => lz4_flex::block::compress(&raw),
+            _ => return None,
+        };
+
+        if CONSTANT_SIZE + bytes.len() >= raw.len() {
+            return None;
+        }
+
+        Some(Self {
+            compression,
+            packet_type: P::ID,
+            bytes,
+        })
+    }
+
+    /// Decompresses the wrapped packet into its header and bytes.
+    pub(crate) fn decompress(&self) -> Result<(HeaderPacket, Vec<u8>), CompressionError> {
+        let bytes = match self.compression {
+            compression::ZSTD => {
+                zstd::block::decompress(&self.bytes, MAX_SIZE).map_err(CompressionError::Decompression)?
+            }
+            compression::LZ4 => {
+                // The output is bounded by the maximum packet size, which prevents decompression bombs.
+                let mut bytes = vec![0u8; MAX_SIZE];
+                let len = lz4_flex::block::decompress_into(&self.bytes, &mut bytes)
+                    .map_err(CompressionError::Lz4Decompression)?;
+                bytes.truncate(len);
+                bytes
+            }
+            compression => return Err(CompressionError::UnsupportedCompression(compression)),
+        };
+
+        if bytes.len() > MAX_SIZE {
+            return Err(CompressionError::TooLarge(bytes.len()));
+        }
+
+        Ok((
+            HeaderPacket {
+                packet_type: self.packet_type,
+                packet_length: bytes.len() as u16,
+            },
+            bytes,
+        ))
+    }
+}
+
+impl Packet for CompressedPacket {
+    const ID: u8 = 0x06;
+
+    fn size_range() -> Range<usize> {
+        (CONSTANT_SIZE)..(MAX_SIZE + 1)
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        Self {
+            compression: bytes[0],
+            packet_type: bytes[1],
+            bytes: bytes[CONSTANT_SIZE..].to_vec(),
+        }
+    }
+
+    fn size(&self) -> usize {
+        CONSTANT_SIZE + self.bytes.len()
+    }
+
+    fn to_bytes(&self, bytes: &mut [u8]) {
+        bytes[0] = self.compression;
+        bytes[1] = self.packet_type;
+        bytes[CONSTANT_SIZE..].copy_from_slice(&self.bytes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::workers::packets::MessagePacket;
+
+    #[test]
+    fn id() {
+        assert_eq!(CompressedPacket::ID, 6);
+    }
+
+    #[test]
+    fn size_range() {
+        assert!(!CompressedPacket::size_range().contains(&(CONSTANT_SIZE - 1)));
+        assert!(CompressedPacket::size_range().contains(&CONSTANT_SIZE));
+        assert!(CompressedPacket::size_range().contains(&MAX_SIZE));
+        assert!(!CompressedPacket::size_range().contains(&(MAX_SIZE + 1)));
+    }
+
+    #[test]
+    fn compress_decompress() {
+        for compression in [compression::ZSTD, compression::LZ4] {
+            let message = MessagePacket::new(vec![42u8; 1000]);
+            let packet = CompressedPacket::compress(&message, compression).unwrap();
+
+            assert!(packet.size() < message.size());
+
+            let mut bytes = vec![0u8; packet.size()];
+            packet.to_bytes(&mut bytes);
+            let (header, bytes) = CompressedPacket::from_bytes(&bytes).decompress().unwrap();
+
+            assert_eq!(header.packet_type, MessagePacket::ID);
+            assert_eq!(header.packet_length, 1000);
+            assert_eq!(bytes, message.bytes);
+        }
+    }
+
+    #[test]
+    fn incompressible() {
+        let message = MessagePacket::new((0..200).map(|_| rand::random::<u8>()).collect());
+
+        assert!(CompressedPacket::compress(&message, compression::ZSTD).is_none());
+        assert!(CompressedPacket::compress(&message, compression::LZ4).is_none());
+    }
+
+    #[test]
+    fn unsupported_compression() {
+        let message = MessagePacket::new(vec![42u8; 1000]);
+
+        assert!(CompressedPacket::compress(&message, 1 << 7).is_none());
+        assert!(matches!(
+            CompressedPacket {
+                compression: 1 << 7,
+                packet_type: MessagePacket::ID,
+                bytes: vec![0u8; 10],
+            }
+            .decompress(),
+            Err(CompressionError::UnsupportedCompression(0x80))
+        ));
+    }
+
+    #[test]
+    fn invalid_lz4() {
+        assert!(matches!(
+            CompressedPacket {
+                compression: compression::LZ4,
+                packet_type: MessagePacket::ID,
+                bytes: vec![0xff; 10],
+            }
+            .decompress(),
+            Err(CompressionError::Lz4Decompression(_))
+        ));
+    }
+}
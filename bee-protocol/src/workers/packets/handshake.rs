@@ -0,0 +1,131 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Handshake packet of the protocol.
+
+use std::ops::Range;
+
+use crate::{types::capabilities::ProtocolCapabilities, workers::packets::Packet};
+
+const VERSION_SIZE: usize = 1;
+const PACKET_TYPES_SIZE: usize = 4;
+const COMPRESSIONS_SIZE: usize = 1;
+const MAX_PACKET_SIZE_SIZE: usize = 4;
+const CONSTANT_SIZE: usize = VERSION_SIZE + PACKET_TYPES_SIZE + COMPRESSIONS_SIZE + MAX_PACKET_SIZE_SIZE;
+// Later versions of the protocol may append fields, which are ignored.
+const MAX_SIZE: usize = u16::MAX as usize;
+
+/// A packet that advertises the capabilities of a node.
+/// This packet is sent right after a connection with a peer is established, both peers then use the capabilities they
+/// agree on.
+#[derive(Clone)]
+pub(crate) struct HandshakePacket {
+    /// Capabilities of the node.
+    pub(crate) capabilities: ProtocolCapabilities,
+}
+
+impl HandshakePacket {
+    pub(crate) fn new(capabilities: ProtocolCapabilities) -> Self {
+        Self { capabilities }
+    }
+}
+
+impl Packet for HandshakePacket {
+    const ID: u8 = 0x05;
+
+    fn size_range() -> Range<usize> {
+        (CONSTANT_SIZE)..(MAX_SIZE + 1)
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        let (bytes, next) = bytes.split_at(VERSION_SIZE);
+        let version = u8::from_le_bytes(bytes.try_into().expect("Invalid buffer size"));
+
+        let (bytes, next) = next.split_at(PACKET_TYPES_SIZE);
+        let packet_types = u32::from_le_bytes(bytes.try_into().expect("Invalid buffer size"));
+
+        let (bytes, next) = next.split_at(COMPRESSIONS_SIZE);
+        let compressions = u8::from_le_bytes(bytes.try_into().expect("Invalid buffer size"));
+
+        let (bytes, _) = next.split_at(MAX_PACKET_SIZE_SIZE);
+        let max_packet_size = u32::from_le_bytes(bytes.try_into().expect("Invalid buffer size"));
+
+        Self {
+            capabilities: ProtocolCapabilities {
+                version,
+                packet_types,
+                compressions,
+                max_packet_size,
+            },
+        }
+    }
+
+    fn size(&self) -> usize {
+        CONSTANT_SIZE
+    }
+
+    fn to_bytes(&self, bytes: &mut [u8]) {
+        let (bytes, next) = bytes.split_at_mut(VERSION_SIZE);
+        bytes.copy_from_slice(&self.capabilities.version.to_le_bytes());
+        let (bytes, next) = next.split_at_mut(PACKET_TYPES_SIZE);
+        bytes.copy_from_slice(&self.capabilities.packet_types.to_le_bytes());
+        let (bytes, next) = next.split_at_mut(COMPRESSIONS_SIZE);
+        bytes.copy_from_slice(&self.capabilities.compressions.to_le_bytes());
+        let (bytes, _) = next.split_at_mut(MAX_PACKET_SIZE_SIZE);
+        bytes.copy_from_slice(&self.capabilities.max_packet_size.to_le_bytes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const CAPABILITIES: ProtocolCapabilities = ProtocolCapabilities {
+        version: 3,
+        packet_types: 0x0000_013e,
+        compressions: 0b11,
+        max_packet_size: 0x0000_8000,
+    };
+
+    #[test]
+    fn id() {
+        assert_eq!(HandshakePacket::ID, 5);
+    }
+
+    #[test]
+    fn size_range() {
+        assert!(!HandshakePacket::size_range().contains(&(CONSTANT_SIZE - 1)));
+        assert!(HandshakePacket::size_range().contains(&CONSTANT_SIZE));
+        assert!(HandshakePacket::size_range().contains(&(CONSTANT_SIZE + 1)));
+        assert!(HandshakePacket::size_range().contains(&MAX_SIZE));
+        assert!(!HandshakePacket::size_range().contains(&(MAX_SIZE + 1)));
+    }
+
+    #[test]
+    fn size() {
+        let packet = HandshakePacket::new(CAPABILITIES);
+
+        assert_eq!(packet.size(), CONSTANT_SIZE);
+    }
+
+    #[test]
+    fn into_from() {
+        let packet_from = HandshakePacket::new(CAPABILITIES);
+        let mut bytes = vec![0u8; packet_from.size()];
+        packet_from.to_bytes(&mut bytes);
+        let packet_to = HandshakePacket::from_bytes(&bytes);
+
+        assert_eq!(packet_to.capabilities, CAPABILITIES);
+    }
+
+    #[test]
+    fn from_extended() {
+        let packet_from = HandshakePacket::new(CAPABILITIES);
+        let mut bytes = vec![0xffu8; packet_from.size() + 8];
+        packet_from.to_bytes(&mut bytes[..CONSTANT_SIZE]);
+        let packet_to = HandshakePacket::from_bytes(&bytes);
+
+        assert_eq!(packet_to.capabilities, CAPABILITIES);
+    }
+}
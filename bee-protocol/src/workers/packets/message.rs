@@ -24,6 +24,7 @@ impl MessagePacket {
 
 impl Packet for MessagePacket {
     const ID: u8 = 0x02;
+    const COMPRESSIBLE: bool = true;
 
     fn size_range() -> Range<usize> {
         (MESSAGE_LENGTH_MIN)..(MESSAGE_LENGTH_MAX + 1)
@@ -3,6 +3,8 @@
 
 // TODO document
 
+mod compressed;
+mod handshake;
 mod header;
 mod heartbeat;
 mod message;
@@ -13,6 +15,8 @@ mod tlv;
 use std::ops::Range;
 
 pub(crate) use self::{
+    compressed::{CompressedPacket, CompressionError},
+    handshake::HandshakePacket,
     header::{HeaderPacket, HEADER_SIZE},
     heartbeat::HeartbeatPacket,
     message::MessagePacket,
@@ -30,6 +34,9 @@ pub(crate) trait Packet {
     /// The unique identifier of the packet within the protocol.
     const ID: u8;
 
+    /// Whether the packet is worth compressing when both peers support it.
+    const COMPRESSIBLE: bool = false;
+
     /// Returns the size range of the packet as it can be compressed.
     fn size_range() -> Range<usize>;
 
@@ -6,8 +6,8 @@ use std::{any::TypeId, convert::Infallible, sync::Arc};
 use async_trait::async_trait;
 use bee_autopeering::event::{Event as AutopeeringEvent, EventRx as AutopeeringEventRx};
 use bee_gossip::{
-    alias, Command, Event as NetworkEvent, NetworkCommandSender, NetworkEventReceiver as NetworkEventRx, PeerRelation,
-    ServiceHost,
+    alias, Command, Event as NetworkEvent, GossipVersion, NetworkCommandSender, NetworkEventReceiver as NetworkEventRx,
+    PeerRelation, ServiceHost,
 };
 use bee_runtime::{node::Node, shutdown_stream::ShutdownStream, worker::Worker};
 use bee_tangle::{Tangle, TangleWorker};
@@ -16,10 +16,14 @@ use log::{info, trace, warn};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
 use crate::{
-    types::{metrics::NodeMetrics, peer::Peer},
+    types::{capabilities::ProtocolCapabilities, metrics::NodeMetrics, peer::Peer},
     workers::{
+        config::ProtocolRateLimitsConfig,
+        event::{PeerAdded, PeerRemoved},
         heartbeater::{new_heartbeat, send_heartbeat},
+        packets::HandshakePacket,
         peer::PeerManager,
+        sender::Sender,
         storage::StorageBackend,
         HasherWorker, MessageResponderWorker, MetricsWorker, MilestoneRequesterWorker, MilestoneResponderWorker,
         PeerManagerResWorker, PeerWorker, RequestedMilestones,
@@ -30,6 +34,7 @@ pub(crate) struct PeerManagerConfig {
     pub(crate) network_rx: NetworkEventRx,
     pub(crate) peering_rx: Option<AutopeeringEventRx>,
     pub(crate) network_name: String,
+    pub(crate) rate_limits: ProtocolRateLimitsConfig,
 }
 
 pub(crate) struct PeerManagerWorker {}
@@ -73,6 +78,7 @@ where
             network_rx,
             peering_rx,
             network_name,
+            rate_limits,
         } = config;
 
         if let Some(peering_rx) = peering_rx {
@@ -94,6 +100,12 @@ where
                         AutopeeringEvent::PeeringDropped { peer_id } => {
                             handle_peering_dropped(peer_id, &gossip_command_tx);
                         }
+                        AutopeeringEvent::ExternalIpDetected { ip } => {
+                            // Panic: sending commands cannot fail: same explanation as in other sender usages.
+                            gossip_command_tx
+                                .send(Command::AnnounceExternalIp { ip })
+                                .expect("send command to gossip layer");
+                        }
                         _ => {}
                     }
                 }
@@ -103,6 +115,7 @@ where
         }
 
         let gossip_command_tx = node.resource::<NetworkCommandSender>();
+        let bus = node.bus();
 
         node.spawn::<Self, _, _>(|shutdown| async move {
             info!("Network handler running.");
@@ -117,12 +130,21 @@ where
                         // TODO check if not already added ?
                         info!("Added peer {}.", info.alias);
 
+                        bus.dispatch(PeerAdded {
+                            peer_id,
+                            address: info.address.clone(),
+                            alias: info.alias.clone(),
+                            relation: info.relation,
+                        });
+
                         let peer = Arc::new(Peer::new(peer_id, info));
                         peer_manager.add(peer);
                     }
                     NetworkEvent::PeerRemoved { peer_id } => {
                         if let Some(peer) = peer_manager.remove(&peer_id) {
                             info!("Removed peer {}.", peer.0.alias());
+
+                            bus.dispatch(PeerRemoved { peer_id });
                         }
                     }
                     NetworkEvent::PeerConnected {
@@ -130,6 +152,8 @@ where
                         info: _,
                         gossip_in: receiver,
                         gossip_out: sender,
+                        bandwidth,
+                        gossip_version,
                     } => {
                         {
                             let metrics = metrics.clone();
@@ -139,12 +163,14 @@ where
                             let milestone_requester = milestone_requester.clone();
                             let tangle = tangle.clone();
                             let requested_milestones = requested_milestones.clone();
+                            let rate_limits = rate_limits.clone();
 
                             peer_manager
                                 .get_mut_map(&peer_id, move |peer| {
                                     let (shutdown_tx, shutdown_rx) = oneshot::channel();
 
                                     peer.0.set_connected(true);
+                                    peer.0.set_bandwidth(bandwidth);
                                     peer.1 = Some((sender, shutdown_tx));
 
                                     tokio::spawn(
@@ -155,6 +181,7 @@ where
                                             message_responder,
                                             milestone_responder,
                                             milestone_requester,
+                                            &rate_limits,
                                         )
                                         .run(
                                             tangle,
@@ -169,6 +196,16 @@ where
                                 .unwrap_or_default();
                         }
 
+                        // The handshake has to be the first packet sent to the peer, and only peers that negotiated a
+                        // gossip version with handshake expect one.
+                        if gossip_version >= GossipVersion::V1_1 {
+                            Sender::<HandshakePacket>::send(
+                                &HandshakePacket::new(ProtocolCapabilities::local()),
+                                &peer_id,
+                                &*peer_manager,
+                            );
+                        }
+
                         // TODO can't do it in the if because of deadlock, but it's not really right to do it here.
                         send_heartbeat(
                             &new_heartbeat(&*tangle, &*peer_manager),
@@ -188,6 +225,9 @@ where
                             info!("Disconnected peer {}.", peer.0.alias());
                         })
                         .unwrap_or_default(),
+                    NetworkEvent::PeerReconnectUpdated { peer_id, reconnect } => peer_manager
+                        .get_map(&peer_id, |peer| peer.0.set_reconnect(reconnect))
+                        .unwrap_or_default(),
                     NetworkEvent::PeerUnreachable { peer_id, peer_info } => {
                         if peer_info.relation.is_discovered() {
                             // Remove that discovered peer.
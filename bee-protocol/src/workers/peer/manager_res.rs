@@ -159,6 +159,17 @@ impl PeerManager {
         None
     }
 
+    /// Collect the connected peers that satisfy a condition, together with their cost.
+    pub(crate) fn connected_costs<C>(&self, cost: impl Fn(&Peer) -> Option<C>) -> Vec<(PeerId, C)> {
+        self.inner
+            .read()
+            .peers
+            .iter()
+            .filter(|(_, (_, ctx))| ctx.is_some())
+            .filter_map(|(peer_id, (peer, _))| cost(peer.as_ref()).map(|cost| (*peer_id, cost)))
+            .collect()
+    }
+
     pub fn is_connected(&self, id: &PeerId) -> bool {
         self.inner.read().get(id).map_or(false, |p| p.1.is_some())
     }
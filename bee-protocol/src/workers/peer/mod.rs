@@ -4,6 +4,7 @@
 mod manager;
 mod manager_res;
 mod packet_handler;
+mod rate_limiter;
 
 use std::sync::Arc;
 
@@ -18,13 +19,20 @@ use tokio_stream::wrappers::UnboundedReceiverStream;
 pub(crate) use self::manager::{PeerManagerConfig, PeerManagerWorker};
 pub use self::manager_res::{PeerManager, PeerManagerResWorker};
 use crate::{
-    types::{metrics::NodeMetrics, peer::Peer},
+    types::{
+        capabilities::{ProtocolCapabilities, LEGACY_PROTOCOL_VERSION},
+        metrics::NodeMetrics,
+        peer::Peer,
+        reputation::Misbehaviour,
+    },
     workers::{
+        config::ProtocolRateLimitsConfig,
+        message::enqueue_message,
         packets::{
-            tlv_from_bytes, HeaderPacket, HeartbeatPacket, MessagePacket, MessageRequestPacket, MilestoneRequestPacket,
-            Packet, TlvError,
+            tlv_from_bytes, CompressedPacket, CompressionError, HandshakePacket, HeaderPacket, HeartbeatPacket,
+            MessagePacket, MessageRequestPacket, MilestoneRequestPacket, Packet, TlvError, HEADER_SIZE,
         },
-        peer::packet_handler::PacketHandler,
+        peer::{packet_handler::PacketHandler, rate_limiter::PacketRateLimiter},
         requester::request_latest_milestone,
         storage::StorageBackend,
         HasherWorkerEvent, MessageResponderWorkerEvent, MilestoneRequesterWorkerEvent, MilestoneResponderWorkerEvent,
@@ -35,9 +43,19 @@ use crate::{
 #[derive(Debug)]
 pub(crate) enum Error {
     UnsupportedPacketType(u8),
+    IncompatibleProtocolVersion(u8),
+    UnnegotiatedCompression(u8),
+    NestedCompressedPacket,
+    CompressionError(CompressionError),
     TlvError(TlvError),
 }
 
+impl From<CompressionError> for Error {
+    fn from(error: CompressionError) -> Self {
+        Error::CompressionError(error)
+    }
+}
+
 impl From<TlvError> for Error {
     fn from(error: TlvError) -> Self {
         Error::TlvError(error)
@@ -47,20 +65,22 @@ impl From<TlvError> for Error {
 pub struct PeerWorker {
     peer: Arc<Peer>,
     metrics: ResourceHandle<NodeMetrics>,
-    hasher: mpsc::UnboundedSender<HasherWorkerEvent>,
+    hasher: mpsc::Sender<HasherWorkerEvent>,
     message_responder: mpsc::UnboundedSender<MessageResponderWorkerEvent>,
     milestone_responder: mpsc::UnboundedSender<MilestoneResponderWorkerEvent>,
     milestone_requester: mpsc::UnboundedSender<MilestoneRequesterWorkerEvent>,
+    rate_limiter: PacketRateLimiter,
 }
 
 impl PeerWorker {
     pub(crate) fn new(
         peer: Arc<Peer>,
         metrics: ResourceHandle<NodeMetrics>,
-        hasher: mpsc::UnboundedSender<HasherWorkerEvent>,
+        hasher: mpsc::Sender<HasherWorkerEvent>,
         message_responder: mpsc::UnboundedSender<MessageResponderWorkerEvent>,
         milestone_responder: mpsc::UnboundedSender<MilestoneResponderWorkerEvent>,
         milestone_requester: mpsc::UnboundedSender<MilestoneRequesterWorkerEvent>,
+        rate_limits: &ProtocolRateLimitsConfig,
     ) -> Self {
         Self {
             peer,
@@ -69,6 +89,7 @@ impl PeerWorker {
             message_responder,
             milestone_responder,
             milestone_requester,
+            rate_limiter: PacketRateLimiter::new(rate_limits),
         }
     }
 
@@ -98,16 +119,87 @@ impl PeerWorker {
         while let Some((header, bytes)) = packet_handler.fetch_packet().await {
             let tangle = tangle.upgrade().expect("Needed Tangle resource but it was removed");
 
+            self.peer
+                .bandwidth()
+                .add_inbound_packet(header.packet_type, HEADER_SIZE + bytes.len());
+
+            // Compressed packets are unwrapped first so that rate limits apply to the packets they wrap.
+            let decompressed;
+            let (header, bytes) = if header.packet_type == CompressedPacket::ID {
+                match self.decompress_packet(&header, bytes) {
+                    Ok(packet) => {
+                        decompressed = packet;
+                        (decompressed.0.clone(), decompressed.1.as_slice())
+                    }
+                    Err(e) => {
+                        self.invalid_packet(e);
+                        continue;
+                    }
+                }
+            } else {
+                (header, bytes)
+            };
+
+            if !self.rate_limiter.allow(header.packet_type) {
+                trace!(
+                    "[{}] Dropping packet of type {}: rate limit exceeded.",
+                    self.peer.alias(),
+                    header.packet_type
+                );
+                self.peer.reputation().penalize(Misbehaviour::Flooding);
+                continue;
+            }
+
             if let Err(e) = self.process_packet(&tangle, &header, bytes) {
-                error!("[{}] Processing packet failed: {:?}.", self.peer.alias(), e);
-                self.peer.metrics().invalid_packets_inc();
-                self.metrics.invalid_packets_inc();
+                self.packet_failed(e);
             }
         }
 
         info!("[{}] Stopped.", self.peer.alias());
     }
 
+    fn packet_failed(&self, error: Error) {
+        match error {
+            // Packet types introduced by newer protocol versions are skipped without penalizing the peer.
+            Error::UnsupportedPacketType(packet_type) => {
+                trace!(
+                    "[{}] Skipping packet of unknown type {}.",
+                    self.peer.alias(),
+                    packet_type
+                );
+            }
+            error => self.invalid_packet(error),
+        }
+    }
+
+    fn invalid_packet(&self, error: Error) {
+        error!("[{}] Processing packet failed: {:?}.", self.peer.alias(), error);
+        self.peer.metrics().invalid_packets_inc();
+        self.metrics.invalid_packets_inc();
+        self.peer.reputation().penalize(Misbehaviour::InvalidPacket);
+    }
+
+    fn decompress_packet(&self, header: &HeaderPacket, bytes: &[u8]) -> Result<(HeaderPacket, Vec<u8>), Error> {
+        trace!("[{}] Reading CompressedPacket...", self.peer.alias());
+
+        let packet = tlv_from_bytes::<CompressedPacket>(header, bytes)?;
+
+        if !self.peer.capabilities().supports_compression(packet.compression) {
+            return Err(Error::UnnegotiatedCompression(packet.compression));
+        }
+
+        if packet.packet_type == CompressedPacket::ID {
+            return Err(Error::NestedCompressedPacket);
+        }
+
+        let (header, bytes) = packet.decompress()?;
+
+        self.metrics.compressed_bytes_received_inc(packet.size() as u64);
+        self.metrics.decompressed_bytes_received_inc(bytes.len() as u64);
+
+        Ok((header, bytes))
+    }
+
     fn process_packet<B: StorageBackend>(
         &mut self,
         tangle: &Tangle<B>,
@@ -133,11 +225,15 @@ impl PeerWorker {
 
                 let packet = tlv_from_bytes::<MessagePacket>(header, bytes)?;
 
-                let _ = self.hasher.send(HasherWorkerEvent {
-                    from: Some(*self.peer.id()),
-                    message_packet: packet,
-                    notifier: None,
-                });
+                enqueue_message(
+                    &self.hasher,
+                    HasherWorkerEvent {
+                        from: Some(*self.peer.id()),
+                        message_packet: packet,
+                        notifier: None,
+                    },
+                    &self.metrics,
+                );
 
                 self.peer.metrics().messages_received_inc();
                 self.metrics.messages_received_inc();
@@ -185,9 +281,79 @@ impl PeerWorker {
                 self.peer.metrics().heartbeats_received_inc();
                 self.metrics.heartbeats_received_inc();
             }
+            HandshakePacket::ID => {
+                trace!("[{}] Reading HandshakePacket...", self.peer.alias());
+
+                let packet = tlv_from_bytes::<HandshakePacket>(header, bytes)?;
+
+                if packet.capabilities.version < LEGACY_PROTOCOL_VERSION {
+                    return Err(Error::IncompatibleProtocolVersion(packet.capabilities.version));
+                }
+
+                let capabilities = ProtocolCapabilities::local().negotiate(&packet.capabilities);
+
+                debug!(
+                    "[{}] Agreed on protocol version {}, packet types {:#b}, compressions {:#b} and max packet size {}.",
+                    self.peer.alias(),
+                    capabilities.version,
+                    capabilities.packet_types,
+                    capabilities.compressions,
+                    capabilities.max_packet_size
+                );
+
+                self.peer.set_capabilities(&capabilities);
+            }
             _ => return Err(Error::UnsupportedPacketType(header.packet_type)),
         };
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bee_gossip::{PeerId, PeerInfo, PeerRelation};
+
+    use super::*;
+    use crate::workers::config::ProtocolConfigBuilder;
+
+    fn peer_worker() -> PeerWorker {
+        let info = PeerInfo {
+            address: "/ip4/127.0.0.1/tcp/15600".parse().unwrap(),
+            alias: "peer".to_string(),
+            relation: PeerRelation::Known,
+            group: None,
+        };
+        let (hasher, _) = mpsc::channel(1);
+        let (message_responder, _) = mpsc::unbounded_channel();
+        let (milestone_responder, _) = mpsc::unbounded_channel();
+        let (milestone_requester, _) = mpsc::unbounded_channel();
+
+        PeerWorker::new(
+            Arc::new(Peer::new(PeerId::random(), info)),
+            ResourceHandle::new(NodeMetrics::new()),
+            hasher,
+            message_responder,
+            milestone_responder,
+            milestone_requester,
+            &ProtocolConfigBuilder::default().finish().reputation.rate_limits,
+        )
+    }
+
+    #[test]
+    fn unknown_packet_type_is_not_penalized() {
+        let worker = peer_worker();
+
+        worker.packet_failed(Error::UnsupportedPacketType(u8::MAX));
+
+        assert_eq!(worker.peer.reputation().score(), 0);
+        assert_eq!(worker.peer.metrics().invalid_packets(), 0);
+        assert_eq!(worker.metrics.invalid_packets(), 0);
+
+        worker.packet_failed(Error::NestedCompressedPacket);
+
+        assert_eq!(worker.peer.reputation().score(), Misbehaviour::InvalidPacket.penalty());
+        assert_eq!(worker.peer.metrics().invalid_packets(), 1);
+        assert_eq!(worker.metrics.invalid_packets(), 1);
+    }
+}
@@ -0,0 +1,48 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    types::reputation::TokenBucket,
+    workers::{
+        config::{PacketRateLimit, ProtocolRateLimitsConfig},
+        packets::{
+            HandshakePacket, HeartbeatPacket, MessagePacket, MessageRequestPacket, MilestoneRequestPacket, Packet,
+        },
+    },
+};
+
+fn bucket(rate_limit: &PacketRateLimit) -> TokenBucket {
+    TokenBucket::new(rate_limit.rate, rate_limit.burst)
+}
+
+/// Enforces the per packet type rate limits of a single peer.
+pub(super) struct PacketRateLimiter {
+    message: TokenBucket,
+    message_request: TokenBucket,
+    milestone_request: TokenBucket,
+    heartbeat: TokenBucket,
+}
+
+impl PacketRateLimiter {
+    pub(super) fn new(config: &ProtocolRateLimitsConfig) -> Self {
+        Self {
+            message: bucket(&config.message),
+            message_request: bucket(&config.message_request),
+            milestone_request: bucket(&config.milestone_request),
+            heartbeat: bucket(&config.heartbeat),
+        }
+    }
+
+    /// Returns whether a packet of the given type is allowed through. Unknown packet types are left to the packet
+    /// processing to skip.
+    pub(super) fn allow(&mut self, packet_type: u8) -> bool {
+        match packet_type {
+            MessagePacket::ID => self.message.try_take(),
+            MessageRequestPacket::ID => self.message_request.try_take(),
+            MilestoneRequestPacket::ID => self.milestone_request.try_take(),
+            // Handshakes are rare too and share the heartbeat limit.
+            HeartbeatPacket::ID | HandshakePacket::ID => self.heartbeat.try_take(),
+            _ => true,
+        }
+    }
+}
@@ -0,0 +1,198 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    any::TypeId,
+    collections::HashMap,
+    convert::Infallible,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use async_trait::async_trait;
+use bee_gossip::{Ban, Command, NetworkCommandSender, PeerId, ServiceHost};
+use bee_runtime::{node::Node, shutdown_stream::ShutdownStream, worker::Worker};
+use futures::stream::StreamExt;
+use log::{debug, info, warn};
+use tokio::time::interval;
+use tokio_stream::wrappers::IntervalStream;
+
+use crate::{
+    types::{peer::Peer, reputation::Misbehaviour},
+    workers::{config::ProtocolReputationConfig, peer::PeerManager, storage::StorageBackend, PeerManagerResWorker},
+};
+
+// A peer that sent at least this many known messages during a check interval, with less than 1% of new ones, is
+// considered to only send useless duplicates.
+const USELESS_DUPLICATES_MIN: u64 = 100;
+
+/// Penalizes a peer, if it is still known, for a misbehaviour.
+pub(crate) fn penalize_peer(peer_manager: &PeerManager, peer_id: &PeerId, misbehaviour: Misbehaviour) {
+    peer_manager
+        .get_map(peer_id, |peer| {
+            if misbehaviour == Misbehaviour::InvalidMessage {
+                peer.0.metrics().invalid_messages_inc();
+            }
+            peer.0.reputation().penalize(misbehaviour);
+        })
+        .unwrap_or_default();
+}
+
+struct PeerState {
+    new_messages: u64,
+    known_messages: u64,
+    connected_timestamp: u64,
+}
+
+enum Sanction {
+    Disconnect,
+    Ban,
+}
+
+fn check_peer(
+    peer: &Peer,
+    state: &mut PeerState,
+    config: &ProtocolReputationConfig,
+    now_millis: u64,
+) -> Option<Sanction> {
+    let reputation = peer.reputation();
+
+    // Useless duplicates.
+    let new_messages = peer.metrics().new_messages();
+    let known_messages = peer.metrics().known_messages();
+    let new_delta = new_messages.saturating_sub(state.new_messages);
+    let known_delta = known_messages.saturating_sub(state.known_messages);
+
+    state.new_messages = new_messages;
+    state.known_messages = known_messages;
+
+    if known_delta >= USELESS_DUPLICATES_MIN && new_delta * 100 < known_delta {
+        reputation.penalize(Misbehaviour::UselessDuplicates);
+    }
+
+    // Stale heartbeat.
+    // A heartbeat received during a previous connection doesn't count.
+    let last_heartbeat = peer.heartbeat_received_timestamp().max(state.connected_timestamp);
+
+    if now_millis.saturating_sub(last_heartbeat) > config.stale_heartbeat * 1000 {
+        reputation.penalize(Misbehaviour::StaleHeartbeat);
+    }
+
+    let score = reputation.score();
+
+    let sanction = if score >= config.ban_threshold {
+        Some(Sanction::Ban)
+    } else if score >= config.disconnect_threshold {
+        Some(Sanction::Disconnect)
+    } else {
+        None
+    };
+
+    reputation.decay(config.decay);
+
+    sanction
+}
+
+#[derive(Default)]
+pub(crate) struct ReputationWorker {}
+
+#[async_trait]
+impl<N: Node> Worker<N> for ReputationWorker
+where
+    N::Backend: StorageBackend,
+{
+    type Config = ProtocolReputationConfig;
+    type Error = Infallible;
+
+    fn dependencies() -> &'static [TypeId] {
+        vec![TypeId::of::<ServiceHost>(), TypeId::of::<PeerManagerResWorker>()].leak()
+    }
+
+    async fn start(node: &mut N, config: Self::Config) -> Result<Self, Self::Error> {
+        let peer_manager = node.resource::<PeerManager>();
+        let gossip_command_tx = node.resource::<NetworkCommandSender>();
+
+        node.spawn::<Self, _, _>(|shutdown| async move {
+            info!("Running.");
+
+            let mut ticker = ShutdownStream::new(
+                shutdown,
+                IntervalStream::new(interval(Duration::from_secs(config.check_interval))),
+            );
+            let mut states = HashMap::<PeerId, PeerState>::new();
+
+            while ticker.next().await.is_some() {
+                let now_millis = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .expect("Clock may have gone backwards")
+                    .as_millis() as u64;
+                let mut sanctions = Vec::new();
+
+                states.retain(|peer_id, _| peer_manager.is_connected(peer_id));
+
+                for peer in peer_manager.get_all() {
+                    if !peer.is_connected() {
+                        continue;
+                    }
+
+                    let state = states.entry(*peer.id()).or_insert_with(|| PeerState {
+                        new_messages: peer.metrics().new_messages(),
+                        known_messages: peer.metrics().known_messages(),
+                        connected_timestamp: now_millis,
+                    });
+
+                    if let Some(sanction) = check_peer(&peer, state, &config, now_millis) {
+                        sanctions.push((peer, sanction));
+                    } else {
+                        debug!(
+                            "Peer {} has a misbehaviour score of {}.",
+                            peer.alias(),
+                            peer.reputation().score()
+                        );
+                    }
+                }
+
+                for (peer, sanction) in sanctions {
+                    let peer_id = *peer.id();
+
+                    if matches!(sanction, Sanction::Ban) && !peer.is_ban_exempt() {
+                        warn!(
+                            "Banning peer {} with a misbehaviour score of {}.",
+                            peer.alias(),
+                            peer.reputation().score()
+                        );
+
+                        // Panic: sending commands cannot fail due to worker dependencies: because the "Reputation"
+                        // worker depends on the `bee-gossip` "ServiceHost", it is guaranteed that the receiver of
+                        // this channel is not dropped before the sender.
+                        gossip_command_tx
+                            .send(Command::BanPeer {
+                                peer_id,
+                                ban: Ban::new(
+                                    Some(format!("Misbehaviour score of {}.", peer.reputation().score())),
+                                    Some(Duration::from_secs(config.ban_duration)),
+                                ),
+                            })
+                            .expect("send command to gossip layer");
+                        peer.reputation().reset();
+                    } else {
+                        warn!(
+                            "Disconnecting peer {} with a misbehaviour score of {}.",
+                            peer.alias(),
+                            peer.reputation().score()
+                        );
+                    }
+
+                    // Panic: see above.
+                    gossip_command_tx
+                        .send(Command::DisconnectPeer { peer_id })
+                        .expect("send command to gossip layer");
+                    states.remove(&peer_id);
+                }
+            }
+
+            info!("Stopped.");
+        });
+
+        Ok(Self::default())
+    }
+}
@@ -7,11 +7,12 @@ use std::{
     collections::HashMap,
     convert::Infallible,
     sync::Arc,
-    time::{Duration, Instant},
+    time::Instant,
 };
 
 use async_priority_queue::PriorityQueue;
 use async_trait::async_trait;
+use bee_gossip::PeerId;
 use bee_message::{milestone::MilestoneIndex, MessageId};
 use bee_runtime::{node::Node, shutdown_stream::ShutdownStream, worker::Worker};
 use bee_tangle::{Tangle, TangleWorker};
@@ -25,13 +26,15 @@ use tokio_stream::wrappers::IntervalStream;
 use crate::{
     types::metrics::NodeMetrics,
     workers::{
-        packets::MessageRequestPacket, peer::PeerManager, sender::Sender, storage::StorageBackend, MetricsWorker,
-        PeerManagerResWorker,
+        packets::MessageRequestPacket,
+        peer::PeerManager,
+        requester::peer_selection::{self, Request, RETRY_CHECK_INTERVAL},
+        sender::Sender,
+        storage::StorageBackend,
+        MetricsWorker, PeerManagerResWorker,
     },
 };
 
-const RETRY_INTERVAL: Duration = Duration::from_millis(2500);
-
 pub async fn request_message<B: StorageBackend>(
     tangle: &Tangle<B>,
     message_requester: &MessageRequesterWorker,
@@ -48,7 +51,7 @@ pub async fn request_message<B: StorageBackend>(
 }
 
 #[derive(Default)]
-pub struct RequestedMessages(RwLock<HashMap<MessageId, (MilestoneIndex, Instant), FxBuildHasher>>);
+pub struct RequestedMessages(RwLock<HashMap<MessageId, (MilestoneIndex, Request), FxBuildHasher>>);
 
 #[allow(clippy::len_without_is_empty)]
 impl RequestedMessages {
@@ -56,9 +59,8 @@ impl RequestedMessages {
         self.0.read().contains_key(message_id)
     }
 
-    pub(crate) fn insert(&self, message_id: MessageId, index: MilestoneIndex) {
-        let now = Instant::now();
-        self.0.write().insert(message_id, (index, now));
+    pub(crate) fn insert(&self, message_id: MessageId, index: MilestoneIndex, peer_id: Option<PeerId>) {
+        self.0.write().insert(message_id, (index, Request::new(peer_id)));
     }
 
     pub fn len(&self) -> usize {
@@ -69,7 +71,7 @@ impl RequestedMessages {
         self.0.read().is_empty()
     }
 
-    pub(crate) fn remove(&self, message_id: &MessageId) -> Option<(MilestoneIndex, Instant)> {
+    pub(crate) fn remove(&self, message_id: &MessageId) -> Option<(MilestoneIndex, Request)> {
         self.0.write().remove(message_id)
     }
 }
@@ -115,24 +117,22 @@ fn process_request(
         return;
     }
 
-    requested_messages.insert(message_id, index);
+    let peer_id = select_peer(index, None, peer_manager);
+
+    // The request is tracked before being sent, so that a fast response is not mistaken for an unrequested message.
+    requested_messages.insert(message_id, index, peer_id);
 
-    process_request_unchecked(message_id, index, peer_manager, metrics);
+    send_request(message_id, peer_id, peer_manager, metrics);
 }
 
-fn process_request_unchecked(
-    message_id: MessageId,
-    index: MilestoneIndex,
-    peer_manager: &PeerManager,
-    metrics: &NodeMetrics,
-) {
-    let message_request = MessageRequestPacket::new(message_id);
+fn select_peer(index: MilestoneIndex, exclude: Option<PeerId>, peer_manager: &PeerManager) -> Option<PeerId> {
+    peer_selection::select_peer(peer_manager, |peer| peer.has_data(index), exclude)
+        .or_else(|| peer_selection::select_peer(peer_manager, |peer| peer.maybe_has_data(index), exclude))
+}
 
-    if let Some(peer_id) = peer_manager
-        .fair_find(|peer| peer.has_data(index))
-        .or_else(|| peer_manager.fair_find(|peer| peer.maybe_has_data(index)))
-    {
-        Sender::<MessageRequestPacket>::send(&message_request, &peer_id, peer_manager, metrics)
+fn send_request(message_id: MessageId, peer_id: Option<PeerId>, peer_manager: &PeerManager, metrics: &NodeMetrics) {
+    if let Some(peer_id) = peer_id {
+        Sender::<MessageRequestPacket>::send(&MessageRequestPacket::new(message_id), &peer_id, peer_manager, metrics);
     }
 }
 
@@ -151,21 +151,29 @@ fn retry_requests<B: StorageBackend>(
     let mut to_retry = Vec::with_capacity(1024);
 
     // TODO this needs abstraction
-    for (message_id, (index, instant)) in requested_messages.0.read().iter() {
-        if now
-            .checked_duration_since(*instant)
-            .map_or(false, |d| d > RETRY_INTERVAL)
-        {
+    for (message_id, (index, request)) in requested_messages.0.read().iter() {
+        if request.is_due(now) {
             to_retry.push((*message_id, *index));
-            retry_counts += 1;
         }
     }
 
     for (message_id, index) in to_retry {
         if tangle.contains(&message_id) {
-            requested_messages.remove(&message_id);
-        } else {
-            process_request_unchecked(message_id, index, peer_manager, metrics);
+            if let Some((_, request)) = requested_messages.remove(&message_id) {
+                peer_selection::request_fulfilled(peer_manager, &request, None);
+            }
+            continue;
+        }
+
+        let mut guard = requested_messages.0.write();
+
+        // The request may have been fulfilled in the meantime.
+        if let Some((_, request)) = guard.get_mut(&message_id) {
+            peer_selection::request_expired(peer_manager, request);
+            let peer_id = select_peer(index, request.peer_id, peer_manager);
+            request.retried(peer_id);
+            send_request(message_id, peer_id, peer_manager, metrics);
+            retry_counts += 1;
         }
     }
 
@@ -226,7 +234,7 @@ where
         node.spawn::<Self, _, _>(|shutdown| async move {
             info!("Retryer running.");
 
-            let mut ticker = ShutdownStream::new(shutdown, IntervalStream::new(interval(RETRY_INTERVAL)));
+            let mut ticker = ShutdownStream::new(shutdown, IntervalStream::new(interval(RETRY_CHECK_INTERVAL)));
 
             while ticker.next().await.is_some() {
                 retry_requests(&requested_messages, &peer_manager, &metrics, &tangle);
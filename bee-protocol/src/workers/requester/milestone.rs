@@ -1,12 +1,7 @@
 // Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{
-    any::TypeId,
-    collections::HashMap,
-    convert::Infallible,
-    time::{Duration, Instant},
-};
+use std::{any::TypeId, collections::HashMap, convert::Infallible, time::Instant};
 
 use async_trait::async_trait;
 use bee_gossip::PeerId;
@@ -23,13 +18,15 @@ use tokio_stream::wrappers::{IntervalStream, UnboundedReceiverStream};
 use crate::{
     types::metrics::NodeMetrics,
     workers::{
-        packets::MilestoneRequestPacket, peer::PeerManager, sender::Sender, storage::StorageBackend, MetricsWorker,
-        PeerManagerResWorker,
+        packets::MilestoneRequestPacket,
+        peer::PeerManager,
+        requester::peer_selection::{self, Request, RETRY_CHECK_INTERVAL},
+        sender::Sender,
+        storage::StorageBackend,
+        MetricsWorker, PeerManagerResWorker,
     },
 };
 
-const RETRY_INTERVAL: Duration = Duration::from_millis(2500);
-
 pub(crate) fn request_milestone<B: StorageBackend>(
     tangle: &Tangle<B>,
     milestone_requester: &mpsc::UnboundedSender<MilestoneRequesterWorkerEvent>,
@@ -54,7 +51,7 @@ pub(crate) fn request_latest_milestone<B: StorageBackend>(
 }
 
 #[derive(Default)]
-pub struct RequestedMilestones(RwLock<HashMap<MilestoneIndex, Instant, FxBuildHasher>>);
+pub struct RequestedMilestones(RwLock<HashMap<MilestoneIndex, Request, FxBuildHasher>>);
 
 #[allow(clippy::len_without_is_empty)]
 impl RequestedMilestones {
@@ -62,9 +59,8 @@ impl RequestedMilestones {
         self.0.read().contains_key(index)
     }
 
-    pub(crate) fn insert(&self, index: MilestoneIndex) {
-        let now = Instant::now();
-        self.0.write().insert(index, now);
+    pub(crate) fn insert(&self, index: MilestoneIndex, peer_id: Option<PeerId>) {
+        self.0.write().insert(index, Request::new(peer_id));
     }
 
     pub fn len(&self) -> usize {
@@ -75,7 +71,7 @@ impl RequestedMilestones {
         self.0.read().is_empty()
     }
 
-    pub(crate) fn remove(&self, index: &MilestoneIndex) -> Option<Instant> {
+    pub(crate) fn remove(&self, index: &MilestoneIndex) -> Option<Request> {
         self.0.write().remove(index)
     }
 }
@@ -101,30 +97,41 @@ fn process_request(
         return;
     }
 
+    let peer_id = select_peer(index, peer_id, None, peer_manager);
+
+    // The latest milestone request is never tracked, so it isn't accounted as in flight either. Other requests are
+    // tracked before being sent, so that a fast response is not mistaken for an unrequested milestone.
     if index.0 != 0 {
-        requested_milestones.insert(index);
+        requested_milestones.insert(index, peer_id);
     }
 
-    process_request_unchecked(index, peer_id, peer_manager, metrics);
+    send_request(index, peer_id, peer_manager, metrics);
 }
 
-fn process_request_unchecked(
+fn select_peer(
     index: MilestoneIndex,
     peer_id: Option<PeerId>,
+    exclude: Option<PeerId>,
     peer_manager: &PeerManager,
-    metrics: &NodeMetrics,
-) {
-    let milestone_request = MilestoneRequestPacket::new(*index);
-
+) -> Option<PeerId> {
     match peer_id {
         Some(peer_id) => {
-            Sender::<MilestoneRequestPacket>::send(&milestone_request, &peer_id, peer_manager, metrics);
-        }
-        None => {
-            if let Some(peer_id) = peer_manager.fair_find(|peer| peer.maybe_has_data(index)) {
-                Sender::<MilestoneRequestPacket>::send(&milestone_request, &peer_id, peer_manager, metrics);
+            if index.0 != 0 {
+                peer_manager
+                    .get_map(&peer_id, |peer| peer.0.request_sent())
+                    .unwrap_or_default();
             }
+            Some(peer_id)
         }
+        None if index.0 == 0 => peer_manager.fair_find(|peer| peer.maybe_has_data(index)),
+        None => peer_selection::select_peer(peer_manager, |peer| peer.has_data(index), exclude)
+            .or_else(|| peer_selection::select_peer(peer_manager, |peer| peer.maybe_has_data(index), exclude)),
+    }
+}
+
+fn send_request(index: MilestoneIndex, peer_id: Option<PeerId>, peer_manager: &PeerManager, metrics: &NodeMetrics) {
+    if let Some(peer_id) = peer_id {
+        Sender::<MilestoneRequestPacket>::send(&MilestoneRequestPacket::new(*index), &peer_id, peer_manager, metrics);
     }
 }
 
@@ -143,21 +150,29 @@ fn retry_requests<B: StorageBackend>(
     let mut to_retry = Vec::with_capacity(1024);
 
     // TODO this needs abstraction
-    for (index, instant) in requested_milestones.0.read().iter() {
-        if now
-            .checked_duration_since(*instant)
-            .map_or(false, |d| d > RETRY_INTERVAL)
-        {
+    for (index, request) in requested_milestones.0.read().iter() {
+        if request.is_due(now) {
             to_retry.push(*index);
-            retry_counts += 1;
         };
     }
 
     for index in to_retry {
         if tangle.contains_milestone(index) {
-            requested_milestones.remove(&index);
-        } else {
-            process_request_unchecked(index, None, peer_manager, metrics);
+            if let Some(request) = requested_milestones.remove(&index) {
+                peer_selection::request_fulfilled(peer_manager, &request, None);
+            }
+            continue;
+        }
+
+        let mut guard = requested_milestones.0.write();
+
+        // The request may have been fulfilled in the meantime.
+        if let Some(request) = guard.get_mut(&index) {
+            peer_selection::request_expired(peer_manager, request);
+            let peer_id = select_peer(index, None, request.peer_id, peer_manager);
+            request.retried(peer_id);
+            send_request(index, peer_id, peer_manager, metrics);
+            retry_counts += 1;
         }
     }
 
@@ -217,7 +232,7 @@ where
         node.spawn::<Self, _, _>(|shutdown| async move {
             info!("Retryer running.");
 
-            let mut ticker = ShutdownStream::new(shutdown, IntervalStream::new(interval(RETRY_INTERVAL)));
+            let mut ticker = ShutdownStream::new(shutdown, IntervalStream::new(interval(RETRY_CHECK_INTERVAL)));
 
             while ticker.next().await.is_some() {
                 retry_requests(&requested_milestones, &peer_manager, &metrics, &tangle);
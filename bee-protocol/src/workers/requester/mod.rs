@@ -4,6 +4,8 @@
 mod message;
 mod milestone;
 
+pub(crate) mod peer_selection;
+
 pub(crate) use self::milestone::{
     request_latest_milestone, request_milestone, MilestoneRequesterWorker, MilestoneRequesterWorkerEvent,
 };
@@ -0,0 +1,255 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::{Duration, Instant};
+
+use bee_gossip::PeerId;
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{types::peer::Peer, workers::peer::PeerManager};
+
+/// Maximum number of requests that can be waiting for a response from a single peer.
+pub(crate) const MAX_REQUESTS_IN_FLIGHT: u32 = 64;
+/// Interval at which pending requests are checked for retries.
+pub(crate) const RETRY_CHECK_INTERVAL: Duration = Duration::from_millis(250);
+
+// Latency assumed for peers that haven't answered any request yet, so that they still get a chance to be selected.
+const DEFAULT_REQUEST_LATENCY: Duration = Duration::from_millis(500);
+const RETRY_INTERVAL_MIN: Duration = Duration::from_millis(1000);
+const RETRY_INTERVAL_MAX: Duration = Duration::from_millis(16000);
+
+/// State of a pending request.
+pub(crate) struct Request {
+    pub(crate) timestamp: Instant,
+    pub(crate) attempts: u32,
+    pub(crate) peer_id: Option<PeerId>,
+}
+
+impl Request {
+    pub(crate) fn new(peer_id: Option<PeerId>) -> Self {
+        Self {
+            timestamp: Instant::now(),
+            attempts: 0,
+            peer_id,
+        }
+    }
+
+    /// Returns whether the request should be sent again. Requests that couldn't be sent to any peer are retried as
+    /// soon as possible, the other ones with an exponential backoff.
+    pub(crate) fn is_due(&self, now: Instant) -> bool {
+        match self.peer_id {
+            None => true,
+            Some(_) => now
+                .checked_duration_since(self.timestamp)
+                .map_or(false, |d| d > retry_interval(self.attempts)),
+        }
+    }
+
+    /// Marks the request as sent again to another, or to no, peer.
+    pub(crate) fn retried(&mut self, peer_id: Option<PeerId>) {
+        if self.peer_id.is_some() {
+            self.attempts = self.attempts.saturating_add(1);
+        }
+        self.timestamp = Instant::now();
+        self.peer_id = peer_id;
+    }
+}
+
+fn retry_interval(attempts: u32) -> Duration {
+    RETRY_INTERVAL_MIN
+        .checked_mul(1 << attempts.min(5))
+        .map_or(RETRY_INTERVAL_MAX, |interval| interval.min(RETRY_INTERVAL_MAX))
+}
+
+fn cost(peer: &Peer) -> Option<(bool, u64)> {
+    let in_flight = peer.requests_in_flight();
+
+    if in_flight >= MAX_REQUESTS_IN_FLIGHT {
+        return None;
+    }
+
+    // Expected time for the peer to answer, assuming it processes requests one after the other.
+    let latency = peer.request_latency().unwrap_or(DEFAULT_REQUEST_LATENCY).as_micros() as u64;
+
+    // Preferred peers are selected first, as long as they can take more requests.
+    Some((!peer.is_preferred(), latency.saturating_mul(u64::from(in_flight) + 1)))
+}
+
+/// Randomly chooses one of the candidates of the best tier, each with a probability inversely proportional to its cost.
+fn choose_weighted(candidates: &[(PeerId, (bool, u64))], rng: &mut impl Rng) -> Option<PeerId> {
+    let tier = candidates.iter().map(|(_, (tier, _))| *tier).min()?;
+    let candidates = candidates.iter().filter(|(_, (t, _))| *t == tier).collect::<Vec<_>>();
+
+    candidates
+        .choose_weighted(rng, |(_, (_, cost))| 1.0 / (*cost).max(1) as f64)
+        .ok()
+        .map(|(peer_id, _)| *peer_id)
+}
+
+/// Selects a peer among the ones satisfying `has_data`, preferably a preferred one and not `exclude`, and records the
+/// request as sent to it. Peers expected to answer faster are more likely to be selected, but slower ones still get a
+/// share of the requests.
+pub(crate) fn select_peer(
+    peer_manager: &PeerManager,
+    has_data: impl Fn(&Peer) -> bool,
+    exclude: Option<PeerId>,
+) -> Option<PeerId> {
+    let mut rng = rand::thread_rng();
+    let peer_id = choose_weighted(
+        &peer_manager.connected_costs(|peer| {
+            if Some(*peer.id()) != exclude && has_data(peer) {
+                cost(peer)
+            } else {
+                None
+            }
+        }),
+        &mut rng,
+    )
+    .or_else(|| {
+        choose_weighted(
+            &peer_manager.connected_costs(|peer| if has_data(peer) { cost(peer) } else { None }),
+            &mut rng,
+        )
+    })?;
+
+    peer_manager
+        .get_map(&peer_id, |peer| peer.0.request_sent())
+        .unwrap_or_default();
+
+    Some(peer_id)
+}
+
+/// Records that a request has been fulfilled, by the peer it was sent to or not.
+pub(crate) fn request_fulfilled(peer_manager: &PeerManager, request: &Request, from: Option<PeerId>) {
+    if let Some(peer_id) = request.peer_id {
+        peer_manager
+            .get_map(&peer_id, |peer| {
+                if from == Some(peer_id) {
+                    peer.0.request_answered(request.timestamp.elapsed());
+                } else {
+                    peer.0.request_cancelled();
+                }
+            })
+            .unwrap_or_default();
+    }
+}
+
+/// Records that a request has not been answered in time by the peer it was sent to.
+pub(crate) fn request_expired(peer_manager: &PeerManager, request: &Request) {
+    if let Some(peer_id) = request.peer_id {
+        peer_manager
+            .get_map(&peer_id, |peer| peer.0.request_expired(request.timestamp.elapsed()))
+            .unwrap_or_default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bee_gossip::{PeerGroup, PeerInfo, PeerRelation};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    fn peer(preferred: bool, latency: Duration) -> Peer {
+        let peer = Peer::new(
+            PeerId::random(),
+            PeerInfo {
+                address: "/ip4/127.0.0.1/tcp/15600".parse().unwrap(),
+                alias: String::new(),
+                relation: PeerRelation::Known,
+                group: Some(PeerGroup {
+                    name: "group".to_string(),
+                    max_peers: Some(1),
+                    preferred,
+                    ban_exempt: false,
+                }),
+            },
+        );
+        peer.request_sent();
+        peer.request_answered(latency);
+
+        peer
+    }
+
+    #[test]
+    fn preferred_peer_beats_cheaper_peer() {
+        let preferred = peer(true, Duration::from_millis(800));
+        let cheaper = peer(false, Duration::from_millis(10));
+
+        assert!(cost(&preferred).unwrap() < cost(&cheaper).unwrap());
+
+        // Among preferred peers, the cheapest one still wins.
+        let cheaper_preferred = peer(true, Duration::from_millis(10));
+
+        assert!(cost(&cheaper_preferred).unwrap() < cost(&preferred).unwrap());
+    }
+
+    #[test]
+    fn preferred_peer_at_request_limit_is_skipped() {
+        let preferred = peer(true, Duration::from_millis(10));
+
+        for _ in 0..MAX_REQUESTS_IN_FLIGHT {
+            preferred.request_sent();
+        }
+
+        assert_eq!(cost(&preferred), None);
+    }
+
+    #[test]
+    fn preferred_tier_is_always_chosen() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let preferred = PeerId::random();
+        let candidates = [(PeerId::random(), (true, 1)), (preferred, (false, 1_000_000))];
+
+        for _ in 0..100 {
+            assert_eq!(choose_weighted(&candidates, &mut rng), Some(preferred));
+        }
+        assert_eq!(choose_weighted(&[], &mut rng), None);
+    }
+
+    #[test]
+    fn choice_is_weighted_by_inverse_cost() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let (fast, slow) = (PeerId::random(), PeerId::random());
+        let candidates = [(fast, (true, 1_000)), (slow, (true, 3_000))];
+
+        let fast_count = (0..4000)
+            .filter(|_| choose_weighted(&candidates, &mut rng) == Some(fast))
+            .count();
+
+        // The fast peer is expected to be chosen 3 times out of 4, and the slow one is not starved.
+        assert!((2700..3300).contains(&fast_count), "{}", fast_count);
+    }
+
+    #[test]
+    fn retry_interval_backoff() {
+        assert_eq!(retry_interval(0), Duration::from_millis(1000));
+        assert_eq!(retry_interval(1), Duration::from_millis(2000));
+        assert_eq!(retry_interval(3), Duration::from_millis(8000));
+        assert_eq!(retry_interval(4), RETRY_INTERVAL_MAX);
+        assert_eq!(retry_interval(u32::MAX), RETRY_INTERVAL_MAX);
+    }
+
+    #[test]
+    fn request_due() {
+        let mut request = Request::new(None);
+        let now = request.timestamp;
+
+        // Requests that couldn't be sent are always due.
+        assert!(request.is_due(now));
+
+        request.retried(Some(PeerId::random()));
+        let now = request.timestamp;
+
+        assert_eq!(request.attempts, 0);
+        assert!(!request.is_due(now + Duration::from_millis(500)));
+        assert!(request.is_due(now + Duration::from_millis(1500)));
+
+        request.retried(Some(PeerId::random()));
+        let now = request.timestamp;
+
+        assert_eq!(request.attempts, 1);
+        assert!(!request.is_due(now + Duration::from_millis(1500)));
+        assert!(request.is_due(now + Duration::from_millis(2500)));
+    }
+}
@@ -4,12 +4,15 @@
 use std::marker::PhantomData;
 
 use bee_gossip::PeerId;
-use log::warn;
+use log::{trace, warn};
 
 use crate::{
-    types::metrics::NodeMetrics,
+    types::{capabilities::ProtocolCapabilities, metrics::NodeMetrics, peer::Peer},
     workers::{
-        packets::{tlv_to_bytes, HeartbeatPacket, MessagePacket, MessageRequestPacket, MilestoneRequestPacket, Packet},
+        packets::{
+            tlv_to_bytes, CompressedPacket, HandshakePacket, HeartbeatPacket, MessagePacket, MessageRequestPacket,
+            MilestoneRequestPacket, Packet,
+        },
         peer::PeerManager,
     },
 };
@@ -18,6 +21,70 @@ pub(crate) struct Sender<P: Packet> {
     marker: PhantomData<P>,
 }
 
+// Serializes a packet, compressed if it is worth it and the capabilities agreed on with the peer allow it.
+fn packet_to_bytes<P: Packet>(packet: &P, capabilities: &ProtocolCapabilities, metrics: &NodeMetrics) -> Vec<u8> {
+    if P::COMPRESSIBLE && capabilities.supports_packet_type(CompressedPacket::ID) {
+        if let Some(compressed) = capabilities
+            .preferred_compression()
+            .and_then(|compression| CompressedPacket::compress(packet, compression))
+        {
+            metrics.compressed_bytes_sent_inc(compressed.size() as u64);
+            metrics.uncompressed_bytes_sent_inc(packet.size() as u64);
+            return tlv_to_bytes(&compressed);
+        }
+    }
+
+    tlv_to_bytes(packet)
+}
+
+// Sends a packet to a peer if the capabilities agreed on with it allow it, and calls `on_sent` on success.
+fn send_packet<P: Packet>(
+    packet: &P,
+    name: &str,
+    id: &PeerId,
+    peer_manager: &PeerManager,
+    metrics: &NodeMetrics,
+    on_sent: impl FnOnce(&Peer),
+) {
+    peer_manager
+        .get_map(id, |peer| {
+            if let Some(ref sender) = peer.1 {
+                let capabilities = peer.0.capabilities();
+
+                if !capabilities.supports_packet_type(P::ID) {
+                    trace!("Not sending {} to {}: unsupported packet type.", name, id);
+                    return;
+                }
+
+                if packet.size() > capabilities.max_packet_size as usize {
+                    warn!(
+                        "Not sending {} to {}: size {} exceeds {}.",
+                        name,
+                        id,
+                        packet.size(),
+                        capabilities.max_packet_size
+                    );
+                    return;
+                }
+
+                let bytes = packet_to_bytes(packet, &capabilities, metrics);
+                // The first byte is the type of the packet on the wire, which may be a compressed one.
+                let (packet_type, len) = (bytes[0], bytes.len());
+
+                match sender.0.send(bytes) {
+                    Ok(_) => {
+                        peer.0.bandwidth().add_outbound_packet(packet_type, len);
+                        on_sent(&peer.0)
+                    }
+                    Err(e) => {
+                        warn!("Sending {} to {} failed: {:?}.", name, id, e);
+                    }
+                }
+            }
+        })
+        .unwrap_or_default()
+}
+
 impl Sender<MilestoneRequestPacket> {
     pub(crate) fn send(
         packet: &MilestoneRequestPacket,
@@ -25,78 +92,49 @@ impl Sender<MilestoneRequestPacket> {
         peer_manager: &PeerManager,
         metrics: &NodeMetrics,
     ) {
-        peer_manager
-            .get_map(id, |peer| {
-                if let Some(ref sender) = peer.1 {
-                    match sender.0.send(tlv_to_bytes(packet)) {
-                        Ok(_) => {
-                            peer.0.metrics().milestone_requests_sent_inc();
-                            metrics.milestone_requests_sent_inc();
-                        }
-                        Err(e) => {
-                            warn!("Sending MilestoneRequestPacket to {} failed: {:?}.", id, e);
-                        }
-                    }
-                }
-            })
-            .unwrap_or_default()
+        send_packet(packet, "MilestoneRequestPacket", id, peer_manager, metrics, |peer| {
+            peer.metrics().milestone_requests_sent_inc();
+            metrics.milestone_requests_sent_inc();
+        })
     }
 }
 
 impl Sender<MessagePacket> {
     pub(crate) fn send(packet: &MessagePacket, id: &PeerId, peer_manager: &PeerManager, metrics: &NodeMetrics) {
-        peer_manager
-            .get_map(id, |peer| {
-                if let Some(ref sender) = peer.1 {
-                    match sender.0.send(tlv_to_bytes(packet)) {
-                        Ok(_) => {
-                            peer.0.metrics().messages_sent_inc();
-                            metrics.messages_sent_inc();
-                        }
-                        Err(e) => {
-                            warn!("Sending MessagePacket to {} failed: {:?}.", id, e);
-                        }
-                    }
-                }
-            })
-            .unwrap_or_default()
+        send_packet(packet, "MessagePacket", id, peer_manager, metrics, |peer| {
+            peer.metrics().messages_sent_inc();
+            metrics.messages_sent_inc();
+        })
     }
 }
 
 impl Sender<MessageRequestPacket> {
     pub(crate) fn send(packet: &MessageRequestPacket, id: &PeerId, peer_manager: &PeerManager, metrics: &NodeMetrics) {
-        peer_manager
-            .get_map(id, |peer| {
-                if let Some(ref sender) = peer.1 {
-                    match sender.0.send(tlv_to_bytes(packet)) {
-                        Ok(_) => {
-                            peer.0.metrics().message_requests_sent_inc();
-                            metrics.message_requests_sent_inc();
-                        }
-                        Err(e) => {
-                            warn!("Sending MessageRequestPacket to {} failed: {:?}.", id, e);
-                        }
-                    }
-                }
-            })
-            .unwrap_or_default()
+        send_packet(packet, "MessageRequestPacket", id, peer_manager, metrics, |peer| {
+            peer.metrics().message_requests_sent_inc();
+            metrics.message_requests_sent_inc();
+        })
     }
 }
 
 impl Sender<HeartbeatPacket> {
     pub(crate) fn send(packet: &HeartbeatPacket, id: &PeerId, peer_manager: &PeerManager, metrics: &NodeMetrics) {
+        send_packet(packet, "HeartbeatPacket", id, peer_manager, metrics, |peer| {
+            peer.metrics().heartbeats_sent_inc();
+            peer.set_heartbeat_sent_timestamp();
+            metrics.heartbeats_sent_inc();
+        });
+    }
+}
+
+impl Sender<HandshakePacket> {
+    /// Handshakes are sent before any capability is agreed on, so they bypass the capability checks.
+    pub(crate) fn send(packet: &HandshakePacket, id: &PeerId, peer_manager: &PeerManager) {
         peer_manager
             .get_map(id, |peer| {
                 if let Some(ref sender) = peer.1 {
-                    match sender.0.send(tlv_to_bytes(packet)) {
-                        Ok(_) => {
-                            peer.0.metrics().heartbeats_sent_inc();
-                            peer.0.set_heartbeat_sent_timestamp();
-                            metrics.heartbeats_sent_inc();
-                        }
-                        Err(e) => {
-                            warn!("Sending HeartbeatPacket to {} failed: {:?}.", id, e);
-                        }
+                    if let Err(e) = sender.0.send(tlv_to_bytes(packet)) {
+                        warn!("Sending HandshakePacket to {} failed: {:?}.", id, e);
                     }
                 }
             })
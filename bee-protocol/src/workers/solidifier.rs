@@ -19,10 +19,12 @@ use tokio_stream::wrappers::UnboundedReceiverStream;
 use crate::{
     types::metrics::NodeMetrics,
     workers::{
+        config::ProtocolWorkersConfig,
         heartbeater::broadcast_heartbeat,
         peer::PeerManager,
         requester::{request_message, request_milestone},
         storage::StorageBackend,
+        warp_sync::{WarpSync, WarpSyncUpdate},
         IndexUpdaterWorker, IndexUpdaterWorkerEvent, MessageRequesterWorker, MetricsWorker, MilestoneRequesterWorker,
         PeerManagerResWorker, RequestedMessages, RequestedMilestones,
     },
@@ -102,7 +104,7 @@ impl<N: Node> Worker<N> for MilestoneSolidifierWorker
 where
     N::Backend: StorageBackend,
 {
-    type Config = u32;
+    type Config = ProtocolWorkersConfig;
     type Error = Infallible;
 
     fn dependencies() -> &'static [TypeId] {
@@ -130,7 +132,8 @@ where
         let metrics = node.resource::<NodeMetrics>();
         let peer_manager = node.resource::<PeerManager>();
         let bus = node.bus();
-        let milestone_sync_count = config;
+        let milestone_sync_count = config.milestone_sync_count;
+        let mut warp_sync = WarpSync::new(config.warp_sync_window);
 
         node.spawn::<Self, _, _>(|shutdown| async move {
             info!("Running.");
@@ -149,7 +152,54 @@ where
                     next = next + MilestoneIndex(1);
                 }
 
-                if index < next {
+                match warp_sync.update(smi, lmi) {
+                    Some(WarpSyncUpdate::Progressed(progress)) => {
+                        info!(
+                            "Warp syncing milestone {} to {}: {:.2} milestones/s, ETA {}.",
+                            *progress.current_index + 1,
+                            progress.target_index,
+                            progress.milestones_per_second,
+                            progress
+                                .eta_secs
+                                .map_or_else(|| "unknown".to_string(), |eta| format!("{}s", eta))
+                        );
+                        bus.dispatch(progress);
+                    }
+                    Some(WarpSyncUpdate::Completed(completed)) => {
+                        info!(
+                            "Warp synced from milestone {} to {} in {}s.",
+                            completed.start_index, completed.target_index, completed.duration_secs
+                        );
+                        bus.dispatch(completed);
+                    }
+                    None => {}
+                }
+
+                if warp_sync.is_active() {
+                    // Request the whole cones of all the known milestones of the window at once, whatever their
+                    // order, so that they are fetched in parallel from all the peers having them.
+                    let mut cone_index = smi + MilestoneIndex(1);
+
+                    while cone_index <= warp_sync.window_end(smi, lmi) {
+                        if let Some(message_id) = tangle.get_milestone_message_id(cone_index) {
+                            if warp_sync.request_cone(cone_index) {
+                                let missing_len = heavy_solidification(
+                                    &tangle,
+                                    &message_requester,
+                                    &requested_messages,
+                                    cone_index,
+                                    message_id,
+                                )
+                                .await;
+                                debug!(
+                                    "Warp solidification of milestone {} {}: {} messages requested.",
+                                    cone_index, message_id, missing_len
+                                );
+                            }
+                        }
+                        cone_index = cone_index + MilestoneIndex(1);
+                    }
+                } else if index < next {
                     if let Some(message_id) = tangle.get_milestone_message_id(index) {
                         if let Some(message) = tangle.get(&message_id) {
                             debug!(
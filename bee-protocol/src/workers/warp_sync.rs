@@ -0,0 +1,206 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::HashSet,
+    time::{Duration, Instant},
+};
+
+use bee_message::milestone::MilestoneIndex;
+
+use crate::workers::event::{WarpSyncCompleted, WarpSyncProgressed};
+
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// What happened to a warp sync after an update.
+pub(crate) enum WarpSyncUpdate {
+    Progressed(WarpSyncProgressed),
+    Completed(WarpSyncCompleted),
+}
+
+struct WarpSyncState {
+    start_index: MilestoneIndex,
+    started: Instant,
+    last_progress: Instant,
+    last_index: MilestoneIndex,
+    // Milestones of the window whose cones have already been requested.
+    cones_requested: HashSet<MilestoneIndex>,
+}
+
+/// Tracks a warp sync: while the node lags more than `window` milestones behind, the cones of all the milestones of the
+/// window are requested at once, rather than only the parents of each milestone.
+pub(crate) struct WarpSync {
+    window: u32,
+    state: Option<WarpSyncState>,
+}
+
+impl WarpSync {
+    /// Creates a new `WarpSync`, a `window` of 0 disables warp syncing.
+    pub(crate) fn new(window: u32) -> Self {
+        Self { window, state: None }
+    }
+
+    pub(crate) fn is_active(&self) -> bool {
+        self.state.is_some()
+    }
+
+    /// Returns the last milestone index of the window of milestones whose cones are requested in parallel.
+    pub(crate) fn window_end(&self, smi: MilestoneIndex, lmi: MilestoneIndex) -> MilestoneIndex {
+        MilestoneIndex((*smi).saturating_add(self.window).min(*lmi))
+    }
+
+    /// Returns whether the cone of the milestone at `index` still has to be requested, and marks it as requested.
+    pub(crate) fn request_cone(&mut self, index: MilestoneIndex) -> bool {
+        self.state
+            .as_mut()
+            .map_or(false, |state| state.cones_requested.insert(index))
+    }
+
+    /// Starts, advances or completes the warp sync depending on the solid and latest milestone indexes.
+    pub(crate) fn update(&mut self, smi: MilestoneIndex, lmi: MilestoneIndex) -> Option<WarpSyncUpdate> {
+        self.update_at(smi, lmi, Instant::now())
+    }
+
+    fn update_at(&mut self, smi: MilestoneIndex, lmi: MilestoneIndex, now: Instant) -> Option<WarpSyncUpdate> {
+        let state = match self.state.as_mut() {
+            Some(state) => state,
+            None => {
+                if self.window == 0 || *lmi <= (*smi).saturating_add(self.window) {
+                    return None;
+                }
+
+                self.state = Some(WarpSyncState {
+                    start_index: smi,
+                    started: now,
+                    last_progress: now,
+                    last_index: smi,
+                    cones_requested: HashSet::new(),
+                });
+
+                return Some(WarpSyncUpdate::Progressed(WarpSyncProgressed {
+                    start_index: smi,
+                    current_index: smi,
+                    target_index: lmi,
+                    milestones_per_second: 0.0,
+                    eta_secs: None,
+                }));
+            }
+        };
+
+        if smi >= lmi {
+            let completed = WarpSyncCompleted {
+                start_index: state.start_index,
+                target_index: smi,
+                duration_secs: now.saturating_duration_since(state.started).as_secs(),
+            };
+            self.state = None;
+
+            return Some(WarpSyncUpdate::Completed(completed));
+        }
+
+        state.cones_requested.retain(|index| *index > smi);
+
+        if smi == state.last_index || now.saturating_duration_since(state.last_progress) < PROGRESS_INTERVAL {
+            return None;
+        }
+
+        state.last_index = smi;
+        state.last_progress = now;
+
+        let elapsed = now.saturating_duration_since(state.started).as_secs_f64();
+        let milestones_per_second = if elapsed > 0.0 {
+            f64::from(*smi - *state.start_index) / elapsed
+        } else {
+            0.0
+        };
+        let eta_secs = if milestones_per_second > 0.0 {
+            Some((f64::from(*lmi - *smi) / milestones_per_second).ceil() as u64)
+        } else {
+            None
+        };
+
+        Some(WarpSyncUpdate::Progressed(WarpSyncProgressed {
+            start_index: state.start_index,
+            current_index: smi,
+            target_index: lmi,
+            milestones_per_second,
+            eta_secs,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disabled() {
+        let mut warp_sync = WarpSync::new(0);
+
+        assert!(warp_sync.update(MilestoneIndex(0), MilestoneIndex(1000)).is_none());
+        assert!(!warp_sync.is_active());
+    }
+
+    #[test]
+    fn not_lagging_enough() {
+        let mut warp_sync = WarpSync::new(50);
+
+        assert!(warp_sync.update(MilestoneIndex(100), MilestoneIndex(150)).is_none());
+        assert!(!warp_sync.is_active());
+    }
+
+    #[test]
+    fn progress_and_completion() {
+        let mut warp_sync = WarpSync::new(50);
+        let start = Instant::now();
+
+        assert!(matches!(
+            warp_sync.update_at(MilestoneIndex(100), MilestoneIndex(300), start),
+            Some(WarpSyncUpdate::Progressed(WarpSyncProgressed { eta_secs: None, .. }))
+        ));
+        assert!(warp_sync.is_active());
+        assert_eq!(
+            warp_sync.window_end(MilestoneIndex(100), MilestoneIndex(300)),
+            MilestoneIndex(150)
+        );
+
+        assert!(warp_sync.request_cone(MilestoneIndex(101)));
+        assert!(!warp_sync.request_cone(MilestoneIndex(101)));
+
+        // Progress is throttled.
+        assert!(warp_sync
+            .update_at(
+                MilestoneIndex(110),
+                MilestoneIndex(300),
+                start + Duration::from_millis(500)
+            )
+            .is_none());
+
+        match warp_sync.update_at(MilestoneIndex(120), MilestoneIndex(300), start + Duration::from_secs(2)) {
+            Some(WarpSyncUpdate::Progressed(progress)) => {
+                assert_eq!(progress.start_index, MilestoneIndex(100));
+                assert_eq!(progress.current_index, MilestoneIndex(120));
+                assert!((progress.milestones_per_second - 10.0).abs() < f64::EPSILON);
+                assert_eq!(progress.eta_secs, Some(18));
+            }
+            _ => panic!("expected progress"),
+        }
+
+        // Cones of solid milestones are forgotten.
+        assert!(warp_sync.request_cone(MilestoneIndex(101)));
+
+        match warp_sync.update_at(
+            MilestoneIndex(310),
+            MilestoneIndex(310),
+            start + Duration::from_secs(20),
+        ) {
+            Some(WarpSyncUpdate::Completed(completed)) => {
+                assert_eq!(completed.start_index, MilestoneIndex(100));
+                assert_eq!(completed.target_index, MilestoneIndex(310));
+                assert_eq!(completed.duration_secs, 20);
+            }
+            _ => panic!("expected completion"),
+        }
+        assert!(!warp_sync.is_active());
+    }
+}
@@ -20,6 +20,7 @@ use bee_storage::{
 };
 use bee_tangle::{
     metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    urts::TipMetadata,
 };
 
 use crate::{storage::Storage, table::TableBatch};
@@ -44,6 +45,7 @@ pub struct StorageBatch {
     milestone_index_to_unreferenced_message: TableBatch<(MilestoneIndex, UnreferencedMessage), ()>,
     milestone_index_to_receipt: TableBatch<(MilestoneIndex, Receipt), ()>,
     spent_to_treasury_output: TableBatch<(bool, TreasuryOutput), ()>,
+    message_id_to_tip_metadata: TableBatch<MessageId, TipMetadata>,
 }
 
 impl BatchBuilder for Storage {
@@ -79,6 +81,7 @@ impl BatchBuilder for Storage {
         apply_batch!(milestone_index_to_unreferenced_message);
         apply_batch!(milestone_index_to_receipt);
         apply_batch!(spent_to_treasury_output);
+        apply_batch!(message_id_to_tip_metadata);
 
         Ok(())
     }
@@ -128,3 +131,4 @@ impl_batch!(
 );
 impl_batch!((MilestoneIndex, Receipt), (), milestone_index_to_receipt);
 impl_batch!((bool, TreasuryOutput), (), spent_to_treasury_output);
+impl_batch!(MessageId, TipMetadata, message_id_to_tip_metadata);
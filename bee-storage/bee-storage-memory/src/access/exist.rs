@@ -17,6 +17,7 @@ use bee_message::{
 use bee_storage::{access::Exist, backend::StorageBackend};
 use bee_tangle::{
     metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    urts::TipMetadata,
 };
 
 use crate::storage::Storage;
@@ -52,3 +53,4 @@ impl_exist!(
 );
 impl_exist!((MilestoneIndex, Receipt), (), milestone_index_to_receipt);
 impl_exist!((bool, TreasuryOutput), (), spent_to_treasury_output);
+impl_exist!(MessageId, TipMetadata, message_id_to_tip_metadata);
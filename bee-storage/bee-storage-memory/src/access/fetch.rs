@@ -17,6 +17,7 @@ use bee_message::{
 use bee_storage::{access::Fetch, backend::StorageBackend, system::System};
 use bee_tangle::{
     metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    urts::TipMetadata,
 };
 
 use crate::storage::Storage;
@@ -52,3 +53,4 @@ impl_fetch!(
 );
 impl_fetch!(MilestoneIndex, Vec<Receipt>, milestone_index_to_receipt);
 impl_fetch!(bool, Vec<TreasuryOutput>, spent_to_treasury_output);
+impl_fetch!(MessageId, TipMetadata, message_id_to_tip_metadata);
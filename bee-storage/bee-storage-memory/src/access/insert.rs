@@ -21,6 +21,7 @@ use bee_storage::{
 };
 use bee_tangle::{
     metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    urts::TipMetadata,
 };
 
 use crate::storage::Storage;
@@ -58,6 +59,7 @@ impl_insert!(
 );
 impl_insert!((MilestoneIndex, Receipt), (), milestone_index_to_receipt);
 impl_insert!((bool, TreasuryOutput), (), spent_to_treasury_output);
+impl_insert!(MessageId, TipMetadata, message_id_to_tip_metadata);
 
 impl InsertStrict<MessageId, MessageMetadata> for Storage {
     fn insert_strict(&self, k: &MessageId, v: &MessageMetadata) -> Result<(), <Self as StorageBackend>::Error> {
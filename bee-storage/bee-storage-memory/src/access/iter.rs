@@ -17,6 +17,7 @@ use bee_message::{
 use bee_storage::{access::AsIterator, backend::StorageBackend, system::System};
 use bee_tangle::{
     metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    urts::TipMetadata,
 };
 
 use crate::{
@@ -77,3 +78,4 @@ impl_iter!(
 );
 impl_iter!((MilestoneIndex, Receipt), (), milestone_index_to_receipt);
 impl_iter!((bool, TreasuryOutput), (), spent_to_treasury_output);
+impl_iter!(MessageId, TipMetadata, message_id_to_tip_metadata);
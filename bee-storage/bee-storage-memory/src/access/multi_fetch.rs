@@ -13,7 +13,7 @@ use bee_message::{
     Message, MessageId,
 };
 use bee_storage::{access::MultiFetch, backend::StorageBackend, system::System};
-use bee_tangle::{metadata::MessageMetadata, solid_entry_point::SolidEntryPoint};
+use bee_tangle::{metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, urts::TipMetadata};
 
 use crate::storage::Storage;
 
@@ -41,3 +41,4 @@ impl_multi_fetch!(MilestoneIndex, Milestone, milestone_index_to_milestone);
 impl_multi_fetch!(SolidEntryPoint, MilestoneIndex, solid_entry_point_to_milestone_index);
 impl_multi_fetch!(MilestoneIndex, OutputDiff, milestone_index_to_output_diff);
 impl_multi_fetch!(Address, Balance, address_to_balance);
+impl_multi_fetch!(MessageId, TipMetadata, message_id_to_tip_metadata);
@@ -17,6 +17,7 @@ use bee_message::{
 use bee_storage::{access::Truncate, backend::StorageBackend};
 use bee_tangle::{
     metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    urts::TipMetadata,
 };
 
 use crate::storage::Storage;
@@ -54,3 +55,4 @@ impl_truncate!(
 );
 impl_truncate!((MilestoneIndex, Receipt), (), milestone_index_to_receipt);
 impl_truncate!((bool, TreasuryOutput), (), spent_to_treasury_output);
+impl_truncate!(MessageId, TipMetadata, message_id_to_tip_metadata);
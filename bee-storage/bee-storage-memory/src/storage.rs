@@ -22,6 +22,7 @@ use bee_storage::{
 };
 use bee_tangle::{
     metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    urts::TipMetadata,
 };
 use thiserror::Error;
 
@@ -75,6 +76,7 @@ pub(crate) struct InnerStorage {
     pub(crate) milestone_index_to_unreferenced_message: VecTable<MilestoneIndex, UnreferencedMessage>,
     pub(crate) milestone_index_to_receipt: VecTable<MilestoneIndex, Receipt>,
     pub(crate) spent_to_treasury_output: VecTable<bool, TreasuryOutput>,
+    pub(crate) message_id_to_tip_metadata: Table<MessageId, TipMetadata>,
 }
 
 impl Storage {
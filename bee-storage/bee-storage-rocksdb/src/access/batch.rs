@@ -16,6 +16,7 @@ use bee_message::{
 use bee_storage::access::{Batch, BatchBuilder};
 use bee_tangle::{
     metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    urts::TipMetadata,
 };
 use rocksdb::{WriteBatch, WriteOptions};
 
@@ -625,3 +626,32 @@ impl Batch<(bool, TreasuryOutput), ()> for Storage {
         Ok(())
     }
 }
+
+impl Batch<MessageId, TipMetadata> for Storage {
+    fn batch_insert(
+        &self,
+        batch: &mut Self::Batch,
+        message_id: &MessageId,
+        metadata: &TipMetadata,
+    ) -> Result<(), Self::Error> {
+        batch.value_buf.clear();
+        // Packing to bytes can't fail.
+        metadata.pack(&mut batch.value_buf).unwrap();
+
+        batch.inner.put_cf(
+            self.cf_handle(CF_MESSAGE_ID_TO_TIP_METADATA)?,
+            message_id,
+            &batch.value_buf,
+        );
+
+        Ok(())
+    }
+
+    fn batch_delete(&self, batch: &mut Self::Batch, message_id: &MessageId) -> Result<(), Self::Error> {
+        batch
+            .inner
+            .delete_cf(self.cf_handle(CF_MESSAGE_ID_TO_TIP_METADATA)?, message_id);
+
+        Ok(())
+    }
+}
@@ -16,6 +16,7 @@ use bee_message::{
 use bee_storage::access::Delete;
 use bee_tangle::{
     metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    urts::TipMetadata,
 };
 
 use crate::{
@@ -197,3 +198,12 @@ impl Delete<(bool, TreasuryOutput), ()> for Storage {
         Ok(())
     }
 }
+
+impl Delete<MessageId, TipMetadata> for Storage {
+    fn delete(&self, message_id: &MessageId) -> Result<(), <Self as StorageBackend>::Error> {
+        self.inner
+            .delete_cf(self.cf_handle(CF_MESSAGE_ID_TO_TIP_METADATA)?, message_id)?;
+
+        Ok(())
+    }
+}
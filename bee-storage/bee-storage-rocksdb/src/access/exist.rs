@@ -16,6 +16,7 @@ use bee_message::{
 use bee_storage::access::Exist;
 use bee_tangle::{
     metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    urts::TipMetadata,
 };
 
 use crate::{
@@ -205,3 +206,12 @@ impl Exist<(bool, TreasuryOutput), ()> for Storage {
             .is_some())
     }
 }
+
+impl Exist<MessageId, TipMetadata> for Storage {
+    fn exist(&self, message_id: &MessageId) -> Result<bool, <Self as StorageBackend>::Error> {
+        Ok(self
+            .inner
+            .get_pinned_cf(self.cf_handle(CF_MESSAGE_ID_TO_TIP_METADATA)?, message_id)?
+            .is_some())
+    }
+}
@@ -16,6 +16,7 @@ use bee_message::{
 use bee_storage::{access::Fetch, system::System};
 use bee_tangle::{
     metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    urts::TipMetadata,
 };
 
 use crate::{
@@ -242,3 +243,13 @@ impl Fetch<bool, Vec<TreasuryOutput>> for Storage {
         ))
     }
 }
+
+impl Fetch<MessageId, TipMetadata> for Storage {
+    fn fetch(&self, message_id: &MessageId) -> Result<Option<TipMetadata>, <Self as StorageBackend>::Error> {
+        Ok(self
+            .inner
+            .get_pinned_cf(self.cf_handle(CF_MESSAGE_ID_TO_TIP_METADATA)?, message_id)?
+            // Unpacking from storage is fine.
+            .map(|v| TipMetadata::unpack_unchecked(&mut &*v).unwrap()))
+    }
+}
@@ -19,6 +19,7 @@ use bee_storage::{
 };
 use bee_tangle::{
     metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    urts::TipMetadata,
 };
 
 use crate::{
@@ -252,3 +253,15 @@ impl Insert<(bool, TreasuryOutput), ()> for Storage {
         Ok(())
     }
 }
+
+impl Insert<MessageId, TipMetadata> for Storage {
+    fn insert(&self, message_id: &MessageId, metadata: &TipMetadata) -> Result<(), <Self as StorageBackend>::Error> {
+        self.inner.put_cf(
+            self.cf_handle(CF_MESSAGE_ID_TO_TIP_METADATA)?,
+            message_id,
+            metadata.pack_new(),
+        )?;
+
+        Ok(())
+    }
+}
@@ -18,6 +18,7 @@ use bee_message::{
 use bee_storage::{access::AsIterator, system::System};
 use bee_tangle::{
     metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    urts::TipMetadata,
 };
 use parking_lot::RwLockReadGuard;
 use rocksdb::{DBIterator, IteratorMode};
@@ -303,6 +304,17 @@ impl<'a> StorageIterator<'a, (bool, TreasuryOutput), ()> {
     }
 }
 
+impl<'a> StorageIterator<'a, MessageId, TipMetadata> {
+    fn unpack_key_value(mut key: &[u8], mut value: &[u8]) -> (MessageId, TipMetadata) {
+        (
+            // Unpacking from storage is fine.
+            MessageId::unpack_unchecked(&mut key).unwrap(),
+            // Unpacking from storage is fine.
+            TipMetadata::unpack_unchecked(&mut value).unwrap(),
+        )
+    }
+}
+
 impl_iter!(u8, System, CF_SYSTEM);
 impl_iter!(MessageId, Message, CF_MESSAGE_ID_TO_MESSAGE);
 impl_iter!((MessageId, MessageId), (), CF_MESSAGE_ID_TO_MESSAGE_ID);
@@ -355,3 +367,4 @@ impl<'a> Iterator for StorageIterator<'a, MessageId, MessageMetadata> {
         // }
     }
 }
+impl_iter!(MessageId, TipMetadata, CF_MESSAGE_ID_TO_TIP_METADATA);
@@ -12,7 +12,7 @@ use bee_message::{
     Message, MessageId,
 };
 use bee_storage::{access::MultiFetch, system::System};
-use bee_tangle::{metadata::MessageMetadata, solid_entry_point::SolidEntryPoint};
+use bee_tangle::{metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, urts::TipMetadata};
 use parking_lot::RwLockReadGuard;
 
 use crate::{
@@ -85,3 +85,4 @@ impl<'a> MultiFetch<'a, MessageId, MessageMetadata> for Storage {
         })
     }
 }
+impl_multi_fetch!(MessageId, TipMetadata, CF_MESSAGE_ID_TO_TIP_METADATA);
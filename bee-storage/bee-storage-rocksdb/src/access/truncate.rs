@@ -15,6 +15,7 @@ use bee_message::{
 use bee_storage::access::Truncate;
 use bee_tangle::{
     metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    urts::TipMetadata,
 };
 
 use crate::{
@@ -120,3 +121,4 @@ impl Truncate<MessageId, MessageMetadata> for Storage {
         Ok(())
     }
 }
+impl_truncate!(MessageId, TipMetadata, CF_MESSAGE_ID_TO_TIP_METADATA);
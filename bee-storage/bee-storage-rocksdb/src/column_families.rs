@@ -19,3 +19,4 @@ pub const CF_ADDRESS_TO_BALANCE: &str = "address_to_balance";
 pub const CF_MILESTONE_INDEX_TO_UNREFERENCED_MESSAGE: &str = "milestone_index_to_unreferenced_message";
 pub const CF_MILESTONE_INDEX_TO_RECEIPT: &str = "milestone_index_to_receipt";
 pub const CF_SPENT_TO_TREASURY_OUTPUT: &str = "spent_to_treasury_output";
+pub const CF_MESSAGE_ID_TO_TIP_METADATA: &str = "message_id_to_tip_metadata";
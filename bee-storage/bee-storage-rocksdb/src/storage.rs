@@ -101,6 +101,9 @@ impl Storage {
         options.set_prefix_extractor(SliceTransform::create_fixed_prefix(std::mem::size_of::<bool>()));
         let cf_spent_to_treasury = ColumnFamilyDescriptor::new(CF_SPENT_TO_TREASURY_OUTPUT, options);
 
+        let cf_message_id_to_tip_metadata =
+            ColumnFamilyDescriptor::new(CF_MESSAGE_ID_TO_TIP_METADATA, Options::default());
+
         let mut opts = Options::default();
         opts.create_if_missing(config.create_if_missing);
         opts.create_missing_column_families(config.create_missing_column_families);
@@ -154,6 +157,7 @@ impl Storage {
                 cf_milestone_index_to_unreferenced_message,
                 cf_milestone_index_to_receipt,
                 cf_spent_to_treasury,
+                cf_message_id_to_tip_metadata,
             ],
         )?;
 
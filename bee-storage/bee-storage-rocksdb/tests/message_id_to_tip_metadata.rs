@@ -0,0 +1,10 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+#[macro_use]
+mod access;
+
+impl_access_test!(
+    message_id_to_tip_metadata_access_rocksdb,
+    message_id_to_tip_metadata_access
+);
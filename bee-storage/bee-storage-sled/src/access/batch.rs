@@ -23,6 +23,7 @@ use bee_storage::{
 };
 use bee_tangle::{
     metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    urts::TipMetadata,
 };
 use sled::{transaction::TransactionError, Transactional};
 
@@ -687,3 +688,38 @@ impl Batch<(bool, TreasuryOutput), ()> for Storage {
         Ok(())
     }
 }
+
+impl Batch<MessageId, TipMetadata> for Storage {
+    fn batch_insert(
+        &self,
+        batch: &mut Self::Batch,
+        message_id: &MessageId,
+        metadata: &TipMetadata,
+    ) -> Result<(), <Self as StorageBackend>::Error> {
+        batch.value_buf.clear();
+        // Packing to bytes can't fail.
+        metadata.pack(&mut batch.value_buf).unwrap();
+
+        batch
+            .inner
+            .entry(TREE_MESSAGE_ID_TO_TIP_METADATA)
+            .or_default()
+            .insert(message_id.as_ref(), batch.value_buf.as_slice());
+
+        Ok(())
+    }
+
+    fn batch_delete(
+        &self,
+        batch: &mut Self::Batch,
+        message_id: &MessageId,
+    ) -> Result<(), <Self as StorageBackend>::Error> {
+        batch
+            .inner
+            .entry(TREE_MESSAGE_ID_TO_TIP_METADATA)
+            .or_default()
+            .remove(message_id.as_ref());
+
+        Ok(())
+    }
+}
@@ -18,6 +18,7 @@ use bee_message::{
 use bee_storage::{access::Delete, backend::StorageBackend};
 use bee_tangle::{
     metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    urts::TipMetadata,
 };
 
 use crate::{storage::Storage, trees::*};
@@ -194,3 +195,13 @@ impl Delete<(bool, TreasuryOutput), ()> for Storage {
         Ok(())
     }
 }
+
+impl Delete<MessageId, TipMetadata> for Storage {
+    fn delete(&self, message_id: &MessageId) -> Result<(), <Self as StorageBackend>::Error> {
+        self.inner
+            .open_tree(TREE_MESSAGE_ID_TO_TIP_METADATA)?
+            .remove(message_id)?;
+
+        Ok(())
+    }
+}
@@ -18,6 +18,7 @@ use bee_message::{
 use bee_storage::{access::Exist, backend::StorageBackend};
 use bee_tangle::{
     metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    urts::TipMetadata,
 };
 
 use crate::{storage::Storage, trees::*};
@@ -183,3 +184,12 @@ impl Exist<(bool, TreasuryOutput), ()> for Storage {
         Ok(self.inner.open_tree(TREE_SPENT_TO_TREASURY_OUTPUT)?.contains_key(key)?)
     }
 }
+
+impl Exist<MessageId, TipMetadata> for Storage {
+    fn exist(&self, message_id: &MessageId) -> Result<bool, <Self as StorageBackend>::Error> {
+        Ok(self
+            .inner
+            .open_tree(TREE_MESSAGE_ID_TO_TIP_METADATA)?
+            .contains_key(message_id)?)
+    }
+}
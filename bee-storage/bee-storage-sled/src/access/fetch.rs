@@ -18,6 +18,7 @@ use bee_message::{
 use bee_storage::{access::Fetch, backend::StorageBackend, system::System};
 use bee_tangle::{
     metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    urts::TipMetadata,
 };
 
 use crate::{storage::Storage, trees::*};
@@ -256,3 +257,14 @@ impl Fetch<bool, Vec<TreasuryOutput>> for Storage {
         ))
     }
 }
+
+impl Fetch<MessageId, TipMetadata> for Storage {
+    fn fetch(&self, message_id: &MessageId) -> Result<Option<TipMetadata>, <Self as StorageBackend>::Error> {
+        Ok(self
+            .inner
+            .open_tree(TREE_MESSAGE_ID_TO_TIP_METADATA)?
+            .get(message_id)?
+            // Unpacking from storage is fine.
+            .map(|v| TipMetadata::unpack_unchecked(&mut v.as_ref()).unwrap()))
+    }
+}
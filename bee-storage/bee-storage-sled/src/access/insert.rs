@@ -22,6 +22,7 @@ use bee_storage::{
 };
 use bee_tangle::{
     metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    urts::TipMetadata,
 };
 
 use crate::{storage::Storage, trees::*};
@@ -237,3 +238,13 @@ impl Insert<(bool, TreasuryOutput), ()> for Storage {
         Ok(())
     }
 }
+
+impl Insert<MessageId, TipMetadata> for Storage {
+    fn insert(&self, message_id: &MessageId, metadata: &TipMetadata) -> Result<(), <Self as StorageBackend>::Error> {
+        self.inner
+            .open_tree(TREE_MESSAGE_ID_TO_TIP_METADATA)?
+            .insert(message_id, metadata.pack_new())?;
+
+        Ok(())
+    }
+}
@@ -20,6 +20,7 @@ use bee_message::{
 use bee_storage::{access::AsIterator, backend::StorageBackend, system::System};
 use bee_tangle::{
     metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    urts::TipMetadata,
 };
 
 use crate::{storage::Storage, trees::*};
@@ -319,6 +320,17 @@ impl<'a> Iterator for StorageIterator<'a, u8, System> {
     }
 }
 
+impl<'a> StorageIterator<'a, MessageId, TipMetadata> {
+    fn unpack_key_value(mut key: &[u8], mut value: &[u8]) -> (MessageId, TipMetadata) {
+        (
+            // Unpacking from storage is fine.
+            MessageId::unpack_unchecked(&mut key).unwrap(),
+            // Unpacking from storage is fine.
+            TipMetadata::unpack_unchecked(&mut value).unwrap(),
+        )
+    }
+}
+
 impl_iter!(MessageId, Message, TREE_MESSAGE_ID_TO_MESSAGE);
 impl_iter!(MessageId, MessageMetadata, TREE_MESSAGE_ID_TO_METADATA);
 impl_iter!((MessageId, MessageId), (), TREE_MESSAGE_ID_TO_MESSAGE_ID);
@@ -344,3 +356,4 @@ impl_iter!(
 );
 impl_iter!((MilestoneIndex, Receipt), (), TREE_MILESTONE_INDEX_TO_RECEIPT);
 impl_iter!((bool, TreasuryOutput), (), TREE_SPENT_TO_TREASURY_OUTPUT);
+impl_iter!(MessageId, TipMetadata, TREE_MESSAGE_ID_TO_TIP_METADATA);
@@ -14,7 +14,7 @@ use bee_message::{
     Message, MessageId,
 };
 use bee_storage::{access::MultiFetch, backend::StorageBackend, system::System};
-use bee_tangle::{metadata::MessageMetadata, solid_entry_point::SolidEntryPoint};
+use bee_tangle::{metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, urts::TipMetadata};
 
 use crate::{storage::Storage, trees::*};
 
@@ -102,3 +102,4 @@ impl_multi_fetch!(
 );
 impl_multi_fetch!(MilestoneIndex, OutputDiff, TREE_MILESTONE_INDEX_TO_OUTPUT_DIFF);
 impl_multi_fetch!(Address, Balance, TREE_ADDRESS_TO_BALANCE);
+impl_multi_fetch!(MessageId, TipMetadata, TREE_MESSAGE_ID_TO_TIP_METADATA);
@@ -17,6 +17,7 @@ use bee_message::{
 use bee_storage::{access::Truncate, backend::StorageBackend};
 use bee_tangle::{
     metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, unreferenced_message::UnreferencedMessage,
+    urts::TipMetadata,
 };
 
 use crate::{storage::Storage, trees::*};
@@ -58,3 +59,4 @@ impl_truncate!(
 );
 impl_truncate!((MilestoneIndex, Receipt), (), TREE_MILESTONE_INDEX_TO_RECEIPT);
 impl_truncate!((bool, TreasuryOutput), (), TREE_SPENT_TO_TREASURY_OUTPUT);
+impl_truncate!(MessageId, TipMetadata, TREE_MESSAGE_ID_TO_TIP_METADATA);
@@ -40,3 +40,5 @@ pub const TREE_MILESTONE_INDEX_TO_UNREFERENCED_MESSAGE: &str = "milestone_index_
 pub const TREE_MILESTONE_INDEX_TO_RECEIPT: &str = "milestone_index_to_receipt";
 /// Identifier for the `bool` to `Vec<TreasuryOutput>` tree.
 pub const TREE_SPENT_TO_TREASURY_OUTPUT: &str = "spent_to_treasury_output";
+/// Identifier for the `MessageId` to `TipMetadata` tree.
+pub const TREE_MESSAGE_ID_TO_TIP_METADATA: &str = "message_id_to_tip_metadata";
@@ -8,6 +8,7 @@ mod ledger_index;
 mod message_id_to_message;
 mod message_id_to_message_id;
 mod message_id_to_metadata;
+mod message_id_to_tip_metadata;
 mod milestone_index_to_milestone;
 mod milestone_index_to_output_diff;
 mod milestone_index_to_receipt;
@@ -24,6 +25,7 @@ pub use self::{
     index_to_message_id::index_to_message_id_access, ledger_index::ledger_index_access,
     message_id_to_message::message_id_to_message_access, message_id_to_message_id::message_id_to_message_id_access,
     message_id_to_metadata::message_id_to_metadata_access,
+    message_id_to_tip_metadata::message_id_to_tip_metadata_access,
     milestone_index_to_milestone::milestone_index_to_milestone_access,
     milestone_index_to_output_diff::milestone_index_to_output_diff_access,
     milestone_index_to_receipt::milestone_index_to_receipt_access,
@@ -0,0 +1,127 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use bee_message::MessageId;
+use bee_storage::{
+    access::{AsIterator, Batch, BatchBuilder, Delete, Exist, Fetch, Insert, MultiFetch, Truncate},
+    backend,
+};
+use bee_tangle::urts::TipMetadata;
+use bee_test::rand::{message::rand_message_id, tip_metadata::rand_tip_metadata};
+
+pub trait StorageBackend:
+    backend::StorageBackend
+    + Exist<MessageId, TipMetadata>
+    + Fetch<MessageId, TipMetadata>
+    + for<'a> MultiFetch<'a, MessageId, TipMetadata>
+    + Insert<MessageId, TipMetadata>
+    + Delete<MessageId, TipMetadata>
+    + BatchBuilder
+    + Batch<MessageId, TipMetadata>
+    + for<'a> AsIterator<'a, MessageId, TipMetadata>
+    + Truncate<MessageId, TipMetadata>
+{
+}
+
+impl<T> StorageBackend for T where
+    T: backend::StorageBackend
+        + Exist<MessageId, TipMetadata>
+        + Fetch<MessageId, TipMetadata>
+        + for<'a> MultiFetch<'a, MessageId, TipMetadata>
+        + Insert<MessageId, TipMetadata>
+        + Delete<MessageId, TipMetadata>
+        + BatchBuilder
+        + Batch<MessageId, TipMetadata>
+        + for<'a> AsIterator<'a, MessageId, TipMetadata>
+        + Truncate<MessageId, TipMetadata>
+{
+}
+
+pub fn message_id_to_tip_metadata_access<B: StorageBackend>(storage: &B) {
+    let (message_id, metadata) = (rand_message_id(), rand_tip_metadata());
+
+    assert!(!Exist::<MessageId, TipMetadata>::exist(storage, &message_id).unwrap());
+    assert!(Fetch::<MessageId, TipMetadata>::fetch(storage, &message_id)
+        .unwrap()
+        .is_none());
+    let results = MultiFetch::<MessageId, TipMetadata>::multi_fetch(storage, &[message_id])
+        .unwrap()
+        .collect::<Vec<_>>();
+    assert_eq!(results.len(), 1);
+    assert!(matches!(results.get(0), Some(Ok(None))));
+
+    Insert::<MessageId, TipMetadata>::insert(storage, &message_id, &metadata).unwrap();
+
+    assert!(Exist::<MessageId, TipMetadata>::exist(storage, &message_id).unwrap());
+    assert_eq!(
+        Fetch::<MessageId, TipMetadata>::fetch(storage, &message_id)
+            .unwrap()
+            .unwrap(),
+        metadata
+    );
+    let results = MultiFetch::<MessageId, TipMetadata>::multi_fetch(storage, &[message_id])
+        .unwrap()
+        .collect::<Vec<_>>();
+    assert_eq!(results.len(), 1);
+    assert!(matches!(results.get(0), Some(Ok(Some(v))) if v == &metadata));
+
+    Delete::<MessageId, TipMetadata>::delete(storage, &message_id).unwrap();
+
+    assert!(!Exist::<MessageId, TipMetadata>::exist(storage, &message_id).unwrap());
+    assert!(Fetch::<MessageId, TipMetadata>::fetch(storage, &message_id)
+        .unwrap()
+        .is_none());
+    let results = MultiFetch::<MessageId, TipMetadata>::multi_fetch(storage, &[message_id])
+        .unwrap()
+        .collect::<Vec<_>>();
+    assert_eq!(results.len(), 1);
+    assert!(matches!(results.get(0), Some(Ok(None))));
+
+    let mut batch = B::batch_begin();
+    let mut message_ids = Vec::new();
+    let mut tips = Vec::new();
+
+    for _ in 0..10 {
+        let (message_id, metadata) = (rand_message_id(), rand_tip_metadata());
+        Insert::<MessageId, TipMetadata>::insert(storage, &message_id, &metadata).unwrap();
+        Batch::<MessageId, TipMetadata>::batch_delete(storage, &mut batch, &message_id).unwrap();
+        message_ids.push(message_id);
+        tips.push((message_id, None));
+    }
+
+    for _ in 0..10 {
+        let (message_id, metadata) = (rand_message_id(), rand_tip_metadata());
+        Batch::<MessageId, TipMetadata>::batch_insert(storage, &mut batch, &message_id, &metadata).unwrap();
+        message_ids.push(message_id);
+        tips.push((message_id, Some(metadata)));
+    }
+
+    storage.batch_commit(batch, true).unwrap();
+
+    let iter = AsIterator::<MessageId, TipMetadata>::iter(storage).unwrap();
+    let mut count = 0;
+
+    for result in iter {
+        let (message_id, metadata) = result.unwrap();
+        assert!(tips.contains(&(message_id, Some(metadata))));
+        count += 1;
+    }
+
+    assert_eq!(count, 10);
+
+    let results = MultiFetch::<MessageId, TipMetadata>::multi_fetch(storage, &message_ids)
+        .unwrap()
+        .collect::<Vec<_>>();
+
+    assert_eq!(results.len(), message_ids.len());
+
+    for ((_, metadata), result) in tips.into_iter().zip(results.into_iter()) {
+        assert_eq!(metadata, result.unwrap());
+    }
+
+    Truncate::<MessageId, TipMetadata>::truncate(storage).unwrap();
+
+    let mut iter = AsIterator::<MessageId, TipMetadata>::iter(storage).unwrap();
+
+    assert!(iter.next().is_none());
+}
@@ -13,6 +13,8 @@ pub mod event;
 pub mod flags;
 /// Message data, including message flags.
 pub mod metadata;
+/// Confirmation statistics of milestones.
+pub mod milestone_stats;
 /// Types used to represent SEPs (Solid Entry Points).
 pub mod solid_entry_point;
 /// Types used for interoperation with a node's storage layer.
@@ -0,0 +1,201 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use bee_message::milestone::MilestoneIndex;
+use serde::Serialize;
+
+use crate::metadata::MessageMetadata;
+
+/// Distribution of a set of latency samples, in milliseconds.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct LatencyStats {
+    /// The smallest sample.
+    pub min: u64,
+    /// The 50th percentile (median) of the samples.
+    pub p50: u64,
+    /// The 90th percentile of the samples.
+    pub p90: u64,
+    /// The 99th percentile of the samples.
+    pub p99: u64,
+    /// The largest sample.
+    pub max: u64,
+}
+
+impl LatencyStats {
+    /// Compute the distribution of the given samples, using the nearest-rank method for the percentiles.
+    pub fn from_samples(mut samples: Vec<u64>) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+
+        samples.sort_unstable();
+
+        // The nearest rank is `ceil(len * p / 100)`, shifted by one to be used as an index.
+        let percentile = |p: usize| samples[(samples.len() * p - 1) / 100];
+
+        Self {
+            min: samples[0],
+            p50: percentile(50),
+            p90: percentile(90),
+            p99: percentile(99),
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// Confirmation statistics of a milestone.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct MilestoneStats {
+    /// The index of the milestone.
+    pub index: MilestoneIndex,
+    /// The timestamp of the milestone.
+    pub timestamp: u64,
+    /// The number of messages referenced by the milestone.
+    pub referenced_messages: usize,
+    /// The delay between the arrival of the referenced messages and their reference by the milestone.
+    pub confirmation_latency: LatencyStats,
+    /// The delay between the arrival of the referenced messages and their solidification.
+    pub solidification_delay: LatencyStats,
+}
+
+/// Accumulates the metadata of the messages referenced by a milestone into `MilestoneStats`.
+pub struct MilestoneStatsBuilder {
+    index: MilestoneIndex,
+    timestamp: u64,
+    referenced_messages: usize,
+    confirmation_latencies: Vec<u64>,
+    solidification_delays: Vec<u64>,
+}
+
+impl MilestoneStatsBuilder {
+    /// Create a new `MilestoneStatsBuilder` for the milestone with the given index and timestamp (seconds from the
+    /// unix epoch).
+    pub fn new(index: MilestoneIndex, timestamp: u64) -> Self {
+        Self {
+            index,
+            timestamp,
+            referenced_messages: 0,
+            confirmation_latencies: Vec::new(),
+            solidification_delays: Vec::new(),
+        }
+    }
+
+    /// Account for a message referenced by the milestone.
+    pub fn add(&mut self, metadata: &MessageMetadata) {
+        self.referenced_messages += 1;
+
+        // Messages that did not arrive through the node, e.g. coming from a snapshot, have no arrival timestamp.
+        if metadata.arrival_timestamp() == 0 {
+            return;
+        }
+
+        // Arrival and solidification timestamps are in milliseconds, reference timestamps in seconds.
+        self.confirmation_latencies
+            .push((self.timestamp * 1000).saturating_sub(metadata.arrival_timestamp()));
+
+        if metadata.flags().is_solid() && metadata.solidification_timestamp() != 0 {
+            self.solidification_delays.push(
+                metadata
+                    .solidification_timestamp()
+                    .saturating_sub(metadata.arrival_timestamp()),
+            );
+        }
+    }
+
+    /// Finish the builder into `MilestoneStats`.
+    pub fn finish(self) -> MilestoneStats {
+        MilestoneStats {
+            index: self.index,
+            timestamp: self.timestamp,
+            referenced_messages: self.referenced_messages,
+            confirmation_latency: LatencyStats::from_samples(self.confirmation_latencies),
+            solidification_delay: LatencyStats::from_samples(self.solidification_delays),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{conflict::ConflictReason, flags::Flags};
+
+    fn metadata(arrival_timestamp: u64, solidification_timestamp: Option<u64>) -> MessageMetadata {
+        let mut flags = Flags::default();
+        flags.set_solid(solidification_timestamp.is_some());
+
+        MessageMetadata::new(
+            flags,
+            None,
+            arrival_timestamp,
+            solidification_timestamp.unwrap_or(0),
+            0,
+            None,
+            ConflictReason::None,
+        )
+    }
+
+    #[test]
+    fn no_samples() {
+        assert_eq!(LatencyStats::from_samples(Vec::new()), LatencyStats::default());
+    }
+
+    #[test]
+    fn single_sample() {
+        assert_eq!(
+            LatencyStats::from_samples(vec![42]),
+            LatencyStats {
+                min: 42,
+                p50: 42,
+                p90: 42,
+                p99: 42,
+                max: 42,
+            }
+        );
+    }
+
+    #[test]
+    fn nearest_rank_percentiles() {
+        // 1..=100 in reverse order, so that the samples need sorting.
+        let stats = LatencyStats::from_samples((1..=100).rev().collect());
+
+        assert_eq!(
+            stats,
+            LatencyStats {
+                min: 1,
+                p50: 50,
+                p90: 90,
+                p99: 99,
+                max: 100,
+            }
+        );
+
+        // With 10 samples, the 99th percentile is the largest sample.
+        let stats = LatencyStats::from_samples((1..=10).map(|i| i * 10).collect());
+
+        assert_eq!(stats.p50, 50);
+        assert_eq!(stats.p90, 90);
+        assert_eq!(stats.p99, 100);
+    }
+
+    #[test]
+    fn build_milestone_stats() {
+        let mut builder = MilestoneStatsBuilder::new(MilestoneIndex(7), 1_000);
+
+        builder.add(&metadata(990_000, Some(990_500)));
+        builder.add(&metadata(998_000, None));
+        // Messages without arrival timestamp are only counted.
+        builder.add(&metadata(0, None));
+        // Messages that arrived after the milestone timestamp have no negative latency.
+        builder.add(&metadata(1_000_200, Some(1_000_300)));
+
+        let stats = builder.finish();
+
+        assert_eq!(stats.index, MilestoneIndex(7));
+        assert_eq!(stats.referenced_messages, 4);
+        assert_eq!(stats.confirmation_latency.min, 0);
+        assert_eq!(stats.confirmation_latency.max, 10_000);
+        assert_eq!(stats.confirmation_latency.p50, 2_000);
+        assert_eq!(stats.solidification_delay.min, 100);
+        assert_eq!(stats.solidification_delay.max, 500);
+    }
+}
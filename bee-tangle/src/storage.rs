@@ -6,11 +6,11 @@ use bee_message::{
     Message, MessageId,
 };
 use bee_storage::{
-    access::{Exist, Fetch, Insert, InsertStrict, Update},
+    access::{AsIterator, Batch, Exist, Fetch, Insert, InsertStrict, Truncate, Update},
     backend,
 };
 
-use crate::{metadata::MessageMetadata, solid_entry_point::SolidEntryPoint};
+use crate::{metadata::MessageMetadata, solid_entry_point::SolidEntryPoint, urts::TipMetadata};
 
 /// A blanket-implemented helper trait for the storage layer.
 pub trait StorageBackend:
@@ -19,6 +19,7 @@ pub trait StorageBackend:
     + Insert<(MessageId, MessageId), ()>
     + Insert<MilestoneIndex, Milestone>
     + Insert<SolidEntryPoint, MilestoneIndex>
+    + Batch<MessageId, TipMetadata>
     + InsertStrict<MessageId, MessageMetadata>
     + Exist<MessageId, Message>
     + Exist<MilestoneIndex, Milestone>
@@ -27,6 +28,8 @@ pub trait StorageBackend:
     + Fetch<MessageId, Vec<MessageId>>
     + Fetch<MilestoneIndex, Milestone>
     + Update<MessageId, MessageMetadata>
+    + Truncate<MessageId, TipMetadata>
+    + for<'a> AsIterator<'a, MessageId, TipMetadata>
 {
 }
 
@@ -36,6 +39,7 @@ impl<T> StorageBackend for T where
         + Insert<(MessageId, MessageId), ()>
         + Insert<MilestoneIndex, Milestone>
         + Insert<SolidEntryPoint, MilestoneIndex>
+        + Batch<MessageId, TipMetadata>
         + InsertStrict<MessageId, MessageMetadata>
         + Exist<MessageId, Message>
         + Exist<MilestoneIndex, Milestone>
@@ -44,5 +48,7 @@ impl<T> StorageBackend for T where
         + Fetch<MessageId, Vec<MessageId>>
         + Fetch<MilestoneIndex, Milestone>
         + Update<MessageId, MessageMetadata>
+        + Truncate<MessageId, TipMetadata>
+        + for<'a> AsIterator<'a, MessageId, TipMetadata>
 {
 }
@@ -1,28 +1,34 @@
 // Copyright 2020-2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::sync::atomic::{AtomicU32, Ordering};
+use std::{
+    collections::VecDeque,
+    sync::atomic::{AtomicU32, Ordering},
+};
 
 use bee_message::{
     milestone::{Milestone, MilestoneIndex},
     Message, MessageId,
 };
 use bee_runtime::resource::ResourceHandle;
+use bee_storage::access::{AsIterator, Batch, BatchBuilder, Truncate};
 use hashbrown::HashMap;
-use log::warn;
+use log::{info, warn};
 use ref_cast::RefCast;
 use tokio::sync::Mutex;
 
 use crate::{
     config::TangleConfig,
     metadata::{IndexId, MessageMetadata},
+    milestone_stats::MilestoneStats,
     solid_entry_point::SolidEntryPoint,
     storage::StorageBackend,
-    urts::UrtsTipPool,
+    urts::{TipMetadata, UrtsTipPool},
 };
 
 const SYNCED_THRESHOLD: u32 = 2;
 const CONFIRMED_THRESHOLD: u32 = 2;
+const MILESTONE_STATS_CAPACITY: usize = 100;
 
 /// A Tangle wrapper designed to encapsulate milestone state.
 pub struct Tangle<B> {
@@ -36,6 +42,7 @@ pub struct Tangle<B> {
     pruning_index: AtomicU32,
     entry_point_index: AtomicU32,
     tip_pool: Mutex<UrtsTipPool>,
+    milestone_stats: Mutex<VecDeque<MilestoneStats>>,
 }
 
 impl<B: StorageBackend> Tangle<B> {
@@ -51,13 +58,64 @@ impl<B: StorageBackend> Tangle<B> {
             pruning_index: Default::default(),
             entry_point_index: Default::default(),
             tip_pool: Mutex::new(UrtsTipPool::new(&config)),
+            milestone_stats: Mutex::new(VecDeque::with_capacity(MILESTONE_STATS_CAPACITY)),
             config,
         }
     }
 
-    /// Shut down the tangle, terminating any and all worker tasks.
+    /// Shut down the tangle, persisting the non-lazy tips of the tip pool.
     pub async fn shutdown(self) {
-        // TODO: Write back changes by calling self.inner.shutdown().await
+        self.persist_tips().await;
+    }
+
+    /// Write the non-lazy tips of the tip pool, and their metadata, to the storage.
+    async fn persist_tips(&self) {
+        if let Err(e) = Truncate::<MessageId, TipMetadata>::truncate(&*self.storage) {
+            warn!("Failed to truncate persisted tips {:?}", e);
+            return;
+        }
+
+        let tip_pool = self.tip_pool.lock().await;
+        let mut batch = B::batch_begin();
+        let mut persisted = 0;
+
+        for (message_id, metadata) in tip_pool.non_lazy_tips_with_metadata() {
+            match Batch::<MessageId, TipMetadata>::batch_insert(&*self.storage, &mut batch, message_id, metadata) {
+                Ok(()) => persisted += 1,
+                Err(e) => warn!("Failed to persist tip {:?}", e),
+            }
+        }
+
+        if let Err(e) = self.storage.batch_commit(batch, true) {
+            warn!("Failed to persist tips {:?}", e);
+            return;
+        }
+
+        info!("Persisted {} non-lazy tips.", persisted);
+    }
+
+    /// Restore the tips persisted during the last shutdown, keeping only the ones that are still non-lazy.
+    /// The solid milestone index needs to be known beforehand, since the tips are scored against it.
+    pub async fn restore_tips(&self) {
+        let tips = match AsIterator::<MessageId, TipMetadata>::iter(&*self.storage) {
+            Ok(iter) => iter
+                .filter_map(|result| result.map_err(|e| warn!("Failed to fetch persisted tip {:?}", e)).ok())
+                .collect::<Vec<_>>(),
+            Err(e) => {
+                warn!("Failed to iterate persisted tips {:?}", e);
+                return;
+            }
+        };
+
+        // Persisted tips are only valid for the run right after they were written.
+        if let Err(e) = Truncate::<MessageId, TipMetadata>::truncate(&*self.storage) {
+            warn!("Failed to truncate persisted tips {:?}", e);
+        }
+
+        let persisted = tips.len();
+        let restored = self.tip_pool.lock().await.restore(self, tips).await;
+
+        info!("Restored {} out of {} persisted tips.", restored, persisted);
     }
 
     /// Get the configuration of this tangle.
@@ -213,6 +271,32 @@ impl<B: StorageBackend> Tangle<B> {
         *self.get_confirmed_milestone_index() >= self.get_latest_milestone_index().saturating_sub(threshold)
     }
 
+    /// Add the confirmation statistics of a milestone, evicting the oldest ones if the timeline is full.
+    pub async fn add_milestone_stats(&self, stats: MilestoneStats) {
+        let mut timeline = self.milestone_stats.lock().await;
+
+        if timeline.len() == MILESTONE_STATS_CAPACITY {
+            timeline.pop_front();
+        }
+        timeline.push_back(stats);
+    }
+
+    /// Get the confirmation statistics of the milestone with the given index, if it is still in the timeline.
+    pub async fn get_milestone_stats(&self, index: MilestoneIndex) -> Option<MilestoneStats> {
+        self.milestone_stats
+            .lock()
+            .await
+            .iter()
+            .rev()
+            .find(|stats| stats.index == index)
+            .cloned()
+    }
+
+    /// Returns a copy of the confirmation statistics of the most recently confirmed milestones, oldest first.
+    pub async fn get_milestone_timeline(&self) -> Vec<MilestoneStats> {
+        self.milestone_stats.lock().await.iter().cloned().collect()
+    }
+
     /// Get the milestone index associated with the given solid entry point.
     pub async fn get_solid_entry_point_index(&self, sep: &SolidEntryPoint) -> Option<MilestoneIndex> {
         self.solid_entry_points.lock().await.get(sep).copied()
@@ -3,6 +3,7 @@
 
 use std::time::Instant;
 
+use bee_common::packable::{Packable, Read, Write};
 use bee_message::MessageId;
 use hashbrown::{hash_map::Entry, HashMap, HashSet};
 use log::debug;
@@ -17,12 +18,12 @@ enum Score {
     Lazy,
 }
 
-// C1: the maximum allowed delta value for the YMRSI of a given message in relation to the current SMI before it
-// gets lazy.
-const YMRSI_DELTA: u32 = 8;
-// C2: the maximum allowed delta value between OMRSI of a given message in relation to the current SMI before it
-// gets semi-lazy.
-const OMRSI_DELTA: u32 = 13;
+/// C1: the maximum allowed delta value for the YMRSI of a given message in relation to the current SMI before it
+/// gets lazy.
+pub const YMRSI_DELTA: u32 = 8;
+/// C2: the maximum allowed delta value between OMRSI of a given message in relation to the current SMI before it
+/// gets semi-lazy.
+pub const OMRSI_DELTA: u32 = 13;
 // If the amount of non-lazy tips exceed this limit, remove the parent(s) of the inserted tip to compensate for the
 // excess. This rule helps to reduce the amount of tips in the network.
 const MAX_LIMIT_NON_LAZY: u8 = 100;
@@ -33,16 +34,56 @@ const MAX_AGE_SECONDS_AFTER_FIRST_CHILD: u8 = 3;
 // used to widen the cone of the tangle.
 const MAX_NUM_CHILDREN: u8 = 2;
 
-#[derive(Default)]
-struct TipMetadata {
+/// Metadata of a tip of the URTS tip pool.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TipMetadata {
     children: HashSet<MessageId>,
     time_first_child: Option<Instant>,
 }
 
 impl TipMetadata {
-    pub(crate) fn new() -> Self {
+    /// Create a new `TipMetadata` without any children.
+    pub fn new() -> Self {
         Self::default()
     }
+
+    /// Create a new `TipMetadata` with the given children.
+    /// The time of the first child is unknown until the tip is inserted back into the tip pool.
+    pub fn with_children(children: impl IntoIterator<Item = MessageId>) -> Self {
+        Self {
+            children: children.into_iter().collect(),
+            time_first_child: None,
+        }
+    }
+
+    /// Get the children of the tip.
+    pub fn children(&self) -> &HashSet<MessageId> {
+        &self.children
+    }
+}
+
+impl Packable for TipMetadata {
+    type Error = <MessageId as Packable>::Error;
+
+    fn packed_len(&self) -> usize {
+        0u64.packed_len() + self.children.iter().map(Packable::packed_len).sum::<usize>()
+    }
+
+    fn pack<W: Write>(&self, writer: &mut W) -> Result<(), Self::Error> {
+        (self.children.len() as u64).pack(writer)?;
+        self.children.iter().try_for_each(|child| child.pack(writer))
+    }
+
+    fn unpack_inner<R: Read + ?Sized, const CHECK: bool>(reader: &mut R) -> Result<Self, Self::Error> {
+        let children = (0..u64::unpack_inner::<R, CHECK>(reader)?)
+            .map(|_| MessageId::unpack_inner::<R, CHECK>(reader))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            children,
+            time_first_child: None,
+        })
+    }
 }
 
 pub(crate) struct UrtsTipPool {
@@ -64,6 +105,39 @@ impl UrtsTipPool {
         &self.non_lazy_tips
     }
 
+    pub(crate) fn non_lazy_tips_with_metadata(&self) -> impl Iterator<Item = (&MessageId, &TipMetadata)> {
+        self.non_lazy_tips
+            .iter()
+            .filter_map(|tip| self.tips.get(tip).map(|metadata| (tip, metadata)))
+    }
+
+    pub(crate) async fn restore<B: StorageBackend>(
+        &mut self,
+        tangle: &Tangle<B>,
+        tips: impl IntoIterator<Item = (MessageId, TipMetadata)>,
+    ) -> usize {
+        let mut restored = 0;
+
+        for (message_id, mut metadata) in tips {
+            // Tips that lost their OMRSI/YMRSI, e.g. because of a new snapshot, can't be scored and are dropped.
+            if tangle.omrsi_and_ymrsi(&message_id).await.is_none() {
+                continue;
+            }
+
+            if let Score::NonLazy = self.tip_score::<B>(tangle, &message_id).await {
+                // The age of the first child is not persisted, the retention rules start over instead.
+                if !metadata.children.is_empty() {
+                    metadata.time_first_child = Some(Instant::now());
+                }
+                self.non_lazy_tips.insert(message_id);
+                self.tips.insert(message_id, metadata);
+                restored += 1;
+            }
+        }
+
+        restored
+    }
+
     pub(crate) async fn insert<B: StorageBackend>(
         &mut self,
         tangle: &Tangle<B>,
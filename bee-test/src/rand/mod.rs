@@ -37,6 +37,8 @@ pub mod snapshot;
 pub mod solid_entry_point;
 /// Module providing random string generation utilities.
 pub mod string;
+/// Module providing random tip metadata generation utilities.
+pub mod tip_metadata;
 /// Module providing random transaction generation utilities.
 pub mod transaction;
 /// Module providing random unreferenced message generation utilities.
@@ -0,0 +1,11 @@
+// Copyright 2020-2022 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use bee_tangle::urts::TipMetadata;
+
+use crate::rand::{message::rand_message_ids, number::rand_number_range};
+
+/// Generates a random tip metadata.
+pub fn rand_tip_metadata() -> TipMetadata {
+    TipMetadata::with_children(rand_message_ids(rand_number_range(0..8)))
+}